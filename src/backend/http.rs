@@ -0,0 +1,351 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use chrono::SecondsFormat;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use super::{Backend, NewTask};
+use crate::db::TaskFilter;
+use crate::error::Error;
+use crate::models::{Comment, Task, percent_encode};
+
+/// How long to wait for the server before giving up on a request.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Backend over the JSON API of a running `tk serve`.
+///
+/// Speaks just enough HTTP/1.1 for the API (one request per connection,
+/// `Content-Length` or chunked bodies), so only plain `http://` URLs work;
+/// put a TLS-terminating proxy in front for anything else.
+pub struct RemoteHttp {
+    /// `host:port` to connect to.
+    authority: String,
+    /// Path prefix from the URL, without a trailing slash (usually empty).
+    base_path: String,
+}
+
+impl RemoteHttp {
+    /// Parse a server URL such as `http://127.0.0.1:3000`.
+    pub fn new(url: &str) -> Result<Self, Error> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            Error::validation(format!(
+                "invalid server URL: {url} (only http:// URLs are supported)"
+            ))
+        })?;
+        let (authority, path) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], &rest[pos..]),
+            None => (rest, ""),
+        };
+        if authority.is_empty() {
+            return Err(Error::validation(format!(
+                "invalid server URL: {url} (missing host)"
+            )));
+        }
+        let authority = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{authority}:80")
+        };
+        Ok(RemoteHttp {
+            authority,
+            base_path: path.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Send one request and return the status code and body.
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<(u16, String), String> {
+        let server = &self.authority;
+        let mut stream = TcpStream::connect(server)
+            .map_err(|e| format!("failed to connect to server {server}: {e}"))?;
+        stream
+            .set_read_timeout(Some(TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(TIMEOUT)))
+            .map_err(|e| format!("failed to configure connection: {e}"))?;
+
+        let payload = body.map(|b| b.to_string()).unwrap_or_default();
+        let mut req = format!(
+            "{method} {}{path} HTTP/1.1\r\nHost: {server}\r\nAccept: application/json\r\nConnection: close\r\n",
+            self.base_path
+        );
+        if body.is_some() {
+            req.push_str(&format!(
+                "Content-Type: application/json\r\nContent-Length: {}\r\n",
+                payload.len()
+            ));
+        }
+        req.push_str("\r\n");
+        req.push_str(&payload);
+        stream
+            .write_all(req.as_bytes())
+            .map_err(|e| format!("failed to send request to {server}: {e}"))?;
+
+        let mut raw = Vec::new();
+        stream
+            .read_to_end(&mut raw)
+            .map_err(|e| format!("failed to read response from {server}: {e}"))?;
+        parse_response(&raw)
+    }
+
+    /// Send a request and decode a 2xx JSON response; other statuses become
+    /// an error carrying the server's message.
+    fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<T, Error> {
+        let (status, text) = self.request(method, path, body)?;
+        if !(200..300).contains(&status) {
+            return Err(server_error(status, &text));
+        }
+        serde_json::from_str(&text).map_err(|e| format!("invalid response from server: {e}").into())
+    }
+}
+
+impl Backend for RemoteHttp {
+    fn list_tasks(&self, filter: &TaskFilter) -> Result<Vec<Task>, Error> {
+        let mut params = Vec::new();
+        if filter.include_done {
+            params.push(("all", "true".to_string()));
+        }
+        if filter.include_archived {
+            params.push(("archived", "true".to_string()));
+        }
+        if let Some(s) = filter.status {
+            params.push(("status", s.to_string()));
+        }
+        if let Some(p) = filter.priority {
+            params.push(("priority", p.to_string()));
+        }
+        if let Some(t) = filter.tag {
+            params.push(("tag", t.to_string()));
+        }
+        if let Some(p) = filter.parent {
+            params.push(("parent", p.to_string()));
+        }
+        if let Some(a) = filter.assignee {
+            params.push(("assignee", a.to_string()));
+        }
+        if let Some(b) = filter.blocked_by {
+            params.push(("blocked_by", b.to_string()));
+        }
+        if let Some(b) = filter.blocking {
+            params.push(("blocking", b.to_string()));
+        }
+        if let Some(s) = filter.search {
+            params.push(("search", s.to_string()));
+        }
+        if let Some(r) = filter.close_reason {
+            params.push(("reason", r.to_string()));
+        }
+        if let Some(d) = filter.created_after {
+            params.push((
+                "created_after",
+                d.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            ));
+        }
+        if let Some(d) = filter.created_before {
+            params.push((
+                "created_before",
+                d.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            ));
+        }
+        if let Some(d) = filter.updated_after {
+            params.push((
+                "updated_after",
+                d.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            ));
+        }
+        if let Some(a) = filter.after {
+            params.push(("after", a.to_string()));
+        }
+        if let Some(l) = filter.limit {
+            params.push(("limit", l.to_string()));
+        }
+        self.call("GET", &format!("/api/tasks{}", query_string(&params)), None)
+    }
+
+    fn ready_tasks(&self, limit: Option<u32>) -> Result<Vec<Task>, Error> {
+        let params: Vec<_> = limit
+            .map(|l| ("limit", l.to_string()))
+            .into_iter()
+            .collect();
+        self.call(
+            "GET",
+            &format!("/api/tasks/ready{}", query_string(&params)),
+            None,
+        )
+    }
+
+    fn tasks_by_ids(&self, ids: &[String]) -> Result<Vec<Task>, Error> {
+        let params = [("ids", ids.join(","))];
+        self.call("GET", &format!("/api/tasks{}", query_string(&params)), None)
+    }
+
+    fn children(&self, id: &str) -> Result<Vec<Task>, Error> {
+        self.call(
+            "GET",
+            &format!("/api/tasks/{}/children", percent_encode(id)),
+            None,
+        )
+    }
+
+    fn siblings(&self, id: &str) -> Result<Vec<Task>, Error> {
+        let parent = self
+            .tasks_by_ids(&[id.to_string()])?
+            .into_iter()
+            .next()
+            .and_then(|t| t.parent_id);
+        let Some(parent) = parent else {
+            return Ok(Vec::new());
+        };
+        let mut siblings = self.children(&parent)?;
+        siblings.retain(|t| t.id != id);
+        Ok(siblings)
+    }
+
+    fn blockers(&self, id: &str) -> Result<Vec<Task>, Error> {
+        self.call(
+            "GET",
+            &format!("/api/tasks/{}/blockers", percent_encode(id)),
+            None,
+        )
+    }
+
+    fn dependents(&self, id: &str) -> Result<Vec<Task>, Error> {
+        self.call(
+            "GET",
+            &format!("/api/tasks/{}/dependents", percent_encode(id)),
+            None,
+        )
+    }
+
+    fn comments(&self, id: &str) -> Result<Vec<Comment>, Error> {
+        self.call(
+            "GET",
+            &format!("/api/tasks/{}/comments", percent_encode(id)),
+            None,
+        )
+    }
+
+    fn create_task(&self, new: &NewTask) -> Result<Task, Error> {
+        let body = json!({
+            "title": new.title,
+            "priority": new.priority,
+            "description": new.description,
+            "tags": new.tags,
+            "parent_id": new.parent,
+            "due_at": new.due_at,
+        });
+        self.call("POST", "/api/tasks", Some(&body))
+    }
+
+    fn close_task(
+        &self,
+        id: &str,
+        reason: Option<&str>,
+        comments: &[String],
+        close_parent: bool,
+    ) -> Result<Task, Error> {
+        let body = json!({ "reason": reason, "comments": comments, "close_parent": close_parent });
+        self.call(
+            "POST",
+            &format!("/api/tasks/{}/close", percent_encode(id)),
+            Some(&body),
+        )
+    }
+}
+
+/// Split a raw HTTP response into status code and (de-chunked) body text.
+fn parse_response(raw: &[u8]) -> Result<(u16, String), String> {
+    let split = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or("malformed response from server: no header terminator")?;
+    let head = String::from_utf8_lossy(&raw[..split]);
+    let body = &raw[split + 4..];
+
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or("malformed response from server: bad status line")?;
+    let chunked = lines.any(|line| {
+        let lower = line.to_ascii_lowercase();
+        lower.starts_with("transfer-encoding:") && lower.contains("chunked")
+    });
+
+    let body = if chunked {
+        dechunk(body)?
+    } else {
+        body.to_vec()
+    };
+    Ok((status, String::from_utf8_lossy(&body).into_owned()))
+}
+
+/// Decode a `Transfer-Encoding: chunked` body.
+fn dechunk(mut data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    loop {
+        let line_end = data
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or("malformed chunked response from server")?;
+        let size_str = String::from_utf8_lossy(&data[..line_end]);
+        let size_hex = size_str.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_hex, 16)
+            .map_err(|_| format!("malformed chunk size from server: {size_hex}"))?;
+        data = &data[line_end + 2..];
+        if size == 0 {
+            return Ok(out);
+        }
+        if data.len() < size {
+            return Err("truncated chunked response from server".to_string());
+        }
+        out.extend_from_slice(&data[..size]);
+        data = data.get(size + 2..).unwrap_or(&[]);
+    }
+}
+
+/// The API replies to errors with `{"error": {"message": ...}}`; keep the
+/// message when present, or the raw body from servers that send plain text.
+/// The status picks the kind, so a 404 exits like a local "not found".
+fn server_error(status: u16, body: &str) -> Error {
+    let parsed = serde_json::from_str::<Value>(body).ok();
+    let message = parsed
+        .as_ref()
+        .and_then(|v| v["error"]["message"].as_str())
+        .unwrap_or(body)
+        .trim();
+    let message = if message.is_empty() {
+        format!("server returned {status}")
+    } else {
+        format!("server returned {status}: {message}")
+    };
+    match status {
+        404 => Error::not_found(message),
+        400 | 422 => Error::validation(message),
+        409 => Error::conflict(message),
+        _ => message.into(),
+    }
+}
+
+/// `?k=v&...` with percent-encoded values, or an empty string.
+fn query_string(params: &[(&str, String)]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = params
+        .iter()
+        .map(|(k, v)| format!("{k}={}", percent_encode(v)))
+        .collect();
+    format!("?{}", pairs.join("&"))
+}
@@ -0,0 +1,170 @@
+mod http;
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::db::{Database, TaskFilter};
+use crate::error::Error;
+use crate::models::{Comment, Status, Task};
+
+pub use http::RemoteHttp;
+
+/// Fields for a new task. The backend assigns the ID (a child ID under
+/// `parent`) and auto-tags the parent as an epic.
+pub struct NewTask<'a> {
+    pub title: &'a str,
+    /// `None` takes the database's default priority.
+    pub priority: Option<u8>,
+    pub description: Option<&'a str>,
+    pub tags: Vec<String>,
+    pub parent: Option<&'a str>,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+/// Where CLI commands read and write tasks: the local SQLite file, or a
+/// running `tk serve` when `TACKS_SERVER` is set.
+pub trait Backend {
+    fn list_tasks(&self, filter: &TaskFilter) -> Result<Vec<Task>, Error>;
+    fn ready_tasks(&self, limit: Option<u32>) -> Result<Vec<Task>, Error>;
+    /// Tasks with the given IDs; missing IDs are omitted.
+    fn tasks_by_ids(&self, ids: &[String]) -> Result<Vec<Task>, Error>;
+    fn children(&self, id: &str) -> Result<Vec<Task>, Error>;
+    /// The other children of `id`'s parent; empty for a top-level task.
+    fn siblings(&self, id: &str) -> Result<Vec<Task>, Error>;
+    /// Tasks that `id` is blocked by.
+    fn blockers(&self, id: &str) -> Result<Vec<Task>, Error>;
+    /// Tasks blocked by `id`.
+    fn dependents(&self, id: &str) -> Result<Vec<Task>, Error>;
+    fn comments(&self, id: &str) -> Result<Vec<Comment>, Error>;
+    fn create_task(&self, new: &NewTask) -> Result<Task, Error>;
+    /// Close `id` with an optional reason and closing comments; returns the
+    /// closed task. With `close_parent`, also close its parent when this was
+    /// the last open child.
+    fn close_task(
+        &self,
+        id: &str,
+        reason: Option<&str>,
+        comments: &[String],
+        close_parent: bool,
+    ) -> Result<Task, Error>;
+}
+
+/// The server URL if set, else the database at `db_path`.
+pub fn open(db_path: &Path, server: Option<&str>) -> Result<Box<dyn Backend>, Error> {
+    match server {
+        Some(url) => Ok(Box::new(RemoteHttp::new(url)?)),
+        None => Ok(Box::new(LocalDb(Database::open(db_path)?))),
+    }
+}
+
+/// Backend over the SQLite file opened directly.
+pub struct LocalDb(pub Database);
+
+impl Backend for LocalDb {
+    fn list_tasks(&self, filter: &TaskFilter) -> Result<Vec<Task>, Error> {
+        self.0.list_tasks(filter)
+    }
+
+    fn ready_tasks(&self, limit: Option<u32>) -> Result<Vec<Task>, Error> {
+        self.0.get_ready_tasks(limit)
+    }
+
+    fn tasks_by_ids(&self, ids: &[String]) -> Result<Vec<Task>, Error> {
+        self.0.get_tasks_by_ids(ids)
+    }
+
+    fn children(&self, id: &str) -> Result<Vec<Task>, Error> {
+        self.0.get_children(id)
+    }
+
+    fn siblings(&self, id: &str) -> Result<Vec<Task>, Error> {
+        self.0.get_siblings(id)
+    }
+
+    fn blockers(&self, id: &str) -> Result<Vec<Task>, Error> {
+        let ids: Vec<String> = self
+            .0
+            .get_blockers(id)?
+            .into_iter()
+            .map(|dep| dep.parent_id)
+            .collect();
+        self.0.get_tasks_by_ids(&ids)
+    }
+
+    fn dependents(&self, id: &str) -> Result<Vec<Task>, Error> {
+        self.0.get_dependents(id)
+    }
+
+    fn comments(&self, id: &str) -> Result<Vec<Comment>, Error> {
+        self.0.get_comments(id)
+    }
+
+    fn create_task(&self, new: &NewTask) -> Result<Task, Error> {
+        // Insert and parent auto-tagging succeed or fail together.
+        self.0.with_transaction(|db| {
+            let id = if let Some(parent_id) = new.parent {
+                // Verify parent exists
+                db.get_task(parent_id)?.ok_or_else(|| {
+                    Error::not_found(format!("parent task not found: {parent_id}"))
+                })?;
+                db.generate_child_id(parent_id)?
+            } else {
+                db.generate_id()?
+            };
+
+            let now = Utc::now();
+            let task = Task {
+                id,
+                title: new.title.to_string(),
+                description: new.description.map(|s| s.to_string()),
+                status: Status::Open,
+                priority: match new.priority {
+                    Some(p) => p,
+                    None => db.default_priority()?,
+                },
+                assignee: None,
+                parent_id: new.parent.map(|s| s.to_string()),
+                tags: new.tags.clone(),
+                created_at: now,
+                updated_at: now,
+                close_reason: None,
+                notes: None,
+                due_at: new.due_at,
+                position: 0.0,
+                archived_at: None,
+                estimate_minutes: None,
+                spent_minutes: None,
+            };
+
+            db.insert_task(&task)?;
+
+            // Auto-tag parent as epic when a child is created (unless disabled in config)
+            if let Some(parent_id) = new.parent {
+                db.auto_tag_epic(parent_id)?;
+            }
+            // Re-read for the position insert_task assigned.
+            db.get_task(&task.id)?
+                .ok_or_else(|| format!("task not found after insert: {}", task.id).into())
+        })
+    }
+
+    fn close_task(
+        &self,
+        id: &str,
+        reason: Option<&str>,
+        comments: &[String],
+        close_parent: bool,
+    ) -> Result<Task, Error> {
+        self.0.with_transaction(|db| {
+            db.close_task(id, reason, comments)?;
+            if close_parent {
+                db.close_parent_if_done(id)?;
+            }
+            Ok(())
+        })?;
+        self.0
+            .get_task(id)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))
+    }
+}
@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use super::{confirm, print_json, quiet_enabled};
+use crate::db::Database;
+use crate::error::Error;
+use crate::models::parse_date_bound;
+
+/// Archive one task, or with `closed_before` every done task last updated
+/// before that date. Archived tasks are hidden from lists but `tk show`
+/// still finds them.
+pub fn run(
+    db_path: &Path,
+    id: Option<&str>,
+    closed_before: Option<&str>,
+    json: bool,
+) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let Some(date) = closed_before else {
+        let id = id.ok_or_else(|| Error::validation("pass a task ID or --closed-before <date>"))?;
+        return set(&db, id, true, json);
+    };
+
+    let cutoff = parse_date_bound(date, false).map_err(Error::validation)?;
+    let ids = db.archive_closed_before(cutoff)?;
+    if json {
+        return print_json(&db.get_tasks_by_ids(&ids)?);
+    }
+    if quiet_enabled() {
+        return Ok(());
+    }
+    println!("Archived {} task(s)", ids.len());
+    for id in &ids {
+        println!("  {id}");
+    }
+    Ok(())
+}
+
+/// Bring an archived task back into lists.
+pub fn unarchive(db_path: &Path, id: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    set(&db, id, false, json)
+}
+
+fn set(db: &Database, id: &str, archived: bool, json: bool) -> Result<(), Error> {
+    db.set_archived(id, archived)?;
+    if json {
+        let task = db
+            .get_task(id)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+        return print_json(&task);
+    }
+    let verb = if archived { "Archived" } else { "Unarchived" };
+    confirm(&format!("{verb} task {id}"));
+    Ok(())
+}
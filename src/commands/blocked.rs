@@ -1,10 +1,62 @@
 use std::path::Path;
 
+use serde::Serialize;
+
+use super::{
+    ndjson_enabled, print_json, print_json_lines, print_task_header, print_task_row, print_tasks,
+};
 use crate::db::Database;
+use crate::error::Error;
+use crate::models::{Status, Task};
+
+/// A blocked task with the open tasks it is waiting on (`tk blocked --explain`).
+#[derive(Serialize)]
+struct BlockedEntry {
+    task: Task,
+    blockers: Vec<Task>,
+}
 
-/// List tasks that are blocked by open dependencies.
-pub fn run(db_path: &Path, json: bool) -> Result<(), String> {
+/// List tasks that are blocked by open dependencies, each followed by its
+/// open blockers. `--json` prints the bare task list unless `explain` asks
+/// for `{task, blockers}` entries.
+pub fn run(db_path: &Path, json: bool, explain: bool) -> Result<(), Error> {
     let db = Database::open(db_path)?;
     let tasks = db.get_blocked_tasks()?;
-    super::print_tasks(&tasks, json)
+    if json && !explain {
+        return print_tasks(&tasks, true);
+    }
+
+    let mut entries = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let blocker_ids: Vec<String> = db
+            .get_blockers(&task.id)?
+            .into_iter()
+            .map(|d| d.parent_id)
+            .collect();
+        let blockers: Vec<Task> = db
+            .get_tasks_by_ids(&blocker_ids)?
+            .into_iter()
+            .filter(|b| b.status != Status::Done)
+            .collect();
+        entries.push(BlockedEntry { task, blockers });
+    }
+    if json && ndjson_enabled() {
+        return print_json_lines(&entries);
+    }
+    if json {
+        return print_json(&entries);
+    }
+
+    if entries.is_empty() {
+        println!("No tasks found.");
+        return Ok(());
+    }
+    print_task_header();
+    for entry in &entries {
+        print_task_row(&entry.task);
+        for b in &entry.blockers {
+            println!("    waiting on {} [{}] {}", b.id, b.status, b.title);
+        }
+    }
+    Ok(())
 }
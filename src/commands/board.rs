@@ -0,0 +1,54 @@
+use std::path::Path;
+
+use colored::Colorize;
+
+use super::print_json;
+use crate::db::Database;
+use crate::error::Error;
+use crate::models::Task;
+
+/// Render the kanban board as stacked Open / In Progress / Blocked / Done
+/// columns, optionally restricted to children of the given epics and to the
+/// given priorities.
+pub fn run(db_path: &Path, epics: &[String], priorities: &[u8], json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let board = db.board(epics, priorities)?;
+
+    if json {
+        print_json(&board)?;
+        return Ok(());
+    }
+
+    let columns: [(&str, &[Task]); 4] = [
+        ("Open", &board.open),
+        ("In Progress", &board.in_progress),
+        ("Blocked", &board.blocked),
+        ("Done", &board.done),
+    ];
+    let pw = super::priority_width();
+    for (i, (name, tasks)) in columns.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}", format!("{name} ({})", tasks.len()).bold());
+        println!("{}", "-".repeat(60));
+        if tasks.is_empty() {
+            println!("  (none)");
+            continue;
+        }
+        for t in tasks.iter() {
+            let title = if t.title.len() > 48 {
+                format!("{}...", &t.title[..45])
+            } else {
+                t.title.clone()
+            };
+            println!(
+                "  {:<12} {:<pw$} {}",
+                t.id,
+                super::format_priority(t.priority),
+                title
+            );
+        }
+    }
+    Ok(())
+}
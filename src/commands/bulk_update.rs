@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use super::{confirm, print_json};
+use crate::db::{Database, TaskFilter};
+use crate::error::Error;
+
+/// Apply `--set-*` changes to every task matching `filter`. A filter with no
+/// status, priority, tag, or parent is refused unless `all` is set, so a bare
+/// `tk bulk-update --set-status done` cannot rewrite the whole database.
+pub fn run(
+    db_path: &Path,
+    filter: &TaskFilter,
+    all: bool,
+    set_status: Option<&str>,
+    set_priority: Option<u8>,
+    add_tags: Option<&str>,
+    json: bool,
+) -> Result<(), Error> {
+    let filtered = filter.status.is_some()
+        || filter.priority.is_some()
+        || filter.tag.is_some()
+        || filter.parent.is_some();
+    if !filtered && !all {
+        return Err(Error::validation(
+            "refusing to update every task: pass --status, --priority, --tag, or --parent, or --all",
+        ));
+    }
+    let add_tags: Vec<String> = add_tags
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let db = Database::open(db_path)?;
+    let filter = TaskFilter {
+        include_done: all,
+        ..*filter
+    };
+    let tasks = db.bulk_update(&filter, set_status, set_priority, &add_tags)?;
+
+    if json {
+        print_json(&tasks)
+    } else {
+        let noun = if tasks.len() == 1 { "task" } else { "tasks" };
+        confirm(&format!("Updated {} {noun}", tasks.len()));
+        Ok(())
+    }
+}
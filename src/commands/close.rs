@@ -1,22 +1,24 @@
-use std::path::Path;
+use super::{confirm, print_json};
+use crate::backend::Backend;
+use crate::error::Error;
+use crate::models::{Status, Task, validate_close_reason};
 
-use crate::db::Database;
-use crate::models::validate_close_reason;
-
-/// Close a task, optionally recording a comment and close reason.
+/// Close a task, optionally recording closing comments and a close reason,
+/// then report the dependents it leaves with no open blocker. With
+/// `close_parent` (or the `auto_close_epic` config key), a parent whose last
+/// open subtask this was is closed too and reported.
 pub fn run(
-    db_path: &Path,
+    backend: &dyn Backend,
     id: &str,
-    comment: Option<&str>,
+    comments: &[String],
     reason: Option<&str>,
     force: bool,
+    close_parent: bool,
     json: bool,
-) -> Result<(), String> {
-    let db = Database::open(db_path)?;
-
+) -> Result<(), Error> {
     // Validate reason before touching the DB.
     if let Some(r) = reason {
-        validate_close_reason(r)?;
+        validate_close_reason(r).map_err(Error::validation)?;
     }
 
     // Close guard: refuse to close a parent task (epic) that still has open
@@ -25,10 +27,10 @@ pub fn run(
     // Note: dependency-graph blocking (dep add) is a separate relationship;
     // closing a prerequisite (blocker) while dependents are still open is the
     // expected workflow and is not guarded here.
-    let children = db.get_children(id)?;
+    let children = backend.children(id)?;
     let open_children: Vec<_> = children
         .iter()
-        .filter(|t| t.status != crate::models::Status::Done)
+        .filter(|t| t.status != Status::Done)
         .collect();
 
     if !open_children.is_empty() && !force {
@@ -36,29 +38,78 @@ pub fn run(
             .iter()
             .map(|t| format!("{} ({})", t.id, t.title))
             .collect();
-        return Err(format!(
+        return Err(Error::conflict(format!(
             "task {} has {} open dependent(s): {}. use --force to close anyway",
             id,
             open_children.len(),
             names.join(", ")
-        ));
+        )));
     }
 
-    db.close_task(id, reason)?;
-
-    if let Some(body) = comment {
-        db.add_comment(id, body)?;
-    }
+    let parent = open_parent(backend, id)?;
+    let task = backend.close_task(id, reason, comments, close_parent)?;
+    let unblocked: Vec<String> = newly_unblocked(backend, id)?
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+    // The parent may have been closed by --close-parent or auto_close_epic.
+    let closed_parent = match parent {
+        Some(p) => backend
+            .tasks_by_ids(std::slice::from_ref(&p.id))?
+            .into_iter()
+            .find(|t| t.status == Status::Done)
+            .map(|t| t.id),
+        None => None,
+    };
 
     if json {
-        let task = db
-            .get_task(id)?
-            .ok_or_else(|| format!("task not found: {id}"))?;
-        let j = serde_json::to_string_pretty(&task).map_err(|e| format!("json error: {e}"))?;
-        println!("{j}");
+        let mut value = serde_json::to_value(&task).map_err(|e| format!("json error: {e}"))?;
+        value["unblocked"] = serde_json::json!(unblocked);
+        value["closed_parent"] = serde_json::json!(closed_parent);
+        print_json(&value)?;
     } else {
-        println!("Closed task {id}");
+        confirm(&format!("Closed task {id}"));
+        if !unblocked.is_empty() {
+            confirm(&format!("unblocked: {}", unblocked.join(", ")));
+        }
+        if let Some(pid) = &closed_parent {
+            confirm(&format!("Closed parent {pid}: all subtasks are done"));
+        }
     }
 
     Ok(())
 }
+
+/// `id`'s parent, if it has one that is not yet done.
+fn open_parent(backend: &dyn Backend, id: &str) -> Result<Option<Task>, Error> {
+    let Some(parent_id) = backend
+        .tasks_by_ids(&[id.to_string()])?
+        .into_iter()
+        .next()
+        .and_then(|t| t.parent_id)
+    else {
+        return Ok(None);
+    };
+    Ok(backend
+        .tasks_by_ids(&[parent_id])?
+        .into_iter()
+        .find(|p| p.status != Status::Done))
+}
+
+/// Open dependents of `id` whose blockers are now all done.
+fn newly_unblocked(backend: &dyn Backend, id: &str) -> Result<Vec<Task>, Error> {
+    let mut unblocked = Vec::new();
+    for task in backend.dependents(id)? {
+        if task.status == Status::Done {
+            continue;
+        }
+        if backend
+            .blockers(&task.id)?
+            .iter()
+            .all(|b| b.status == Status::Done)
+        {
+            unblocked.push(task);
+        }
+    }
+    Ok(unblocked)
+}
@@ -1,17 +1,80 @@
 use std::path::Path;
 
+use super::{confirm, print_json, read_text_file};
 use crate::db::Database;
+use crate::error::Error;
+
+/// Add a comment to a task, optionally as a reply to one of its earlier
+/// comments. The body comes either from the positional argument or from
+/// `--file` (`-` reads stdin), never both.
+pub fn run(
+    db_path: &Path,
+    id: &str,
+    body: Option<&str>,
+    file: Option<&Path>,
+    reply_to: Option<i64>,
+    json: bool,
+) -> Result<(), Error> {
+    let body = match (body, file) {
+        (Some(_), Some(_)) => {
+            return Err(Error::validation(
+                "give the comment body either as an argument or with --file, not both",
+            ));
+        }
+        (Some(b), None) => b.to_string(),
+        (None, Some(path)) => read_text_file(path, "comment body")?,
+        (None, None) => {
+            return Err(Error::validation(
+                "comment body required (argument or --file)",
+            ));
+        }
+    };
 
-pub fn run(db_path: &Path, id: &str, body: &str, json: bool) -> Result<(), String> {
     let db = Database::open(db_path)?;
-    let comment = db.add_comment(id, body)?;
+    let comment = match reply_to {
+        Some(parent) => db.add_reply(id, &body, parent)?,
+        None => db.add_comment(id, &body)?,
+    };
 
     if json {
-        let j = serde_json::to_string_pretty(&comment).map_err(|e| format!("json error: {e}"))?;
-        println!("{j}");
+        print_json(&comment)?;
+    } else if let Some(parent) = reply_to {
+        confirm(&format!("Added reply to comment {parent} on {id}"));
     } else {
-        println!("Added comment to {id}");
+        confirm(&format!("Added comment to {id}"));
     }
 
     Ok(())
 }
+
+/// Replace the body of comment `comment_id`.
+pub fn edit(db_path: &Path, comment_id: i64, body: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let comment = db.update_comment(comment_id, body)?;
+
+    if json {
+        print_json(&comment)?;
+    } else {
+        confirm(&format!(
+            "Edited comment {comment_id} on {}",
+            comment.task_id
+        ));
+    }
+    Ok(())
+}
+
+/// Delete comment `comment_id`.
+pub fn delete(db_path: &Path, comment_id: i64, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let comment = db.delete_comment(comment_id)?;
+
+    if json {
+        print_json(&comment)?;
+    } else {
+        confirm(&format!(
+            "Deleted comment {comment_id} from {}",
+            comment.task_id
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use super::print_json;
+use crate::db::Database;
+use crate::error::Error;
+use crate::models::RecentComment;
+
+/// List comments: one task's comments (oldest first) when `id` is given,
+/// otherwise the most recent comments across all tasks (newest first).
+pub fn run(db_path: &Path, id: Option<&str>, limit: u32, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+
+    let comments: Vec<RecentComment> = match id {
+        Some(id) => {
+            let task = db
+                .get_task(id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+            db.get_comments(id)?
+                .into_iter()
+                .take(limit as usize)
+                .map(|comment| RecentComment {
+                    comment,
+                    task_title: task.title.clone(),
+                })
+                .collect()
+        }
+        None => db.recent_comments(limit)?,
+    };
+
+    if json {
+        print_json(&comments)?;
+        return Ok(());
+    }
+
+    if comments.is_empty() {
+        println!("No comments found.");
+        return Ok(());
+    }
+
+    for c in &comments {
+        println!(
+            "[{}] {} ({}): {}",
+            c.comment.created_at.format("%Y-%m-%d %H:%M"),
+            c.comment.task_id,
+            c.task_title,
+            c.comment.body
+        );
+    }
+    Ok(())
+}
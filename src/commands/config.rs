@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use super::{confirm, print_json};
+use crate::db::{Database, READY_ORDERS, TEMPLATE_KEY_PREFIX, VIEW_KEY_PREFIX};
+use crate::error::Error;
+use crate::models::{parse_id_length, parse_priority};
+
+/// Keys `tk config set` accepts.
+const SETTABLE_KEYS: [&str; 9] = [
+    "auto_block",
+    "auto_close_epic",
+    "auto_epic_tag",
+    "default_priority",
+    "format",
+    "id_length",
+    "max_dep_depth",
+    "priority_names",
+    "ready_order",
+];
+
+/// Print every config key and its value.
+pub fn list(db_path: &Path, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    // Templates and views are JSON blobs with their own commands
+    // (`tk template list`, `tk view list`).
+    let entries: Vec<_> = db
+        .list_config()?
+        .into_iter()
+        .filter(|(key, _)| {
+            !key.starts_with(TEMPLATE_KEY_PREFIX) && !key.starts_with(VIEW_KEY_PREFIX)
+        })
+        .collect();
+
+    if json {
+        let out: serde_json::Map<_, _> = entries
+            .into_iter()
+            .map(|(key, value)| (key, serde_json::Value::String(value)))
+            .collect();
+        return print_json(&out);
+    }
+
+    if entries.is_empty() {
+        println!("No config keys set.");
+        return Ok(());
+    }
+    let width = entries.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    for (key, value) in &entries {
+        println!("{key:<width$}  {value}");
+    }
+    Ok(())
+}
+
+/// Print one config key's value. Fails when the key is unset.
+pub fn get(db_path: &Path, key: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let value = db
+        .get_config(key)?
+        .ok_or_else(|| Error::not_found(format!("config key not found: {key}")))?;
+    if json {
+        return print_json(&serde_json::json!({ "key": key, "value": value }));
+    }
+    println!("{value}");
+    Ok(())
+}
+
+/// Set a config key after checking the value is one the key accepts.
+pub fn set(db_path: &Path, key: &str, value: &str, json: bool) -> Result<(), Error> {
+    let value = normalize(key, value).map_err(Error::validation)?;
+    let db = Database::open(db_path)?;
+    db.set_config(key, &value)?;
+    if json {
+        return print_json(&serde_json::json!({ "key": key, "value": value }));
+    }
+    confirm(&format!("Set {key} = {value}"));
+    Ok(())
+}
+
+/// The value stored for `key`, in canonical form (priority names become
+/// numbers, `yes`/`on` become `true`), or an error for an unknown key or a
+/// value the key does not accept.
+fn normalize(key: &str, value: &str) -> Result<String, String> {
+    match key {
+        "prefix" => Err("prefix cannot be set directly; use `tk reprefix`".to_string()),
+        "version" | "schema_version" => Err(format!("{key} is managed by tk and cannot be set")),
+        "auto_block" | "auto_close_epic" | "auto_epic_tag" | "priority_names" => {
+            match value.trim().to_lowercase().as_str() {
+                "true" | "1" | "yes" | "on" => Ok("true".to_string()),
+                "false" | "0" | "no" | "off" => Ok("false".to_string()),
+                _ => Err(format!("invalid {key} value: {value}. use true or false")),
+            }
+        }
+        "default_priority" => parse_priority(value).map(|p| p.to_string()),
+        "format" => match value.trim() {
+            v @ ("table" | "json" | "porcelain") => Ok(v.to_string()),
+            _ => Err(format!(
+                "invalid format value: {value}. valid formats: table, json, porcelain"
+            )),
+        },
+        "id_length" => parse_id_length(value).map(|n| n.to_string()),
+        "max_dep_depth" => match value.trim().parse::<usize>() {
+            Ok(n) if n > 0 => Ok(n.to_string()),
+            _ => Err(format!(
+                "invalid max_dep_depth value: {value}. use a positive number"
+            )),
+        },
+        "ready_order" => match value.trim() {
+            v if READY_ORDERS.contains(&v) => Ok(v.to_string()),
+            _ => Err(format!(
+                "invalid ready_order value: {value}. use {}",
+                READY_ORDERS.join(" or ")
+            )),
+        },
+        _ => Err(format!(
+            "unknown config key: {key}. settable keys: {}",
+            SETTABLE_KEYS.join(", ")
+        )),
+    }
+}
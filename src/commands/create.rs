@@ -1,31 +1,23 @@
 use std::path::Path;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
-use crate::db::Database;
-use crate::models::{Status, Task};
+use super::{confirm, print_json, text_or_file};
+use crate::backend::{Backend, NewTask};
+use crate::error::Error;
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
-    db_path: &Path,
+    backend: &dyn Backend,
     title: &str,
-    priority: u8,
+    priority: Option<u8>,
     description: Option<&str>,
+    description_file: Option<&Path>,
     tags: Option<&str>,
     parent: Option<&str>,
+    due_at: Option<DateTime<Utc>>,
     json: bool,
-) -> Result<(), String> {
-    let db = Database::open(db_path)?;
-
-    let id = if let Some(parent_id) = parent {
-        // Verify parent exists
-        db.get_task(parent_id)?
-            .ok_or_else(|| format!("parent task not found: {parent_id}"))?;
-        db.generate_child_id(parent_id)?
-    } else {
-        db.generate_id()?
-    };
-
-    let now = Utc::now();
+) -> Result<(), Error> {
     let tag_list: Vec<String> = tags
         .map(|t| {
             t.split(',')
@@ -35,37 +27,20 @@ pub fn run(
         })
         .unwrap_or_default();
 
-    let task = Task {
-        id: id.clone(),
-        title: title.to_string(),
-        description: description.map(|s| s.to_string()),
-        status: Status::Open,
+    let description = text_or_file(description, description_file, "description")?;
+    let task = backend.create_task(&NewTask {
+        title,
         priority,
-        assignee: None,
-        parent_id: parent.map(|s| s.to_string()),
+        description: description.as_deref(),
         tags: tag_list,
-        created_at: now,
-        updated_at: now,
-        close_reason: None,
-        notes: None,
-    };
-
-    db.insert_task(&task)?;
-
-    // Auto-tag parent as epic when a child is created
-    if let Some(parent_id) = parent {
-        let mut parent_tags = db.get_task_tags(parent_id)?;
-        if !parent_tags.contains(&"epic".to_string()) {
-            parent_tags.push("epic".to_string());
-            db.update_tags(parent_id, &parent_tags)?;
-        }
-    }
+        parent,
+        due_at,
+    })?;
 
     if json {
-        let j = serde_json::to_string_pretty(&task).map_err(|e| format!("json error: {e}"))?;
-        println!("{j}");
+        print_json(&task)?;
     } else {
-        println!("Created task {id}: {title}");
+        confirm(&format!("Created task {}: {title}", task.id));
     }
 
     Ok(())
@@ -0,0 +1,30 @@
+use std::path::Path;
+
+use super::{format_status, print_json};
+use crate::db::Database;
+use crate::error::Error;
+
+/// Print the longest chain of open blockers leading into `id`, deepest
+/// blocker first and `id` last.
+pub fn run(db_path: &Path, id: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let chain = db.longest_blocker_chain(id)?;
+
+    if json {
+        return print_json(&chain);
+    }
+    if chain.len() <= 1 {
+        println!("Task {id} has no open blockers.");
+        return Ok(());
+    }
+    for (n, t) in chain.iter().enumerate() {
+        println!(
+            "{}. {} [{}] {}",
+            n + 1,
+            t.id,
+            format_status(&t.status),
+            t.title
+        );
+    }
+    Ok(())
+}
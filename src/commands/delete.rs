@@ -0,0 +1,19 @@
+use std::path::Path;
+
+use super::{confirm, print_json};
+use crate::db::Database;
+use crate::error::Error;
+
+/// Permanently delete a task, its comments, and its dependency edges.
+pub fn run(db_path: &Path, id: &str, force: bool, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let task = db.delete_task(id, force)?;
+
+    if json {
+        print_json(&task)?;
+        return Ok(());
+    }
+
+    confirm(&format!("Deleted {}: {}", task.id, task.title));
+    Ok(())
+}
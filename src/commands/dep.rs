@@ -1,17 +1,189 @@
 use std::path::Path;
 
-use crate::db::Database;
+use super::{confirm, format_status, print_json};
+use crate::db::{Database, DepGraph};
+use crate::error::Error;
+use crate::models::{Status, Task};
 
-pub fn add(db_path: &Path, child: &str, parent: &str) -> Result<(), String> {
+/// Add `child` as blocked by each of `parents` in one transaction. Edges
+/// that already exist are skipped and reported; any other failure (missing
+/// task, cycle) rolls back the whole batch. With `auto_block`, an open
+/// `child` is moved to `blocked` even when the `auto_block` config key is off.
+pub fn add(
+    db_path: &Path,
+    child: &str,
+    parents: &[String],
+    auto_block: bool,
+    json: bool,
+) -> Result<(), Error> {
     let db = Database::open(db_path)?;
-    db.add_dependency(child, parent)?;
-    println!("Added dependency: {child} is blocked by {parent}");
+    let (added, skipped) = db.with_transaction(|db| {
+        let mut added = Vec::new();
+        let mut skipped = Vec::new();
+        for parent in parents {
+            let exists = db
+                .get_blockers(child)?
+                .iter()
+                .any(|dep| dep.parent_id == *parent);
+            if exists {
+                skipped.push(parent.as_str());
+            } else {
+                db.add_dependency(child, parent)?;
+                added.push(parent.as_str());
+            }
+        }
+        if auto_block {
+            db.recompute_blocked_status(child, true)?;
+        }
+        Ok((added, skipped))
+    })?;
+
+    if json {
+        return print_json(&serde_json::json!({
+            "child": child,
+            "added": added,
+            "skipped": skipped,
+        }));
+    }
+    for parent in &added {
+        confirm(&format!("Added dependency: {child} is blocked by {parent}"));
+    }
+    for parent in &skipped {
+        confirm(&format!("Skipped: {child} is already blocked by {parent}"));
+    }
     Ok(())
 }
 
-pub fn remove(db_path: &Path, child: &str, parent: &str) -> Result<(), String> {
+pub fn remove(db_path: &Path, child: &str, parent: &str) -> Result<(), Error> {
     let db = Database::open(db_path)?;
     db.remove_dependency(child, parent)?;
-    println!("Removed dependency: {child} no longer blocked by {parent}");
+    confirm(&format!(
+        "Removed dependency: {child} no longer blocked by {parent}"
+    ));
+    Ok(())
+}
+
+/// Print the tasks `id` is blocked by and the tasks it blocks.
+pub fn list(db_path: &Path, id: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    db.get_task(id)?
+        .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+
+    let blocker_ids: Vec<String> = db
+        .get_blockers(id)?
+        .into_iter()
+        .map(|d| d.parent_id)
+        .collect();
+    let blockers = db.get_tasks_by_ids(&blocker_ids)?;
+    let dependents = db.get_dependents(id)?;
+
+    if json {
+        let out = serde_json::json!({ "blockers": blockers, "dependents": dependents });
+        print_json(&out)?;
+        return Ok(());
+    }
+
+    print_edge_list("Blockers", &blockers);
+    print_edge_list("Dependents", &dependents);
+    Ok(())
+}
+
+/// Print the dependency graph: each task with the tasks blocking it. With
+/// `json`, emit `{nodes, edges}` from the same data; with `dot`, a Graphviz
+/// `digraph` whose edges point from blocker to blocked. `root` narrows the
+/// graph to that task and everything it transitively blocks.
+pub fn graph(
+    db_path: &Path,
+    all: bool,
+    root: Option<&str>,
+    dot: bool,
+    json: bool,
+) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let mut graph = db.dependency_graph(all)?;
+    if let Some(root) = root {
+        let task = db
+            .get_task(root)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {root}")))?;
+        if !all && task.status == Status::Done {
+            return Err(Error::validation(format!(
+                "task {root} is done; pass --all to graph from it"
+            )));
+        }
+        graph = graph.reachable_from(root)?;
+    }
+
+    if dot {
+        print!("{}", to_dot(&graph));
+        return Ok(());
+    }
+    if json {
+        return print_json(&graph);
+    }
+
+    if graph.nodes.is_empty() {
+        println!("No tasks");
+        return Ok(());
+    }
+    for t in &graph.nodes {
+        println!("{} [{}] {}", t.id, format_status(&t.status), t.title);
+        for edge in graph.edges.iter().filter(|e| e.child_id == t.id) {
+            println!("  <- blocked by {}", edge.parent_id);
+        }
+    }
     Ok(())
 }
+
+/// Render `graph` as a Graphviz `digraph`. Nodes are labelled with ID and
+/// title and colored like [`format_status`].
+fn to_dot(graph: &DepGraph) -> String {
+    let mut out = String::from("digraph tacks {\n    node [shape=box];\n");
+    for t in &graph.nodes {
+        let label = format!("{}\n{}", t.id, t.title);
+        out.push_str(&format!(
+            "    {} [label={}, color={}];\n",
+            dot_quote(&t.id),
+            dot_quote(&label),
+            dot_color(&t.status)
+        ));
+    }
+    for e in &graph.edges {
+        out.push_str(&format!(
+            "    {} -> {};\n",
+            dot_quote(&e.parent_id),
+            dot_quote(&e.child_id)
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Graphviz color names matching the terminal palette of [`format_status`].
+fn dot_color(status: &Status) -> &'static str {
+    match status {
+        Status::Open => "green",
+        Status::InProgress => "cyan",
+        Status::Done => "gray",
+        Status::Blocked => "red",
+    }
+}
+
+/// Quote a DOT ID, escaping backslashes and double quotes; newlines become
+/// DOT's `\n` line break.
+fn dot_quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+fn print_edge_list(heading: &str, tasks: &[Task]) {
+    println!("{heading}:");
+    if tasks.is_empty() {
+        println!("  (none)");
+    }
+    for t in tasks {
+        println!("  - {} [{}] {}", t.id, format_status(&t.status), t.title);
+    }
+}
@@ -0,0 +1,58 @@
+use std::path::Path;
+
+use super::print_json;
+use crate::db::{Database, IntegrityFinding, Severity};
+use crate::error::Error;
+
+/// Run the integrity checks and print what they find. With `fix`, repair the
+/// fixable findings in one transaction first and report what is left. Fails
+/// while any error-severity finding remains.
+pub fn run(db_path: &Path, fix: bool, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let fixed = if fix { db.repair_integrity()? } else { 0 };
+    let findings = db.integrity_report()?;
+
+    if json {
+        let mut out = serde_json::json!({ "findings": findings });
+        if fix {
+            out["fixed"] = serde_json::json!(fixed);
+        }
+        print_json(&out)?;
+    } else {
+        if fix {
+            println!("Fixed {fixed} row(s)");
+        }
+        print_findings(&findings);
+    }
+
+    let errors = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Error)
+        .count();
+    if errors > 0 {
+        return Err(format!("{errors} integrity error(s) found").into());
+    }
+    Ok(())
+}
+
+fn print_findings(findings: &[IntegrityFinding]) {
+    if findings.is_empty() {
+        println!("No problems found");
+        return;
+    }
+    for f in findings {
+        let severity = match f.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let hint = if f.fixable {
+            " (fixable with --fix)"
+        } else {
+            ""
+        };
+        println!(
+            "{severity:<8} {:<20} {:<12} {}{hint}",
+            f.check, f.task_id, f.message
+        );
+    }
+}
@@ -1,38 +1,80 @@
 use std::path::Path;
 
-use crate::db::Database;
-use crate::models::Status;
+use super::print_json;
+use crate::db::{Database, TaskFilter};
+use crate::error::Error;
+use crate::models::{Effort, Status, Task};
+
+/// An epic with its direct subtasks, as counted by `tk epic` and
+/// `tk stats --epic`.
+pub struct EpicProgress {
+    pub epic: Task,
+    pub children: Vec<Task>,
+}
+
+impl EpicProgress {
+    /// Number of subtasks that are done.
+    pub fn done(&self) -> usize {
+        self.children
+            .iter()
+            .filter(|c| c.status == Status::Done)
+            .count()
+    }
+
+    /// Share of subtasks done, rounded down; 0 for an epic with none.
+    pub fn pct(&self) -> u32 {
+        let total = self.children.len();
+        if total > 0 {
+            (self.done() as f64 / total as f64 * 100.0) as u32
+        } else {
+            0
+        }
+    }
+
+    /// Estimate and spent minutes summed over the subtasks.
+    pub fn effort(&self) -> Effort {
+        Effort::of(&self.children)
+    }
+}
+
+/// Every non-done task tagged `epic`, with its subtasks.
+pub fn epic_progress(db: &Database) -> Result<Vec<EpicProgress>, Error> {
+    let epics = db.list_tasks(&TaskFilter {
+        tag: Some("epic"),
+        ..Default::default()
+    })?;
+    epics
+        .into_iter()
+        .map(|epic| {
+            let children = db.get_children(&epic.id)?;
+            Ok(EpicProgress { epic, children })
+        })
+        .collect()
+}
 
 /// Show epic progress: tasks tagged 'epic' with child completion stats.
-pub fn run(db_path: &Path, json: bool) -> Result<(), String> {
+pub fn run(db_path: &Path, json: bool) -> Result<(), Error> {
     let db = Database::open(db_path)?;
-
-    // Get all tasks tagged as epic
-    let epics = db.list_tasks(false, None, None, Some("epic"), None, None)?;
+    let epics = epic_progress(&db)?;
 
     if json {
-        let mut results = Vec::new();
-        for epic in &epics {
-            let children = db.get_children(&epic.id)?;
-            let total = children.len();
-            let done = children.iter().filter(|c| c.status == Status::Done).count();
-            let pct = if total > 0 {
-                (done as f64 / total as f64 * 100.0) as u32
-            } else {
-                0
-            };
-            results.push(serde_json::json!({
-                "id": epic.id,
-                "title": epic.title,
-                "status": epic.status,
-                "priority": epic.priority,
-                "children_total": total,
-                "children_done": done,
-                "progress_pct": pct,
-            }));
-        }
-        let j = serde_json::to_string_pretty(&results).map_err(|e| format!("json error: {e}"))?;
-        println!("{j}");
+        let results: Vec<serde_json::Value> = epics
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "id": p.epic.id,
+                    "title": p.epic.title,
+                    "status": p.epic.status,
+                    "priority": p.epic.priority,
+                    "children_total": p.children.len(),
+                    "children_done": p.done(),
+                    "progress_pct": p.pct(),
+                    "estimate_minutes": p.effort().estimate_minutes,
+                    "spent_minutes": p.effort().spent_minutes,
+                })
+            })
+            .collect();
+        print_json(&results)?;
         return Ok(());
     }
 
@@ -42,34 +84,32 @@ pub fn run(db_path: &Path, json: bool) -> Result<(), String> {
         return Ok(());
     }
 
+    let pw = super::priority_width();
     println!(
-        "{:<12} {:<4} {:<12} {:<40} PROGRESS",
-        "ID", "PRI", "STATUS", "TITLE"
+        "{:<12} {:<pw$} {:<12} {:<40} {:<14} SPENT/EST",
+        "ID", "PRI", "STATUS", "TITLE", "PROGRESS"
     );
-    println!("{}", "-".repeat(80));
-    for epic in &epics {
-        let children = db.get_children(&epic.id)?;
-        let total = children.len();
-        let done = children.iter().filter(|c| c.status == Status::Done).count();
-        let pct = if total > 0 {
-            (done as f64 / total as f64 * 100.0) as u32
-        } else {
-            0
-        };
+    println!("{}", "-".repeat(96));
+    for p in &epics {
+        let epic = &p.epic;
         let title = if epic.title.len() > 38 {
             format!("{}...", &epic.title[..35])
         } else {
             epic.title.clone()
         };
+        let effort = p.effort();
+        let spent_est = if effort == Effort::default() {
+            "-".to_string()
+        } else {
+            format!("{}m/{}m", effort.spent_minutes, effort.estimate_minutes)
+        };
         println!(
-            "{:<12} {:<4} {:<12} {:<40} {}/{} ({}%)",
+            "{:<12} {:<pw$} {:<12} {:<40} {:<14} {spent_est}",
             epic.id,
             super::format_priority(epic.priority),
             super::format_status(&epic.status),
             title,
-            done,
-            total,
-            pct,
+            format!("{}/{} ({}%)", p.done(), p.children.len(), p.pct()),
         );
     }
     Ok(())
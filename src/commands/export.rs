@@ -0,0 +1,131 @@
+use std::path::Path;
+
+use super::{confirm, print_json};
+use crate::db::{Database, TaskFilter};
+use crate::error::Error;
+use crate::models::Task;
+
+/// Columns of `tk export --format csv`, in order.
+const CSV_COLUMNS: [&str; 10] = [
+    "id",
+    "title",
+    "status",
+    "priority",
+    "assignee",
+    "parent_id",
+    "tags",
+    "created_at",
+    "updated_at",
+    "close_reason",
+];
+
+/// Write a standalone SQLite copy of the database to `out`, optionally
+/// leaving out done tasks.
+pub fn run(db_path: &Path, out: &Path, open_only: bool, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let count = db.export_sqlite(out, open_only)?;
+
+    if json {
+        let j = serde_json::json!({
+            "path": out.display().to_string(),
+            "tasks": count,
+            "open_only": open_only,
+        });
+        print_json(&j)?;
+        return Ok(());
+    }
+
+    confirm(&format!("Exported {count} task(s) to {}", out.display()));
+    Ok(())
+}
+
+/// Export tasks as CSV (`csv`) or a JSON array, to `output` or stdout.
+///
+/// With `output`, the file is overwritten and a summary is printed (as JSON
+/// with `json`); without it the export itself is the only thing printed.
+pub fn tasks(
+    db_path: &Path,
+    csv: bool,
+    output: Option<&Path>,
+    open_only: bool,
+    json: bool,
+) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let tasks = db.list_tasks(&TaskFilter {
+        include_done: !open_only,
+        include_archived: !open_only,
+        ..Default::default()
+    })?;
+    let contents = if csv {
+        tasks_to_csv(&tasks)
+    } else {
+        let mut j = serde_json::to_string_pretty(&tasks).map_err(|e| format!("json error: {e}"))?;
+        j.push('\n');
+        j
+    };
+
+    let Some(out) = output else {
+        print!("{contents}");
+        return Ok(());
+    };
+    std::fs::write(out, contents).map_err(|e| format!("failed to write {}: {e}", out.display()))?;
+
+    if json {
+        let j = serde_json::json!({
+            "path": out.display().to_string(),
+            "format": if csv { "csv" } else { "json" },
+            "tasks": tasks.len(),
+            "open_only": open_only,
+        });
+        print_json(&j)?;
+        return Ok(());
+    }
+
+    confirm(&format!(
+        "Exported {} task(s) to {}",
+        tasks.len(),
+        out.display()
+    ));
+    Ok(())
+}
+
+/// Render tasks as RFC 4180 CSV: a header row, CRLF line endings, and tags
+/// joined with `;` so they stay in one cell.
+fn tasks_to_csv(tasks: &[Task]) -> String {
+    let mut out = csv_row(CSV_COLUMNS.iter().map(|c| c.to_string()));
+    for t in tasks {
+        out.push_str(&csv_row([
+            t.id.clone(),
+            t.title.clone(),
+            t.status.as_str().to_string(),
+            t.priority.to_string(),
+            t.assignee.clone().unwrap_or_default(),
+            t.parent_id.clone().unwrap_or_default(),
+            t.tags.join(";"),
+            t.created_at.to_rfc3339(),
+            t.updated_at.to_rfc3339(),
+            t.close_reason.clone().unwrap_or_default(),
+        ]));
+    }
+    out
+}
+
+fn csv_row(fields: impl IntoIterator<Item = String>) -> String {
+    let mut row = fields
+        .into_iter()
+        .map(|f| csv_field(&f))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+/// Quote a field if it contains a delimiter, quote, or line break, doubling
+/// any embedded quotes.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::Path;
+
+use super::print_json;
+use crate::db::Database;
+use crate::error::Error;
+
+/// Print the recorded field changes of a task, oldest first.
+pub fn run(db_path: &Path, id: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    db.get_task(id)?
+        .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+    let activity = db.get_activity(id)?;
+
+    if json {
+        return print_json(&activity);
+    }
+    if activity.is_empty() {
+        println!("No history for {id}.");
+        return Ok(());
+    }
+    for a in &activity {
+        println!(
+            "[{}] {}: {} -> {}",
+            a.created_at.format("%Y-%m-%d %H:%M"),
+            a.field,
+            a.old_value.as_deref().unwrap_or("-"),
+            a.new_value.as_deref().unwrap_or("-"),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::{confirm, print_json, read_text_file};
+use crate::db::Database;
+use crate::error::Error;
+use crate::models::{PRIORITIES, Status, Task, validate_close_reason};
+
+/// One task from an import file. Matches the objects `tk list --json`
+/// prints; everything but `title` is optional.
+#[derive(Debug, Deserialize)]
+struct ImportTask {
+    /// Only used to resolve `parent_id` and `blocked_by` within the file;
+    /// the task gets a fresh ID.
+    id: Option<String>,
+    title: String,
+    description: Option<String>,
+    status: Option<Status>,
+    priority: Option<u8>,
+    assignee: Option<String>,
+    parent_id: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    created_at: Option<DateTime<Utc>>,
+    close_reason: Option<String>,
+    notes: Option<String>,
+    due_at: Option<DateTime<Utc>>,
+    estimate_minutes: Option<u32>,
+    spent_minutes: Option<u32>,
+    /// Tasks this one is blocked by: IDs, or task objects as in the
+    /// `blockers` array of `tk show --json`.
+    #[serde(default, alias = "blockers")]
+    blocked_by: Vec<TaskRef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TaskRef {
+    Id(String),
+    Task { id: String },
+}
+
+impl TaskRef {
+    fn id(&self) -> &str {
+        match self {
+            TaskRef::Id(id) | TaskRef::Task { id } => id,
+        }
+    }
+}
+
+/// Create tasks from `file`: a JSON array of task objects, or a Markdown
+/// checklist (`- [ ] title`, nested items become subtasks). IDs in the file
+/// are replaced by new ones, with `parent_id` and `blocked_by` remapped to
+/// match; references to tasks outside the file must already exist. All
+/// tasks are created in one transaction, so a bad file imports nothing.
+pub fn run(db_path: &Path, file: &Path, json: bool) -> Result<(), Error> {
+    let text = read_text_file(file, "import file")?;
+    let records = if text.trim_start().starts_with('[') {
+        serde_json::from_str::<Vec<ImportTask>>(&text).map_err(|e| {
+            Error::validation(format!("invalid import file {}: {e}", file.display()))
+        })?
+    } else {
+        parse_checklist(&text)
+    };
+    if records.is_empty() {
+        return Err(Error::validation(format!(
+            "invalid import file {}: no tasks found (expected a JSON array or `- [ ] title` lines)",
+            file.display()
+        )));
+    }
+
+    let db = Database::open(db_path)?;
+    let ids = db.with_transaction(|db| import(db, &records))?;
+
+    if json {
+        return print_json(&db.get_tasks_by_ids(&ids)?);
+    }
+    confirm(&format!(
+        "Imported {} task(s) from {}",
+        ids.len(),
+        file.display()
+    ));
+    Ok(())
+}
+
+/// Insert `records`, parents before their children, then add the
+/// dependencies between them. Returns the new IDs in file order.
+fn import(db: &Database, records: &[ImportTask]) -> Result<Vec<String>, Error> {
+    let mut new_ids: Vec<Option<String>> = vec![None; records.len()];
+    // File ID -> new ID, filled in as tasks are created.
+    let mut remap: HashMap<&str, String> = HashMap::new();
+    let in_file: HashMap<&str, usize> = records
+        .iter()
+        .enumerate()
+        .filter_map(|(i, r)| r.id.as_deref().map(|id| (id, i)))
+        .collect();
+
+    // Each pass creates every task whose parent is ready; a pass that makes
+    // no progress means the parents form a cycle.
+    while new_ids.iter().any(Option::is_none) {
+        let mut progressed = false;
+        for (i, record) in records.iter().enumerate() {
+            if new_ids[i].is_some() {
+                continue;
+            }
+            let parent = match record.parent_id.as_deref() {
+                Some(pid) if in_file.contains_key(pid) => match remap.get(pid) {
+                    Some(new_pid) => Some(new_pid.clone()),
+                    None => continue,
+                },
+                Some(pid) => {
+                    db.get_task(pid)?
+                        .ok_or_else(|| Error::not_found(format!("parent task not found: {pid}")))?;
+                    Some(pid.to_string())
+                }
+                None => None,
+            };
+            let id = insert(db, record, parent.as_deref())?;
+            if let Some(old) = record.id.as_deref() {
+                remap.insert(old, id.clone());
+            }
+            new_ids[i] = Some(id);
+            progressed = true;
+        }
+        if !progressed {
+            return Err(Error::validation(
+                "invalid import file: parent_id references form a cycle",
+            ));
+        }
+    }
+    let new_ids: Vec<String> = new_ids.into_iter().flatten().collect();
+
+    for (record, child) in records.iter().zip(&new_ids) {
+        for blocker in &record.blocked_by {
+            let blocker = blocker.id();
+            let parent = match remap.get(blocker) {
+                Some(id) => id.clone(),
+                None => {
+                    db.get_task(blocker)?
+                        .ok_or_else(|| Error::not_found(format!("task not found: {blocker}")))?;
+                    blocker.to_string()
+                }
+            };
+            let exists = db
+                .get_blockers(child)?
+                .iter()
+                .any(|dep| dep.parent_id == parent);
+            if !exists {
+                db.add_dependency(child, &parent)?;
+            }
+        }
+    }
+    Ok(new_ids)
+}
+
+/// Insert one record under `parent` with a freshly generated ID.
+fn insert(db: &Database, record: &ImportTask, parent: Option<&str>) -> Result<String, Error> {
+    let title = record.title.trim();
+    if title.is_empty() {
+        return Err(Error::validation(
+            "invalid import file: a task has an empty title",
+        ));
+    }
+    let priority = match record.priority {
+        Some(p) if !PRIORITIES.contains(&p) => {
+            return Err(Error::validation(format!(
+                "invalid priority {p} in import file: must be 0-3"
+            )));
+        }
+        Some(p) => p,
+        None => db.default_priority()?,
+    };
+    if let Some(reason) = &record.close_reason {
+        validate_close_reason(reason).map_err(Error::validation)?;
+    }
+    let status = record.status.unwrap_or(Status::Open);
+    let close_reason = match (&record.close_reason, status) {
+        (Some(reason), Status::Done) => Some(reason.clone()),
+        (None, Status::Done) => Some("done".to_string()),
+        _ => None,
+    };
+
+    let id = match parent {
+        Some(pid) => db.generate_child_id(pid)?,
+        None => db.generate_id()?,
+    };
+    let now = Utc::now();
+    db.insert_task(&Task {
+        id: id.clone(),
+        title: title.to_string(),
+        description: record.description.clone(),
+        status,
+        priority,
+        assignee: record.assignee.clone(),
+        parent_id: parent.map(str::to_string),
+        tags: record.tags.clone(),
+        created_at: record.created_at.unwrap_or(now),
+        updated_at: now,
+        close_reason,
+        notes: record.notes.clone(),
+        due_at: record.due_at,
+        position: 0.0,
+        archived_at: None,
+        estimate_minutes: record.estimate_minutes,
+        spent_minutes: record.spent_minutes,
+    })?;
+    if let Some(pid) = parent {
+        db.auto_tag_epic(pid)?;
+    }
+    Ok(id)
+}
+
+/// Turn `- [ ] title` lines into tasks; `[x]` items are imported as done.
+/// An item indented under another becomes its subtask. Other lines are
+/// ignored.
+fn parse_checklist(text: &str) -> Vec<ImportTask> {
+    let mut records = Vec::new();
+    // (indent, synthetic ID) of the items enclosing the current line.
+    let mut open: Vec<(usize, String)> = Vec::new();
+    for line in text.lines() {
+        let Some((indent, done, title)) = checklist_item(line) else {
+            continue;
+        };
+        while open.last().is_some_and(|(i, _)| *i >= indent) {
+            open.pop();
+        }
+        let id = format!("line-{}", records.len() + 1);
+        records.push(ImportTask {
+            id: Some(id.clone()),
+            title: title.to_string(),
+            description: None,
+            status: done.then_some(Status::Done),
+            priority: None,
+            assignee: None,
+            parent_id: open.last().map(|(_, pid)| pid.clone()),
+            tags: Vec::new(),
+            created_at: None,
+            close_reason: None,
+            notes: None,
+            due_at: None,
+            estimate_minutes: None,
+            spent_minutes: None,
+            blocked_by: Vec::new(),
+        });
+        open.push((indent, id));
+    }
+    records
+}
+
+/// Split a checklist line into its indent width, whether it is checked, and
+/// its title. `-`, `*`, and `+` bullets are accepted; a tab counts as four
+/// spaces.
+fn checklist_item(line: &str) -> Option<(usize, bool, &str)> {
+    let rest = line.trim_start();
+    let indent = line[..line.len() - rest.len()]
+        .chars()
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum();
+    let rest = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix("* "))
+        .or_else(|| rest.strip_prefix("+ "))?;
+    let (done, title) = if let Some(t) = rest.strip_prefix("[ ]") {
+        (false, t)
+    } else if let Some(t) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, t)
+    } else {
+        return None;
+    };
+    let title = title.trim();
+    (!title.is_empty()).then_some((indent, done, title))
+}
@@ -1,19 +1,188 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use super::confirm;
 use crate::db::Database;
+use crate::error::Error;
+use crate::models::validate_prefix;
+
+/// Prefix used when neither `--prefix` nor an imported export supplies one.
+const DEFAULT_PREFIX: &str = "tk";
+
+/// Initialize the database. `auto_block`, `id_length`, `priority_names` and
+/// `default_priority` set the config keys of the same name; leaving them out
+/// keeps whatever an earlier init or the import set.
+pub fn run(
+    db_path: &Path,
+    prefix: Option<&str>,
+    import: Option<&Path>,
+    auto_block: bool,
+    id_length: Option<usize>,
+    priority_names: bool,
+    default_priority: Option<u8>,
+) -> Result<(), Error> {
+    // Validate before touching the filesystem so a bad prefix leaves no trace.
+    if let Some(prefix) = prefix {
+        validate_prefix(prefix).map_err(Error::validation)?;
+    }
 
-pub fn run(db_path: &Path, prefix: &str) -> Result<(), String> {
     // Create the .tacks directory if it doesn't exist
     if let Some(parent) = db_path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| format!("failed to create directory: {e}"))?;
     }
 
+    if let Some(source) = import {
+        import_export(db_path, prefix, source)?;
+        return apply_settings(
+            &Database::open(db_path)?,
+            auto_block,
+            id_length,
+            priority_names,
+            default_priority,
+        );
+    }
+    let prefix = prefix.unwrap_or(DEFAULT_PREFIX);
+
     let db = Database::open(db_path)?;
     db.migrate()?;
+
+    // Re-running init must not silently change the prefix under existing
+    // tasks; that would mix two ID schemes. `tk reprefix` rewrites them.
+    if let Some(existing) = db.get_config("prefix")?.filter(|p| p != prefix) {
+        let task_count: i64 = db.task_count_by_status()?.iter().map(|(_, n)| n).sum();
+        if task_count > 0 {
+            return Err(Error::conflict(format!(
+                "database already uses prefix {existing:?} for {task_count} task(s); use `tk reprefix {prefix}` to rename them"
+            )));
+        }
+    }
     db.set_config("prefix", prefix)?;
     db.set_config("version", env!("CARGO_PKG_VERSION"))?;
+    apply_settings(&db, auto_block, id_length, priority_names, default_priority)?;
 
-    println!("Initialized tacks database at {}", db_path.display());
+    confirm(&format!(
+        "Initialized tacks database at {}",
+        db_path.display()
+    ));
     println!("Task prefix: {prefix}");
     Ok(())
 }
+
+/// Store the settings chosen with `tk init` flags as config keys.
+fn apply_settings(
+    db: &Database,
+    auto_block: bool,
+    id_length: Option<usize>,
+    priority_names: bool,
+    default_priority: Option<u8>,
+) -> Result<(), Error> {
+    if auto_block {
+        db.set_config("auto_block", "true")?;
+    }
+    if priority_names {
+        db.set_config("priority_names", "true")?;
+    }
+    if let Some(length) = id_length {
+        db.set_config("id_length", &length.to_string())?;
+    }
+    if let Some(priority) = default_priority {
+        db.set_config("default_priority", &priority.to_string())?;
+    }
+    Ok(())
+}
+
+/// Create the database at `db_path` from a `tk export --sqlite` file.
+///
+/// The export is copied to a scratch file beside the target, migrated, and
+/// renamed into place only once everything succeeded, so a bad import never
+/// leaves a half-initialized database behind. Task IDs are renamed when
+/// `prefix` differs from the export's.
+fn import_export(db_path: &Path, prefix: Option<&str>, source: &Path) -> Result<(), Error> {
+    if db_path.exists() {
+        return Err(Error::conflict(format!(
+            "database already exists at {}; --import only initializes a new one",
+            db_path.display()
+        )));
+    }
+    if !source.is_file() {
+        return Err(Error::not_found(format!(
+            "import file not found: {}",
+            source.display()
+        )));
+    }
+
+    let scratch = scratch_path(db_path);
+    remove_db_files(&scratch);
+    let result = std::fs::copy(source, &scratch)
+        .map_err(|e| Error::from(format!("failed to copy {}: {e}", source.display())))
+        .and_then(|_| prepare_import(&scratch, prefix, source))
+        .and_then(|summary| {
+            std::fs::rename(&scratch, db_path)
+                .map_err(|e| format!("failed to move imported database into place: {e}"))?;
+            Ok(summary)
+        });
+    let (prefix, tasks, renamed) = match result {
+        Ok(summary) => summary,
+        Err(e) => {
+            remove_db_files(&scratch);
+            return Err(e);
+        }
+    };
+
+    println!(
+        "Initialized tacks database at {} from {} ({tasks} task(s))",
+        db_path.display(),
+        source.display()
+    );
+    if renamed > 0 {
+        confirm(&format!(
+            "Renamed {renamed} imported task(s) to prefix {prefix}"
+        ));
+    }
+    println!("Task prefix: {prefix}");
+    Ok(())
+}
+
+/// Migrate and configure the copied export in place. Returns the final
+/// prefix, the task count, and how many task IDs were renamed. The
+/// connection is closed on return so the file can be renamed.
+fn prepare_import(
+    path: &Path,
+    prefix: Option<&str>,
+    source: &Path,
+) -> Result<(String, i64, usize), Error> {
+    let not_an_export = || {
+        Error::validation(format!(
+            "{} is not a tacks export (expected a file written by `tk export --sqlite`)",
+            source.display()
+        ))
+    };
+    let db = Database::open(path).map_err(|_| not_an_export())?;
+    let exported_prefix = db
+        .get_config("prefix")
+        .map_err(|_| not_an_export())?
+        .ok_or_else(not_an_export)?;
+    db.migrate()?;
+
+    let prefix = prefix.unwrap_or(&exported_prefix).to_string();
+    let renamed = db.reprefix(&prefix)?;
+    db.set_config("version", env!("CARGO_PKG_VERSION"))?;
+    db.recompute_blocked_flags()?;
+    let tasks = db.task_count_by_status()?.iter().map(|(_, n)| n).sum();
+    Ok((prefix, tasks, renamed))
+}
+
+/// `<db>.importing`, next to the target so the final rename stays on one filesystem.
+fn scratch_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".importing");
+    db_path.with_file_name(name)
+}
+
+/// Best-effort removal of a database file and its WAL sidecars.
+fn remove_db_files(path: &Path) {
+    for suffix in ["", "-wal", "-shm"] {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(suffix);
+        let _ = std::fs::remove_file(PathBuf::from(name));
+    }
+}
@@ -1,18 +1,70 @@
-use std::path::Path;
-
-use super::print_tasks;
-use crate::db::Database;
+use super::{
+    ndjson_enabled, print_json, print_json_lines, print_task_ids, print_tasks, print_tasks_labeled,
+    print_tasks_porcelain,
+};
+use crate::backend::Backend;
+use crate::db::TaskFilter;
+use crate::error::Error;
+use crate::models::{
+    TASK_FIELDS, Task, parse_date_bound, parse_fields, select_fields, validate_close_reason,
+};
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
-    db_path: &Path,
-    all: bool,
-    status: Option<&str>,
-    priority: Option<u8>,
-    tag: Option<&str>,
-    parent: Option<&str>,
+    backend: &dyn Backend,
+    filter: &TaskFilter,
+    created_after: Option<&str>,
+    created_before: Option<&str>,
+    updated_after: Option<&str>,
     json: bool,
-) -> Result<(), String> {
-    let db = Database::open(db_path)?;
-    let tasks = db.list_tasks(all, status, priority, tag, parent, None)?;
+    porcelain: bool,
+    labels: bool,
+    ids_only: bool,
+    fields: Option<&str>,
+) -> Result<(), Error> {
+    let fields = fields
+        .map(|f| parse_fields(f, TASK_FIELDS))
+        .transpose()
+        .map_err(Error::validation)?;
+    if let Some(reason) = filter.close_reason {
+        validate_close_reason(reason).map_err(Error::validation)?;
+    }
+    let filter = TaskFilter {
+        created_after: created_after
+            .map(|v| parse_date_bound(v, false))
+            .transpose()
+            .map_err(Error::validation)?,
+        created_before: created_before
+            .map(|v| parse_date_bound(v, false))
+            .transpose()
+            .map_err(Error::validation)?,
+        updated_after: updated_after
+            .map(|v| parse_date_bound(v, false))
+            .transpose()
+            .map_err(Error::validation)?,
+        ..*filter
+    };
+    let tasks = backend.list_tasks(&filter)?;
+    if ids_only {
+        return print_task_ids(&tasks);
+    }
+    if porcelain {
+        return print_tasks_porcelain(&tasks);
+    }
+    if let Some(ref fields) = fields {
+        let mut value = if labels {
+            serde_json::Value::Array(tasks.iter().map(Task::to_labeled_json).collect())
+        } else {
+            serde_json::to_value(&tasks).map_err(|e| format!("json error: {e}"))?
+        };
+        select_fields(&mut value, fields);
+        if let Some(items) = value.as_array().filter(|_| ndjson_enabled()) {
+            return print_json_lines(items);
+        }
+        return print_json(&value);
+    }
+    if json && labels {
+        return print_tasks_labeled(&tasks);
+    }
     print_tasks(&tasks, json)
 }
@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use super::print_tasks;
+use crate::db::{Database, TaskFilter};
+use crate::error::Error;
+use crate::models::Status;
+
+/// List the open and in-progress tasks assigned to `assignee` (from
+/// `--assignee` or `TACKS_USER`).
+pub fn run(db_path: &Path, assignee: Option<&str>, json: bool) -> Result<(), Error> {
+    let assignee = assignee
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .ok_or_else(|| Error::validation("no assignee: pass --assignee or set TACKS_USER"))?;
+    let db = Database::open(db_path)?;
+    let mut tasks = db.list_tasks(&TaskFilter {
+        assignee: Some(assignee),
+        ..Default::default()
+    })?;
+    tasks.retain(|t| matches!(t.status, Status::Open | Status::InProgress));
+    print_tasks(&tasks, json)
+}
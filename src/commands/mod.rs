@@ -1,29 +1,224 @@
+pub mod archive;
 pub mod blocked;
+pub mod board;
+pub mod bulk_update;
 pub mod children;
 pub mod close;
 pub mod comment;
+pub mod comments;
+pub mod config;
 pub mod create;
+pub mod critical_path;
+pub mod delete;
 pub mod dep;
+pub mod doctor;
 pub mod epic;
+pub mod export;
+pub mod history;
+pub mod import;
 pub mod init;
 pub mod list;
+pub mod mine;
+pub mod r#move;
+pub mod next;
+pub mod overdue;
+pub mod plan;
 pub mod prime;
+pub mod prune;
 pub mod ready;
+pub mod recent;
+pub mod reopen;
+pub mod replay;
+pub mod reprefix;
+pub mod search;
 pub mod show;
 pub mod stats;
+pub mod tag;
+pub mod template;
 pub mod update;
+pub mod view;
+pub mod watch;
 
-use crate::models::Task;
+use crate::error::Error;
+use crate::models::{Task, priority_label};
 use colored::Colorize;
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-/// Format a priority number as a colored string.
+/// Whether JSON output is wrapped in an [`Envelope`] (`--envelope`). Set once
+/// in `main`, like the color override.
+static ENVELOPE: AtomicBool = AtomicBool::new(false);
+
+/// Turn the `--envelope` JSON wrapper on or off for the rest of the process.
+pub fn set_envelope(on: bool) {
+    ENVELOPE.store(on, Ordering::Relaxed);
+}
+
+/// Whether `--envelope` is in effect.
+pub fn envelope_enabled() -> bool {
+    ENVELOPE.load(Ordering::Relaxed)
+}
+
+/// Whether task lists print as JSON Lines (`--ndjson`). Set once in `main`.
+static NDJSON: AtomicBool = AtomicBool::new(false);
+
+/// Turn JSON Lines list output on or off for the rest of the process.
+pub fn set_ndjson(on: bool) {
+    NDJSON.store(on, Ordering::Relaxed);
+}
+
+/// Whether `--ndjson` is in effect.
+pub fn ndjson_enabled() -> bool {
+    NDJSON.load(Ordering::Relaxed)
+}
+
+/// Whether confirmation messages ("Created task …") are suppressed
+/// (`--quiet`). Set once in `main`.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Turn confirmation messages off or on for the rest of the process.
+pub fn set_quiet(on: bool) {
+    QUIET.store(on, Ordering::Relaxed);
+}
+
+/// Whether `--quiet` is in effect.
+pub fn quiet_enabled() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Print a human-readable confirmation of a change, unless `--quiet` is set.
+/// Data output (tables, JSON) and errors never go through here.
+pub fn confirm(message: &str) {
+    if !quiet_enabled() {
+        println!("{message}");
+    }
+}
+
+/// Whether priorities print as names (`high`) rather than `P1`. Set once in
+/// `main` from the `priority_names` config key.
+static PRIORITY_NAMES: AtomicBool = AtomicBool::new(false);
+
+/// Turn priority names on or off for the rest of the process.
+pub fn set_priority_names(on: bool) {
+    PRIORITY_NAMES.store(on, Ordering::Relaxed);
+}
+
+/// Column width that fits any [`format_priority`] label.
+pub fn priority_width() -> usize {
+    if PRIORITY_NAMES.load(Ordering::Relaxed) {
+        8
+    } else {
+        4
+    }
+}
+
+/// Uniform JSON wrapper for `--envelope`: `{"ok":true,"data":...,"meta":{...}}`
+/// on success, `{"ok":false,"error":{"message":...},"meta":{...}}` on failure.
+#[derive(Serialize)]
+pub struct Envelope<T: Serialize> {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<EnvelopeError>,
+    pub meta: EnvelopeMeta,
+}
+
+#[derive(Serialize)]
+pub struct EnvelopeError {
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct EnvelopeMeta {
+    /// Number of items when `data` is an array.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+    /// Version of the `tk` binary that produced the output.
+    pub version: &'static str,
+}
+
+impl Envelope<serde_json::Value> {
+    pub fn success(data: serde_json::Value) -> Self {
+        Envelope {
+            ok: true,
+            meta: EnvelopeMeta {
+                count: data.as_array().map(Vec::len),
+                version: env!("CARGO_PKG_VERSION"),
+            },
+            data: Some(data),
+            error: None,
+        }
+    }
+
+    pub fn failure(message: &str) -> Self {
+        Envelope {
+            ok: false,
+            data: None,
+            error: Some(EnvelopeError {
+                message: message.to_string(),
+            }),
+            meta: EnvelopeMeta {
+                count: None,
+                version: env!("CARGO_PKG_VERSION"),
+            },
+        }
+    }
+}
+
+/// Pretty-print a command's JSON result, wrapped in an [`Envelope`] when
+/// `--envelope` is set. Every `--json` code path goes through here.
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<(), Error> {
+    let j = if envelope_enabled() {
+        let data = serde_json::to_value(value).map_err(|e| format!("json error: {e}"))?;
+        serde_json::to_string_pretty(&Envelope::success(data))
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+    .map_err(|e| format!("json error: {e}"))?;
+    println!("{j}");
+    Ok(())
+}
+
+/// Print each item as compact JSON on its own line (`--ndjson`). Lines are
+/// written as they are serialized, and a closed pipe (`| head`) ends the
+/// output quietly instead of failing.
+pub fn print_json_lines<T: Serialize>(items: &[T]) -> Result<(), Error> {
+    let mut out = std::io::stdout().lock();
+    for item in items {
+        let line = serde_json::to_string(item).map_err(|e| format!("json error: {e}"))?;
+        match writeln!(out, "{line}") {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(e) => return Err(format!("write error: {e}").into()),
+        }
+    }
+    Ok(())
+}
+
+/// Print a failed command as an error [`Envelope`] on stdout.
+pub fn print_json_error(message: &str) {
+    if let Ok(j) = serde_json::to_string_pretty(&Envelope::failure(message)) {
+        println!("{j}");
+    }
+}
+
+/// Format a priority number as a colored string: `P1`, or `high` when the
+/// `priority_names` config key is on.
 pub fn format_priority(p: u8) -> String {
+    let label = if PRIORITY_NAMES.load(Ordering::Relaxed) && p <= 3 {
+        priority_label(p).to_string()
+    } else {
+        format!("P{p}")
+    };
     match p {
-        0 => "P0".red().bold().to_string(),
-        1 => "P1".yellow().bold().to_string(),
-        2 => "P2".white().to_string(),
-        3 => "P3".bright_black().to_string(),
-        _ => format!("P{p}"),
+        0 => label.red().bold().to_string(),
+        1 => label.yellow().bold().to_string(),
+        2 => label.white().to_string(),
+        3 => label.bright_black().to_string(),
+        _ => label,
     }
 }
 
@@ -38,10 +233,12 @@ pub fn format_status(s: &crate::models::Status) -> String {
 }
 
 /// Print a list of tasks as a table or JSON.
-pub fn print_tasks(tasks: &[Task], json: bool) -> Result<(), String> {
+pub fn print_tasks(tasks: &[Task], json: bool) -> Result<(), Error> {
+    if json && ndjson_enabled() {
+        return print_json_lines(tasks);
+    }
     if json {
-        let j = serde_json::to_string_pretty(tasks).map_err(|e| format!("json error: {e}"))?;
-        println!("{j}");
+        print_json(tasks)?;
         return Ok(());
     }
 
@@ -50,31 +247,230 @@ pub fn print_tasks(tasks: &[Task], json: bool) -> Result<(), String> {
         return Ok(());
     }
 
-    // Simple aligned table output
+    print_task_header();
+    for t in tasks {
+        print_task_row(t);
+    }
+    Ok(())
+}
+
+/// Table width used when stdout is not a terminal, so piped output is stable.
+const DEFAULT_TABLE_WIDTH: usize = 90;
+
+/// Columns left after the title for the tags, at any terminal width.
+const TAGS_MIN_WIDTH: usize = 8;
+
+/// Narrowest title column, even when the terminal is narrower than the table.
+const TITLE_MIN_WIDTH: usize = 20;
+
+/// Width of the task table: the terminal's when stdout is one, else
+/// [`DEFAULT_TABLE_WIDTH`].
+fn table_width() -> usize {
+    terminal_size::terminal_size_of(std::io::stdout())
+        .map(|(terminal_size::Width(w), _)| usize::from(w))
+        .unwrap_or(DEFAULT_TABLE_WIDTH)
+}
+
+/// Title column width: whatever the table width leaves after the ID,
+/// priority, and status columns and [`TAGS_MIN_WIDTH`].
+fn title_width(table_width: usize) -> usize {
+    let fixed = 12 + 1 + priority_width() + 1 + 12 + 1 + 1;
+    table_width
+        .saturating_sub(fixed + TAGS_MIN_WIDTH)
+        .max(TITLE_MIN_WIDTH)
+}
+
+/// Shorten `title` to fit a column of `width` characters, ending in `...`
+/// when cut. Counts characters, not bytes, so multi-byte titles never split.
+fn fit_title(title: &str, width: usize) -> String {
+    if title.chars().count() > width - 2 {
+        let kept: String = title.chars().take(width - 5).collect();
+        format!("{kept}...")
+    } else {
+        title.to_string()
+    }
+}
+
+/// Print the column header of the task table used by [`print_tasks`].
+pub fn print_task_header() {
+    let pw = priority_width();
+    let width = table_width();
+    let tw = title_width(width);
     println!(
-        "{:<12} {:<4} {:<12} {:<50} TAGS",
+        "{:<12} {:<pw$} {:<12} {:<tw$} TAGS",
         "ID", "PRI", "STATUS", "TITLE"
     );
-    println!("{}", "-".repeat(90));
+    println!("{}", "-".repeat(width));
+}
+
+/// Print one row of the task table used by [`print_tasks`].
+pub fn print_task_row(t: &Task) {
+    let pw = priority_width();
+    let tw = title_width(table_width());
+    let tags = if t.tags.is_empty() {
+        String::new()
+    } else {
+        t.tags.join(", ")
+    };
+    println!(
+        "{:<12} {:<pw$} {:<12} {:<tw$} {}",
+        t.id,
+        format_priority(t.priority),
+        format_status(&t.status),
+        fit_title(&t.title, tw),
+        tags,
+    );
+}
+
+/// Print one task ID per line and nothing else, for shell loops
+/// (`--format ids`). Output is empty when there are no tasks.
+pub fn print_task_ids(tasks: &[Task]) -> Result<(), Error> {
     for t in tasks {
-        let tags = if t.tags.is_empty() {
-            String::new()
-        } else {
-            t.tags.join(", ")
-        };
-        let title = if t.title.len() > 48 {
-            format!("{}...", &t.title[..45])
-        } else {
-            t.title.clone()
-        };
-        println!(
-            "{:<12} {:<4} {:<12} {:<50} {}",
-            t.id,
-            format_priority(t.priority),
-            format_status(&t.status),
-            title,
-            tags,
-        );
+        println!("{}", t.id);
+    }
+    Ok(())
+}
+
+/// Print tasks as a JSON array with `priority_label`, `status_label`, and
+/// `color` hints added to each task (`--labels`).
+pub fn print_tasks_labeled(tasks: &[Task]) -> Result<(), Error> {
+    let values: Vec<serde_json::Value> = tasks.iter().map(Task::to_labeled_json).collect();
+    if ndjson_enabled() {
+        return print_json_lines(&values);
     }
+    print_json(&values)?;
     Ok(())
 }
+
+/// Escape a porcelain value so every field stays on a single line.
+fn porcelain_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Render one task as a porcelain record: `key=value` lines in a fixed order,
+/// terminated by a blank line. Absent optional fields are emitted as `key=`.
+fn porcelain_record(t: &Task) -> String {
+    let fields = [
+        ("id", t.id.clone()),
+        ("title", t.title.clone()),
+        ("status", t.status.as_str().to_string()),
+        ("priority", t.priority.to_string()),
+        ("assignee", t.assignee.clone().unwrap_or_default()),
+        ("parent_id", t.parent_id.clone().unwrap_or_default()),
+        ("tags", t.tags.join(",")),
+        ("created_at", t.created_at.to_rfc3339()),
+        ("updated_at", t.updated_at.to_rfc3339()),
+        ("close_reason", t.close_reason.clone().unwrap_or_default()),
+        ("notes", t.notes.clone().unwrap_or_default()),
+        ("description", t.description.clone().unwrap_or_default()),
+        (
+            "due_at",
+            t.due_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+        ),
+        ("position", t.position.to_string()),
+        (
+            "archived_at",
+            t.archived_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+        ),
+        (
+            "estimate_minutes",
+            t.estimate_minutes
+                .map(|m| m.to_string())
+                .unwrap_or_default(),
+        ),
+        (
+            "spent_minutes",
+            t.spent_minutes.map(|m| m.to_string()).unwrap_or_default(),
+        ),
+    ];
+    let mut out = String::new();
+    for (key, value) in fields {
+        out.push_str(key);
+        out.push('=');
+        out.push_str(&porcelain_escape(&value));
+        out.push('\n');
+    }
+    out.push('\n');
+    out
+}
+
+/// Print tasks in the stable porcelain format for shell scripting.
+///
+/// Unlike the human table, this format is guaranteed stable across versions:
+/// keys are never renamed or reordered (new keys may be appended). Backslashes,
+/// newlines, and carriage returns in values are escaped as `\\`, `\n`, and `\r`.
+pub fn print_tasks_porcelain(tasks: &[Task]) -> Result<(), Error> {
+    for t in tasks {
+        print!("{}", porcelain_record(t));
+    }
+    Ok(())
+}
+
+/// Read text for an option from a file, or stdin when the path is `-`.
+/// Inner newlines are kept; only the single trailing newline most files end
+/// with is dropped. `what` names the text in errors ("comment body").
+pub fn read_text_file(path: &Path, what: &str) -> Result<String, Error> {
+    let mut text = if path == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())
+            .map_err(|e| format!("failed to read {what} from stdin: {e}"))?
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?
+    };
+    if text.ends_with('\n') {
+        text.pop();
+        if text.ends_with('\r') {
+            text.pop();
+        }
+    }
+    if text.trim().is_empty() {
+        return Err(Error::validation(format!("{what} is empty")));
+    }
+    Ok(text)
+}
+
+/// Resolve an option that can be given inline (`--<name>`) or read from a
+/// file (`--<name>-file`, `-` for stdin), but not both.
+pub fn text_or_file(
+    value: Option<&str>,
+    file: Option<&Path>,
+    name: &str,
+) -> Result<Option<String>, Error> {
+    match (value, file) {
+        (Some(_), Some(_)) => Err(Error::validation(format!(
+            "give either --{name} or --{name}-file, not both"
+        ))),
+        (Some(v), None) => Ok(Some(v.to_string())),
+        (None, Some(path)) => read_text_file(path, name).map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_fills_default_width() {
+        // ID, priority, status, and title columns plus the tags reserve add
+        // up to the table width, so the rule lines up with the columns.
+        let tw = title_width(DEFAULT_TABLE_WIDTH);
+        assert_eq!(
+            12 + 1 + priority_width() + 1 + 12 + 1 + tw + 1 + TAGS_MIN_WIDTH,
+            DEFAULT_TABLE_WIDTH
+        );
+        assert_eq!(title_width(200), tw + 110);
+        assert_eq!(title_width(10), TITLE_MIN_WIDTH);
+    }
+
+    #[test]
+    fn test_fit_title_counts_characters() {
+        assert_eq!(fit_title("short", 20), "short");
+        let long = "é".repeat(30);
+        let fitted = fit_title(&long, 20);
+        assert_eq!(fitted, format!("{}...", "é".repeat(15)));
+    }
+}
@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use super::{confirm, print_json};
+use crate::db::Database;
+use crate::error::Error;
+
+/// Move a task under `parent`, or make it top-level when `parent` is empty.
+/// The task keeps its ID.
+pub fn run(db_path: &Path, id: &str, parent: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let new_parent = Some(parent).filter(|p| !p.is_empty());
+    let task = db.set_parent(id, new_parent)?;
+
+    if json {
+        print_json(&task)?;
+    } else if let Some(parent) = new_parent {
+        confirm(&format!("Moved task {id} under {parent}"));
+    } else {
+        confirm(&format!("Moved task {id} to the top level"));
+    }
+    Ok(())
+}
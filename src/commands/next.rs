@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use super::{print_json, print_tasks};
+use crate::db::Database;
+use crate::error::Error;
+
+/// Claim the top ready task for `assignee` (default "agent", as with
+/// `update --claim`) and print it. Picking and claiming share a transaction,
+/// so two agents running `tk next` at once never get the same task.
+pub fn run(db_path: &Path, assignee: Option<&str>, json: bool) -> Result<(), Error> {
+    let assignee = assignee
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .unwrap_or("agent");
+    let db = Database::open(db_path)?;
+
+    let claimed = db.with_transaction(|db| {
+        let Some(task) = db.get_ready_tasks(Some(1))?.into_iter().next() else {
+            return Ok(None);
+        };
+        db.update_task(
+            &task.id,
+            None,
+            None,
+            Some("in_progress"),
+            None,
+            Some(assignee),
+            None,
+            None,
+        )?;
+        db.get_task(&task.id)
+    })?;
+
+    match claimed {
+        Some(task) if json => print_json(&task),
+        Some(task) => {
+            println!("Claimed {} for {assignee}", task.id);
+            print_tasks(&[task], false)
+        }
+        None if json => print_json(&serde_json::Value::Null),
+        None => {
+            println!("Nothing ready to work on");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use super::{format_priority, print_json};
+use crate::db::Database;
+use crate::error::Error;
+
+/// List open and in-progress tasks past their due date, most overdue first.
+pub fn run(db_path: &Path, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let tasks = db.get_overdue_tasks()?;
+
+    if json {
+        return print_json(&tasks);
+    }
+    if tasks.is_empty() {
+        println!("No overdue tasks.");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let pw = super::priority_width();
+    println!(
+        "{:<12} {:<pw$} {:<10} {:<17} TITLE",
+        "ID", "PRI", "OVERDUE", "DUE"
+    );
+    println!("{}", "-".repeat(80));
+    for t in &tasks {
+        let Some(due_at) = t.due_at else { continue };
+        println!(
+            "{:<12} {:<pw$} {:<10} {:<17} {}",
+            t.id,
+            format_priority(t.priority),
+            overdue_by(due_at, now),
+            due_at.format("%Y-%m-%d %H:%M"),
+            t.title,
+        );
+    }
+    Ok(())
+}
+
+/// How long ago `due_at` passed, in whole days, or hours when under a day.
+fn overdue_by(due_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let late = now - due_at;
+    if late.num_days() > 0 {
+        format!("{}d", late.num_days())
+    } else {
+        format!("{}h", late.num_hours())
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use super::{format_priority, format_status, print_json};
+use crate::db::Database;
+use crate::error::Error;
+
+/// Print the open work as waves: everything in a wave can start once the
+/// earlier waves are done. Built from the same graph as `tk dep graph`.
+pub fn run(db_path: &Path, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let graph = db.dependency_graph(false)?;
+    let waves = graph.waves()?;
+
+    if json {
+        let j = serde_json::json!({ "waves": waves });
+        print_json(&j)?;
+        return Ok(());
+    }
+
+    if waves.is_empty() {
+        println!("No open tasks");
+        return Ok(());
+    }
+    for (i, wave) in waves.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("Wave {}:", i + 1);
+        for t in wave {
+            println!(
+                "  - {} [{}] {} {}",
+                t.id,
+                format_status(&t.status),
+                format_priority(t.priority),
+                t.title
+            );
+        }
+    }
+    Ok(())
+}
@@ -1,51 +1,174 @@
 use std::path::Path;
+use std::process::Command;
 
-use crate::db::Database;
-use crate::models::Task;
+use crate::db::{Database, TaskFilter};
+use crate::error::Error;
+use crate::models::{RecentComment, Status, Task};
 
 const READY_LIMIT: u32 = 5;
+const RECENT_COMMENTS_LIMIT: u32 = 5;
 
 const COMMAND_REFERENCE: &[&str] = &[
-    "tk create <title> [-p priority] [-d desc] [-t tags] [--parent id]",
+    "tk create <title> [-p priority] [-d desc] [-t tags] [--parent id] [--due date]",
     "tk list [-s status] [-p pri] [-t tag] [--json]",
     "tk ready [--limit N] [--json]",
+    "tk overdue [--json]",
     "tk show <id> [--json]",
     "tk update <id> [fields...] [--claim]",
     "tk close <id> [-c comment]",
     "tk dep add|remove <child> <parent>",
     "tk comment <id> <body>",
+    "tk comments --recent [--limit N]",
     "tk stats [--oneline] [--json]",
 ];
 
+/// Tasks mentioned in the commit messages of `git log <since>..HEAD`.
+struct Referenced<'a> {
+    since: &'a str,
+    /// `None` when git could not be run (not a repository, unknown ref, or
+    /// git not installed); the section is then left out.
+    tasks: Option<Vec<Task>>,
+}
+
 /// Run the `tk prime` command.
 ///
-/// Outputs an AI-optimized context summary composed of stats, in-progress tasks,
-/// and the ready queue. If no `.tacks/` database exists, exits silently.
-pub fn run(db_path: &Path, json: bool) -> Result<(), String> {
+/// Outputs an AI-optimized context summary composed of stats, overdue tasks,
+/// in-progress tasks, the ready queue, and recent comments. If no `.tacks/` database exists, exits silently.
+/// With `since`, also lists the tasks referenced by commits after that git ref.
+pub fn run(db_path: &Path, since: Option<&str>, json: bool) -> Result<(), Error> {
     // Silent exit when no tacks database is present — hooks call this on every
     // session, so it must be a no-op in projects that don't use tacks.
     if !db_path.exists() {
         return Ok(());
     }
+    if let Some(since) = since.filter(|s| s.starts_with('-')) {
+        return Err(Error::validation(format!("invalid git ref: {since}")));
+    }
 
     let db = Database::open(db_path)?;
+    render(&db, since, json)
+}
 
-    let by_status = db.task_count_by_status()?;
-    let in_progress = db.list_tasks(false, Some("in_progress"), None, None, None, None)?;
+/// Print the prime summary for an open database (shared with `tk watch`).
+pub fn render(db: &Database, since: Option<&str>, json: bool) -> Result<(), Error> {
+    let by_status = db.stats_snapshot()?.by_status;
+    let overdue = db.get_overdue_tasks()?;
+    let in_progress = db.list_tasks(&TaskFilter {
+        status: Some("in_progress"),
+        ..Default::default()
+    })?;
     let ready = db.get_ready_tasks(Some(READY_LIMIT))?;
+    let recent = db.recent_comments(RECENT_COMMENTS_LIMIT)?;
+    let referenced = match since {
+        Some(since) => Some(Referenced {
+            since,
+            tasks: referenced_tasks(db, since)?,
+        }),
+        None => None,
+    };
 
     if json {
-        print_json(&by_status, &in_progress, &ready)
+        print_json(
+            &by_status,
+            &overdue,
+            &in_progress,
+            &ready,
+            &recent,
+            referenced.as_ref(),
+        )
     } else {
-        print_markdown(&by_status, &in_progress, &ready)
+        print_markdown(
+            &by_status,
+            &overdue,
+            &in_progress,
+            &ready,
+            &recent,
+            referenced.as_ref(),
+        )
+    }
+}
+
+/// Tasks whose IDs appear in commit messages after `since`, most recent
+/// commit first. `None` if git fails for any reason.
+fn referenced_tasks(db: &Database, since: &str) -> Result<Option<Vec<Task>>, Error> {
+    let Some(messages) = git_log_messages(since) else {
+        return Ok(None);
+    };
+    let prefix = db.get_config("prefix")?.unwrap_or_else(|| "tk".to_string());
+    let ids = task_ids_in(&messages, &prefix);
+    let mut tasks = db.get_tasks_by_ids(&ids)?;
+    tasks.sort_by_key(|t| ids.iter().position(|id| *id == t.id));
+    Ok(Some(tasks))
+}
+
+/// The bodies of the commits in `since..HEAD`, or `None` when git is missing
+/// or the command fails (e.g. outside a repository).
+fn git_log_messages(since: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["log", "--format=%B", &format!("{since}..HEAD")])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Task IDs (`<prefix>-<hex>`, with any `.N` subtask suffixes) mentioned in
+/// `text`, in order of first mention and without duplicates.
+fn task_ids_in(text: &str, prefix: &str) -> Vec<String> {
+    let needle = format!("{prefix}-");
+    let mut ids: Vec<String> = Vec::new();
+    for (start, _) in text.match_indices(&needle) {
+        let at_boundary = text[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '-' || c == '_'));
+        if !at_boundary {
+            continue;
+        }
+        let rest = &text[start + needle.len()..];
+        let hex = rest
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(rest.len());
+        if hex == 0 {
+            continue;
+        }
+        let mut end = hex;
+        // Subtask suffixes: `.1`, `.1.2`, ...
+        while let Some(digits) = rest[end..].strip_prefix('.') {
+            let n = digits
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(digits.len());
+            if n == 0 {
+                break;
+            }
+            end += 1 + n;
+        }
+        // A hex run glued to more letters is a word, not an ID.
+        if rest[end..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+        {
+            continue;
+        }
+        let id = format!("{needle}{}", &rest[..end]);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
     }
+    ids
 }
 
 fn print_markdown(
     by_status: &[(String, i64)],
+    overdue: &[Task],
     in_progress: &[Task],
     ready: &[Task],
-) -> Result<(), String> {
+    recent: &[RecentComment],
+    referenced: Option<&Referenced>,
+) -> Result<(), Error> {
     println!("# Tacks: Project Status");
 
     // Stats section
@@ -58,6 +181,23 @@ fn print_markdown(
         println!("{}", parts.join(", "));
     }
 
+    // Overdue section, only when something has slipped
+    if !overdue.is_empty() {
+        println!();
+        println!("## Overdue");
+        for task in overdue {
+            if let Some(due_at) = task.due_at {
+                println!(
+                    "- {}: {} [P{}] (due {})",
+                    task.id,
+                    task.title,
+                    task.priority,
+                    due_at.format("%Y-%m-%d %H:%M")
+                );
+            }
+        }
+    }
+
     // In Progress section
     println!();
     println!("## In Progress");
@@ -84,6 +224,43 @@ fn print_markdown(
         }
     }
 
+    // Recent Activity section
+    println!();
+    println!("## Recent Activity");
+    if recent.is_empty() {
+        println!("none");
+    } else {
+        for c in recent {
+            println!(
+                "- {} ({}): {}",
+                c.comment.task_id, c.task_title, c.comment.body
+            );
+        }
+    }
+
+    // Recently referenced section, only with --since and a working git
+    if let Some(Referenced {
+        since,
+        tasks: Some(tasks),
+    }) = referenced
+    {
+        println!();
+        println!("## Recently referenced (since {since})");
+        if tasks.is_empty() {
+            println!("none");
+        } else {
+            for task in tasks {
+                println!(
+                    "- {}: {} [{}] [P{}]",
+                    task.id,
+                    task.title,
+                    task.status.as_str(),
+                    task.priority
+                );
+            }
+        }
+    }
+
     // Command Reference section
     println!();
     println!("## Command Reference");
@@ -96,14 +273,19 @@ fn print_markdown(
 
 fn print_json(
     by_status: &[(String, i64)],
+    overdue: &[Task],
     in_progress: &[Task],
     ready: &[Task],
-) -> Result<(), String> {
+    recent: &[RecentComment],
+    referenced: Option<&Referenced>,
+) -> Result<(), Error> {
     // Build a stats object with the four canonical statuses always present.
     let mut stats = serde_json::Map::new();
-    let canonical = ["open", "in_progress", "blocked", "done"];
-    for key in canonical {
-        stats.insert(key.to_string(), serde_json::Value::Number(0.into()));
+    for status in Status::ALL {
+        stats.insert(
+            status.as_str().to_string(),
+            serde_json::Value::Number(0.into()),
+        );
     }
     for (status, count) in by_status {
         stats.insert(status.clone(), serde_json::Value::Number((*count).into()));
@@ -114,22 +296,27 @@ fn print_json(
         .map(|s| serde_json::Value::String(s.to_string()))
         .collect();
 
-    let out = serde_json::json!({
+    let mut out = serde_json::json!({
         "stats": stats,
+        "overdue": overdue,
         "in_progress": in_progress,
         "ready": ready,
+        "recent_comments": recent,
         "command_reference": cmd_ref,
     });
+    // Present whenever --since is given; empty when git was unavailable.
+    if let Some(referenced) = referenced {
+        out["recently_referenced"] =
+            serde_json::json!(referenced.tasks.as_deref().unwrap_or_default());
+    }
 
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&out).map_err(|e| format!("json error: {e}"))?
-    );
+    super::print_json(&out)?;
 
     Ok(())
 }
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
     use assert_cmd::Command;
     use serde_json::Value;
@@ -196,6 +383,7 @@ mod tests {
 
         assert!(json["in_progress"].is_array());
         assert!(json["ready"].is_array());
+        assert!(json["recent_comments"].is_array());
         assert!(json["command_reference"].is_array());
         assert!(!json["command_reference"].as_array().unwrap().is_empty());
     }
@@ -278,4 +466,159 @@ mod tests {
         // stats should show 1 in_progress
         assert_eq!(json["stats"]["in_progress"], 1);
     }
+
+    #[test]
+    fn test_prime_shows_recent_comments() {
+        let tmp = TempDir::new().unwrap();
+        init_db(&tmp);
+
+        let create_out = tk(&tmp)
+            .args(["--json", "create", "Discussed task"])
+            .output()
+            .unwrap();
+        let created: Value = serde_json::from_slice(&create_out.stdout).unwrap();
+        let task_id = created["id"].as_str().unwrap().to_string();
+        tk(&tmp)
+            .args(["comment", &task_id, "Found the root cause"])
+            .assert()
+            .success();
+
+        let out = tk(&tmp).args(["--json", "prime"]).output().unwrap();
+        let json: Value = serde_json::from_slice(&out.stdout).unwrap();
+        let recent = json["recent_comments"].as_array().unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0]["body"], "Found the root cause");
+        assert_eq!(recent[0]["task_title"], "Discussed task");
+
+        let output = tk(&tmp).args(["prime"]).output().unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("## Recent Activity"));
+        assert!(stdout.contains("Found the root cause"));
+    }
+
+    #[test]
+    fn test_prime_shows_overdue_tasks() {
+        let tmp = TempDir::new().unwrap();
+        init_db(&tmp);
+
+        tk(&tmp)
+            .args(["create", "Slipping task", "--due", "2020-01-01"])
+            .assert()
+            .success();
+        tk(&tmp)
+            .args(["create", "Future task", "--due", "2999-01-01"])
+            .assert()
+            .success();
+
+        let out = tk(&tmp).args(["--json", "prime"]).output().unwrap();
+        let json: Value = serde_json::from_slice(&out.stdout).unwrap();
+        let overdue = json["overdue"].as_array().unwrap();
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0]["title"], "Slipping task");
+
+        let output = tk(&tmp).args(["prime"]).output().unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("## Overdue"));
+        assert!(stdout.contains("Slipping task"));
+    }
+
+    #[test]
+    fn test_task_ids_in_commit_messages() {
+        let text = "Fix login (tk-a1b2)\n\nCloses tk-a1b2.3, see tk-ff.\n\
+                    Not ids: atk-1234 tk-zz tk-12ab_x tk-\nRefs tk-c3d4";
+        assert_eq!(
+            super::task_ids_in(text, "tk"),
+            vec!["tk-a1b2", "tk-a1b2.3", "tk-ff", "tk-c3d4"]
+        );
+        assert_eq!(
+            super::task_ids_in("app-01 and tk-01", "app"),
+            vec!["app-01"]
+        );
+    }
+
+    /// Run git in `dir`, panicking on failure.
+    fn git(dir: &std::path::Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .expect("failed to run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_prime_since_lists_tasks_from_commit_messages() {
+        // Skip where git is not installed; prime skips the section there too.
+        if std::process::Command::new("git")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let tmp = TempDir::new().unwrap();
+        init_db(&tmp);
+        let out = tk(&tmp)
+            .args(["--json", "create", "Referenced task"])
+            .output()
+            .unwrap();
+        let id = serde_json::from_slice::<Value>(&out.stdout).unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        tk(&tmp)
+            .args(["create", "Untouched task"])
+            .assert()
+            .success();
+
+        git(tmp.path(), &["init", "-q"]);
+        git(
+            tmp.path(),
+            &["commit", "-q", "--allow-empty", "-m", "start"],
+        );
+        git(tmp.path(), &["tag", "base"]);
+        let message = format!("Implement the thing ({id})");
+        git(
+            tmp.path(),
+            &["commit", "-q", "--allow-empty", "-m", &message],
+        );
+
+        let output = tk(&tmp)
+            .args(["--json", "prime", "--since", "base"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+        let referenced = json["recently_referenced"].as_array().unwrap();
+        assert_eq!(referenced.len(), 1);
+        assert_eq!(referenced[0]["id"], id.as_str());
+
+        let output = tk(&tmp)
+            .args(["prime", "--since", "base"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("## Recently referenced (since base)"));
+        assert!(stdout.contains(&format!("- {id}: Referenced task [open]")));
+    }
+
+    #[test]
+    fn test_prime_since_outside_git_skips_the_section() {
+        let tmp = TempDir::new().unwrap();
+        init_db(&tmp);
+
+        let output = tk(&tmp)
+            .args(["prime", "--since", "HEAD~5"])
+            .current_dir(tmp.path())
+            .env("GIT_CEILING_DIRECTORIES", tmp.path().parent().unwrap())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.contains("Recently referenced"));
+        assert!(stdout.contains("## Command Reference"));
+    }
 }
@@ -0,0 +1,53 @@
+use std::path::Path;
+
+use chrono::{Duration, Utc};
+
+use super::print_json;
+use crate::db::Database;
+use crate::error::Error;
+
+/// Delete done tasks closed more than `older_than_days` ago, with their
+/// comments and dependency edges. Only reports what would be pruned unless
+/// `yes` is set.
+pub fn run(
+    db_path: &Path,
+    older_than_days: u32,
+    reason: Option<&str>,
+    yes: bool,
+    json: bool,
+) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let cutoff = Utc::now() - Duration::days(i64::from(older_than_days));
+    let report = db.prune_done_tasks(cutoff, reason, !yes)?;
+
+    if json {
+        print_json(&report)?;
+        return Ok(());
+    }
+
+    let verb = if report.dry_run {
+        "Would prune"
+    } else {
+        "Pruned"
+    };
+    println!(
+        "{verb} {} task(s), {} comment(s), {} dependency edge(s)",
+        report.tasks.len(),
+        report.comments,
+        report.dependencies
+    );
+    for id in &report.tasks {
+        println!("  {id}");
+    }
+    if !report.skipped.is_empty() {
+        println!(
+            "Skipped {} task(s) with surviving children: {}",
+            report.skipped.len(),
+            report.skipped.join(", ")
+        );
+    }
+    if report.dry_run && !report.tasks.is_empty() {
+        println!("Dry run: re-run with --yes to delete.");
+    }
+    Ok(())
+}
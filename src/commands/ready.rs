@@ -1,10 +1,80 @@
-use std::path::Path;
+use std::collections::HashSet;
 
-use super::print_tasks;
-use crate::db::Database;
+use serde::Serialize;
 
-pub fn run(db_path: &Path, limit: Option<u32>, json: bool) -> Result<(), String> {
-    let db = Database::open(db_path)?;
-    let tasks = db.get_ready_tasks(limit)?;
-    print_tasks(&tasks, json)
+use super::{print_json, print_task_ids, print_tasks};
+use crate::backend::Backend;
+use crate::db::TaskFilter;
+use crate::error::Error;
+use crate::models::{Status, Task};
+
+/// An open task left out of the ready list, with why (`tk ready --explain`).
+#[derive(Serialize)]
+struct NotReady {
+    task: Task,
+    reasons: Vec<String>,
+}
+
+pub fn run(
+    backend: &dyn Backend,
+    limit: Option<u32>,
+    json: bool,
+    ids_only: bool,
+    explain: bool,
+) -> Result<(), Error> {
+    let tasks = backend.ready_tasks(limit)?;
+    if ids_only {
+        return print_task_ids(&tasks);
+    }
+    if !explain {
+        return print_tasks(&tasks, json);
+    }
+
+    let not_ready = not_ready_tasks(backend, &tasks)?;
+    if json {
+        return print_json(&serde_json::json!({ "ready": tasks, "not_ready": not_ready }));
+    }
+
+    print_tasks(&tasks, false)?;
+    println!("\nNot ready:");
+    if not_ready.is_empty() {
+        println!("  (none)");
+    }
+    for entry in &not_ready {
+        println!("  {} {}", entry.task.id, entry.task.title);
+        for reason in &entry.reasons {
+            println!("      - {reason}");
+        }
+    }
+    Ok(())
+}
+
+/// Open or blocked tasks that are not in `ready`, each with the reasons it is
+/// excluded. Tasks that are only missing because of `--limit` are skipped.
+fn not_ready_tasks(backend: &dyn Backend, ready: &[Task]) -> Result<Vec<NotReady>, Error> {
+    let ready_ids: HashSet<&str> = ready.iter().map(|t| t.id.as_str()).collect();
+    let mut not_ready = Vec::new();
+    for task in backend.list_tasks(&TaskFilter::default())? {
+        if ready_ids.contains(task.id.as_str())
+            || !matches!(task.status, Status::Open | Status::Blocked)
+        {
+            continue;
+        }
+        let mut reasons = Vec::new();
+        if task.status == Status::Blocked {
+            reasons.push("status is blocked".to_string());
+        }
+        for blocker in backend.blockers(&task.id)? {
+            if blocker.status != Status::Done {
+                reasons.push(format!(
+                    "blocked by {} ({}, {})",
+                    blocker.id, blocker.title, blocker.status
+                ));
+            }
+        }
+        if !reasons.is_empty() {
+            not_ready.push(NotReady { task, reasons });
+        }
+    }
+    Ok(not_ready)
 }
@@ -0,0 +1,20 @@
+use std::path::Path;
+
+use chrono::{Duration, Utc};
+
+use super::print_tasks;
+use crate::db::{Database, TaskFilter};
+use crate::error::Error;
+
+/// List tasks modified in the last `hours` hours, closed ones included, most
+/// recently updated first.
+pub fn run(db_path: &Path, hours: u32, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let mut tasks = db.list_tasks(&TaskFilter {
+        include_done: true,
+        updated_after: Some(Utc::now() - Duration::hours(i64::from(hours))),
+        ..Default::default()
+    })?;
+    tasks.sort_by_key(|t| std::cmp::Reverse(t.updated_at));
+    print_tasks(&tasks, json)
+}
@@ -0,0 +1,19 @@
+use std::path::Path;
+
+use super::{confirm, print_json};
+use crate::db::Database;
+use crate::error::Error;
+use crate::models::Status;
+
+/// Reopen a done task, moving it back to `status` and clearing its close reason.
+pub fn run(db_path: &Path, id: &str, status: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let task = db.reopen_task(id, status.parse::<Status>()?)?;
+
+    if json {
+        print_json(&task)?;
+    } else {
+        confirm(&format!("Reopened task {id} ({})", task.status.as_str()));
+    }
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use super::print_json;
+use crate::db::Database;
+use crate::error::Error;
+
+/// Replay a `tk export --sqlite` file against the current code: load it into
+/// a throwaway in-memory database, migrate it, and report tasks that today's
+/// validation rules would reject. Neither the export nor the project
+/// database is written. With `check`, any problem makes the command fail.
+pub fn run(file: &Path, check: bool, json: bool) -> Result<(), Error> {
+    let db = Database::open_copy_in_memory(file)?;
+    if db.get_config("prefix").ok().flatten().is_none() {
+        return Err(Error::validation(format!(
+            "{} is not a tacks export (expected a file written by `tk export --sqlite`)",
+            file.display()
+        )));
+    }
+    db.migrate()?;
+    db.recompute_blocked_flags()?;
+    let tasks: i64 = db.task_count_by_status()?.iter().map(|(_, n)| n).sum();
+    let problems = db.check_tasks()?;
+
+    if json {
+        let j = serde_json::json!({
+            "file": file.display().to_string(),
+            "tasks": tasks,
+            "problems": problems,
+        });
+        print_json(&j)?;
+    } else {
+        println!("Replayed {tasks} task(s) from {}", file.display());
+        if problems.is_empty() {
+            println!("No problems found");
+        } else {
+            println!("{} problem(s):", problems.len());
+            for p in &problems {
+                println!("  - {}: {}", p.task_id, p.problem);
+            }
+        }
+    }
+
+    if check && !problems.is_empty() {
+        return Err(format!("{} problem(s) found in {}", problems.len(), file.display()).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use super::{confirm, print_json};
+use crate::db::Database;
+use crate::error::Error;
+
+/// Switch the task ID prefix, renaming existing tasks that use the old one.
+pub fn run(db_path: &Path, prefix: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let renamed = db.reprefix(prefix)?;
+
+    if json {
+        let out = serde_json::json!({ "prefix": prefix, "renamed": renamed });
+        print_json(&out)?;
+        return Ok(());
+    }
+
+    confirm(&format!(
+        "Task prefix: {prefix} ({renamed} task(s) renamed)"
+    ));
+    Ok(())
+}
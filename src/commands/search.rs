@@ -0,0 +1,11 @@
+use std::path::Path;
+
+use crate::db::Database;
+use crate::error::Error;
+
+/// Full-text search over task titles and descriptions, best match first.
+pub fn run(db_path: &Path, query: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let tasks = db.search_tasks(query)?;
+    super::print_tasks(&tasks, json)
+}
@@ -1,47 +1,195 @@
-use std::path::Path;
+use colored::Colorize;
 
-use super::{format_priority, format_status};
-use crate::db::Database;
+use super::{format_priority, format_status, print_json, print_tasks_porcelain};
+use crate::backend::Backend;
+use crate::error::Error;
+use crate::models::{Dependency, TASK_FIELDS, Task, parse_fields, select_fields};
 
-pub fn run(db_path: &Path, id: &str, json: bool) -> Result<(), String> {
-    let db = Database::open(db_path)?;
-    let task = db
-        .get_task(id)?
-        .ok_or_else(|| format!("task not found: {id}"))?;
+/// Keys `show --json` adds on top of the task's own fields.
+const DETAIL_FIELDS: &[&str] = &[
+    "comments",
+    "blockers",
+    "children",
+    "dependents",
+    "dependency_edges",
+    "related",
+];
+
+/// Show one or more tasks. A single missing ID is an error; when several IDs
+/// are given, missing ones are reported on stderr and skipped.
+pub fn run(
+    backend: &dyn Backend,
+    ids: &[String],
+    json: bool,
+    porcelain: bool,
+    labels: bool,
+    fields: Option<&str>,
+    related: bool,
+) -> Result<(), Error> {
+    let allowed: Vec<&str> = TASK_FIELDS.iter().chain(DETAIL_FIELDS).copied().collect();
+    let fields = fields
+        .map(|f| parse_fields(f, &allowed))
+        .transpose()
+        .map_err(Error::validation)?;
+    let tasks = backend.tasks_by_ids(ids)?;
+
+    if ids.len() == 1 && tasks.is_empty() {
+        return Err(Error::not_found(format!("task not found: {}", ids[0])));
+    }
+    for id in ids {
+        if !tasks.iter().any(|t| &t.id == id) {
+            eprintln!("warning: task not found: {id}");
+        }
+    }
+    if tasks.is_empty() {
+        return Err(Error::not_found("requested tasks not found"));
+    }
+
+    if porcelain {
+        return print_tasks_porcelain(&tasks);
+    }
 
     if json {
-        let mut value = serde_json::to_value(&task).map_err(|e| format!("json error: {e}"))?;
-        // Add comments, blockers, children, and dependents to JSON output
-        let comments = db.get_comments(id)?;
-        let blocker_deps = db.get_blockers(id)?;
-        let blocker_tasks: Vec<_> = blocker_deps
-            .iter()
-            .filter_map(|d| db.get_task(&d.parent_id).ok().flatten())
-            .collect();
-        let children = db.get_children(id)?;
-        let dependents = db.get_dependents(id)?;
-        if let Some(obj) = value.as_object_mut() {
-            obj.insert(
-                "comments".to_string(),
-                serde_json::to_value(&comments).unwrap_or_default(),
-            );
-            obj.insert(
-                "blockers".to_string(),
-                serde_json::to_value(&blocker_tasks).unwrap_or_default(),
-            );
-            obj.insert(
-                "children".to_string(),
-                serde_json::to_value(&children).unwrap_or_default(),
+        let mut values = Vec::with_capacity(tasks.len());
+        for task in &tasks {
+            let mut value = task_detail_json(backend, task, labels)?;
+            if related {
+                let rel = related_json(backend, task)?;
+                if let Some(obj) = value.as_object_mut() {
+                    obj.insert("related".to_string(), rel);
+                }
+            }
+            values.push(value);
+        }
+        // A single ID keeps the original object shape; several IDs yield an array.
+        let mut value = if ids.len() == 1 {
+            values.remove(0)
+        } else {
+            serde_json::Value::Array(values)
+        };
+        if let Some(ref fields) = fields {
+            select_fields(&mut value, fields);
+        }
+        print_json(&value)?;
+        return Ok(());
+    }
+
+    for (i, task) in tasks.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_task_detail(backend, task)?;
+        if related {
+            print_related(backend, task)?;
+        }
+    }
+    Ok(())
+}
+
+/// The task's neighbourhood for `--related`: its parent (or null), the
+/// parent's other children, and the tasks it blocks and is blocked by.
+fn related_json(backend: &dyn Backend, task: &Task) -> Result<serde_json::Value, Error> {
+    let id = task.id.as_str();
+    let parent = match task.parent_id {
+        Some(ref parent_id) => backend
+            .tasks_by_ids(std::slice::from_ref(parent_id))?
+            .into_iter()
+            .next(),
+        None => None,
+    };
+    Ok(serde_json::json!({
+        "parent": parent,
+        "siblings": backend.siblings(id)?,
+        "blockers": backend.blockers(id)?,
+        "dependents": backend.dependents(id)?,
+    }))
+}
+
+/// The `--related` sections the plain detail view lacks: the parent task and
+/// its other children. Blockers and dependents are already listed above.
+fn print_related(backend: &dyn Backend, task: &Task) -> Result<(), Error> {
+    if let Some(ref parent_id) = task.parent_id {
+        let parents = backend.tasks_by_ids(std::slice::from_ref(parent_id))?;
+        if let Some(parent) = parents.first() {
+            println!("\nParent task:");
+            println!(
+                "  - {} [{}] {}",
+                parent.id,
+                format_status(&parent.status),
+                parent.title
             );
-            obj.insert(
-                "dependents".to_string(),
-                serde_json::to_value(&dependents).unwrap_or_default(),
+        }
+    }
+    let siblings = backend.siblings(&task.id)?;
+    if !siblings.is_empty() {
+        println!("\nSiblings:");
+        for sibling in &siblings {
+            println!(
+                "  - {} [{}] {}",
+                sibling.id,
+                format_status(&sibling.status),
+                sibling.title
             );
         }
-        let j = serde_json::to_string_pretty(&value).map_err(|e| format!("json error: {e}"))?;
-        println!("{j}");
-        return Ok(());
     }
+    Ok(())
+}
+
+/// Task JSON enriched with comments, blockers, children, dependents, and the
+/// raw `dependency_edges` touching the task (both directions).
+fn task_detail_json(
+    backend: &dyn Backend,
+    task: &Task,
+    labels: bool,
+) -> Result<serde_json::Value, Error> {
+    let id = task.id.as_str();
+    let mut value = if labels {
+        task.to_labeled_json()
+    } else {
+        serde_json::to_value(task).map_err(|e| format!("json error: {e}"))?
+    };
+    let comments = backend.comments(id)?;
+    let blocker_tasks = backend.blockers(id)?;
+    let children = backend.children(id)?;
+    let dependents = backend.dependents(id)?;
+    let edges: Vec<Dependency> = blocker_tasks
+        .iter()
+        .map(|t| Dependency {
+            child_id: id.to_string(),
+            parent_id: t.id.clone(),
+        })
+        .chain(dependents.iter().map(|t| Dependency {
+            child_id: t.id.clone(),
+            parent_id: id.to_string(),
+        }))
+        .collect();
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert(
+            "comments".to_string(),
+            serde_json::to_value(&comments).unwrap_or_default(),
+        );
+        obj.insert(
+            "blockers".to_string(),
+            serde_json::to_value(&blocker_tasks).unwrap_or_default(),
+        );
+        obj.insert(
+            "children".to_string(),
+            serde_json::to_value(&children).unwrap_or_default(),
+        );
+        obj.insert(
+            "dependents".to_string(),
+            serde_json::to_value(&dependents).unwrap_or_default(),
+        );
+        obj.insert(
+            "dependency_edges".to_string(),
+            serde_json::to_value(&edges).unwrap_or_default(),
+        );
+    }
+    Ok(value)
+}
+
+fn print_task_detail(backend: &dyn Backend, task: &Task) -> Result<(), Error> {
+    let id = task.id.as_str();
 
     // Human-readable output
     println!("ID:          {}", task.id);
@@ -66,27 +214,42 @@ pub fn run(db_path: &Path, id: &str, json: bool) -> Result<(), String> {
     if !task.tags.is_empty() {
         println!("Tags:        {}", task.tags.join(", "));
     }
+    if let Some(due_at) = task.due_at {
+        let overdue = if task.is_overdue(chrono::Utc::now()) {
+            " (overdue)".red().to_string()
+        } else {
+            String::new()
+        };
+        println!("Due:         {}{overdue}", due_at.format("%Y-%m-%d %H:%M"));
+    }
+    if let Some(estimate) = task.estimate_minutes {
+        println!("Estimate:    {estimate}m");
+    }
+    if let Some(spent) = task.spent_minutes {
+        println!("Spent:       {spent}m");
+    }
     println!("Created:     {}", task.created_at.format("%Y-%m-%d %H:%M"));
     println!("Updated:     {}", task.updated_at.format("%Y-%m-%d %H:%M"));
+    if let Some(archived_at) = task.archived_at {
+        println!("Archived:    {}", archived_at.format("%Y-%m-%d %H:%M"));
+    }
 
     // Show blockers
-    let blockers = db.get_blockers(id)?;
+    let blockers = backend.blockers(id)?;
     if !blockers.is_empty() {
         println!("\nBlockers:");
-        for dep in &blockers {
-            if let Some(blocker_task) = db.get_task(&dep.parent_id)? {
-                println!(
-                    "  - {} [{}] {}",
-                    dep.parent_id,
-                    format_status(&blocker_task.status),
-                    blocker_task.title
-                );
-            }
+        for blocker in &blockers {
+            println!(
+                "  - {} [{}] {}",
+                blocker.id,
+                format_status(&blocker.status),
+                blocker.title
+            );
         }
     }
 
     // Show dependents
-    let dependents = db.get_dependents(id)?;
+    let dependents = backend.dependents(id)?;
     if !dependents.is_empty() {
         println!("\nDependents:");
         for dep in &dependents {
@@ -100,7 +263,7 @@ pub fn run(db_path: &Path, id: &str, json: bool) -> Result<(), String> {
     }
 
     // Show children
-    let children = db.get_children(id)?;
+    let children = backend.children(id)?;
     if !children.is_empty() {
         println!("\nSubtasks:");
         for child in &children {
@@ -115,11 +278,20 @@ pub fn run(db_path: &Path, id: &str, json: bool) -> Result<(), String> {
     }
 
     // Show comments
-    let comments = db.get_comments(id)?;
+    let comments = backend.comments(id)?;
     if !comments.is_empty() {
         println!("\nComments:");
         for c in &comments {
-            println!("  [{}] {}", c.created_at.format("%Y-%m-%d %H:%M"), c.body);
+            let edited = if c.updated_at.is_some() {
+                " (edited)"
+            } else {
+                ""
+            };
+            println!(
+                "  [{}] {}{edited}",
+                c.created_at.format("%Y-%m-%d %H:%M"),
+                c.body
+            );
         }
     }
 
@@ -1,33 +1,51 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use crate::db::Database;
+use super::epic::{EpicProgress, epic_progress};
+use super::print_json;
+use crate::db::{Database, StatsSnapshot};
+use crate::error::Error;
+use crate::models::{PRIORITIES, Status, VALID_CLOSE_REASONS};
 
-pub fn run(db_path: &Path, oneline: bool, json: bool) -> Result<(), String> {
+/// Print task counts. With `epic`, print per-epic subtask counts instead.
+pub fn run(db_path: &Path, oneline: bool, epic: bool, json: bool) -> Result<(), Error> {
     let db = Database::open(db_path)?;
+    if epic {
+        return run_epic(&db, json);
+    }
 
-    let by_status = db.task_count_by_status()?;
-    let by_priority = db.task_count_by_priority()?;
-    let by_tag = db.task_count_by_tag()?;
+    let StatsSnapshot {
+        by_status,
+        by_priority,
+        by_tag,
+        by_assignee,
+        by_reason,
+    } = db.stats_snapshot()?;
 
     if json {
-        let status_map: HashMap<&str, i64> =
-            by_status.iter().map(|(s, c)| (s.as_str(), *c)).collect();
-        let priority_map: HashMap<String, i64> = by_priority
-            .iter()
-            .map(|(p, c)| (format!("P{p}"), *c))
-            .collect();
+        // Empty-state contract: every canonical status and priority key is
+        // present (zero-filled); tags are open-ended so `by_tag` may be `{}`.
+        let mut status_map: HashMap<&str, i64> =
+            Status::ALL.iter().map(|s| (s.as_str(), 0)).collect();
+        status_map.extend(by_status.iter().map(|(s, c)| (s.as_str(), *c)));
+        let mut priority_map: HashMap<String, i64> =
+            PRIORITIES.iter().map(|p| (format!("P{p}"), 0)).collect();
+        priority_map.extend(by_priority.iter().map(|(p, c)| (format!("P{p}"), *c)));
         let tag_map: HashMap<&str, i64> = by_tag.iter().map(|(t, c)| (t.as_str(), *c)).collect();
+        let assignee_map: HashMap<&str, i64> =
+            by_assignee.iter().map(|(a, c)| (a.as_str(), *c)).collect();
+        let mut reason_map: HashMap<&str, i64> =
+            VALID_CLOSE_REASONS.iter().map(|r| (*r, 0)).collect();
+        reason_map.extend(by_reason.iter().map(|(r, c)| (r.as_str(), *c)));
 
         let out = serde_json::json!({
             "by_status": status_map,
             "by_priority": priority_map,
             "by_tag": tag_map,
+            "by_assignee": assignee_map,
+            "by_reason": reason_map,
         });
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&out).map_err(|e| format!("json error: {e}"))?
-        );
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -35,8 +53,15 @@ pub fn run(db_path: &Path, oneline: bool, json: bool) -> Result<(), String> {
         let parts: Vec<String> = by_status.iter().map(|(s, c)| format!("{c} {s}")).collect();
         if parts.is_empty() {
             println!("no tasks");
-        } else {
+        } else if by_assignee.iter().all(|(a, _)| a == "unassigned") {
             println!("{}", parts.join(", "));
+        } else {
+            // Only worth the extra width once someone has claimed work.
+            let assignees: Vec<String> = by_assignee
+                .iter()
+                .map(|(a, c)| format!("{a} {c}"))
+                .collect();
+            println!("{}; {}", parts.join(", "), assignees.join(", "));
         }
         return Ok(());
     }
@@ -74,5 +99,88 @@ pub fn run(db_path: &Path, oneline: bool, json: bool) -> Result<(), String> {
         }
     }
 
+    // By assignee
+    println!();
+    println!("By Assignee");
+    println!("{}", "-".repeat(24));
+    for (assignee, count) in &by_assignee {
+        println!("  {:<14} {}", assignee, count);
+    }
+
+    // By close reason
+    if !by_reason.is_empty() {
+        println!();
+        println!("By Close Reason");
+        println!("{}", "-".repeat(24));
+        for (reason, count) in &by_reason {
+            println!("  {:<14} {}", reason, count);
+        }
+    }
+
+    Ok(())
+}
+
+/// Per-epic completion: each open epic's subtasks counted by status.
+fn run_epic(db: &Database, json: bool) -> Result<(), Error> {
+    let epics = epic_progress(db)?;
+
+    if json {
+        let out: Vec<serde_json::Value> = epics
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "id": p.epic.id,
+                    "title": p.epic.title,
+                    "children_total": p.children.len(),
+                    "children_done": p.done(),
+                    "progress_pct": p.pct(),
+                    "by_status": status_counts(p),
+                })
+            })
+            .collect();
+        return print_json(&out);
+    }
+
+    if epics.is_empty() {
+        println!("No epics found.");
+        return Ok(());
+    }
+
+    println!("By Epic");
+    println!("{}", "-".repeat(78));
+    println!(
+        "  {:<12} {:<30} {:>5} {:>7} {:>7} {:>5}  PROGRESS",
+        "ID", "TITLE", "OPEN", "IN_PROG", "BLOCKED", "DONE"
+    );
+    for p in &epics {
+        let counts = status_counts(p);
+        let title = if p.epic.title.len() > 28 {
+            format!("{}...", &p.epic.title[..25])
+        } else {
+            p.epic.title.clone()
+        };
+        println!(
+            "  {:<12} {:<30} {:>5} {:>7} {:>7} {:>5}  {}/{} ({}%)",
+            p.epic.id,
+            title,
+            counts[Status::Open.as_str()],
+            counts[Status::InProgress.as_str()],
+            counts[Status::Blocked.as_str()],
+            counts[Status::Done.as_str()],
+            p.done(),
+            p.children.len(),
+            p.pct(),
+        );
+    }
     Ok(())
 }
+
+/// An epic's subtasks counted by status, every status present.
+fn status_counts(progress: &EpicProgress) -> HashMap<&'static str, usize> {
+    let mut counts: HashMap<&'static str, usize> =
+        Status::ALL.iter().map(|s| (s.as_str(), 0)).collect();
+    for child in &progress.children {
+        *counts.entry(child.status.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
@@ -0,0 +1,54 @@
+use std::path::Path;
+
+use super::{confirm, print_json};
+use crate::db::Database;
+use crate::error::Error;
+
+/// Print every tag with the number of tasks carrying it, most used first.
+pub fn list(db_path: &Path, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let counts = db.task_count_by_tag()?;
+
+    if json {
+        let out: Vec<_> = counts
+            .iter()
+            .map(|(tag, count)| serde_json::json!({ "tag": tag, "count": count }))
+            .collect();
+        return print_json(&out);
+    }
+
+    if counts.is_empty() {
+        println!("No tags.");
+        return Ok(());
+    }
+    let width = counts.iter().map(|(tag, _)| tag.len()).max().unwrap_or(0);
+    for (tag, count) in &counts {
+        println!("{tag:<width$}  {count}");
+    }
+    Ok(())
+}
+
+/// Rename a tag on every task that carries it.
+pub fn rename(db_path: &Path, old: &str, new: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let ids = db.rename_tag(old, new)?;
+    if json {
+        return print_json(&serde_json::json!({ "tag": old, "renamed_to": new, "tasks": ids }));
+    }
+    confirm(&format!(
+        "Renamed tag {old} to {new} on {} task(s)",
+        ids.len()
+    ));
+    Ok(())
+}
+
+/// Strip a tag from every task that carries it.
+pub fn remove(db_path: &Path, tag: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let ids = db.remove_tag(tag)?;
+    if json {
+        return print_json(&serde_json::json!({ "tag": tag, "tasks": ids }));
+    }
+    confirm(&format!("Removed tag {tag} from {} task(s)", ids.len()));
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::path::Path;
+
+use super::{confirm, print_json, print_tasks};
+use crate::backend::{Backend, LocalDb, NewTask};
+use crate::db::Database;
+use crate::error::Error;
+use crate::models::Template;
+
+/// Save task `from` and its subtask tree as the template `name`.
+pub fn save(db_path: &Path, name: &str, from: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let template = db.task_template(from)?;
+    db.save_template(name, &template)?;
+    if json {
+        return print_json(&serde_json::json!({ "name": name, "template": template }));
+    }
+    confirm(&format!(
+        "Saved template {name} from {from} ({} task(s))",
+        template.task_count()
+    ));
+    Ok(())
+}
+
+/// Print every saved template with its root title and task count.
+pub fn list(db_path: &Path, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let templates = db.list_templates()?;
+
+    if json {
+        let out: Vec<_> = templates
+            .iter()
+            .map(|(name, template)| serde_json::json!({ "name": name, "template": template }))
+            .collect();
+        return print_json(&out);
+    }
+
+    if templates.is_empty() {
+        println!("No templates.");
+        return Ok(());
+    }
+    let width = templates
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    for (name, template) in &templates {
+        println!(
+            "{name:<width$}  {} ({} task(s))",
+            template.title,
+            template.task_count()
+        );
+    }
+    Ok(())
+}
+
+/// `tk create --from-template`: create the template's task tree with fresh
+/// IDs in one transaction, optionally retitling the root or placing it under
+/// `parent`.
+pub fn instantiate(
+    db_path: &Path,
+    name: &str,
+    title: Option<&str>,
+    parent: Option<&str>,
+    json: bool,
+) -> Result<(), Error> {
+    let backend = LocalDb(Database::open(db_path)?);
+    let mut template = backend
+        .0
+        .get_template(name)?
+        .ok_or_else(|| Error::not_found(format!("template not found: {name}")))?;
+    if let Some(title) = title {
+        template.title = title.to_string();
+    }
+
+    let ids = backend.0.with_transaction(|_| {
+        let mut ids = Vec::new();
+        create_tree(&backend, &template, parent, &mut ids)?;
+        Ok(ids)
+    })?;
+    // Re-read so parents show the epic tag their subtasks added.
+    let tasks = backend.tasks_by_ids(&ids)?;
+
+    if json {
+        return print_json(&tasks);
+    }
+    confirm(&format!(
+        "Created task {}: {} from template {name}",
+        ids[0], template.title
+    ));
+    print_tasks(&tasks, false)
+}
+
+/// Create `template` under `parent`, then its children under it, pushing
+/// each new ID onto `ids` in creation order.
+fn create_tree(
+    backend: &LocalDb,
+    template: &Template,
+    parent: Option<&str>,
+    ids: &mut Vec<String>,
+) -> Result<(), Error> {
+    let task = backend.create_task(&NewTask {
+        title: &template.title,
+        priority: Some(template.priority),
+        description: template.description.as_deref(),
+        tags: template.tags.clone(),
+        parent,
+        due_at: None,
+    })?;
+    ids.push(task.id.clone());
+    for child in &template.children {
+        create_tree(backend, child, Some(&task.id), ids)?;
+    }
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use std::path::Path;
 
+use chrono::{DateTime, Utc};
+
+use super::{confirm, print_json, text_or_file};
 use crate::db::Database;
+use crate::error::Error;
 
 #[allow(clippy::too_many_arguments)]
 pub fn run(
@@ -10,13 +14,22 @@ pub fn run(
     priority: Option<u8>,
     status: Option<&str>,
     description: Option<&str>,
+    description_file: Option<&Path>,
     claim: bool,
     assignee: Option<&str>,
     add_tags: Option<&str>,
     remove_tags: Option<&str>,
+    toggle_tags: &[String],
     notes: Option<&str>,
+    notes_file: Option<&Path>,
+    due_at: Option<Option<DateTime<Utc>>>,
+    estimate_minutes: Option<u32>,
+    spent_minutes: Option<u32>,
+    if_unchanged_since: Option<DateTime<Utc>>,
     json: bool,
-) -> Result<(), String> {
+) -> Result<(), Error> {
+    let description = text_or_file(description, description_file, "description")?;
+    let notes = text_or_file(notes, notes_file, "notes")?;
     let db = Database::open(db_path)?;
 
     // Handle claim: set status to in_progress and assignee
@@ -28,49 +41,74 @@ pub fn run(
         assignee
     };
 
-    db.update_task(
-        id,
-        title,
-        priority,
-        effective_status,
-        description,
-        effective_assignee,
-        None,
-        notes,
-    )?;
+    // Field and tag changes are applied together or not at all.
+    db.with_transaction(|db| {
+        if let Some(expected) = if_unchanged_since {
+            db.ensure_unchanged_since(id, expected)?;
+        }
+        db.update_task(
+            id,
+            title,
+            priority,
+            effective_status,
+            description.as_deref(),
+            effective_assignee,
+            None,
+            notes.as_deref(),
+        )?;
+
+        if let Some(due_at) = due_at {
+            db.update_due_at(id, due_at)?;
+        }
+        db.update_effort(id, estimate_minutes, spent_minutes)?;
+
+        // Handle tag changes
+        if add_tags.is_some() || remove_tags.is_some() || !toggle_tags.is_empty() {
+            let mut current_tags = db.get_task_tags(id)?;
+
+            if let Some(add) = add_tags {
+                for tag in add
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                {
+                    if !current_tags.contains(&tag) {
+                        current_tags.push(tag);
+                    }
+                }
+            }
 
-    // Handle tag changes
-    if add_tags.is_some() || remove_tags.is_some() {
-        let mut current_tags = db.get_task_tags(id)?;
+            if let Some(remove) = remove_tags {
+                let remove_set: Vec<String> =
+                    remove.split(',').map(|s| s.trim().to_string()).collect();
+                current_tags.retain(|t| !remove_set.contains(t));
+            }
 
-        if let Some(add) = add_tags {
-            for tag in add
-                .split(',')
-                .map(|s| s.trim().to_string())
+            // Each --toggle-tag flips independently, after adds and removes.
+            for tag in toggle_tags
+                .iter()
+                .map(|s| s.trim())
                 .filter(|s| !s.is_empty())
             {
-                if !current_tags.contains(&tag) {
-                    current_tags.push(tag);
+                if let Some(pos) = current_tags.iter().position(|t| t == tag) {
+                    current_tags.remove(pos);
+                } else {
+                    current_tags.push(tag.to_string());
                 }
             }
-        }
 
-        if let Some(remove) = remove_tags {
-            let remove_set: Vec<String> = remove.split(',').map(|s| s.trim().to_string()).collect();
-            current_tags.retain(|t| !remove_set.contains(t));
+            db.update_tags(id, &current_tags)?;
         }
-
-        db.update_tags(id, &current_tags)?;
-    }
+        Ok(())
+    })?;
 
     if json {
         let task = db
             .get_task(id)?
-            .ok_or_else(|| format!("task not found: {id}"))?;
-        let j = serde_json::to_string_pretty(&task).map_err(|e| format!("json error: {e}"))?;
-        println!("{j}");
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+        print_json(&task)?;
     } else {
-        println!("Updated task {id}");
+        confirm(&format!("Updated task {id}"));
     }
 
     Ok(())
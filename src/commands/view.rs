@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use super::{confirm, print_json, print_tasks};
+use crate::db::{Database, TaskFilter};
+use crate::error::Error;
+use crate::models::SavedView;
+
+/// Save `view` under `name`, replacing any view of that name.
+pub fn save(db_path: &Path, name: &str, view: &SavedView, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    db.save_view(name, view)?;
+    if json {
+        return print_json(&serde_json::json!({ "name": name, "view": view }));
+    }
+    confirm(&format!("Saved view {name}: {}", describe(view)));
+    Ok(())
+}
+
+/// List the tasks the view saved as `name` matches now.
+pub fn run(db_path: &Path, name: &str, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let view = db
+        .get_view(name)?
+        .ok_or_else(|| Error::not_found(format!("view not found: {name}")))?;
+    let tasks = db.list_tasks(&TaskFilter {
+        include_done: view.all,
+        status: view.status.as_deref(),
+        priority: view.priority,
+        tag: view.tag.as_deref(),
+        parent: view.parent.as_deref(),
+        assignee: view.assignee.as_deref(),
+        search: view.search.as_deref(),
+        ..Default::default()
+    })?;
+    print_tasks(&tasks, json)
+}
+
+/// Print every saved view with its filters.
+pub fn list(db_path: &Path, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let views = db.list_views()?;
+
+    if json {
+        let out: Vec<_> = views
+            .iter()
+            .map(|(name, view)| serde_json::json!({ "name": name, "view": view }))
+            .collect();
+        return print_json(&out);
+    }
+
+    if views.is_empty() {
+        println!("No views.");
+        return Ok(());
+    }
+    let width = views.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, view) in &views {
+        println!("{name:<width$}  {}", describe(view));
+    }
+    Ok(())
+}
+
+/// The view's filters as `tk list` flags, e.g. `-s open -p 1 --assignee alice`.
+fn describe(view: &SavedView) -> String {
+    let mut parts = Vec::new();
+    if view.all {
+        parts.push("-a".to_string());
+    }
+    if let Some(s) = &view.status {
+        parts.push(format!("-s {s}"));
+    }
+    if let Some(p) = view.priority {
+        parts.push(format!("-p {p}"));
+    }
+    if let Some(t) = &view.tag {
+        parts.push(format!("-t {t}"));
+    }
+    if let Some(p) = &view.parent {
+        parts.push(format!("--parent {p}"));
+    }
+    if let Some(a) = &view.assignee {
+        parts.push(format!("--assignee {a:?}"));
+    }
+    if let Some(q) = &view.search {
+        parts.push(format!("--search {q:?}"));
+    }
+    if parts.is_empty() {
+        "(no filters)".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
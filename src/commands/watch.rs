@@ -0,0 +1,49 @@
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::db::Database;
+use crate::error::Error;
+
+/// Clear the terminal and move the cursor to the top-left corner.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Print `tk prime`, then print it again each time the database changes,
+/// checking every `interval` until Ctrl+C. Changes are detected with
+/// `PRAGMA data_version` (like `/api/poll`), so a quiet database costs one
+/// pragma per tick instead of a full prime read. On a terminal the screen
+/// is cleared before each render.
+pub fn run(db_path: &Path, interval: Duration, json: bool) -> Result<(), Error> {
+    let db = Database::open(db_path)?;
+    let clear = std::io::stdout().is_terminal();
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("failed to create tokio runtime: {e}"))?;
+
+    rt.block_on(async {
+        let ctrl_c = tokio::signal::ctrl_c();
+        tokio::pin!(ctrl_c);
+        let mut last_version = None;
+        loop {
+            let version = db.data_version()?;
+            if last_version != Some(version) {
+                last_version = Some(version);
+                if clear {
+                    print!("{CLEAR_SCREEN}");
+                }
+                super::prime::render(&db, None, json)?;
+                std::io::stdout()
+                    .flush()
+                    .map_err(|e| format!("failed to write output: {e}"))?;
+            }
+            tokio::select! {
+                result = &mut ctrl_c => {
+                    return result
+                        .map_err(|e| format!("failed to listen for Ctrl+C: {e}").into());
+                }
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    })
+}
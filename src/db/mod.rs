@@ -1,27 +1,372 @@
 use chrono::{DateTime, Utc};
-use rusqlite::{Connection, params};
+use rusqlite::{
+    Connection, DatabaseName, ErrorCode, OpenFlags, OptionalExtension, Transaction,
+    TransactionBehavior, params,
+};
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
-use crate::models::{Comment, Dependency, Status, Task, validate_close_reason};
+use crate::error::Error;
+use crate::models::{
+    Activity, Comment, Dependency, RecentComment, SavedView, Status, Task, Template,
+    parse_id_length, parse_priority, validate_close_reason, validate_prefix, validate_tags,
+    validate_template_name, validate_view_name,
+};
 
 pub struct Database {
     conn: Connection,
 }
 
+/// Filters for [`Database::list_tasks`]. The default lists every non-done task.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TaskFilter<'a> {
+    /// Include done tasks when no `status` is given.
+    pub include_done: bool,
+    /// Include archived tasks, which are left out by default.
+    pub include_archived: bool,
+    pub status: Option<&'a str>,
+    pub priority: Option<u8>,
+    /// Exact match against one of the task's tags.
+    pub tag: Option<&'a str>,
+    /// Tasks carrying any of these tags; empty means no filter.
+    pub any_tag: &'a [String],
+    pub parent: Option<&'a str>,
+    /// Exact assignee; an empty string matches unassigned tasks.
+    pub assignee: Option<&'a str>,
+    /// Only tasks blocked by this task (its dependents).
+    pub blocked_by: Option<&'a str>,
+    /// Only tasks blocking this task (its blockers).
+    pub blocking: Option<&'a str>,
+    /// Full-text match on title and description (see
+    /// [`Database::search_tasks`]); blank means no filter.
+    pub search: Option<&'a str>,
+    pub close_reason: Option<&'a str>,
+    /// Only tasks created at or after this instant.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only tasks created strictly before this instant.
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only tasks last modified at or after this instant.
+    pub updated_after: Option<DateTime<Utc>>,
+    /// Keyset cursor: only tasks that sort strictly after this task ID are
+    /// returned, so pages stay stable under concurrent inserts.
+    pub after: Option<&'a str>,
+    pub limit: Option<u32>,
+}
+
+/// Sort order for [`Database::get_children_filtered`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChildSort {
+    /// Numeric `.N` suffix of the child ID, i.e. creation order.
+    #[default]
+    Index,
+    /// Priority, then manual `position` within a priority, then creation time.
+    Priority,
+    Created,
+}
+
+impl FromStr for ChildSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "index" => Ok(ChildSort::Index),
+            "priority" => Ok(ChildSort::Priority),
+            "created" => Ok(ChildSort::Created),
+            other => Err(format!(
+                "invalid sort: {other}. valid values: index, priority, created"
+            )),
+        }
+    }
+}
+
+/// Outcome of [`Database::prune_done_tasks`].
+#[derive(Debug, Default, serde::Serialize)]
+pub struct PruneReport {
+    /// IDs of the tasks pruned (or that would be, on a dry run).
+    pub tasks: Vec<String>,
+    pub comments: usize,
+    pub dependencies: usize,
+    /// Matching tasks kept because a child task survives the prune.
+    pub skipped: Vec<String>,
+    pub dry_run: bool,
+}
+
+/// Tasks grouped into kanban columns by [`Database::board`].
+#[derive(Debug, Default, serde::Serialize)]
+pub struct Board {
+    pub open: Vec<Task>,
+    pub in_progress: Vec<Task>,
+    /// Tasks with status `blocked` plus open tasks that have a not-done blocker.
+    pub blocked: Vec<Task>,
+    pub done: Vec<Task>,
+}
+
+/// Every task count `tk stats`, `tk prime`, and `/api/stats` report, from
+/// one scan of the tasks table (plus the tag index) by
+/// [`Database::stats_snapshot`]. Each list is
+/// ordered like the matching `task_count_by_*` query.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    /// By status name, ascending.
+    pub by_status: Vec<(String, i64)>,
+    /// By priority, ascending.
+    pub by_priority: Vec<(u8, i64)>,
+    /// Per tag, most-used first; a task counts once for each of its tags.
+    pub by_tag: Vec<(String, i64)>,
+    /// Per assignee, most-loaded first; no assignee counts as `unassigned`.
+    pub by_assignee: Vec<(String, i64)>,
+    /// Done tasks per close reason, ascending; tasks closed without one are
+    /// left out.
+    pub by_reason: Vec<(String, i64)>,
+}
+
+impl StatsSnapshot {
+    /// Total number of tasks.
+    pub fn total(&self) -> i64 {
+        self.by_status.iter().map(|(_, n)| n).sum()
+    }
+}
+
+/// Tasks and the dependency edges between them, built by
+/// [`Database::dependency_graph`]. `tk dep graph` and `tk plan` render from
+/// this, so their text and JSON outputs always agree.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct DepGraph {
+    pub nodes: Vec<Task>,
+    /// Edges with both ends in `nodes`; `child_id` is blocked by `parent_id`.
+    pub edges: Vec<Dependency>,
+}
+
+impl DepGraph {
+    /// The subgraph of `root` and every task it transitively blocks,
+    /// following edges from blocker to blocked. Node order is kept.
+    pub fn reachable_from(self, root: &str) -> Result<DepGraph, Error> {
+        if !self.nodes.iter().any(|t| t.id == root) {
+            return Err(Error::not_found(format!("task not found in graph: {root}")));
+        }
+        let mut reached: std::collections::HashSet<String> = std::collections::HashSet::new();
+        let mut queue = vec![root.to_string()];
+        while let Some(id) = queue.pop() {
+            if !reached.insert(id.clone()) {
+                continue;
+            }
+            for edge in self.edges.iter().filter(|e| e.parent_id == id) {
+                queue.push(edge.child_id.clone());
+            }
+        }
+        Ok(DepGraph {
+            nodes: self
+                .nodes
+                .into_iter()
+                .filter(|t| reached.contains(&t.id))
+                .collect(),
+            edges: self
+                .edges
+                .into_iter()
+                .filter(|e| reached.contains(&e.parent_id))
+                .collect(),
+        })
+    }
+
+    /// Group the nodes into waves: the first wave has no blockers in the
+    /// graph, and every later task is blocked only by tasks in earlier
+    /// waves. Tasks keep node order within a wave.
+    pub fn waves(&self) -> Result<Vec<Vec<&Task>>, Error> {
+        let mut placed: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        let mut remaining: Vec<&Task> = self.nodes.iter().collect();
+        let mut waves = Vec::new();
+        while !remaining.is_empty() {
+            let (wave, rest): (Vec<&Task>, Vec<&Task>) = remaining.into_iter().partition(|t| {
+                self.edges
+                    .iter()
+                    .filter(|e| e.child_id == t.id)
+                    .all(|e| placed.contains(e.parent_id.as_str()))
+            });
+            if wave.is_empty() {
+                let ids: Vec<&str> = rest.iter().map(|t| t.id.as_str()).collect();
+                return Err(Error::conflict(format!(
+                    "dependency cycle among: {}",
+                    ids.join(", ")
+                )));
+            }
+            placed.extend(wave.iter().map(|t| t.id.as_str()));
+            waves.push(wave);
+            remaining = rest;
+        }
+        Ok(waves)
+    }
+}
+
+/// A stored task that breaks a rule the current code enforces on input,
+/// reported by [`Database::check_tasks`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TaskProblem {
+    pub task_id: String,
+    pub problem: String,
+}
+
+/// How serious an [`IntegrityFinding`] is: an `Error` is broken data, a
+/// `Warning` is merely suspicious.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One problem found by [`Database::integrity_report`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct IntegrityFinding {
+    pub severity: Severity,
+    /// Which check found it: `dangling_dependency`, `dangling_parent`,
+    /// `empty_epic`, `dependency_cycle`, or `stale_blocked_flag`.
+    pub check: &'static str,
+    pub task_id: String,
+    pub message: String,
+    /// Whether [`Database::repair_integrity`] fixes it.
+    pub fixable: bool,
+}
+
+/// `--db` value that selects an ephemeral in-memory database instead of a file.
+pub const IN_MEMORY_PATH: &str = ":memory:";
+
 impl Database {
-    /// Open (or create) the database at the given path.
-    pub fn open(path: &Path) -> Result<Self, String> {
+    /// Open (or create) the database at the given path. The special path
+    /// [`IN_MEMORY_PATH`] opens a fresh, already-migrated in-memory database.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        if path == Path::new(IN_MEMORY_PATH) {
+            return Self::open_in_memory();
+        }
+
         let conn = Connection::open(path).map_err(|e| format!("failed to open database: {e}"))?;
+        conn.busy_timeout(busy_timeout()?)
+            .map_err(|e| format!("failed to set busy timeout: {e}"))?;
+
+        retry_busy(|| conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;"))
+            .map_err(|e| format!("failed to set pragmas: {e}"))?;
+
+        Ok(Database { conn })
+    }
+
+    /// Open an existing database file for reading only, e.g. as one of the
+    /// web server's pooled readers. In WAL mode such a connection reads
+    /// alongside the writer without waiting for it; any write through it fails.
+    pub fn open_read_only(path: &Path) -> Result<Self, Error> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .map_err(|e| format!("failed to open database: {e}"))?;
+        conn.busy_timeout(busy_timeout()?)
+            .map_err(|e| format!("failed to set busy timeout: {e}"))?;
+        Ok(Database { conn })
+    }
 
-        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")
+    /// Open a private in-memory database and migrate it. Data lives only as
+    /// long as this `Database`; every call starts empty. WAL does not apply to
+    /// in-memory databases, so only the foreign-key pragma is set.
+    pub fn open_in_memory() -> Result<Self, Error> {
+        let conn = Connection::open_in_memory()
+            .map_err(|e| format!("failed to open in-memory database: {e}"))?;
+
+        conn.execute_batch("PRAGMA foreign_keys=ON;")
             .map_err(|e| format!("failed to set pragmas: {e}"))?;
 
+        let db = Database { conn };
+        db.migrate()?;
+        Ok(db)
+    }
+
+    /// Copy the database file at `path` into a private in-memory database,
+    /// schema and rows as stored, without migrating it. The file is only read,
+    /// so later writes (migrations included) never reach it. The full-text
+    /// index is not copied; search on the copy uses the LIKE fallback.
+    pub fn open_copy_in_memory(path: &Path) -> Result<Self, Error> {
+        if !path.is_file() {
+            return Err(Error::not_found(format!(
+                "file not found: {}",
+                path.display()
+            )));
+        }
+        let source = path
+            .to_str()
+            .ok_or_else(|| format!("path is not valid UTF-8: {}", path.display()))?;
+        let conn = Connection::open_in_memory()
+            .map_err(|e| format!("failed to open in-memory database: {e}"))?;
+        conn.execute("ATTACH DATABASE ?1 AS source", params![source])
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+
+        // Tables first (with their rows), then indexes over them.
+        let copy = || -> rusqlite::Result<()> {
+            let mut stmt = conn.prepare(
+                "SELECT type, name, sql FROM source.sqlite_master
+                 WHERE type IN ('table', 'index') AND sql IS NOT NULL
+                   AND name NOT LIKE 'sqlite_%' AND name NOT LIKE 'tasks_fts%'
+                 ORDER BY type = 'index'",
+            )?;
+            let objects = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            for (kind, name, sql) in objects {
+                conn.execute_batch(&sql)?;
+                if kind == "table" {
+                    let name = name.replace('"', "\"\"");
+                    conn.execute(
+                        &format!("INSERT INTO main.\"{name}\" SELECT * FROM source.\"{name}\""),
+                        [],
+                    )?;
+                }
+            }
+            Ok(())
+        };
+        let copied = copy().map_err(|e| format!("failed to read {}: {e}", path.display()));
+        conn.execute("DETACH DATABASE source", [])
+            .map_err(|e| format!("failed to detach {}: {e}", path.display()))?;
+        copied?;
+
+        conn.execute_batch("PRAGMA foreign_keys=ON;")
+            .map_err(|e| format!("failed to set pragmas: {e}"))?;
         Ok(Database { conn })
     }
 
+    /// Run `f` atomically: its writes are committed together if it returns
+    /// `Ok`, and all rolled back if it returns `Err`. When a transaction is
+    /// already open (a multi-step op calling another), `f` joins it instead of
+    /// nesting, and the outermost call decides commit or rollback.
+    ///
+    /// The write lock is taken up front (`BEGIN IMMEDIATE`), waiting out the
+    /// busy timeout and then retrying, so a concurrent writer delays `f`
+    /// rather than failing it halfway through.
+    pub fn with_transaction<T>(
+        &self,
+        f: impl FnOnce(&Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        if !self.conn.is_autocommit() {
+            return f(self);
+        }
+        // Read-only connections cannot take the write lock.
+        let behavior = if self.conn.is_readonly(DatabaseName::Main).unwrap_or(false) {
+            TransactionBehavior::Deferred
+        } else {
+            TransactionBehavior::Immediate
+        };
+        let tx = retry_busy(|| Transaction::new_unchecked(&self.conn, behavior))
+            .map_err(|e| format!("failed to begin transaction: {e}"))?;
+        let value = f(self)?;
+        tx.commit()
+            .map_err(|e| format!("failed to commit transaction: {e}"))?;
+        Ok(value)
+    }
+
     /// Create the schema tables if they don't exist, then run any pending version-gated migrations.
-    pub fn migrate(&self) -> Result<(), String> {
+    pub fn migrate(&self) -> Result<(), Error> {
         self.conn
             .execute_batch(
                 "
@@ -75,12 +420,275 @@ impl Database {
             )
             .map_err(|e| format!("failed to seed schema_version: {e}"))?;
 
-        run_migrations(&self.conn)
+        run_migrations(&self.conn)?;
+        self.recompute_blocked_flags()?;
+        Ok(())
+    }
+
+    /// Return the schema version recorded in the config table.
+    pub fn schema_version(&self) -> Result<i32, Error> {
+        get_schema_version(&self.conn)
+    }
+
+    /// Recompute the denormalized `is_blocked` flag for every task from the
+    /// dependency graph. Returns how many rows were out of date (0 means the
+    /// stored flags were already consistent).
+    pub fn recompute_blocked_flags(&self) -> Result<usize, Error> {
+        self.conn
+            .execute(
+                &format!(
+                    "UPDATE tasks SET is_blocked = ({IS_BLOCKED_EXPR})
+                     WHERE is_blocked != ({IS_BLOCKED_EXPR})"
+                ),
+                [],
+            )
+            .map_err(|e| format!("failed to recompute blocked flags: {e}").into())
+    }
+
+    /// Stored tasks that the current input rules would reject: a priority
+    /// outside 0–3, an unknown status or close reason, an unparseable
+    /// timestamp, or a parent that does not exist. Reads raw column values,
+    /// since loading a [`Task`] papers over most of these.
+    pub fn check_tasks(&self) -> Result<Vec<TaskProblem>, Error> {
+        use rusqlite::types::Value;
+
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT t.id, t.status, t.priority, t.created_at, t.updated_at, t.close_reason,
+                        t.parent_id, p.id IS NULL, t.due_at
+                 FROM tasks t LEFT JOIN tasks p ON p.id = t.parent_id
+                 ORDER BY t.id",
+            )
+            .map_err(|e| format!("query error: {e}"))?;
+        let mut rows = stmt.query([]).map_err(|e| format!("query error: {e}"))?;
+
+        let mut problems = Vec::new();
+        while let Some(row) = rows.next().map_err(|e| format!("row error: {e}"))? {
+            let read = |idx: usize| -> Result<Value, String> {
+                row.get(idx).map_err(|e| format!("row error: {e}"))
+            };
+            let task_id: String = row.get(0).map_err(|e| format!("row error: {e}"))?;
+            let mut report = |problem: String| {
+                problems.push(TaskProblem {
+                    task_id: task_id.clone(),
+                    problem,
+                })
+            };
+            match read(1)? {
+                Value::Text(s) if Status::from_str(&s).is_ok() => {}
+                other => report(format!("unknown status {}", describe_value(&other))),
+            }
+            match read(2)? {
+                Value::Integer(p) if (0..=3).contains(&p) => {}
+                other => report(format!(
+                    "priority {} is outside 0-3",
+                    describe_value(&other)
+                )),
+            }
+            for (idx, column) in [(3, "created_at"), (4, "updated_at")] {
+                match read(idx)? {
+                    Value::Text(s) if DateTime::parse_from_rfc3339(&s).is_ok() => {}
+                    other => report(format!(
+                        "invalid {column} timestamp {}",
+                        describe_value(&other)
+                    )),
+                }
+            }
+            match read(5)? {
+                Value::Null => {}
+                Value::Text(s) if validate_close_reason(&s).is_ok() => {}
+                other => report(format!("unknown close reason {}", describe_value(&other))),
+            }
+            match read(8)? {
+                Value::Null => {}
+                Value::Text(s) if DateTime::parse_from_rfc3339(&s).is_ok() => {}
+                other => report(format!(
+                    "invalid due_at timestamp {}",
+                    describe_value(&other)
+                )),
+            }
+            if let Value::Text(parent) = read(6)? {
+                let missing: bool = row.get(7).map_err(|e| format!("row error: {e}"))?;
+                if !parent.is_empty() && missing {
+                    report(format!("parent {parent} does not exist"));
+                }
+            }
+        }
+        Ok(problems)
+    }
+
+    /// Cross-row consistency checks: dependency rows whose tasks are gone,
+    /// parents that no longer exist, open epics without subtasks, dependency
+    /// cycles, and stale `is_blocked` flags. Ordered by check, then task.
+    pub fn integrity_report(&self) -> Result<Vec<IntegrityFinding>, Error> {
+        let mut findings = Vec::new();
+        let query = |sql: &str| -> Result<Vec<(String, String)>, String> {
+            let mut stmt = self
+                .conn
+                .prepare(sql)
+                .map_err(|e| format!("query error: {e}"))?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(|e| format!("query error: {e}"))?;
+            rows.collect::<Result<_, _>>()
+                .map_err(|e| format!("row error: {e}"))
+        };
+
+        for (child, parent) in query(
+            "SELECT child_id, parent_id FROM dependencies d
+             WHERE NOT EXISTS (SELECT 1 FROM tasks WHERE id = d.child_id)
+                OR NOT EXISTS (SELECT 1 FROM tasks WHERE id = d.parent_id)
+             ORDER BY child_id, parent_id",
+        )? {
+            let exists = |id: &str| -> Result<bool, Error> { Ok(self.get_task(id)?.is_some()) };
+            let message = match (exists(&child)?, exists(&parent)?) {
+                (true, _) => format!("blocked by {parent}, which does not exist"),
+                (false, true) => format!("missing task is recorded as blocked by {parent}"),
+                (false, false) => format!("dependency between two missing tasks ({parent})"),
+            };
+            findings.push(IntegrityFinding {
+                severity: Severity::Error,
+                check: "dangling_dependency",
+                task_id: child,
+                message,
+                fixable: true,
+            });
+        }
+
+        for (id, parent) in query(
+            "SELECT t.id, t.parent_id FROM tasks t
+             WHERE t.parent_id IS NOT NULL AND t.parent_id != ''
+               AND NOT EXISTS (SELECT 1 FROM tasks p WHERE p.id = t.parent_id)
+             ORDER BY t.id",
+        )? {
+            findings.push(IntegrityFinding {
+                severity: Severity::Error,
+                check: "dangling_parent",
+                task_id: id,
+                message: format!("parent {parent} does not exist"),
+                fixable: true,
+            });
+        }
+
+        for (id, _) in query(
+            "SELECT t.id, t.title FROM tasks t
+             JOIN task_tags tt ON tt.task_id = t.id AND tt.tag = 'epic'
+             WHERE t.status != 'done'
+               AND NOT EXISTS (SELECT 1 FROM tasks c WHERE c.parent_id = t.id)
+             ORDER BY t.id",
+        )? {
+            findings.push(IntegrityFinding {
+                severity: Severity::Warning,
+                check: "empty_epic",
+                task_id: id,
+                message: "tagged epic but has no subtasks".to_string(),
+                fixable: false,
+            });
+        }
+
+        let cycle = self.dependency_cycle_members()?;
+        if let Some(first) = cycle.first() {
+            findings.push(IntegrityFinding {
+                severity: Severity::Error,
+                check: "dependency_cycle",
+                task_id: first.clone(),
+                message: format!("dependency cycle among: {}", cycle.join(", ")),
+                fixable: false,
+            });
+        }
+
+        for (id, stored) in query(&format!(
+            "SELECT id, CAST(is_blocked AS TEXT) FROM tasks
+             WHERE is_blocked != ({IS_BLOCKED_EXPR}) ORDER BY id"
+        ))? {
+            findings.push(IntegrityFinding {
+                severity: Severity::Warning,
+                check: "stale_blocked_flag",
+                task_id: id,
+                message: format!("stored is_blocked = {stored} disagrees with its blockers"),
+                fixable: true,
+            });
+        }
+        Ok(findings)
+    }
+
+    /// Tasks on (or between) dependency cycles, by ID: what is left after
+    /// repeatedly removing tasks with no remaining blockers or no remaining
+    /// dependents. Empty when the graph is acyclic.
+    fn dependency_cycle_members(&self) -> Result<Vec<String>, Error> {
+        let mut edges: Vec<Dependency> = self.all_dependencies()?;
+        loop {
+            let blocked: std::collections::HashSet<&str> =
+                edges.iter().map(|e| e.child_id.as_str()).collect();
+            let blocking: std::collections::HashSet<&str> =
+                edges.iter().map(|e| e.parent_id.as_str()).collect();
+            let kept: Vec<Dependency> = edges
+                .iter()
+                .filter(|e| blocked.contains(e.parent_id.as_str()))
+                .filter(|e| blocking.contains(e.child_id.as_str()))
+                .cloned()
+                .collect();
+            if kept.len() == edges.len() {
+                break;
+            }
+            edges = kept;
+        }
+        let members: std::collections::BTreeSet<String> =
+            edges.into_iter().map(|e| e.child_id).collect();
+        Ok(members.into_iter().collect())
+    }
+
+    /// Fix what [`integrity_report`](Self::integrity_report) marks fixable,
+    /// in one transaction: delete dependency rows with a missing end, clear
+    /// parents that do not exist, and recompute `is_blocked`. Returns the
+    /// number of rows changed.
+    pub fn repair_integrity(&self) -> Result<usize, Error> {
+        self.with_transaction(|db| {
+            let deps = db
+                .conn
+                .execute(
+                    "DELETE FROM dependencies
+                     WHERE NOT EXISTS (SELECT 1 FROM tasks WHERE id = dependencies.child_id)
+                        OR NOT EXISTS (SELECT 1 FROM tasks WHERE id = dependencies.parent_id)",
+                    [],
+                )
+                .map_err(|e| format!("failed to delete dangling dependencies: {e}"))?;
+            let parents = db
+                .conn
+                .execute(
+                    "UPDATE tasks SET parent_id = NULL
+                     WHERE parent_id IS NOT NULL AND parent_id != ''
+                       AND NOT EXISTS (SELECT 1 FROM tasks p WHERE p.id = tasks.parent_id)",
+                    [],
+                )
+                .map_err(|e| format!("failed to clear dangling parents: {e}"))?;
+            Ok(deps + parents + db.recompute_blocked_flags()?)
+        })
+    }
+
+    /// Refresh `is_blocked` for the given tasks only. Called from every path
+    /// that can change whether a task has an open blocker.
+    fn refresh_blocked_flags(&self, ids: &[String]) -> Result<(), Error> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let placeholders: Vec<String> = (1..=ids.len()).map(|i| format!("?{i}")).collect();
+        self.conn
+            .execute(
+                &format!(
+                    "UPDATE tasks SET is_blocked = ({IS_BLOCKED_EXPR}) WHERE id IN ({})",
+                    placeholders.join(", ")
+                ),
+                rusqlite::params_from_iter(ids.iter()),
+            )
+            .map_err(|e| format!("failed to refresh blocked flags: {e}"))?;
+        Ok(())
     }
 
     // -- Config --
 
-    pub fn set_config(&self, key: &str, value: &str) -> Result<(), String> {
+    pub fn set_config(&self, key: &str, value: &str) -> Result<(), Error> {
         self.conn
             .execute(
                 "INSERT OR REPLACE INTO config (key, value) VALUES (?1, ?2)",
@@ -90,7 +698,7 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_config(&self, key: &str) -> Result<Option<String>, String> {
+    pub fn get_config(&self, key: &str) -> Result<Option<String>, Error> {
         let mut stmt = self
             .conn
             .prepare("SELECT value FROM config WHERE key = ?1")
@@ -100,19 +708,147 @@ impl Database {
             .map_err(|e| format!("query error: {e}"))?;
         match rows.next() {
             Some(Ok(v)) => Ok(Some(v)),
-            Some(Err(e)) => Err(format!("query error: {e}")),
+            Some(Err(e)) => Err(format!("query error: {e}").into()),
             None => Ok(None),
         }
     }
 
+    /// Read a boolean config key, falling back to `default` when it is unset
+    /// or holds an unrecognised value.
+    pub fn get_config_bool(&self, key: &str, default: bool) -> Result<bool, Error> {
+        Ok(match self.get_config(key)?.as_deref() {
+            Some("true" | "1" | "yes" | "on") => true,
+            Some("false" | "0" | "no" | "off") => false,
+            _ => default,
+        })
+    }
+
+    /// Every config key and its value, sorted by key.
+    pub fn list_config(&self) -> Result<Vec<(String, String)>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT key, value FROM config ORDER BY key")
+            .map_err(|e| format!("query error: {e}"))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("query error: {e}"))?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+        Ok(entries)
+    }
+
+    // -- Templates --
+
+    /// Snapshot task `id` and, recursively, its subtasks as a template.
+    pub fn task_template(&self, id: &str) -> Result<Template, Error> {
+        let task = self
+            .get_task(id)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+        let children = self
+            .get_children(id)?
+            .iter()
+            .map(|child| self.task_template(&child.id))
+            .collect::<Result<_, _>>()?;
+        Ok(Template {
+            title: task.title,
+            priority: task.priority,
+            tags: task.tags,
+            description: task.description,
+            children,
+        })
+    }
+
+    /// Store `template` as JSON under the config key `template.<name>`,
+    /// replacing any template of that name.
+    pub fn save_template(&self, name: &str, template: &Template) -> Result<(), Error> {
+        validate_template_name(name).map_err(Error::validation)?;
+        let json = serde_json::to_string(template).map_err(|e| format!("json error: {e}"))?;
+        self.set_config(&format!("{TEMPLATE_KEY_PREFIX}{name}"), &json)
+    }
+
+    /// The template saved as `name`, if any.
+    pub fn get_template(&self, name: &str) -> Result<Option<Template>, Error> {
+        self.get_config(&format!("{TEMPLATE_KEY_PREFIX}{name}"))?
+            .map(|json| {
+                serde_json::from_str(&json)
+                    .map_err(|e| format!("invalid template {name} in config: {e}").into())
+            })
+            .transpose()
+    }
+
+    /// Every saved template, by name.
+    pub fn list_templates(&self) -> Result<Vec<(String, Template)>, Error> {
+        let mut templates = Vec::new();
+        for (key, json) in self.list_config()? {
+            let Some(name) = key.strip_prefix(TEMPLATE_KEY_PREFIX) else {
+                continue;
+            };
+            let template = serde_json::from_str(&json)
+                .map_err(|e| format!("invalid template {name} in config: {e}"))?;
+            templates.push((name.to_string(), template));
+        }
+        Ok(templates)
+    }
+
+    /// Store `view` as JSON under the config key `view.<name>`, replacing
+    /// any view of that name.
+    pub fn save_view(&self, name: &str, view: &SavedView) -> Result<(), Error> {
+        validate_view_name(name).map_err(Error::validation)?;
+        let json = serde_json::to_string(view).map_err(|e| format!("json error: {e}"))?;
+        self.set_config(&format!("{VIEW_KEY_PREFIX}{name}"), &json)
+    }
+
+    /// The view saved as `name`, if any.
+    pub fn get_view(&self, name: &str) -> Result<Option<SavedView>, Error> {
+        self.get_config(&format!("{VIEW_KEY_PREFIX}{name}"))?
+            .map(|json| {
+                serde_json::from_str(&json)
+                    .map_err(|e| format!("invalid view {name} in config: {e}").into())
+            })
+            .transpose()
+    }
+
+    /// Every saved view, by name.
+    pub fn list_views(&self) -> Result<Vec<(String, SavedView)>, Error> {
+        let mut views = Vec::new();
+        for (key, json) in self.list_config()? {
+            let Some(name) = key.strip_prefix(VIEW_KEY_PREFIX) else {
+                continue;
+            };
+            let view = serde_json::from_str(&json)
+                .map_err(|e| format!("invalid view {name} in config: {e}"))?;
+            views.push((name.to_string(), view));
+        }
+        Ok(views)
+    }
+
+    /// Priority for new tasks created without one: the `default_priority`
+    /// config key, else P2.
+    pub fn default_priority(&self) -> Result<u8, Error> {
+        match self.get_config("default_priority")? {
+            Some(v) => parse_priority(&v).map_err(|_| {
+                Error::validation(format!("invalid default_priority config value: {v}"))
+            }),
+            None => Ok(DEFAULT_PRIORITY),
+        }
+    }
+
     // -- Tasks --
 
-    pub fn insert_task(&self, task: &Task) -> Result<(), String> {
+    /// Insert a new task. `task.position` is ignored: the task goes after the
+    /// last task of its priority.
+    pub fn insert_task(&self, task: &Task) -> Result<(), Error> {
+        validate_tags(&task.tags).map_err(Error::validation)?;
         let tags_str = task.tags.join(",");
-        self.conn
+        self.with_transaction(|db| {
+            db.conn
             .execute(
-                "INSERT INTO tasks (id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                "INSERT INTO tasks (id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes, due_at, position, estimate_minutes, spent_minutes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13,
+                         (SELECT COALESCE(MAX(position), 0) + 1 FROM tasks WHERE priority = ?5),
+                         ?14, ?15)",
                 params![
                     task.id,
                     task.title,
@@ -126,43 +862,97 @@ impl Database {
                     task.updated_at.to_rfc3339(),
                     task.close_reason,
                     task.notes,
+                    task.due_at.map(|t| t.to_rfc3339()),
+                    task.estimate_minutes,
+                    task.spent_minutes,
                 ],
             )
             .map_err(|e| format!("failed to insert task: {e}"))?;
-        Ok(())
+            db.write_task_tags(&task.id, &task.tags)
+        })
     }
 
-    pub fn get_task(&self, id: &str) -> Result<Option<Task>, String> {
+    pub fn get_task(&self, id: &str) -> Result<Option<Task>, Error> {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes
+                "SELECT id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes, due_at, position, archived_at, estimate_minutes, spent_minutes
                  FROM tasks WHERE id = ?1",
             )
             .map_err(|e| format!("query error: {e}"))?;
 
         let mut rows = stmt
-            .query_map(params![id], |row| Ok(row_to_task(row)))
+            .query_map(params![id], row_to_task)
             .map_err(|e| format!("query error: {e}"))?;
 
         match rows.next() {
             Some(Ok(task)) => Ok(Some(task)),
-            Some(Err(e)) => Err(format!("query error: {e}")),
+            Some(Err(e)) => Err(format!("query error: {e}").into()),
             None => Ok(None),
         }
     }
 
-    pub fn list_tasks(
-        &self,
-        include_done: bool,
-        status_filter: Option<&str>,
-        priority_filter: Option<u8>,
-        tag_filter: Option<&str>,
-        parent_filter: Option<&str>,
-        search: Option<&str>,
-    ) -> Result<Vec<Task>, String> {
+    /// Fetch several tasks in one query, returned in the order of `ids`.
+    ///
+    /// IDs that don't exist are silently omitted; duplicates are returned once.
+    pub fn get_tasks_by_ids(&self, ids: &[String]) -> Result<Vec<Task>, Error> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders: Vec<String> = (1..=ids.len()).map(|i| format!("?{i}")).collect();
+        let sql = format!(
+            "SELECT id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes, due_at, position, archived_at, estimate_minutes, spent_minutes
+             FROM tasks WHERE id IN ({})",
+            placeholders.join(", ")
+        );
+        let mut stmt = self
+            .conn
+            .prepare(&sql)
+            .map_err(|e| format!("query error: {e}"))?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(ids.iter()), row_to_task)
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let mut found = Vec::new();
+        for row in rows {
+            found.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+
+        let mut tasks = Vec::with_capacity(found.len());
+        for id in ids {
+            // swap_remove also drops the task from `found`, so a repeated ID
+            // is only emitted the first time.
+            if let Some(pos) = found.iter().position(|t| &t.id == id) {
+                tasks.push(found.swap_remove(pos));
+            }
+        }
+        Ok(tasks)
+    }
+
+    /// List tasks matching `filter`, ordered by priority, board position,
+    /// creation time, then id.
+    pub fn list_tasks(&self, filter: &TaskFilter) -> Result<Vec<Task>, Error> {
+        let TaskFilter {
+            include_done,
+            include_archived,
+            status: status_filter,
+            priority: priority_filter,
+            tag: tag_filter,
+            any_tag,
+            parent: parent_filter,
+            assignee: assignee_filter,
+            blocked_by,
+            blocking,
+            search,
+            close_reason,
+            created_after,
+            created_before,
+            updated_after,
+            after,
+            limit,
+        } = *filter;
         let mut sql = String::from(
-            "SELECT id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes FROM tasks WHERE 1=1",
+            "SELECT id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes, due_at, position, archived_at, estimate_minutes, spent_minutes FROM tasks WHERE 1=1",
         );
         let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
         let mut param_idx = 1;
@@ -177,6 +967,10 @@ impl Database {
             param_idx += 1;
         }
 
+        if !include_archived {
+            sql.push_str(" AND archived_at IS NULL");
+        }
+
         if let Some(p) = priority_filter {
             sql.push_str(&format!(" AND priority = ?{param_idx}"));
             param_values.push(Box::new(p));
@@ -185,27 +979,137 @@ impl Database {
 
         if let Some(tag) = tag_filter {
             sql.push_str(&format!(
-                " AND (',' || tags || ',') LIKE '%,' || ?{param_idx} || ',%'"
+                " AND EXISTS (SELECT 1 FROM task_tags tt WHERE tt.task_id = tasks.id AND tt.tag = ?{param_idx})"
             ));
             param_values.push(Box::new(tag.to_string()));
             param_idx += 1;
         }
 
+        if !any_tag.is_empty() {
+            let placeholders: Vec<String> = (param_idx..param_idx + any_tag.len())
+                .map(|i| format!("?{i}"))
+                .collect();
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM task_tags tt WHERE tt.task_id = tasks.id AND tt.tag IN ({}))",
+                placeholders.join(", ")
+            ));
+            for tag in any_tag {
+                param_values.push(Box::new(tag.clone()));
+            }
+            param_idx += any_tag.len();
+        }
+
         if let Some(parent) = parent_filter {
             sql.push_str(&format!(" AND parent_id = ?{param_idx}"));
             param_values.push(Box::new(parent.to_string()));
             param_idx += 1;
         }
 
-        if let Some(s) = search {
+        if let Some(assignee) = assignee_filter {
+            if assignee.is_empty() {
+                sql.push_str(" AND (assignee IS NULL OR assignee = '')");
+            } else {
+                sql.push_str(&format!(" AND assignee = ?{param_idx}"));
+                param_values.push(Box::new(assignee.to_string()));
+                param_idx += 1;
+            }
+        }
+
+        if let Some(blocker) = blocked_by {
             sql.push_str(&format!(
-                " AND title LIKE '%' || ?{param_idx} || '%' COLLATE NOCASE"
+                " AND id IN (SELECT child_id FROM dependencies WHERE parent_id = ?{param_idx})"
             ));
-            param_values.push(Box::new(s.to_string()));
-            let _ = param_idx; // suppress unused warning after last param
+            param_values.push(Box::new(blocker.to_string()));
+            param_idx += 1;
         }
 
-        sql.push_str(" ORDER BY priority ASC, created_at ASC");
+        if let Some(dependent) = blocking {
+            sql.push_str(&format!(
+                " AND id IN (SELECT parent_id FROM dependencies WHERE child_id = ?{param_idx})"
+            ));
+            param_values.push(Box::new(dependent.to_string()));
+            param_idx += 1;
+        }
+
+        if let Some(s) = search.filter(|s| !s.trim().is_empty()) {
+            if !self.has_fts()? {
+                sql.push_str(&format!(
+                    " AND (title LIKE '%' || ?{param_idx} || '%' COLLATE NOCASE ESCAPE '\\'
+                       OR description LIKE '%' || ?{param_idx} || '%' COLLATE NOCASE ESCAPE '\\')"
+                ));
+                param_values.push(Box::new(escape_like(s)));
+                param_idx += 1;
+            } else if fts_query(s).is_empty() {
+                // Only punctuation: the index has no tokens to match it with.
+                sql.push_str(" AND 0");
+            } else {
+                sql.push_str(&format!(
+                    " AND id IN (SELECT id FROM tasks_fts WHERE tasks_fts MATCH ?{param_idx})"
+                ));
+                param_values.push(Box::new(fts_query(s)));
+                param_idx += 1;
+            }
+        }
+
+        if let Some(reason) = close_reason {
+            sql.push_str(&format!(" AND close_reason = ?{param_idx}"));
+            param_values.push(Box::new(reason.to_string()));
+            param_idx += 1;
+        }
+
+        // Timestamps are always stored as UTC RFC3339, so string order matches time order.
+        if let Some(t) = created_after {
+            sql.push_str(&format!(" AND created_at >= ?{param_idx}"));
+            param_values.push(Box::new(t.to_rfc3339()));
+            param_idx += 1;
+        }
+
+        if let Some(t) = created_before {
+            sql.push_str(&format!(" AND created_at < ?{param_idx}"));
+            param_values.push(Box::new(t.to_rfc3339()));
+            param_idx += 1;
+        }
+
+        if let Some(t) = updated_after {
+            sql.push_str(&format!(" AND updated_at >= ?{param_idx}"));
+            param_values.push(Box::new(t.to_rfc3339()));
+            param_idx += 1;
+        }
+
+        if let Some(cursor) = after {
+            let (cursor_priority, cursor_position, cursor_created): (u8, f64, String) = self
+                .conn
+                .query_row(
+                    "SELECT priority, position, created_at FROM tasks WHERE id = ?1",
+                    params![cursor],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )
+                .map_err(|e| match e {
+                    rusqlite::Error::QueryReturnedNoRows => {
+                        Error::validation(format!("unknown cursor: {cursor}"))
+                    }
+                    e => format!("query error: {e}").into(),
+                })?;
+            sql.push_str(&format!(
+                " AND (priority, position, created_at, id) > (?{}, ?{}, ?{}, ?{})",
+                param_idx,
+                param_idx + 1,
+                param_idx + 2,
+                param_idx + 3
+            ));
+            param_values.push(Box::new(cursor_priority));
+            param_values.push(Box::new(cursor_position));
+            param_values.push(Box::new(cursor_created));
+            param_values.push(Box::new(cursor.to_string()));
+            param_idx += 4;
+        }
+
+        sql.push_str(" ORDER BY priority ASC, position ASC, created_at ASC, id ASC");
+
+        if let Some(n) = limit {
+            sql.push_str(&format!(" LIMIT ?{param_idx}"));
+            param_values.push(Box::new(n));
+        }
 
         let mut stmt = self
             .conn
@@ -216,7 +1120,7 @@ impl Database {
             param_values.iter().map(|p| p.as_ref()).collect();
 
         let rows = stmt
-            .query_map(params_ref.as_slice(), |row| Ok(row_to_task(row)))
+            .query_map(params_ref.as_slice(), row_to_task)
             .map_err(|e| format!("query error: {e}"))?;
 
         let mut tasks = Vec::new();
@@ -226,6 +1130,85 @@ impl Database {
         Ok(tasks)
     }
 
+    /// Search titles and descriptions of all tasks, done ones included, best
+    /// match first. Each word of `query` matches as a word prefix and all
+    /// must match. Uses the `tasks_fts` index, or a LIKE scan of the whole
+    /// query when this SQLite has no FTS5 (`%` and `_` in it match literally).
+    pub fn search_tasks(&self, query: &str) -> Result<Vec<Task>, Error> {
+        if query.trim().is_empty() {
+            return Err(Error::validation("search query is empty"));
+        }
+        let (sql, param) = if self.has_fts()? {
+            let fts = fts_query(query);
+            if fts.is_empty() {
+                // Only punctuation: the index has no tokens to match it with.
+                return Ok(Vec::new());
+            }
+            (
+                "SELECT t.id, t.title, t.description, t.status, t.priority, t.assignee,
+                        t.parent_id, t.tags, t.created_at, t.updated_at, t.close_reason, t.notes, t.due_at, t.position, t.archived_at,
+                        t.estimate_minutes, t.spent_minutes
+                 FROM tasks_fts f JOIN tasks t ON t.id = f.id
+                 WHERE tasks_fts MATCH ?1
+                 ORDER BY bm25(tasks_fts, 0.0, 10.0, 1.0), t.priority ASC, t.created_at ASC, t.id ASC",
+                fts,
+            )
+        } else {
+            (
+                "SELECT id, title, description, status, priority, assignee, parent_id, tags,
+                        created_at, updated_at, close_reason, notes, due_at, position, archived_at,
+                        estimate_minutes, spent_minutes
+                 FROM tasks
+                 WHERE title LIKE '%' || ?1 || '%' COLLATE NOCASE ESCAPE '\\'
+                    OR description LIKE '%' || ?1 || '%' COLLATE NOCASE ESCAPE '\\'
+                 ORDER BY title LIKE '%' || ?1 || '%' COLLATE NOCASE ESCAPE '\\' DESC,
+                          priority ASC, created_at ASC, id ASC",
+                escape_like(query.trim()),
+            )
+        };
+        let mut stmt = self
+            .conn
+            .prepare(sql)
+            .map_err(|e| format!("query error: {e}"))?;
+        let rows = stmt
+            .query_map(params![param], row_to_task)
+            .map_err(|e| format!("query error: {e}"))?;
+        let mut tasks = Vec::new();
+        for row in rows {
+            tasks.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+        Ok(tasks)
+    }
+
+    /// Whether the `tasks_fts` full-text index exists (schema v5 on a SQLite
+    /// built with FTS5).
+    fn has_fts(&self) -> Result<bool, Error> {
+        self.conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'tasks_fts')",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("query error: {e}").into())
+    }
+
+    /// Fail unless task `id` was last updated exactly at `expected`, so a
+    /// caller working from a stale read does not overwrite a newer change.
+    /// Call it inside the same transaction as the write it guards.
+    pub fn ensure_unchanged_since(&self, id: &str, expected: DateTime<Utc>) -> Result<(), Error> {
+        let task = self
+            .get_task(id)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+        if task.updated_at != expected {
+            return Err(Error::conflict(format!(
+                "task {id} changed since {}: last updated {}",
+                expected.to_rfc3339(),
+                task.updated_at.to_rfc3339()
+            )));
+        }
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn update_task(
         &self,
@@ -237,7 +1220,7 @@ impl Database {
         assignee: Option<&str>,
         close_reason: Option<&str>,
         notes: Option<&str>,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         let mut sets = Vec::new();
         let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
         let mut idx = 1;
@@ -254,7 +1237,7 @@ impl Database {
         }
         if let Some(s) = status {
             // Validate status
-            Status::from_str(s)?;
+            Status::from_str(s).map_err(Error::validation)?;
             sets.push(format!("status = ?{idx}"));
             param_values.push(Box::new(s.to_string()));
             idx += 1;
@@ -269,8 +1252,13 @@ impl Database {
             param_values.push(Box::new(a.to_string()));
             idx += 1;
         }
-        if let Some(r) = close_reason {
-            validate_close_reason(r)?;
+        // Moving a task out of `done` reopens it, so any close_reason no longer
+        // applies and is cleared in the same statement.
+        let reopening = status.is_some_and(|s| s != "done");
+        if reopening {
+            sets.push("close_reason = NULL".to_string());
+        } else if let Some(r) = close_reason {
+            validate_close_reason(r).map_err(Error::validation)?;
             sets.push(format!("close_reason = ?{idx}"));
             param_values.push(Box::new(r.to_string()));
             idx += 1;
@@ -296,49 +1284,812 @@ impl Database {
         let params_ref: Vec<&dyn rusqlite::types::ToSql> =
             param_values.iter().map(|p| p.as_ref()).collect();
 
-        let rows_changed = self
-            .conn
-            .execute(&sql, params_ref.as_slice())
-            .map_err(|e| format!("update failed: {e}"))?;
+        self.with_transaction(|db| {
+            let before = db
+                .get_task(id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+            db.conn
+                .execute(&sql, params_ref.as_slice())
+                .map_err(|e| format!("update failed: {e}"))?;
+            let after = db
+                .get_task(id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+            db.record_activity(&before, &after)?;
 
-        if rows_changed == 0 {
-            return Err(format!("task not found: {id}"));
-        }
-        Ok(())
-    }
+            // Positions order tasks within a priority, so a task moved to
+            // another priority goes last there, as a new task would.
+            if before.priority != after.priority {
+                db.conn
+                    .execute(
+                        "UPDATE tasks SET position =
+                             (SELECT COALESCE(MAX(position), 0) + 1 FROM tasks
+                              WHERE priority = ?1 AND id != ?2)
+                         WHERE id = ?2",
+                        params![after.priority, id],
+                    )
+                    .map_err(|e| format!("position update failed: {e}"))?;
+            }
 
-    /// Close a task: set status to done and record the close_reason.
-    pub fn close_task(&self, id: &str, reason: Option<&str>) -> Result<(), String> {
-        self.update_task(id, None, None, Some("done"), None, None, reason, None)
+            // A status change can block/unblock this task's dependents (and a
+            // done task is never blocked itself).
+            if status.is_some() {
+                let dependents: Vec<String> =
+                    db.get_dependents(id)?.into_iter().map(|t| t.id).collect();
+                let mut affected = dependents.clone();
+                affected.push(id.to_string());
+                db.refresh_blocked_flags(&affected)?;
+                if before.status != after.status {
+                    let auto_block = db.get_config_bool("auto_block", false)?;
+                    for dependent in &dependents {
+                        db.recompute_blocked_status(dependent, auto_block)?;
+                    }
+                }
+            }
+            Ok(())
+        })
     }
 
-    pub fn update_tags(&self, id: &str, tags: &[String]) -> Result<(), String> {
-        let tags_str = tags.join(",");
+    /// Insert an `activity` row for each tracked field (status, priority,
+    /// assignee, close_reason) that differs between `before` and `after`.
+    fn record_activity(&self, before: &Task, after: &Task) -> Result<(), Error> {
+        let changes = [
+            (
+                "status",
+                Some(before.status.as_str().to_string()),
+                Some(after.status.as_str().to_string()),
+            ),
+            (
+                "priority",
+                Some(before.priority.to_string()),
+                Some(after.priority.to_string()),
+            ),
+            ("assignee", before.assignee.clone(), after.assignee.clone()),
+            (
+                "close_reason",
+                before.close_reason.clone(),
+                after.close_reason.clone(),
+            ),
+        ];
         let now = Utc::now().to_rfc3339();
+        for (field, old_value, new_value) in changes {
+            if old_value == new_value {
+                continue;
+            }
+            self.conn
+                .execute(
+                    "INSERT INTO activity (task_id, field, old_value, new_value, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![after.id, field, old_value, new_value, now],
+                )
+                .map_err(|e| format!("failed to record activity: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Recorded field changes for a task, oldest first.
+    pub fn get_activity(&self, task_id: &str) -> Result<Vec<Activity>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, task_id, field, old_value, new_value, created_at
+                 FROM activity WHERE task_id = ?1 ORDER BY created_at ASC, id ASC",
+            )
+            .map_err(|e| format!("query error: {e}"))?;
+        let rows = stmt
+            .query_map(params![task_id], |row| {
+                Ok(Activity {
+                    id: row.get(0)?,
+                    task_id: row.get(1)?,
+                    field: row.get(2)?,
+                    old_value: row.get(3)?,
+                    new_value: row.get(4)?,
+                    created_at: timestamp_column(row, 5, "created_at")?,
+                })
+            })
+            .map_err(|e| format!("query error: {e}"))?;
+        let mut activity = Vec::new();
+        for row in rows {
+            activity.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+        Ok(activity)
+    }
+
+    /// Apply the same status, priority, and tag additions to every task
+    /// matching `filter`, in one transaction. Returns the updated tasks in
+    /// list order; an empty match is not an error.
+    pub fn bulk_update(
+        &self,
+        filter: &TaskFilter,
+        status: Option<&str>,
+        priority: Option<u8>,
+        add_tags: &[String],
+    ) -> Result<Vec<Task>, Error> {
+        if status.is_none() && priority.is_none() && add_tags.is_empty() {
+            return Err(Error::validation(
+                "nothing to update: pass --set-status, --set-priority, or --add-tags",
+            ));
+        }
+        self.with_transaction(|db| {
+            let matched = db.list_tasks(filter)?;
+            let mut updated = Vec::with_capacity(matched.len());
+            for task in matched {
+                db.update_task(&task.id, None, priority, status, None, None, None, None)?;
+                if !add_tags.is_empty() {
+                    let mut tags = db.get_task_tags(&task.id)?;
+                    for tag in add_tags {
+                        if !tags.contains(tag) {
+                            tags.push(tag.clone());
+                        }
+                    }
+                    db.update_tags(&task.id, &tags)?;
+                }
+                updated.push(
+                    db.get_task(&task.id)?
+                        .ok_or_else(|| Error::not_found(format!("task not found: {}", task.id)))?,
+                );
+            }
+            Ok(updated)
+        })
+    }
+
+    /// Close a task: set status to done, record the close_reason, and store
+    /// each of `comments` as a separate comment, in order.
+    ///
+    /// When the `auto_close_epic` config key is `true` (default `false`) and
+    /// this was the last non-done child of its parent, the parent is closed
+    /// too (see [`Database::close_parent_if_done`]). Callers that take a
+    /// per-call opt-in (`tk close --close-parent`) call that afterwards.
+    pub fn close_task(
+        &self,
+        id: &str,
+        reason: Option<&str>,
+        comments: &[String],
+    ) -> Result<(), Error> {
+        self.with_transaction(|db| {
+            db.update_task(id, None, None, Some("done"), None, None, reason, None)?;
+            for body in comments {
+                db.add_comment(id, body)?;
+            }
+
+            if db.get_config_bool("auto_close_epic", false)? {
+                db.close_parent_if_done(id)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Whether every child of `parent_id` is done. A task with no children
+    /// counts as all done.
+    pub fn all_children_done(&self, parent_id: &str) -> Result<bool, Error> {
+        Ok(self
+            .get_children(parent_id)?
+            .iter()
+            .all(|c| c.status == Status::Done))
+    }
+
+    /// If `id`'s parent is still open and all of its children are now done,
+    /// close the parent with reason `done` and a comment noting the
+    /// auto-close. Returns the parent's ID when it was closed. Only one level
+    /// is handled: closing the parent never cascades further up.
+    pub fn close_parent_if_done(&self, id: &str) -> Result<Option<String>, Error> {
+        self.with_transaction(|db| {
+            let parent = match db.get_task(id)?.and_then(|t| t.parent_id) {
+                Some(pid) => db.get_task(&pid)?,
+                None => None,
+            };
+            let Some(parent) = parent.filter(|p| p.status != Status::Done) else {
+                return Ok(None);
+            };
+            if !db.all_children_done(&parent.id)? {
+                return Ok(None);
+            }
+            db.update_task(
+                &parent.id,
+                None,
+                None,
+                Some("done"),
+                None,
+                None,
+                Some("done"),
+                None,
+            )?;
+            db.add_comment(&parent.id, "Auto-closed: all subtasks are done")?;
+            Ok(Some(parent.id))
+        })
+    }
+
+    /// Reopen a done task: move it back to `status` (`open` or `in_progress`)
+    /// and clear its close_reason. Errors if the task is not done.
+    pub fn reopen_task(&self, id: &str, status: Status) -> Result<Task, Error> {
+        let task = self
+            .get_task(id)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+        if task.status != Status::Done {
+            return Err(Error::conflict(format!(
+                "task {id} is not closed (status: {})",
+                task.status.as_str()
+            )));
+        }
+        if !matches!(status, Status::Open | Status::InProgress) {
+            return Err(Error::validation(format!(
+                "cannot reopen to {}: use open or in_progress",
+                status.as_str()
+            )));
+        }
+        self.update_task(
+            id,
+            None,
+            None,
+            Some(status.as_str()),
+            None,
+            None,
+            None,
+            None,
+        )?;
+        self.get_task(id)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))
+    }
+
+    /// Delete done tasks closed before `cutoff` (optionally only those closed
+    /// with `reason`), along with their comments and dependency edges, in one
+    /// transaction. With `dry_run` nothing is deleted but the report is the
+    /// same.
+    ///
+    /// The close time is that of the task's last `done` status change in the
+    /// activity log, so editing a closed task does not postpone its prune;
+    /// tasks closed before the log existed fall back to `updated_at`.
+    ///
+    /// A task with a child that is not itself being pruned is skipped, since
+    /// deleting it would orphan the child. Pruning a task that still blocks a
+    /// non-done task is refused outright.
+    pub fn prune_done_tasks(
+        &self,
+        cutoff: DateTime<Utc>,
+        reason: Option<&str>,
+        dry_run: bool,
+    ) -> Result<PruneReport, Error> {
+        if let Some(r) = reason {
+            validate_close_reason(r).map_err(Error::validation)?;
+        }
+        // The candidate scan and the refusal checks must see the tasks and
+        // edges the delete removes, so even a dry run shares one transaction.
+        self.with_transaction(|db| {
+            let parent_of = db.done_tasks_closed_before(cutoff, reason)?;
+            let mut candidates: std::collections::HashSet<String> =
+                parent_of.keys().cloned().collect();
+
+            // Drop candidates with a surviving child until nothing changes, so
+            // a skipped child also keeps its own ancestors.
+            let mut skipped = Vec::new();
+            loop {
+                let mut keep = Vec::new();
+                for id in &candidates {
+                    if db
+                        .get_children(id)?
+                        .iter()
+                        .any(|c| !candidates.contains(&c.id))
+                    {
+                        keep.push(id.clone());
+                    }
+                }
+                if keep.is_empty() {
+                    break;
+                }
+                for id in keep {
+                    candidates.remove(&id);
+                    skipped.push(id);
+                }
+            }
+
+            let mut ids: Vec<String> = candidates.into_iter().collect();
+            ids.sort();
+            skipped.sort();
+
+            let mut report = PruneReport {
+                skipped,
+                dry_run,
+                ..Default::default()
+            };
+            let mut edges = std::collections::HashSet::new();
+            for id in &ids {
+                if let Some(open) = db
+                    .get_dependents(id)?
+                    .into_iter()
+                    .find(|t| t.status != Status::Done)
+                {
+                    return Err(Error::conflict(format!(
+                        "cannot prune {id}: it still blocks open task {}",
+                        open.id
+                    )));
+                }
+                report.comments += db.get_comments(id)?.len();
+                for dep in db.get_blockers(id)? {
+                    edges.insert((dep.child_id, dep.parent_id));
+                }
+                for dependent in db.get_dependents(id)? {
+                    edges.insert((dependent.id, id.clone()));
+                }
+            }
+            report.dependencies = edges.len();
+
+            if !dry_run {
+                // Deepest first, so children go before their parents and the
+                // parent_id reference never dangles.
+                let depth = |id: &String| {
+                    let mut depth = 0;
+                    let mut cur = parent_of.get(id).cloned().flatten();
+                    while let Some(p) = cur {
+                        depth += 1;
+                        cur = parent_of.get(&p).cloned().flatten();
+                    }
+                    depth
+                };
+                let mut ordered = ids.clone();
+                ordered.sort_by_key(|id| std::cmp::Reverse(depth(id)));
+                for id in &ordered {
+                    db.delete_task_rows(id)?;
+                }
+            }
+
+            report.tasks = ids;
+            Ok(report)
+        })
+    }
+
+    /// Done tasks (archived ones included) closed before `cutoff`, and
+    /// optionally with `reason`, mapped to their parent IDs. See
+    /// [`Self::prune_done_tasks`] for how the close time is found.
+    fn done_tasks_closed_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        reason: Option<&str>,
+    ) -> Result<std::collections::HashMap<String, Option<String>>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT t.id, t.parent_id,
+                        COALESCE((SELECT MAX(a.created_at) FROM activity a
+                                  WHERE a.task_id = t.id AND a.field = 'status'
+                                    AND a.new_value = 'done'),
+                                 t.updated_at)
+                 FROM tasks t
+                 WHERE t.status = 'done' AND (?1 IS NULL OR t.close_reason = ?1)",
+            )
+            .map_err(|e| format!("query error: {e}"))?;
+        let rows = stmt
+            .query_map(params![reason], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    timestamp_column(row, 2, "closed_at")?,
+                ))
+            })
+            .map_err(|e| format!("query error: {e}"))?;
+        let mut parent_of = std::collections::HashMap::new();
+        for row in rows {
+            let (id, parent_id, closed_at) = row.map_err(|e| format!("row error: {e}"))?;
+            if closed_at < cutoff {
+                parent_of.insert(id, parent_id);
+            }
+        }
+        Ok(parent_of)
+    }
+
+    /// Permanently delete a task with its comments and dependency edges, and
+    /// return it. A task that blocks others or has subtasks is refused unless
+    /// `force` is set; when forced, its subtasks are kept as top-level tasks
+    /// and its dependents lose it as a blocker.
+    pub fn delete_task(&self, id: &str, force: bool) -> Result<Task, Error> {
+        // The refusal checks must see the edges the delete removes, so they
+        // share its transaction.
+        self.with_transaction(|db| {
+            let task = db
+                .get_task(id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+            let dependents = db.get_dependents(id)?;
+            let children = db.get_children(id)?;
+            if !force {
+                if !dependents.is_empty() {
+                    let ids: Vec<&str> = dependents.iter().map(|t| t.id.as_str()).collect();
+                    return Err(Error::conflict(format!(
+                        "cannot delete {id}: it blocks {}; use --force to delete it and drop those dependencies",
+                        ids.join(", ")
+                    )));
+                }
+                if !children.is_empty() {
+                    let ids: Vec<&str> = children.iter().map(|t| t.id.as_str()).collect();
+                    return Err(Error::conflict(format!(
+                        "cannot delete {id}: it has subtasks {}; use --force to delete it and keep them as top-level tasks",
+                        ids.join(", ")
+                    )));
+                }
+            }
+
+            db.conn
+                .execute(
+                    "UPDATE tasks SET parent_id = NULL WHERE parent_id = ?1",
+                    params![id],
+                )
+                .map_err(|e| format!("failed to detach subtasks: {e}"))?;
+            db.delete_task_rows(id)?;
+            let dependent_ids: Vec<String> = dependents.into_iter().map(|t| t.id).collect();
+            db.refresh_blocked_flags(&dependent_ids)?;
+            let auto_block = db.get_config_bool("auto_block", false)?;
+            for dep in &dependent_ids {
+                db.recompute_blocked_status(dep, auto_block)?;
+            }
+            Ok(task)
+        })
+    }
+
+    /// Delete a task row and every dependency edge and comment that refers
+    /// to it. Callers handle subtasks and run this inside a transaction.
+    fn delete_task_rows(&self, id: &str) -> Result<(), Error> {
         self.conn
             .execute(
-                "UPDATE tasks SET tags = ?1, updated_at = ?2 WHERE id = ?3",
-                params![tags_str, now, id],
+                "DELETE FROM dependencies WHERE child_id = ?1 OR parent_id = ?1",
+                params![id],
             )
+            .map_err(|e| format!("failed to delete dependencies: {e}"))?;
+        self.conn
+            .execute("DELETE FROM comments WHERE task_id = ?1", params![id])
+            .map_err(|e| format!("failed to delete comments: {e}"))?;
+        self.conn
+            .execute("DELETE FROM activity WHERE task_id = ?1", params![id])
+            .map_err(|e| format!("failed to delete activity: {e}"))?;
+        self.conn
+            .execute("DELETE FROM task_tags WHERE task_id = ?1", params![id])
+            .map_err(|e| format!("failed to delete tags: {e}"))?;
+        self.conn
+            .execute("DELETE FROM tasks WHERE id = ?1", params![id])
+            .map_err(|e| format!("failed to delete task: {e}"))?;
+        Ok(())
+    }
+
+    pub fn update_tags(&self, id: &str, tags: &[String]) -> Result<(), Error> {
+        validate_tags(tags).map_err(Error::validation)?;
+        let tags_str = tags.join(",");
+        let now = Utc::now().to_rfc3339();
+        self.with_transaction(|db| {
+            let rows_changed = db
+                .conn
+                .execute(
+                    "UPDATE tasks SET tags = ?1, updated_at = ?2 WHERE id = ?3",
+                    params![tags_str, now, id],
+                )
+                .map_err(|e| format!("tag update failed: {e}"))?;
+            if rows_changed == 0 {
+                return Ok(());
+            }
+            db.write_task_tags(id, tags)
+        })
+    }
+
+    /// Replace the `task_tags` rows of task `id` with `tags`, mirroring the
+    /// `tags` column. Callers write both in one transaction.
+    fn write_task_tags(&self, id: &str, tags: &[String]) -> Result<(), Error> {
+        self.conn
+            .execute("DELETE FROM task_tags WHERE task_id = ?1", params![id])
             .map_err(|e| format!("tag update failed: {e}"))?;
+        for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            self.conn
+                .execute(
+                    "INSERT OR IGNORE INTO task_tags (task_id, tag) VALUES (?1, ?2)",
+                    params![id, tag],
+                )
+                .map_err(|e| format!("tag update failed: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Archive a task now, or unarchive it. `updated_at` is left alone so
+    /// archiving does not reset the age `archive_closed_before` goes by.
+    pub fn set_archived(&self, id: &str, archived: bool) -> Result<(), Error> {
+        let archived_at = archived.then(|| Utc::now().to_rfc3339());
+        let rows_changed = self
+            .conn
+            .execute(
+                "UPDATE tasks SET archived_at = ?1 WHERE id = ?2",
+                params![archived_at, id],
+            )
+            .map_err(|e| format!("archive failed: {e}"))?;
+        if rows_changed == 0 {
+            return Err(Error::not_found(format!("task not found: {id}")));
+        }
+        Ok(())
+    }
+
+    /// Archive every unarchived done task last updated before `cutoff` and
+    /// return their IDs.
+    pub fn archive_closed_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<String>, Error> {
+        self.with_transaction(|db| {
+            let ids: Vec<String> = db
+                .list_tasks(&TaskFilter {
+                    status: Some("done"),
+                    ..Default::default()
+                })?
+                .into_iter()
+                .filter(|t| t.updated_at < cutoff)
+                .map(|t| t.id)
+                .collect();
+            for id in &ids {
+                db.set_archived(id, true)?;
+            }
+            Ok(ids)
+        })
+    }
+
+    /// Set or clear (`None`) a task's due date.
+    pub fn update_due_at(&self, id: &str, due_at: Option<DateTime<Utc>>) -> Result<(), Error> {
+        let now = Utc::now().to_rfc3339();
+        let rows_changed = self
+            .conn
+            .execute(
+                "UPDATE tasks SET due_at = ?1, updated_at = ?2 WHERE id = ?3",
+                params![due_at.map(|t| t.to_rfc3339()), now, id],
+            )
+            .map_err(|e| format!("due date update failed: {e}"))?;
+        if rows_changed == 0 {
+            return Err(Error::not_found(format!("task not found: {id}")));
+        }
+        Ok(())
+    }
+
+    /// Set a task's estimate and/or spent minutes; `None` leaves that field
+    /// as it is.
+    pub fn update_effort(
+        &self,
+        id: &str,
+        estimate_minutes: Option<u32>,
+        spent_minutes: Option<u32>,
+    ) -> Result<(), Error> {
+        if estimate_minutes.is_none() && spent_minutes.is_none() {
+            return Ok(());
+        }
+        let now = Utc::now().to_rfc3339();
+        let rows_changed = self
+            .conn
+            .execute(
+                "UPDATE tasks SET estimate_minutes = COALESCE(?1, estimate_minutes),
+                                  spent_minutes = COALESCE(?2, spent_minutes),
+                                  updated_at = ?3
+                 WHERE id = ?4",
+                params![estimate_minutes, spent_minutes, now, id],
+            )
+            .map_err(|e| format!("effort update failed: {e}"))?;
+        if rows_changed == 0 {
+            return Err(Error::not_found(format!("task not found: {id}")));
+        }
+        Ok(())
+    }
+
+    /// Set a task's position among the tasks of its priority (lower sorts
+    /// first). Clients pick the midpoint of two neighbours to drop a task
+    /// between them; once that leaves less than `MIN_POSITION_GAP` to a
+    /// neighbour, the priority's positions are renumbered.
+    pub fn set_position(&self, id: &str, position: f64) -> Result<(), Error> {
+        if !position.is_finite() {
+            return Err(Error::validation(format!("invalid position: {position}")));
+        }
+        self.with_transaction(|db| {
+            let now = Utc::now().to_rfc3339();
+            let rows_changed = db
+                .conn
+                .execute(
+                    "UPDATE tasks SET position = ?1, updated_at = ?2 WHERE id = ?3",
+                    params![position, now, id],
+                )
+                .map_err(|e| format!("position update failed: {e}"))?;
+            if rows_changed == 0 {
+                return Err(Error::not_found(format!("task not found: {id}")));
+            }
+            let (priority, gap): (u8, Option<f64>) = db
+                .conn
+                .query_row(
+                    "SELECT t.priority,
+                            (SELECT MIN(ABS(o.position - t.position)) FROM tasks o
+                             WHERE o.priority = t.priority AND o.id != t.id)
+                     FROM tasks t WHERE t.id = ?1",
+                    params![id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .map_err(|e| format!("query error: {e}"))?;
+            if gap.is_some_and(|g| g < MIN_POSITION_GAP) {
+                db.rebalance_positions(priority)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Renumber the positions of every task with `priority` to 1, 2, 3, …
+    /// in their current order. Ties keep creation order.
+    pub fn rebalance_positions(&self, priority: u8) -> Result<(), Error> {
+        let ids: Vec<String> = {
+            let mut stmt = self
+                .conn
+                .prepare(
+                    "SELECT id FROM tasks WHERE priority = ?1
+                     ORDER BY position ASC, created_at ASC, id ASC",
+                )
+                .map_err(|e| format!("query error: {e}"))?;
+            let rows = stmt
+                .query_map(params![priority], |row| row.get(0))
+                .map_err(|e| format!("query error: {e}"))?;
+            rows.collect::<Result<_, _>>()
+                .map_err(|e| format!("row error: {e}"))?
+        };
+        for (i, id) in ids.iter().enumerate() {
+            self.conn
+                .execute(
+                    "UPDATE tasks SET position = ?1 WHERE id = ?2",
+                    params![(i + 1) as f64, id],
+                )
+                .map_err(|e| format!("position update failed: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Move a task under `new_parent`, or make it top-level with `None`, and
+    /// return it. The task keeps its ID, so dependency rows stay valid. A
+    /// move that would make the task its own ancestor is refused.
+    ///
+    /// The new parent is auto-tagged as an epic as on create; when
+    /// `auto_epic_tag` is on, the old parent loses its `epic` tag once it has
+    /// no children left.
+    pub fn set_parent(&self, id: &str, new_parent: Option<&str>) -> Result<Task, Error> {
+        let task = self
+            .get_task(id)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+        if let Some(parent) = new_parent {
+            // Walk up from the new parent; reaching `id` means a cycle.
+            let mut ancestor = Some(parent.to_string());
+            while let Some(current) = ancestor {
+                if current == id {
+                    return Err(Error::validation(format!(
+                        "cannot move {id} under {parent}: {parent} is {id} or one of its subtasks"
+                    )));
+                }
+                ancestor = self
+                    .get_task(&current)?
+                    .ok_or_else(|| Error::not_found(format!("parent task not found: {current}")))?
+                    .parent_id;
+            }
+        }
+
+        self.with_transaction(|db| {
+            db.conn
+                .execute(
+                    "UPDATE tasks SET parent_id = ?1, updated_at = ?2 WHERE id = ?3",
+                    params![new_parent, Utc::now().to_rfc3339(), id],
+                )
+                .map_err(|e| format!("failed to move task: {e}"))?;
+            if let Some(parent) = new_parent {
+                db.auto_tag_epic(parent)?;
+            }
+            let old_parent = task.parent_id.as_deref().filter(|p| Some(*p) != new_parent);
+            if let Some(old) = old_parent {
+                db.untag_empty_epic(old)?;
+            }
+            Ok(())
+        })?;
+        self.get_task(id)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))
+    }
+
+    /// Drop the `epic` tag from `parent_id` once its last subtask has moved
+    /// away. A no-op when `auto_epic_tag` is off, since the tag was then set
+    /// by hand.
+    fn untag_empty_epic(&self, parent_id: &str) -> Result<(), Error> {
+        if !self.get_config_bool("auto_epic_tag", true)?
+            || !self.get_children(parent_id)?.is_empty()
+        {
+            return Ok(());
+        }
+        let mut tags = self.get_task_tags(parent_id)?;
+        let before = tags.len();
+        tags.retain(|t| t != "epic");
+        if tags.len() != before {
+            self.update_tags(parent_id, &tags)?;
+        }
+        Ok(())
+    }
+
+    /// Tag `parent_id` as an epic after a subtask has been created under it.
+    ///
+    /// Controlled by the `auto_epic_tag` config key (default `true`). When the
+    /// key is `false` the parent's tags are left untouched. The tag is never
+    /// added twice.
+    pub fn auto_tag_epic(&self, parent_id: &str) -> Result<(), Error> {
+        if !self.get_config_bool("auto_epic_tag", true)? {
+            return Ok(());
+        }
+        let mut parent_tags = self.get_task_tags(parent_id)?;
+        if !parent_tags.iter().any(|t| t == "epic") {
+            parent_tags.push("epic".to_string());
+            self.update_tags(parent_id, &parent_tags)?;
+        }
         Ok(())
     }
 
-    pub fn get_task_tags(&self, id: &str) -> Result<Vec<String>, String> {
+    pub fn get_task_tags(&self, id: &str) -> Result<Vec<String>, Error> {
         let task = self
             .get_task(id)?
-            .ok_or_else(|| format!("task not found: {id}"))?;
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
         Ok(task.tags)
     }
 
+    /// Rename tag `old` to `new` on every task, in one transaction. A task
+    /// that already carries `new` keeps a single copy; its other tags are left
+    /// as they were. Returns the IDs of the tasks changed.
+    pub fn rename_tag(&self, old: &str, new: &str) -> Result<Vec<String>, Error> {
+        let new = new.trim();
+        if new.is_empty() || new.contains(',') {
+            return Err(Error::validation(format!(
+                "invalid tag '{new}': must be non-empty and contain no commas"
+            )));
+        }
+        self.rewrite_tag(old.trim(), Some(new))
+    }
+
+    /// Strip tag `tag` from every task, in one transaction. Returns the IDs
+    /// of the tasks changed.
+    pub fn remove_tag(&self, tag: &str) -> Result<Vec<String>, Error> {
+        self.rewrite_tag(tag.trim(), None)
+    }
+
+    /// Replace `old` with `new` (or drop it when `new` is `None`) in the tags
+    /// of every task carrying it. Tasks are found through `task_tags`, so a
+    /// tag is only matched whole.
+    fn rewrite_tag(&self, old: &str, new: Option<&str>) -> Result<Vec<String>, Error> {
+        self.with_transaction(|db| {
+            let tagged: Vec<String> = {
+                let mut stmt = db
+                    .conn
+                    .prepare("SELECT task_id FROM task_tags WHERE tag = ?1 ORDER BY task_id")
+                    .map_err(|e| format!("query error: {e}"))?;
+                let rows = stmt
+                    .query_map(params![old], |row| row.get(0))
+                    .map_err(|e| format!("query error: {e}"))?;
+                rows.collect::<Result<_, _>>()
+                    .map_err(|e| format!("row error: {e}"))?
+            };
+
+            let mut changed = Vec::new();
+            for id in tagged {
+                let tags = db.get_task_tags(&id)?;
+                let mut rewritten: Vec<String> = Vec::with_capacity(tags.len());
+                for tag in tags {
+                    let tag = match new {
+                        Some(new) if tag == old => new.to_string(),
+                        None if tag == old => continue,
+                        _ => tag,
+                    };
+                    if !rewritten.contains(&tag) {
+                        rewritten.push(tag);
+                    }
+                }
+                db.update_tags(&id, &rewritten)?;
+                changed.push(id);
+            }
+            Ok(changed)
+        })
+    }
+
     // -- Dependencies --
 
-    pub fn add_dependency(&self, child_id: &str, parent_id: &str) -> Result<(), String> {
+    pub fn add_dependency(&self, child_id: &str, parent_id: &str) -> Result<(), Error> {
+        // The existence, duplicate, and cycle checks must see the graph the
+        // insert lands in, so they share its transaction.
+        self.with_transaction(|db| db.add_dependency_checked(child_id, parent_id))
+    }
+
+    fn add_dependency_checked(&self, child_id: &str, parent_id: &str) -> Result<(), Error> {
         // Verify both tasks exist
         self.get_task(child_id)?
-            .ok_or_else(|| format!("task not found: {child_id}"))?;
+            .ok_or_else(|| Error::not_found(format!("task not found: {child_id}")))?;
         self.get_task(parent_id)?
-            .ok_or_else(|| format!("task not found: {parent_id}"))?;
+            .ok_or_else(|| Error::not_found(format!("task not found: {parent_id}")))?;
 
         // Detect duplicate before inserting
         let exists: bool = self
@@ -352,17 +2103,22 @@ impl Database {
             .map_err(|e| format!("query error: {e}"))?;
 
         if exists {
-            return Err(format!(
+            return Err(Error::conflict(format!(
                 "dependency already exists: {child_id} is already blocked by {parent_id}"
-            ));
+            )));
         }
 
         // Guard against cycles: check whether parent_id transitively depends on child_id
-        if would_create_cycle(&self.conn, child_id, parent_id)? {
-            return Err(
-                "circular dependency detected: adding this dependency would create a cycle"
-                    .to_string(),
-            );
+        let max_depth = match self.get_config("max_dep_depth")? {
+            Some(v) => v.parse::<usize>().map_err(|_| {
+                Error::validation(format!("invalid max_dep_depth config value: {v}"))
+            })?,
+            None => DEFAULT_MAX_DEP_DEPTH,
+        };
+        if would_create_cycle(&self.conn, child_id, parent_id, max_depth)? {
+            return Err(Error::conflict(
+                "circular dependency detected: adding this dependency would create a cycle",
+            ));
         }
 
         self.conn
@@ -371,33 +2127,80 @@ impl Database {
                 params![child_id, parent_id],
             )
             .map_err(|e| format!("failed to add dependency: {e}"))?;
-        Ok(())
+        self.refresh_blocked_flags(&[child_id.to_string()])?;
+        let auto_block = self.get_config_bool("auto_block", false)?;
+        self.recompute_blocked_status(child_id, auto_block)
     }
 
-    pub fn remove_dependency(&self, child_id: &str, parent_id: &str) -> Result<(), String> {
-        // Verify both tasks exist
-        self.get_task(child_id)?
-            .ok_or_else(|| format!("task not found: {child_id}"))?;
-        self.get_task(parent_id)?
-            .ok_or_else(|| format!("task not found: {parent_id}"))?;
+    pub fn remove_dependency(&self, child_id: &str, parent_id: &str) -> Result<(), Error> {
+        self.with_transaction(|db| {
+            // Verify both tasks exist
+            db.get_task(child_id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {child_id}")))?;
+            db.get_task(parent_id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {parent_id}")))?;
 
-        let rows = self
-            .conn
-            .execute(
-                "DELETE FROM dependencies WHERE child_id = ?1 AND parent_id = ?2",
-                params![child_id, parent_id],
-            )
-            .map_err(|e| format!("failed to remove dependency: {e}"))?;
+            let rows = db
+                .conn
+                .execute(
+                    "DELETE FROM dependencies WHERE child_id = ?1 AND parent_id = ?2",
+                    params![child_id, parent_id],
+                )
+                .map_err(|e| format!("failed to remove dependency: {e}"))?;
 
-        if rows == 0 {
-            return Err(format!(
-                "no dependency found: {child_id} is not blocked by {parent_id}"
-            ));
+            if rows == 0 {
+                return Err(Error::not_found(format!(
+                    "no dependency found: {child_id} is not blocked by {parent_id}"
+                )));
+            }
+            db.refresh_blocked_flags(&[child_id.to_string()])?;
+            let auto_block = db.get_config_bool("auto_block", false)?;
+            db.recompute_blocked_status(child_id, auto_block)
+        })
+    }
+
+    /// With `auto_block`, bring a task's `status` in line with its blockers:
+    /// an `open` task with an open blocker becomes `blocked`, and a `blocked`
+    /// task with no open blocker goes back to `open`. Any other status, and
+    /// every status without `auto_block`, is left alone, so a task blocked by
+    /// hand stays blocked. Dependency changes and blocker status changes call
+    /// this for the affected task, passing the `auto_block` config key.
+    pub fn recompute_blocked_status(&self, id: &str, auto_block: bool) -> Result<(), Error> {
+        if !auto_block {
+            return Ok(());
         }
-        Ok(())
+        let Some(task) = self.get_task(id)? else {
+            return Ok(());
+        };
+        let has_open_blocker: bool = self
+            .conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM dependencies d JOIN tasks b ON d.parent_id = b.id
+                                WHERE d.child_id = ?1 AND b.status != 'done')",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("query error: {e}"))?;
+        let target = match task.status {
+            Status::Blocked if !has_open_blocker => Status::Open,
+            Status::Open if has_open_blocker => Status::Blocked,
+            _ => return Ok(()),
+        };
+        self.update_task(
+            id,
+            None,
+            None,
+            Some(target.as_str()),
+            None,
+            None,
+            None,
+            None,
+        )
     }
 
-    pub fn get_blockers(&self, task_id: &str) -> Result<Vec<Dependency>, String> {
+    /// Get the dependency edges where `task_id` is the blocked side
+    /// (`child_id == task_id`); each edge's `parent_id` is a blocker.
+    pub fn get_blockers(&self, task_id: &str) -> Result<Vec<Dependency>, Error> {
         let mut stmt = self
             .conn
             .prepare("SELECT child_id, parent_id FROM dependencies WHERE child_id = ?1")
@@ -424,12 +2227,13 @@ impl Database {
     /// Returns every task whose work cannot proceed until `task_id` is resolved.
     /// This is the "dependents" direction: `task_id` is the blocker, and the
     /// returned tasks are the ones waiting on it.
-    pub fn get_dependents(&self, task_id: &str) -> Result<Vec<Task>, String> {
+    pub fn get_dependents(&self, task_id: &str) -> Result<Vec<Task>, Error> {
         let mut stmt = self
             .conn
             .prepare(
                 "SELECT t.id, t.title, t.description, t.status, t.priority, t.assignee,
-                        t.parent_id, t.tags, t.created_at, t.updated_at, t.close_reason, t.notes
+                        t.parent_id, t.tags, t.created_at, t.updated_at, t.close_reason, t.notes, t.due_at, t.position, t.archived_at,
+                        t.estimate_minutes, t.spent_minutes
                  FROM tasks t
                  JOIN dependencies d ON t.id = d.child_id
                  WHERE d.parent_id = ?1
@@ -438,7 +2242,7 @@ impl Database {
             .map_err(|e| format!("query error: {e}"))?;
 
         let rows = stmt
-            .query_map(params![task_id], |row| Ok(row_to_task(row)))
+            .query_map(params![task_id], row_to_task)
             .map_err(|e| format!("query error: {e}"))?;
 
         let mut tasks = Vec::new();
@@ -448,23 +2252,109 @@ impl Database {
         Ok(tasks)
     }
 
-    /// Get tasks that are ready: open and have no open/in_progress blockers.
+    /// Every dependency edge, ordered by child then parent.
+    pub fn all_dependencies(&self) -> Result<Vec<Dependency>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT child_id, parent_id FROM dependencies ORDER BY child_id, parent_id")
+            .map_err(|e| format!("query error: {e}"))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(Dependency {
+                    child_id: row.get(0)?,
+                    parent_id: row.get(1)?,
+                })
+            })
+            .map_err(|e| format!("query error: {e}"))?;
+        let mut edges = Vec::new();
+        for row in rows {
+            edges.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+        Ok(edges)
+    }
+
+    /// Non-done, unarchived tasks (every task with `include_done`) and the dependency
+    /// edges among them. Edges to tasks outside that set are left out.
+    pub fn dependency_graph(&self, include_done: bool) -> Result<DepGraph, Error> {
+        let nodes = self.list_tasks(&TaskFilter {
+            include_done,
+            include_archived: include_done,
+            ..Default::default()
+        })?;
+        let ids: std::collections::HashSet<&str> = nodes.iter().map(|t| t.id.as_str()).collect();
+        let edges = self
+            .all_dependencies()?
+            .into_iter()
+            .filter(|e| ids.contains(e.child_id.as_str()) && ids.contains(e.parent_id.as_str()))
+            .collect();
+        Ok(DepGraph { nodes, edges })
+    }
+
+    /// The longest chain of non-done blockers leading into `id`, ordered from
+    /// the deepest blocker down to the task itself. A task with no open
+    /// blockers yields just itself; ties go to the blocker with the lowest ID.
+    pub fn longest_blocker_chain(&self, id: &str) -> Result<Vec<Task>, Error> {
+        use std::collections::HashMap;
+
+        let target = self
+            .get_task(id)?
+            .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+        let mut tasks: HashMap<String, Task> = self
+            .list_tasks(&TaskFilter {
+                include_archived: true,
+                ..Default::default()
+            })?
+            .into_iter()
+            .map(|t| (t.id.clone(), t))
+            .collect();
+        let mut blockers: HashMap<String, Vec<String>> = HashMap::new();
+        for edge in self.all_dependencies()? {
+            if tasks.contains_key(&edge.parent_id) {
+                blockers
+                    .entry(edge.child_id)
+                    .or_default()
+                    .push(edge.parent_id);
+            }
+        }
+
+        let mut memo: HashMap<String, Vec<String>> = HashMap::new();
+        let ids = chain_into(id, &blockers, &mut memo, 0, tasks.len() + 1)?;
+        tasks.insert(target.id.clone(), target);
+        Ok(ids
+            .into_iter()
+            .filter_map(|id| tasks.get(&id).cloned())
+            .collect())
+    }
+
+    /// Get tasks that are ready: open, not archived, and have no
+    /// open/in_progress blockers, sorted as the `ready_order` config key says
+    /// (priority then age by default).
     /// If `limit` is `Some(n)`, return at most `n` tasks.
-    pub fn get_ready_tasks(&self, limit: Option<u32>) -> Result<Vec<Task>, String> {
+    pub fn get_ready_tasks(&self, limit: Option<u32>) -> Result<Vec<Task>, Error> {
         let mut sql = String::from(
             "
-            SELECT t.id, t.title, t.description, t.status, t.priority, t.assignee, t.parent_id, t.tags, t.created_at, t.updated_at, t.close_reason, t.notes
+            SELECT t.id, t.title, t.description, t.status, t.priority, t.assignee, t.parent_id, t.tags, t.created_at, t.updated_at, t.close_reason, t.notes, t.due_at, t.position, t.archived_at,
+                   t.estimate_minutes, t.spent_minutes
             FROM tasks t
             WHERE t.status = 'open'
+              AND t.archived_at IS NULL
               AND NOT EXISTS (
                 SELECT 1 FROM dependencies d
                 JOIN tasks blocker ON d.parent_id = blocker.id
                 WHERE d.child_id = t.id
                   AND blocker.status IN ('open', 'in_progress', 'blocked')
               )
-            ORDER BY t.priority ASC, t.created_at ASC
         ",
         );
+        sql.push_str(match self.get_config("ready_order")?.as_deref() {
+            None | Some("priority") => " ORDER BY t.priority ASC, t.position ASC, t.created_at ASC",
+            Some("age") => " ORDER BY t.created_at ASC, t.priority ASC, t.position ASC",
+            Some(v) => {
+                return Err(Error::validation(format!(
+                    "invalid ready_order config value: {v}"
+                )));
+            }
+        });
 
         if let Some(n) = limit {
             sql.push_str(&format!(" LIMIT {n}"));
@@ -476,7 +2366,7 @@ impl Database {
             .map_err(|e| format!("query error: {e}"))?;
 
         let rows = stmt
-            .query_map([], |row| Ok(row_to_task(row)))
+            .query_map([], row_to_task)
             .map_err(|e| format!("query error: {e}"))?;
 
         let mut tasks = Vec::new();
@@ -486,24 +2376,45 @@ impl Database {
         Ok(tasks)
     }
 
-    /// Get tasks that have at least one open/in_progress blocker.
-    pub fn get_blocked_tasks(&self) -> Result<Vec<Task>, String> {
+    /// Open and in-progress tasks whose due date has passed, most overdue first.
+    pub fn get_overdue_tasks(&self) -> Result<Vec<Task>, Error> {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT DISTINCT t.id, t.title, t.description, t.status, t.priority, t.assignee,
-                    t.parent_id, t.tags, t.created_at, t.updated_at, t.close_reason, t.notes
+                "SELECT id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes, due_at, position, archived_at,
+                        estimate_minutes, spent_minutes
+                 FROM tasks
+                 WHERE status IN ('open', 'in_progress') AND due_at IS NOT NULL AND due_at < ?1
+                 ORDER BY due_at ASC, priority ASC, id ASC",
+            )
+            .map_err(|e| format!("query error: {e}"))?;
+        let rows = stmt
+            .query_map(params![Utc::now().to_rfc3339()], row_to_task)
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let mut tasks = Vec::new();
+        for row in rows {
+            tasks.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+        Ok(tasks)
+    }
+
+    /// Get unarchived tasks that have at least one open/in_progress blocker.
+    pub fn get_blocked_tasks(&self) -> Result<Vec<Task>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT t.id, t.title, t.description, t.status, t.priority, t.assignee,
+                    t.parent_id, t.tags, t.created_at, t.updated_at, t.close_reason, t.notes, t.due_at, t.position, t.archived_at,
+                        t.estimate_minutes, t.spent_minutes
              FROM tasks t
-             JOIN dependencies d ON t.id = d.child_id
-             JOIN tasks blocker ON d.parent_id = blocker.id
-             WHERE t.status != 'done'
-               AND blocker.status IN ('open', 'in_progress', 'blocked')
+             WHERE t.is_blocked = 1 AND t.archived_at IS NULL
              ORDER BY t.priority ASC, t.created_at ASC",
             )
             .map_err(|e| format!("query error: {e}"))?;
 
         let rows = stmt
-            .query_map([], |row| Ok(row_to_task(row)))
+            .query_map([], row_to_task)
             .map_err(|e| format!("query error: {e}"))?;
 
         let mut tasks = Vec::new();
@@ -513,18 +2424,105 @@ impl Database {
         Ok(tasks)
     }
 
+    /// Group tasks into kanban columns, optionally restricted to children of
+    /// any of `parents` and to any of `priorities` (empty slices match all).
+    ///
+    /// Open tasks with a not-done blocker land in the blocked column, since
+    /// `dep add` does not change a task's status. Shared by `tk board` and the
+    /// web board so both agree on column membership.
+    pub fn board(&self, parents: &[String], priorities: &[u8]) -> Result<Board, Error> {
+        // Single values are pushed down to SQL; multi-values are post-filtered.
+        let parent = match parents {
+            [p] => Some(p.as_str()),
+            _ => None,
+        };
+        let priority = match priorities {
+            [p] => Some(*p),
+            _ => None,
+        };
+        let fetch = |status: &str| -> Result<Vec<Task>, Error> {
+            let mut tasks = self.list_tasks(&TaskFilter {
+                status: Some(status),
+                priority,
+                parent,
+                ..Default::default()
+            })?;
+            if parents.len() > 1 {
+                tasks.retain(|t| {
+                    t.parent_id
+                        .as_deref()
+                        .is_some_and(|pid| parents.iter().any(|p| p == pid))
+                });
+            }
+            if priorities.len() > 1 {
+                tasks.retain(|t| priorities.contains(&t.priority));
+            }
+            Ok(tasks)
+        };
+
+        let dep_blocked_ids: std::collections::HashSet<String> = self
+            .get_blocked_tasks()?
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        let (dep_blocked, open): (Vec<Task>, Vec<Task>) = fetch("open")?
+            .into_iter()
+            .partition(|t| dep_blocked_ids.contains(&t.id));
+        let mut blocked = fetch("blocked")?;
+        blocked.extend(dep_blocked);
+
+        Ok(Board {
+            open,
+            in_progress: fetch("in_progress")?,
+            blocked,
+            done: fetch("done")?,
+        })
+    }
+
     // -- Comments --
 
-    pub fn add_comment(&self, task_id: &str, body: &str) -> Result<Comment, String> {
+    pub fn add_comment(&self, task_id: &str, body: &str) -> Result<Comment, Error> {
+        self.insert_comment(task_id, body, None)
+    }
+
+    /// Add a comment replying to comment `reply_to`, which must belong to
+    /// the same task.
+    pub fn add_reply(&self, task_id: &str, body: &str, reply_to: i64) -> Result<Comment, Error> {
+        let parent_task: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT task_id FROM comments WHERE id = ?1",
+                params![reply_to],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("query error: {e}"))?;
+        match parent_task {
+            None => Err(Error::validation(format!(
+                "reply_to comment not found: {reply_to}"
+            ))),
+            Some(other) if other != task_id => Err(Error::validation(format!(
+                "reply_to comment {reply_to} belongs to task {other}, not {task_id}"
+            ))),
+            Some(_) => self.insert_comment(task_id, body, Some(reply_to)),
+        }
+    }
+
+    fn insert_comment(
+        &self,
+        task_id: &str,
+        body: &str,
+        reply_to: Option<i64>,
+    ) -> Result<Comment, Error> {
         // Verify task exists
         self.get_task(task_id)?
-            .ok_or_else(|| format!("task not found: {task_id}"))?;
+            .ok_or_else(|| Error::not_found(format!("task not found: {task_id}")))?;
 
         let now = Utc::now();
         self.conn
             .execute(
-                "INSERT INTO comments (task_id, body, created_at) VALUES (?1, ?2, ?3)",
-                params![task_id, body, now.to_rfc3339()],
+                "INSERT INTO comments (task_id, body, created_at, reply_to) VALUES (?1, ?2, ?3, ?4)",
+                params![task_id, body, now.to_rfc3339(), reply_to],
             )
             .map_err(|e| format!("failed to add comment: {e}"))?;
 
@@ -534,28 +2532,100 @@ impl Database {
             task_id: task_id.to_string(),
             body: body.to_string(),
             created_at: now,
+            reply_to,
+            updated_at: None,
         })
     }
 
-    pub fn get_comments(&self, task_id: &str) -> Result<Vec<Comment>, String> {
+    pub fn get_comment(&self, id: i64) -> Result<Option<Comment>, Error> {
+        self.conn
+            .query_row(
+                "SELECT id, task_id, body, created_at, reply_to, updated_at FROM comments WHERE id = ?1",
+                params![id],
+                row_to_comment,
+            )
+            .optional()
+            .map_err(|e| format!("query error: {e}").into())
+    }
+
+    /// Replace a comment's body and stamp its `updated_at`; returns the
+    /// edited comment.
+    pub fn update_comment(&self, id: i64, body: &str) -> Result<Comment, Error> {
+        if body.trim().is_empty() {
+            return Err(Error::validation("comment body is empty"));
+        }
+        let rows_changed = self
+            .conn
+            .execute(
+                "UPDATE comments SET body = ?1, updated_at = ?2 WHERE id = ?3",
+                params![body, Utc::now().to_rfc3339(), id],
+            )
+            .map_err(|e| format!("failed to update comment: {e}"))?;
+        if rows_changed == 0 {
+            return Err(Error::not_found(format!("comment not found: {id}")));
+        }
+        self.get_comment(id)?
+            .ok_or_else(|| Error::not_found(format!("comment not found: {id}")))
+    }
+
+    /// Delete a comment and return it. Replies to it are kept and become
+    /// top-level comments on the task.
+    pub fn delete_comment(&self, id: i64) -> Result<Comment, Error> {
+        let comment = self
+            .get_comment(id)?
+            .ok_or_else(|| Error::not_found(format!("comment not found: {id}")))?;
+        self.with_transaction(|db| {
+            db.conn
+                .execute(
+                    "UPDATE comments SET reply_to = NULL WHERE reply_to = ?1",
+                    params![id],
+                )
+                .map_err(|e| format!("failed to detach replies: {e}"))?;
+            db.conn
+                .execute("DELETE FROM comments WHERE id = ?1", params![id])
+                .map_err(|e| format!("failed to delete comment: {e}"))?;
+            Ok(())
+        })?;
+        Ok(comment)
+    }
+
+    pub fn get_comments(&self, task_id: &str) -> Result<Vec<Comment>, Error> {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT id, task_id, body, created_at FROM comments WHERE task_id = ?1 ORDER BY created_at ASC",
+                "SELECT id, task_id, body, created_at, reply_to, updated_at FROM comments WHERE task_id = ?1 ORDER BY created_at ASC",
             )
             .map_err(|e| format!("query error: {e}"))?;
 
         let rows = stmt
-            .query_map(params![task_id], |row| {
-                let created_str: String = row.get(3)?;
-                let created_at = DateTime::parse_from_rfc3339(&created_str)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now());
-                Ok(Comment {
-                    id: row.get(0)?,
-                    task_id: row.get(1)?,
-                    body: row.get(2)?,
-                    created_at,
+            .query_map(params![task_id], row_to_comment)
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let mut comments = Vec::new();
+        for row in rows {
+            comments.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+        Ok(comments)
+    }
+
+    /// Most recent comments across all tasks, newest first, each with its
+    /// task's title.
+    pub fn recent_comments(&self, limit: u32) -> Result<Vec<RecentComment>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT c.id, c.task_id, c.body, c.created_at, c.reply_to, c.updated_at, t.title
+                 FROM comments c JOIN tasks t ON t.id = c.task_id
+                 ORDER BY c.created_at DESC, c.id DESC
+                 LIMIT ?1",
+            )
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let rows = stmt
+            .query_map(params![limit], |row| {
+                Ok(RecentComment {
+                    comment: row_to_comment(row)?,
+                    task_title: row.get(6)?,
                 })
             })
             .map_err(|e| format!("query error: {e}"))?;
@@ -570,7 +2640,7 @@ impl Database {
     // -- Stats --
 
     /// Count tasks grouped by status.
-    pub fn task_count_by_status(&self) -> Result<Vec<(String, i64)>, String> {
+    pub fn task_count_by_status(&self) -> Result<Vec<(String, i64)>, Error> {
         let mut stmt = self
             .conn
             .prepare("SELECT status, COUNT(*) FROM tasks GROUP BY status ORDER BY status")
@@ -589,8 +2659,10 @@ impl Database {
         Ok(counts)
     }
 
-    /// Count tasks grouped by priority.
-    pub fn task_count_by_priority(&self) -> Result<Vec<(u8, i64)>, String> {
+    /// Count tasks grouped by priority. Callers use [`Self::stats_snapshot`];
+    /// this grouped query stays as the reference the snapshot is tested against.
+    #[cfg(test)]
+    pub fn task_count_by_priority(&self) -> Result<Vec<(u8, i64)>, Error> {
         let mut stmt = self
             .conn
             .prepare("SELECT priority, COUNT(*) FROM tasks GROUP BY priority ORDER BY priority")
@@ -604,49 +2676,322 @@ impl Database {
         for row in rows {
             counts.push(row.map_err(|e| format!("row error: {e}"))?);
         }
-        Ok(counts)
-    }
+        Ok(counts)
+    }
+
+    /// Count tasks grouped by assignee, most-loaded first. Tasks without an
+    /// assignee are counted under `unassigned`. Like `task_count_by_priority`,
+    /// kept as the reference for [`Self::stats_snapshot`].
+    #[cfg(test)]
+    pub fn task_count_by_assignee(&self) -> Result<Vec<(String, i64)>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT COALESCE(NULLIF(assignee, ''), 'unassigned') AS who, COUNT(*) AS n
+                 FROM tasks GROUP BY who ORDER BY n DESC, who",
+            )
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let mut counts = Vec::new();
+        for row in rows {
+            counts.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+        Ok(counts)
+    }
+
+    /// Count done tasks grouped by close reason, by reason name. Tasks closed
+    /// without a reason are not counted.
+    pub fn task_count_by_close_reason(&self) -> Result<Vec<(String, i64)>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT close_reason, COUNT(*) FROM tasks
+                 WHERE status = 'done' AND close_reason IS NOT NULL
+                 GROUP BY close_reason ORDER BY close_reason",
+            )
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let mut counts = Vec::new();
+        for row in rows {
+            counts.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+        Ok(counts)
+    }
+
+    /// Count tasks grouped by tag (tasks with multiple tags are counted once per tag).
+    pub fn task_count_by_tag(&self) -> Result<Vec<(String, i64)>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT tag, COUNT(*) FROM task_tags
+                 GROUP BY tag ORDER BY COUNT(*) DESC, tag ASC",
+            )
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("query error: {e}"))?;
+
+        let mut counts = Vec::new();
+        for row in rows {
+            counts.push(row.map_err(|e| format!("row error: {e}"))?);
+        }
+        Ok(counts)
+    }
+
+    /// All task counts in a single pass over `tasks`, grouped in Rust rather
+    /// than by one grouped query per metric. Tag counts come from `task_tags`.
+    pub fn stats_snapshot(&self) -> Result<StatsSnapshot, Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT status, priority, assignee, close_reason FROM tasks")
+            .map_err(|e| format!("query error: {e}"))?;
+        let mut rows = stmt.query([]).map_err(|e| format!("query error: {e}"))?;
+
+        let mut by_status: std::collections::BTreeMap<String, i64> = Default::default();
+        let mut by_priority: std::collections::BTreeMap<u8, i64> = Default::default();
+        let mut by_assignee: std::collections::HashMap<String, i64> = Default::default();
+        let mut by_reason: std::collections::BTreeMap<String, i64> = Default::default();
+        while let Some(row) = rows.next().map_err(|e| format!("row error: {e}"))? {
+            let status: String = row.get(0).map_err(|e| format!("row error: {e}"))?;
+            let priority: u8 = row.get(1).map_err(|e| format!("row error: {e}"))?;
+            let assignee: Option<String> = row.get(2).map_err(|e| format!("row error: {e}"))?;
+            let reason: Option<String> = row.get(3).map_err(|e| format!("row error: {e}"))?;
+
+            if status == "done"
+                && let Some(reason) = reason
+            {
+                *by_reason.entry(reason).or_insert(0) += 1;
+            }
+            *by_status.entry(status).or_insert(0) += 1;
+            *by_priority.entry(priority).or_insert(0) += 1;
+            let assignee = assignee
+                .filter(|a| !a.is_empty())
+                .unwrap_or_else(|| "unassigned".to_string());
+            *by_assignee.entry(assignee).or_insert(0) += 1;
+        }
+
+        let most_first = |map: std::collections::HashMap<String, i64>| {
+            let mut counts: Vec<(String, i64)> = map.into_iter().collect();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            counts
+        };
+        Ok(StatsSnapshot {
+            by_status: by_status.into_iter().collect(),
+            by_priority: by_priority.into_iter().collect(),
+            by_tag: self.task_count_by_tag()?,
+            by_assignee: most_first(by_assignee),
+            by_reason: by_reason.into_iter().collect(),
+        })
+    }
+
+    /// Generate an unused top-level task ID: the prefix and `id_length`
+    /// (config key, default 4) random hex digits. Retries on a collision and
+    /// gives up after `ID_ATTEMPTS` tries.
+    pub fn generate_id(&self) -> Result<String, Error> {
+        let prefix = self
+            .get_config("prefix")?
+            .unwrap_or_else(|| "tk".to_string());
+        let length = match self.get_config("id_length")? {
+            Some(v) => parse_id_length(&v)
+                .map_err(|_| Error::validation(format!("invalid id_length config value: {v}")))?,
+            None => DEFAULT_ID_LENGTH,
+        };
+        for _ in 0..ID_ATTEMPTS {
+            let uuid = uuid::Uuid::new_v4();
+            let hash = &format!("{:032x}", uuid.as_u128())[..length];
+            let id = format!("{prefix}-{hash}");
+            if self.get_task(&id)?.is_none() {
+                return Ok(id);
+            }
+        }
+        Err(format!(
+            "could not find an unused task ID in {ID_ATTEMPTS} attempts; \
+             raise the id_length config key (currently {length})"
+        )
+        .into())
+    }
+
+    /// Change the task ID prefix and rewrite every task ID that uses the old
+    /// one (`old-ab12.3` becomes `new-ab12.3`), along with all parent,
+    /// dependency and comment references, in one transaction. Returns the
+    /// number of tasks renamed.
+    pub fn reprefix(&self, new_prefix: &str) -> Result<usize, Error> {
+        validate_prefix(new_prefix).map_err(Error::validation)?;
+        let old_prefix = self
+            .get_config("prefix")?
+            .unwrap_or_else(|| "tk".to_string());
+        if old_prefix == new_prefix {
+            return Ok(0);
+        }
 
-    /// Count tasks grouped by tag (tasks with multiple tags are counted once per tag).
-    pub fn task_count_by_tag(&self) -> Result<Vec<(String, i64)>, String> {
-        // Pull all non-empty tags columns and split them in Rust
-        let mut stmt = self
-            .conn
-            .prepare("SELECT tags FROM tasks WHERE tags != ''")
-            .map_err(|e| format!("query error: {e}"))?;
+        self.with_transaction(|db| {
+            // References are rewritten one table at a time; defer the foreign-key
+            // check to commit, when they all agree again.
+            db.conn
+                .execute_batch("PRAGMA defer_foreign_keys = ON;")
+                .map_err(|e| format!("failed to set pragmas: {e}"))?;
 
-        let rows = stmt
-            .query_map([], |row| row.get::<_, String>(0))
-            .map_err(|e| format!("query error: {e}"))?;
+            let old = format!("{old_prefix}-");
+            let new = format!("{new_prefix}-");
+            let collision: Option<String> = db
+                .conn
+                .query_row(
+                    "SELECT a.id FROM tasks a JOIN tasks b
+                       ON b.id = ?2 || substr(a.id, length(?1) + 1)
+                     WHERE substr(a.id, 1, length(?1)) = ?1
+                     LIMIT 1",
+                    params![old, new],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| format!("query error: {e}"))?;
+            if let Some(id) = collision {
+                return Err(Error::conflict(format!(
+                    "cannot reprefix: renaming {id} would collide with an existing task"
+                )));
+            }
 
-        let mut map: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
-        for row in rows {
-            let tags_str = row.map_err(|e| format!("row error: {e}"))?;
-            for tag in tags_str.split(',') {
-                let tag = tag.trim();
-                if !tag.is_empty() {
-                    *map.entry(tag.to_string()).or_insert(0) += 1;
+            let mut renamed = 0;
+            for (table, column) in [
+                ("tasks", "id"),
+                ("tasks", "parent_id"),
+                ("dependencies", "child_id"),
+                ("dependencies", "parent_id"),
+                ("comments", "task_id"),
+                ("activity", "task_id"),
+                ("task_tags", "task_id"),
+            ] {
+                let rows = db
+                    .conn
+                    .execute(
+                        &format!(
+                            "UPDATE {table} SET {column} = ?2 || substr({column}, length(?1) + 1)
+                             WHERE substr({column}, 1, length(?1)) = ?1"
+                        ),
+                        params![old, new],
+                    )
+                    .map_err(|e| format!("failed to rewrite {table}.{column}: {e}"))?;
+                if (table, column) == ("tasks", "id") {
+                    renamed = rows;
                 }
             }
-        }
+            db.set_config("prefix", new_prefix)?;
 
-        let mut counts: Vec<(String, i64)> = map.into_iter().collect();
-        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
-        Ok(counts)
+            Ok(renamed)
+        })
     }
 
-    /// Generate a short hash-based ID with the configured prefix.
-    pub fn generate_id(&self) -> Result<String, String> {
-        let prefix = self
-            .get_config("prefix")?
-            .unwrap_or_else(|| "tk".to_string());
-        let uuid = uuid::Uuid::new_v4();
-        let hash = &format!("{:x}", uuid.as_u128())[..4];
-        Ok(format!("{prefix}-{hash}"))
+    /// Write a standalone, immediately-openable copy of this database to `path`,
+    /// which must not already exist. Returns the number of tasks exported.
+    ///
+    /// A full export is a `VACUUM INTO` snapshot. With `open_only`, a fresh
+    /// schema is created at `path` and only non-done, unarchived tasks are
+    /// copied in, along with their comments and the dependency edges between
+    /// them; a parent link to an omitted task is cleared. Nothing from the
+    /// omitted tasks is left behind in the file's free pages.
+    pub fn export_sqlite(&self, path: &Path, open_only: bool) -> Result<usize, Error> {
+        if path.exists() {
+            return Err(Error::conflict(format!(
+                "refusing to overwrite existing file: {}",
+                path.display()
+            )));
+        }
+        let target = path
+            .to_str()
+            .ok_or_else(|| format!("export path is not valid UTF-8: {}", path.display()))?;
+
+        if !open_only {
+            self.conn
+                .execute("VACUUM INTO ?1", params![target])
+                .map_err(|e| format!("failed to export database: {e}"))?;
+            return self
+                .conn
+                .query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get::<_, i64>(0))
+                .map(|n| n as usize)
+                .map_err(|e| format!("query error: {e}").into());
+        }
+
+        // Create the target with the current schema, then copy rows across.
+        // A failure at any step removes the half-built file, so a retry is
+        // not refused as an overwrite.
+        if let Err(e) = Database::open(path).and_then(|target| target.migrate()) {
+            let _ = std::fs::remove_file(path);
+            return Err(e);
+        }
+        if let Err(e) = self
+            .conn
+            .execute("ATTACH DATABASE ?1 AS export", params![target])
+        {
+            let _ = std::fs::remove_file(path);
+            return Err(format!("failed to attach export database: {e}").into());
+        }
+        let result = self.with_transaction(|db| {
+            db.conn
+                .execute_batch(
+                    "CREATE TEMP TABLE export_ids AS
+                         SELECT id FROM main.tasks
+                         WHERE status != 'done' AND archived_at IS NULL;
+                     INSERT OR REPLACE INTO export.config (key, value)
+                         SELECT key, value FROM main.config;
+                     INSERT INTO export.tasks (id, title, description, status, priority,
+                             assignee, parent_id, tags, created_at, updated_at,
+                             close_reason, notes, due_at, is_blocked, position, archived_at,
+                             estimate_minutes, spent_minutes)
+                         SELECT id, title, description, status, priority, assignee,
+                             CASE WHEN parent_id IN (SELECT id FROM export_ids)
+                                  THEN parent_id END,
+                             tags, created_at, updated_at, close_reason, notes, due_at,
+                             is_blocked, position, archived_at, estimate_minutes, spent_minutes
+                         FROM main.tasks WHERE id IN (SELECT id FROM export_ids);
+                     INSERT INTO export.dependencies (child_id, parent_id)
+                         SELECT child_id, parent_id FROM main.dependencies
+                         WHERE child_id IN (SELECT id FROM export_ids)
+                           AND parent_id IN (SELECT id FROM export_ids);
+                     INSERT INTO export.comments (id, task_id, body, created_at, reply_to,
+                             updated_at)
+                         SELECT id, task_id, body, created_at, reply_to, updated_at
+                         FROM main.comments
+                         WHERE task_id IN (SELECT id FROM export_ids);
+                     INSERT INTO export.activity (id, task_id, field, old_value, new_value,
+                             created_at)
+                         SELECT id, task_id, field, old_value, new_value, created_at
+                         FROM main.activity
+                         WHERE task_id IN (SELECT id FROM export_ids);
+                     INSERT INTO export.task_tags (task_id, tag)
+                         SELECT task_id, tag FROM main.task_tags
+                         WHERE task_id IN (SELECT id FROM export_ids);
+                     DROP TABLE temp.export_ids;",
+                )
+                .map_err(|e| format!("failed to export tasks: {e}"))?;
+            db.conn
+                .query_row("SELECT COUNT(*) FROM export.tasks", [], |row| {
+                    row.get::<_, i64>(0)
+                })
+                .map(|n| n as usize)
+                .map_err(|e| format!("query error: {e}").into())
+        });
+        self.conn
+            .execute("DETACH DATABASE export", [])
+            .map_err(|e| format!("failed to detach export database: {e}"))?;
+        if result.is_err() {
+            let _ = std::fs::remove_file(path);
+        }
+        result
     }
 
     /// Generate a child ID under a parent.
-    pub fn generate_child_id(&self, parent_id: &str) -> Result<String, String> {
+    pub fn generate_child_id(&self, parent_id: &str) -> Result<String, Error> {
         // Count existing children to determine next index
         let mut stmt = self
             .conn
@@ -655,42 +3000,193 @@ impl Database {
         let count: i64 = stmt
             .query_row(params![parent_id], |row| row.get(0))
             .map_err(|e| format!("query error: {e}"))?;
-        Ok(format!("{parent_id}.{}", count + 1))
+        // Moved or detached subtasks keep their IDs, so the count can point at
+        // an ID that is already taken; skip ahead to the next free one.
+        let mut index = count + 1;
+        loop {
+            let id = format!("{parent_id}.{index}");
+            if self.get_task(&id)?.is_none() {
+                return Ok(id);
+            }
+            index += 1;
+        }
     }
 
     /// Return the current SQLite `PRAGMA data_version` value.
     ///
     /// This integer increments whenever the database is modified by any connection,
     /// making it suitable as a lightweight change-detection signal for polling clients.
-    pub fn data_version(&self) -> Result<i64, String> {
+    pub fn data_version(&self) -> Result<i64, Error> {
         self.conn
             .query_row("PRAGMA data_version", [], |row| row.get::<_, i64>(0))
-            .map_err(|e| format!("failed to read data_version: {e}"))
+            .map_err(|e| format!("failed to read data_version: {e}").into())
+    }
+
+    /// A token that changes whenever the data does, for cache validators.
+    ///
+    /// `data_version` only moves when *another* connection commits, so it is
+    /// paired with this connection's `total_changes()` to also cover writes
+    /// made through this handle (e.g. by the web server itself).
+    pub fn content_version(&self) -> Result<String, Error> {
+        let changes: i64 = self
+            .conn
+            .query_row("SELECT total_changes()", [], |row| row.get(0))
+            .map_err(|e| format!("failed to read total_changes: {e}"))?;
+        Ok(format!("{}-{changes}", self.data_version()?))
     }
 
-    pub fn get_children(&self, parent_id: &str) -> Result<Vec<Task>, String> {
+    /// Children of `parent_id` in creation order, by the numeric `.N` suffix
+    /// of their IDs (so `.2` sorts before `.10`).
+    pub fn get_children(&self, parent_id: &str) -> Result<Vec<Task>, Error> {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes
+                "SELECT id, title, description, status, priority, assignee, parent_id, tags, created_at, updated_at, close_reason, notes, due_at, position, archived_at,
+                        estimate_minutes, spent_minutes
                  FROM tasks WHERE parent_id = ?1 ORDER BY id ASC",
             )
             .map_err(|e| format!("query error: {e}"))?;
 
         let rows = stmt
-            .query_map(params![parent_id], |row| Ok(row_to_task(row)))
+            .query_map(params![parent_id], row_to_task)
             .map_err(|e| format!("query error: {e}"))?;
 
         let mut tasks = Vec::new();
         for row in rows {
             tasks.push(row.map_err(|e| format!("row error: {e}"))?);
         }
+        tasks.sort_by_key(|t| child_index(&t.id));
+        Ok(tasks)
+    }
+
+    /// The other children of `id`'s parent, in child order. Empty for a
+    /// top-level task or an unknown ID.
+    pub fn get_siblings(&self, id: &str) -> Result<Vec<Task>, Error> {
+        let Some(parent_id) = self.get_task(id)?.and_then(|t| t.parent_id) else {
+            return Ok(Vec::new());
+        };
+        let mut siblings = self.get_children(&parent_id)?;
+        siblings.retain(|t| t.id != id);
+        Ok(siblings)
+    }
+
+    /// Children of `parent_id` filtered by `status` and `priority`, ordered by
+    /// `sort`, and truncated to `limit`.
+    pub fn get_children_filtered(
+        &self,
+        parent_id: &str,
+        status: Option<&str>,
+        priority: Option<u8>,
+        sort: ChildSort,
+        limit: Option<usize>,
+    ) -> Result<Vec<Task>, Error> {
+        let mut tasks = self.list_tasks(&TaskFilter {
+            include_done: true,
+            include_archived: true,
+            status,
+            priority,
+            parent: Some(parent_id),
+            ..Default::default()
+        })?;
+        // list_tasks already orders by priority, then manual position, then
+        // creation time.
+        match sort {
+            ChildSort::Index => tasks.sort_by_key(|t| child_index(&t.id)),
+            ChildSort::Priority => {}
+            ChildSort::Created => tasks.sort_by_key(|t| t.created_at),
+        }
+        if let Some(n) = limit {
+            tasks.truncate(n);
+        }
         Ok(tasks)
     }
 }
 
+/// Numeric suffix of a hierarchical child ID (`tk-a1b2.10` -> 10), so
+/// children sort 1, 2 … 10 rather than lexicographically. Non-child IDs sort first.
+fn child_index(id: &str) -> u64 {
+    id.rfind('.')
+        .and_then(|pos| id[pos + 1..].parse::<u64>().ok())
+        .unwrap_or(0)
+}
+
+/// Config keys holding a saved template are this prefix plus its name.
+pub const TEMPLATE_KEY_PREFIX: &str = "template.";
+
+/// Config keys holding a saved view are this prefix plus its name.
+pub const VIEW_KEY_PREFIX: &str = "view.";
+
+/// Milliseconds a connection waits on another connection's lock before
+/// reporting the database busy, unless `TACKS_BUSY_TIMEOUT_MS` says otherwise.
+pub const DEFAULT_BUSY_TIMEOUT_MS: u64 = 5000;
+
+/// Extra attempts [`retry_busy`] makes after the busy timeout runs out.
+const BUSY_RETRIES: u32 = 3;
+
+/// Hex digits in a generated task ID unless the `id_length` config key says
+/// otherwise.
+const DEFAULT_ID_LENGTH: usize = 4;
+
+/// Priority of a new task when neither the caller nor the
+/// `default_priority` config key gives one.
+pub const DEFAULT_PRIORITY: u8 = 2;
+
+/// Values of the `ready_order` config key: `priority` sorts the ready queue
+/// by priority then age (the default), `age` by age then priority.
+pub const READY_ORDERS: [&str; 2] = ["priority", "age"];
+
+/// How many random IDs [`Database::generate_id`] tries before giving up.
+const ID_ATTEMPTS: usize = 20;
+
+/// Closest two positions in a priority may get before
+/// [`Database::set_position`] renumbers them. Far above `f64` precision at
+/// the magnitudes positions take, so midpoints stay distinct.
+const MIN_POSITION_GAP: f64 = 1e-6;
+
+/// Default bound on dependency-chain length walked by cycle detection.
+/// Override with the `max_dep_depth` config key.
+const DEFAULT_MAX_DEP_DEPTH: usize = 1000;
+
+/// SQL expression (evaluated against a `tasks` row) that is 1 when the task
+/// is not done and has at least one blocker that is not done.
+const IS_BLOCKED_EXPR: &str = "tasks.status != 'done' AND EXISTS (
+    SELECT 1 FROM dependencies d JOIN tasks b ON d.parent_id = b.id
+    WHERE d.child_id = tasks.id AND b.status != 'done')";
+
+/// The busy timeout for new connections: `TACKS_BUSY_TIMEOUT_MS` if set,
+/// else [`DEFAULT_BUSY_TIMEOUT_MS`].
+fn busy_timeout() -> Result<Duration, Error> {
+    let ms = match std::env::var("TACKS_BUSY_TIMEOUT_MS") {
+        Ok(v) => v
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| Error::validation(format!("invalid TACKS_BUSY_TIMEOUT_MS: {v}")))?,
+        Err(_) => DEFAULT_BUSY_TIMEOUT_MS,
+    };
+    Ok(Duration::from_millis(ms))
+}
+
+/// Run `op`, retrying with a short backoff while SQLite reports the database
+/// busy or locked. Some conflicts (such as a WAL snapshot going stale) fail
+/// at once without waiting on the busy timeout, so this covers those too.
+fn retry_busy<T>(mut op: impl FnMut() -> rusqlite::Result<T>) -> rusqlite::Result<T> {
+    let mut attempt = 0;
+    loop {
+        match op() {
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if matches!(e.code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+                    && attempt < BUSY_RETRIES =>
+            {
+                attempt += 1;
+                std::thread::sleep(Duration::from_millis(50 * u64::from(attempt)));
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Read the current schema version from the config table.
-fn get_schema_version(conn: &Connection) -> Result<i32, String> {
+fn get_schema_version(conn: &Connection) -> Result<i32, Error> {
     let mut stmt = conn
         .prepare("SELECT value FROM config WHERE key = 'schema_version'")
         .map_err(|e| format!("failed to read schema_version: {e}"))?;
@@ -700,14 +3196,14 @@ fn get_schema_version(conn: &Connection) -> Result<i32, String> {
     match rows.next() {
         Some(Ok(v)) => v
             .parse::<i32>()
-            .map_err(|e| format!("invalid schema_version value: {e}")),
-        Some(Err(e)) => Err(format!("failed to read schema_version row: {e}")),
+            .map_err(|e| format!("invalid schema_version value: {e}").into()),
+        Some(Err(e)) => Err(format!("failed to read schema_version row: {e}").into()),
         None => Ok(0),
     }
 }
 
 /// Persist the schema version to the config table.
-fn set_schema_version(conn: &Connection, version: i32) -> Result<(), String> {
+fn set_schema_version(conn: &Connection, version: i32) -> Result<(), Error> {
     conn.execute(
         "INSERT OR REPLACE INTO config (key, value) VALUES ('schema_version', ?1)",
         params![version.to_string()],
@@ -723,7 +3219,7 @@ fn set_schema_version(conn: &Connection, version: i32) -> Result<(), String> {
 /// baseline created by the `CREATE TABLE IF NOT EXISTS` block in `migrate()`;
 /// future migrations (v1, v2, ...) will be added as additional `if version < N`
 /// blocks here.
-fn run_migrations(conn: &Connection) -> Result<(), String> {
+fn run_migrations(conn: &Connection) -> Result<(), Error> {
     let version = get_schema_version(conn)?;
 
     // v0 is the baseline -- no ALTER TABLE statements needed.
@@ -748,9 +3244,210 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
         set_schema_version(conn, 2)?;
     }
 
+    if version < 3 {
+        // Denormalized "has an open blocker" flag so board/blocked queries can
+        // filter with an index instead of joining dependencies. Backfilled by
+        // `recompute_blocked_flags`, which `migrate` runs after this.
+        conn.execute_batch(
+            "BEGIN;
+             ALTER TABLE tasks ADD COLUMN is_blocked INTEGER NOT NULL DEFAULT 0;
+             CREATE INDEX IF NOT EXISTS idx_tasks_is_blocked ON tasks(is_blocked);
+             COMMIT;",
+        )
+        .map_err(|e| format!("migration v3 failed: {e}"))?;
+        set_schema_version(conn, 3)?;
+    }
+
+    if version < 4 {
+        // Threaded comments: a reply points at an earlier comment on the same task.
+        conn.execute_batch(
+            "BEGIN;
+             ALTER TABLE comments ADD COLUMN reply_to INTEGER REFERENCES comments(id);
+             COMMIT;",
+        )
+        .map_err(|e| format!("migration v4 failed: {e}"))?;
+        set_schema_version(conn, 4)?;
+    }
+
+    if version < 5 {
+        // Full-text index over title and description, kept in sync by
+        // triggers. SQLite builds without FTS5 skip it, and search falls back
+        // to LIKE (see `Database::has_fts`).
+        let created = conn.execute_batch(
+            "BEGIN;
+             CREATE VIRTUAL TABLE tasks_fts USING fts5(id UNINDEXED, title, description);
+             INSERT INTO tasks_fts (id, title, description)
+                 SELECT id, title, description FROM tasks;
+             CREATE TRIGGER tasks_fts_insert AFTER INSERT ON tasks BEGIN
+                 INSERT INTO tasks_fts (id, title, description)
+                     VALUES (new.id, new.title, new.description);
+             END;
+             CREATE TRIGGER tasks_fts_delete AFTER DELETE ON tasks BEGIN
+                 DELETE FROM tasks_fts WHERE id = old.id;
+             END;
+             CREATE TRIGGER tasks_fts_update AFTER UPDATE OF id, title, description ON tasks BEGIN
+                 DELETE FROM tasks_fts WHERE id = old.id;
+                 INSERT INTO tasks_fts (id, title, description)
+                     VALUES (new.id, new.title, new.description);
+             END;
+             COMMIT;",
+        );
+        if let Err(e) = created {
+            let _ = conn.execute_batch("ROLLBACK;");
+            if !e.to_string().contains("no such module") {
+                return Err(format!("migration v5 failed: {e}").into());
+            }
+        }
+        set_schema_version(conn, 5)?;
+    }
+
+    if version < 6 {
+        conn.execute_batch(
+            "BEGIN;
+             ALTER TABLE tasks ADD COLUMN due_at TEXT;
+             CREATE INDEX IF NOT EXISTS idx_tasks_due_at ON tasks(due_at);
+             COMMIT;",
+        )
+        .map_err(|e| format!("migration v6 failed: {e}"))?;
+        set_schema_version(conn, 6)?;
+    }
+
+    if version < 7 {
+        // Set when a comment is edited, so edited comments can be told apart.
+        conn.execute_batch(
+            "BEGIN;
+             ALTER TABLE comments ADD COLUMN updated_at TEXT;
+             COMMIT;",
+        )
+        .map_err(|e| format!("migration v7 failed: {e}"))?;
+        set_schema_version(conn, 7)?;
+    }
+
+    if version < 8 {
+        // Audit trail of status, priority, assignee, and close_reason changes,
+        // written by update_task in the same transaction as the change.
+        conn.execute_batch(
+            "BEGIN;
+             CREATE TABLE IF NOT EXISTS activity (
+                 id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                 task_id    TEXT NOT NULL REFERENCES tasks(id),
+                 field      TEXT NOT NULL,
+                 old_value  TEXT,
+                 new_value  TEXT,
+                 created_at TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_activity_task ON activity(task_id);
+             COMMIT;",
+        )
+        .map_err(|e| format!("migration v8 failed: {e}"))?;
+        set_schema_version(conn, 8)?;
+    }
+
+    if version < 9 {
+        // Manual order within a priority. Fractional, so a task can be moved
+        // between two others without renumbering; existing tasks keep their
+        // creation order.
+        conn.execute_batch(
+            "BEGIN;
+             ALTER TABLE tasks ADD COLUMN position REAL NOT NULL DEFAULT 0;
+             UPDATE tasks SET position = (
+                 SELECT COUNT(*) FROM tasks t
+                 WHERE t.priority = tasks.priority
+                   AND (t.created_at, t.id) <= (tasks.created_at, tasks.id)
+             );
+             CREATE INDEX IF NOT EXISTS idx_tasks_position ON tasks(priority, position);
+             COMMIT;",
+        )
+        .map_err(|e| format!("migration v9 failed: {e}"))?;
+        set_schema_version(conn, 9)?;
+    }
+
+    if version < 10 {
+        // Soft archive: archived tasks drop out of lists, the board, and the
+        // ready/blocked views but stay reachable by ID.
+        conn.execute_batch(
+            "BEGIN;
+             ALTER TABLE tasks ADD COLUMN archived_at TEXT;
+             CREATE INDEX IF NOT EXISTS idx_tasks_archived_at ON tasks(archived_at);
+             COMMIT;",
+        )
+        .map_err(|e| format!("migration v10 failed: {e}"))?;
+        set_schema_version(conn, 10)?;
+    }
+
+    if version < 11 {
+        // Effort tracking in whole minutes; NULL means not estimated / not logged.
+        conn.execute_batch(
+            "BEGIN;
+             ALTER TABLE tasks ADD COLUMN estimate_minutes INTEGER;
+             ALTER TABLE tasks ADD COLUMN spent_minutes INTEGER;
+             COMMIT;",
+        )
+        .map_err(|e| format!("migration v11 failed: {e}"))?;
+        set_schema_version(conn, 11)?;
+    }
+
+    if version < 12 {
+        // One row per (task, tag) so tag filters and counts use an index
+        // instead of matching the comma-joined `tags` column, which is kept in
+        // sync by `Database::write_task_tags`. Backfilled by splitting it.
+        conn.execute_batch(
+            "BEGIN;
+             CREATE TABLE IF NOT EXISTS task_tags (
+                 task_id TEXT NOT NULL REFERENCES tasks(id),
+                 tag     TEXT NOT NULL,
+                 PRIMARY KEY (task_id, tag)
+             );
+             CREATE INDEX IF NOT EXISTS idx_task_tags_tag ON task_tags(tag);
+             WITH RECURSIVE split(task_id, tag, rest) AS (
+                 SELECT id, '', tags || ',' FROM tasks WHERE tags != ''
+                 UNION ALL
+                 SELECT task_id, trim(substr(rest, 1, instr(rest, ',') - 1)),
+                        substr(rest, instr(rest, ',') + 1)
+                 FROM split WHERE rest != ''
+             )
+             INSERT OR IGNORE INTO task_tags (task_id, tag)
+                 SELECT task_id, tag FROM split WHERE tag != '';
+             COMMIT;",
+        )
+        .map_err(|e| format!("migration v12 failed: {e}"))?;
+        set_schema_version(conn, 12)?;
+    }
+
     Ok(())
 }
 
+/// Longest path of blocker IDs ending at `id`, memoized per node. The
+/// dependency graph is kept acyclic on write, but recursion is still capped
+/// at `max_depth` (the node count) so a corrupt database cannot overflow the
+/// stack.
+fn chain_into(
+    id: &str,
+    blockers: &std::collections::HashMap<String, Vec<String>>,
+    memo: &mut std::collections::HashMap<String, Vec<String>>,
+    depth: usize,
+    max_depth: usize,
+) -> Result<Vec<String>, Error> {
+    if let Some(chain) = memo.get(id) {
+        return Ok(chain.clone());
+    }
+    if depth > max_depth {
+        return Err(Error::conflict(format!(
+            "dependency cycle detected at {id}"
+        )));
+    }
+    let mut longest: Vec<String> = Vec::new();
+    for blocker in blockers.get(id).into_iter().flatten() {
+        let chain = chain_into(blocker, blockers, memo, depth + 1, max_depth)?;
+        if chain.len() > longest.len() {
+            longest = chain;
+        }
+    }
+    longest.push(id.to_string());
+    memo.insert(id.to_string(), longest.clone());
+    Ok(longest)
+}
+
 /// Return `true` if inserting the edge `child_id → parent_id` would create a cycle.
 ///
 /// The dependency table records that `child_id` is blocked by `parent_id`.  A
@@ -761,22 +3458,30 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
 /// The BFS walks from `parent_id` through its own blockers (rows where
 /// `child_id = current`), looking for `child_id` in the visited set.  The
 /// search is bounded by the total number of distinct nodes in the graph, so it
-/// always terminates even on a large but acyclic graph.
-fn would_create_cycle(conn: &Connection, child_id: &str, parent_id: &str) -> Result<bool, String> {
+/// always terminates even on a large but acyclic graph. It additionally aborts
+/// with an error once a chain longer than `max_depth` edges is found.
+fn would_create_cycle(
+    conn: &Connection,
+    child_id: &str,
+    parent_id: &str,
+    max_depth: usize,
+) -> Result<bool, Error> {
     use std::collections::{HashSet, VecDeque};
 
     let mut visited: HashSet<String> = HashSet::new();
-    let mut queue: VecDeque<String> = VecDeque::new();
+    let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+
+    // Prepared once: the BFS may visit every node in the graph.
+    let mut stmt = conn
+        .prepare("SELECT parent_id FROM dependencies WHERE child_id = ?1")
+        .map_err(|e| format!("query error: {e}"))?;
 
+    // Depth counts edges from `child_id`; the edge being added is depth 1.
     visited.insert(parent_id.to_string());
-    queue.push_back(parent_id.to_string());
+    queue.push_back((parent_id.to_string(), 1));
 
-    while let Some(current) = queue.pop_front() {
+    while let Some((current, depth)) = queue.pop_front() {
         // Fetch all tasks that `current` depends on (its direct blockers)
-        let mut stmt = conn
-            .prepare("SELECT parent_id FROM dependencies WHERE child_id = ?1")
-            .map_err(|e| format!("query error: {e}"))?;
-
         let blocker_ids: Vec<String> = stmt
             .query_map(params![current], |row| row.get::<_, String>(0))
             .map_err(|e| format!("query error: {e}"))?
@@ -788,8 +3493,14 @@ fn would_create_cycle(conn: &Connection, child_id: &str, parent_id: &str) -> Res
                 return Ok(true);
             }
             if !visited.contains(&blocker) {
+                if depth + 1 > max_depth {
+                    return Err(Error::conflict(format!(
+                        "dependency chain exceeds max_dep_depth ({max_depth}); \
+                         raise it with the max_dep_depth config key if this is intended"
+                    )));
+                }
                 visited.insert(blocker.clone());
-                queue.push_back(blocker);
+                queue.push_back((blocker, depth + 1));
             }
         }
     }
@@ -797,15 +3508,81 @@ fn would_create_cycle(conn: &Connection, child_id: &str, parent_id: &str) -> Res
     Ok(false)
 }
 
-fn row_to_task(row: &rusqlite::Row) -> Task {
+/// Turn free text into an FTS5 query: every word quoted (so punctuation is
+/// never parsed as query syntax) and matched as a prefix, all required.
+/// Words without a letter or digit are dropped, since they hold no token.
+fn fts_query(input: &str) -> String {
+    input
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escape `\`, `%` and `_` so a LIKE pattern with `ESCAPE '\'` matches
+/// them literally.
+fn escape_like(input: &str) -> String {
+    input
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// A raw column value for problem reports: text quoted, NULL spelled out.
+fn describe_value(value: &rusqlite::types::Value) -> String {
+    use rusqlite::types::Value;
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Integer(n) => n.to_string(),
+        Value::Real(f) => f.to_string(),
+        Value::Text(s) => format!("{s:?}"),
+        Value::Blob(b) => format!("<{} byte blob>", b.len()),
+    }
+}
+
+/// Read an RFC3339 timestamp column. A malformed value is an error naming
+/// `column`, not a silent fallback to now, so corrupt rows are reported
+/// instead of masked.
+fn timestamp_column(
+    row: &rusqlite::Row,
+    idx: usize,
+    column: &str,
+) -> rusqlite::Result<DateTime<Utc>> {
+    let raw: String = row.get(idx)?;
+    DateTime::parse_from_rfc3339(&raw)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(
+                idx,
+                rusqlite::types::Type::Text,
+                format!("invalid {column} timestamp {raw:?}: {e}").into(),
+            )
+        })
+}
+
+/// Map a row of `id, task_id, body, created_at, reply_to, updated_at`.
+fn row_to_comment(row: &rusqlite::Row) -> rusqlite::Result<Comment> {
+    Ok(Comment {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        body: row.get(2)?,
+        created_at: timestamp_column(row, 3, "created_at")?,
+        reply_to: row.get(4)?,
+        updated_at: row
+            .get::<_, Option<String>>(5)?
+            .map(|_| timestamp_column(row, 5, "updated_at"))
+            .transpose()?,
+    })
+}
+
+fn row_to_task(row: &rusqlite::Row) -> rusqlite::Result<Task> {
     let status_str: String = row.get(3).unwrap_or_default();
     let tags_str: String = row.get(7).unwrap_or_default();
-    let created_str: String = row.get(8).unwrap_or_default();
-    let updated_str: String = row.get(9).unwrap_or_default();
     let close_reason: Option<String> = row.get(10).unwrap_or(None);
     let notes: Option<String> = row.get(11).unwrap_or(None);
 
-    Task {
+    Ok(Task {
         id: row.get(0).unwrap_or_default(),
         title: row.get(1).unwrap_or_default(),
         description: row.get(2).ok(),
@@ -824,13 +3601,506 @@ fn row_to_task(row: &rusqlite::Row) -> Task {
         } else {
             tags_str.split(',').map(|s| s.trim().to_string()).collect()
         },
-        created_at: DateTime::parse_from_rfc3339(&created_str)
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(|_| Utc::now()),
-        updated_at: DateTime::parse_from_rfc3339(&updated_str)
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(|_| Utc::now()),
+        created_at: timestamp_column(row, 8, "created_at")?,
+        updated_at: timestamp_column(row, 9, "updated_at")?,
         close_reason,
         notes,
+        due_at: row
+            .get::<_, Option<String>>(12)?
+            .map(|_| timestamp_column(row, 12, "due_at"))
+            .transpose()?,
+        position: row.get(13).unwrap_or(0.0),
+        archived_at: row
+            .get::<_, Option<String>>(14)?
+            .map(|_| timestamp_column(row, 14, "archived_at"))
+            .transpose()?,
+        estimate_minutes: row.get(15)?,
+        spent_minutes: row.get(16)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    fn task(id: &str, title: &str) -> Task {
+        let now = Utc::now();
+        Task {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: None,
+            status: Status::Open,
+            priority: 2,
+            assignee: None,
+            parent_id: None,
+            tags: vec![],
+            created_at: now,
+            updated_at: now,
+            close_reason: None,
+            notes: None,
+            due_at: None,
+            position: 0.0,
+            archived_at: None,
+            estimate_minutes: None,
+            spent_minutes: None,
+        }
+    }
+
+    #[test]
+    fn test_in_memory_persists_within_connection() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_task(&task("tk-a", "First")).unwrap();
+        db.insert_task(&task("tk-b", "Second")).unwrap();
+        db.add_dependency("tk-b", "tk-a").unwrap();
+
+        let tasks = db.list_tasks(&TaskFilter::default()).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(db.get_blocked_tasks().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_fresh_in_memory_database_starts_empty() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_task(&task("tk-a", "First")).unwrap();
+
+        let fresh = Database::open(Path::new(IN_MEMORY_PATH)).unwrap();
+        assert!(fresh.list_tasks(&TaskFilter::default()).unwrap().is_empty());
+        assert!(fresh.get_task("tk-a").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_with_transaction_rolls_back_on_error() {
+        let db = Database::open_in_memory().unwrap();
+        let result: Result<(), Error> = db.with_transaction(|db| {
+            db.insert_task(&task("tk-a", "Parent"))?;
+            db.insert_task(&task("tk-b", "Child"))?;
+            Err("simulated failure".into())
+        });
+        assert_eq!(result.unwrap_err().message, "simulated failure");
+        assert!(db.get_task("tk-a").unwrap().is_none());
+        assert!(db.get_task("tk-b").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_nested_with_transaction_joins_outer() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_task(&task("tk-a", "First")).unwrap();
+        let result: Result<(), Error> = db.with_transaction(|db| {
+            // close_task opens its own transaction; it must join this one.
+            db.close_task("tk-a", Some("done"), &["closing".to_string()])?;
+            Err("simulated failure".into())
+        });
+        assert!(result.is_err());
+        let t = db.get_task("tk-a").unwrap().unwrap();
+        assert_eq!(t.status, Status::Open);
+        assert!(db.get_comments("tk-a").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_with_transaction_commits_on_ok() {
+        let db = Database::open_in_memory().unwrap();
+        db.with_transaction(|db| db.insert_task(&task("tk-a", "Kept")))
+            .unwrap();
+        assert!(db.get_task("tk-a").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_search_tasks_falls_back_to_like_without_fts() {
+        let db = Database::open_in_memory().unwrap();
+        db.conn
+            .execute_batch(
+                "DROP TRIGGER tasks_fts_insert; DROP TRIGGER tasks_fts_delete;
+                 DROP TRIGGER tasks_fts_update; DROP TABLE tasks_fts;",
+            )
+            .unwrap();
+        let mut in_desc = task("tk-a", "Tidy logging");
+        in_desc.description = Some("the parser drops spans".to_string());
+        db.insert_task(&in_desc).unwrap();
+        db.insert_task(&task("tk-b", "Parser rewrite")).unwrap();
+
+        let ids: Vec<String> = db
+            .search_tasks("parser")
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ids, vec!["tk-b", "tk-a"]);
+        assert!(db.search_tasks("  ").is_err());
+
+        db.insert_task(&task("tk-c", "Cap at 100% load")).unwrap();
+        db.insert_task(&task("tk-d", "Cap at 1000 items")).unwrap();
+        let ids: Vec<String> = db
+            .search_tasks("100%")
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ids, vec!["tk-c"]);
+        assert!(db.search_tasks("at_1").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_writers_do_not_fail_busy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tacks.db");
+        let db = Database::open(&path).unwrap();
+        db.migrate().unwrap();
+        db.insert_task(&task("tk-root", "Shared blocker")).unwrap();
+
+        let writers: Vec<_> = (0..8)
+            .map(|w| {
+                let path = path.clone();
+                std::thread::spawn(move || -> Result<(), Error> {
+                    let db = Database::open(&path)?;
+                    for i in 0..20 {
+                        let id = format!("tk-{w}x{i}");
+                        db.insert_task(&task(&id, "Concurrent"))?;
+                        db.update_task(&id, None, Some(1), None, None, None, None, None)?;
+                        db.add_dependency(&id, "tk-root")?;
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap().unwrap();
+        }
+
+        assert_eq!(db.get_dependents("tk-root").unwrap().len(), 160);
+    }
+
+    #[test]
+    fn test_check_tasks_on_in_memory_copy_flags_bad_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_in_memory().unwrap();
+        db.insert_task(&task("tk-a", "Fine")).unwrap();
+        db.insert_task(&task("tk-b", "Odd")).unwrap();
+        let out = dir.path().join("export.db");
+        db.export_sqlite(&out, false).unwrap();
+        Connection::open(&out)
+            .unwrap()
+            .execute_batch(
+                "UPDATE tasks SET priority = 9, status = 'someday' WHERE id = 'tk-b';
+                 UPDATE tasks SET updated_at = 'yesterday' WHERE id = 'tk-a';",
+            )
+            .unwrap();
+
+        let copy = Database::open_copy_in_memory(&out).unwrap();
+        copy.migrate().unwrap();
+        copy.insert_task(&task("tk-c", "Only in memory")).unwrap();
+        let problems: Vec<(String, String)> = copy
+            .check_tasks()
+            .unwrap()
+            .into_iter()
+            .map(|p| (p.task_id, p.problem))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (
+                    "tk-a".to_string(),
+                    "invalid updated_at timestamp \"yesterday\"".to_string()
+                ),
+                ("tk-b".to_string(), "unknown status \"someday\"".to_string()),
+                ("tk-b".to_string(), "priority 9 is outside 0-3".to_string()),
+            ]
+        );
+
+        let original = Connection::open(&out).unwrap();
+        let count: i64 = original
+            .query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_corrupt_timestamp_is_an_error_not_now() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_task(&task("tk-a", "Corrupt")).unwrap();
+        db.conn
+            .execute(
+                "UPDATE tasks SET created_at = 'not-a-date' WHERE id = 'tk-a'",
+                [],
+            )
+            .unwrap();
+
+        let err = db.get_task("tk-a").unwrap_err();
+        assert!(
+            err.message.contains("created_at"),
+            "unexpected error: {err}"
+        );
+        assert!(
+            err.message.contains("not-a-date"),
+            "unexpected error: {err}"
+        );
+        assert!(db.list_tasks(&TaskFilter::default()).is_err());
+    }
+
+    #[test]
+    fn test_generate_id_retries_collisions_and_reports_exhaustion() {
+        let db = Database::open_in_memory().unwrap();
+        db.set_config("id_length", "2").unwrap();
+        // Half of the 256 two-digit IDs: later draws collide often and retry.
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..128 {
+            let id = db.generate_id().unwrap();
+            assert_eq!(id.len(), "tk-".len() + 2, "{id}");
+            assert!(seen.insert(id.clone()), "duplicate ID {id}");
+            db.insert_task(&task(&id, "Seeded")).unwrap();
+        }
+
+        for n in 0..=255u8 {
+            let id = format!("tk-{n:02x}");
+            if !seen.contains(&id) {
+                db.insert_task(&task(&id, "Filler")).unwrap();
+            }
+        }
+        let err = db.generate_id().unwrap_err();
+        assert!(err.message.contains("id_length"), "{err}");
+
+        db.set_config("id_length", "1").unwrap();
+        assert!(
+            db.generate_id()
+                .unwrap_err()
+                .message
+                .contains("invalid id_length")
+        );
+    }
+
+    #[test]
+    fn test_integrity_report_finds_and_repairs_bad_rows() {
+        let db = Database::open_in_memory().unwrap();
+        for id in ["tk-a", "tk-b", "tk-c", "tk-d"] {
+            db.insert_task(&task(id, id)).unwrap();
+        }
+        db.insert_task(&Task {
+            tags: vec!["epic".to_string()],
+            ..task("tk-e", "Lonely epic")
+        })
+        .unwrap();
+        assert_eq!(
+            db.integrity_report()
+                .unwrap()
+                .iter()
+                .map(|f| f.check)
+                .collect::<Vec<_>>(),
+            ["empty_epic"]
+        );
+
+        db.conn
+            .execute_batch(
+                "PRAGMA foreign_keys = OFF;
+                 INSERT INTO dependencies (child_id, parent_id) VALUES ('tk-a', 'tk-gone');
+                 INSERT INTO dependencies (child_id, parent_id) VALUES ('tk-b', 'tk-c');
+                 INSERT INTO dependencies (child_id, parent_id) VALUES ('tk-c', 'tk-b');
+                 UPDATE tasks SET parent_id = 'tk-missing' WHERE id = 'tk-d';
+                 PRAGMA foreign_keys = ON;",
+            )
+            .unwrap();
+        let found: Vec<(&str, String)> = db
+            .integrity_report()
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.check, f.task_id))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("dangling_dependency", "tk-a".to_string()),
+                ("dangling_parent", "tk-d".to_string()),
+                ("empty_epic", "tk-e".to_string()),
+                ("dependency_cycle", "tk-b".to_string()),
+                ("stale_blocked_flag", "tk-b".to_string()),
+                ("stale_blocked_flag", "tk-c".to_string()),
+            ]
+        );
+
+        assert_eq!(db.repair_integrity().unwrap(), 4);
+        let left: Vec<&str> = db
+            .integrity_report()
+            .unwrap()
+            .iter()
+            .map(|f| f.check)
+            .collect();
+        assert_eq!(left, ["empty_epic", "dependency_cycle"]);
+        assert!(db.get_task("tk-d").unwrap().unwrap().parent_id.is_none());
+    }
+
+    #[test]
+    fn test_migrating_from_v11_backfills_tag_table() {
+        let db = Database::open_in_memory().unwrap();
+        for (id, tags) in [("tk-a", "50%,ui"), ("tk-b", "5x")] {
+            db.insert_task(&Task {
+                tags: tags.split(',').map(String::from).collect(),
+                ..task(id, id)
+            })
+            .unwrap();
+        }
+        let tagged = |tag| {
+            db.list_tasks(&TaskFilter {
+                tag: Some(tag),
+                ..Default::default()
+            })
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect::<Vec<_>>()
+        };
+
+        db.conn
+            .execute_batch(
+                "DROP TABLE task_tags;
+                 UPDATE config SET value = '11' WHERE key = 'schema_version';",
+            )
+            .unwrap();
+        db.migrate().unwrap();
+        assert_eq!(db.schema_version().unwrap(), 12);
+        assert_eq!(tagged("ui"), ["tk-a"]);
+        assert_eq!(tagged("5x"), ["tk-b"]);
+        assert_eq!(
+            db.task_count_by_tag().unwrap(),
+            [
+                ("50%".to_string(), 1),
+                ("5x".to_string(), 1),
+                ("ui".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_position_reorders_and_rebalances() {
+        let db = Database::open_in_memory().unwrap();
+        for id in ["tk-a", "tk-b", "tk-c"] {
+            db.insert_task(&task(id, id)).unwrap();
+        }
+        let order = || -> Vec<(String, f64)> {
+            db.list_tasks(&TaskFilter::default())
+                .unwrap()
+                .into_iter()
+                .map(|t| (t.id, t.position))
+                .collect()
+        };
+        let ids = |order: Vec<(String, f64)>| -> Vec<String> {
+            order.into_iter().map(|(id, _)| id).collect()
+        };
+        assert_eq!(
+            order(),
+            vec![
+                ("tk-a".to_string(), 1.0),
+                ("tk-b".to_string(), 2.0),
+                ("tk-c".to_string(), 3.0),
+            ]
+        );
+
+        db.set_position("tk-c", 1.5).unwrap();
+        assert_eq!(ids(order()), ["tk-a", "tk-c", "tk-b"]);
+        let after_a = db
+            .list_tasks(&TaskFilter {
+                after: Some("tk-a"),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(after_a.len(), 2);
+        assert_eq!(after_a[0].id, "tk-c");
+
+        // Too close to tk-a: the priority is renumbered, order kept.
+        db.set_position("tk-b", 1.0 + 1e-9).unwrap();
+        assert_eq!(
+            order(),
+            vec![
+                ("tk-a".to_string(), 1.0),
+                ("tk-b".to_string(), 2.0),
+                ("tk-c".to_string(), 3.0),
+            ]
+        );
+
+        assert!(db.set_position("tk-a", f64::NAN).is_err());
+        let err = db.set_position("tk-zz", 1.0).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NotFound, "{err}");
+    }
+
+    /// `n` tasks spread over every status, priority, a few assignees, and
+    /// overlapping tags, for comparing the snapshot with the grouped queries.
+    fn seed_stats_tasks(db: &Database, n: usize) {
+        db.with_transaction(|db| {
+            for i in 0..n {
+                let mut t = task(&format!("tk-{i}"), "Task");
+                t.status = Status::ALL[i % Status::ALL.len()];
+                t.priority = (i % 4) as u8;
+                t.assignee = match i % 5 {
+                    0 => None,
+                    1 => Some(String::new()),
+                    k => Some(format!("agent-{k}")),
+                };
+                t.tags = match i % 3 {
+                    0 => vec![],
+                    1 => vec!["backend".to_string()],
+                    _ => vec!["backend".to_string(), format!("area-{}", i % 7)],
+                };
+                db.insert_task(&t)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_stats_snapshot_matches_per_metric_queries() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(db.stats_snapshot().unwrap(), StatsSnapshot::default());
+
+        seed_stats_tasks(&db, 200);
+        let snapshot = db.stats_snapshot().unwrap();
+        assert_eq!(snapshot.by_status, db.task_count_by_status().unwrap());
+        assert_eq!(snapshot.by_priority, db.task_count_by_priority().unwrap());
+        assert_eq!(snapshot.by_tag, db.task_count_by_tag().unwrap());
+        assert_eq!(snapshot.by_assignee, db.task_count_by_assignee().unwrap());
+        assert_eq!(snapshot.total(), 200);
+    }
+
+    /// Timing comparison on 10k tasks: `cargo test --release stats_snapshot_bench -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn stats_snapshot_bench() {
+        let db = Database::open_in_memory().unwrap();
+        seed_stats_tasks(&db, 10_000);
+        let runs = 20;
+
+        let start = std::time::Instant::now();
+        for _ in 0..runs {
+            db.task_count_by_status().unwrap();
+            db.task_count_by_priority().unwrap();
+            db.task_count_by_tag().unwrap();
+            db.task_count_by_assignee().unwrap();
+        }
+        let per_metric = start.elapsed() / runs;
+
+        let start = std::time::Instant::now();
+        for _ in 0..runs {
+            db.stats_snapshot().unwrap();
+        }
+        let snapshot = start.elapsed() / runs;
+
+        println!("10k tasks: per-metric queries {per_metric:?}, stats_snapshot {snapshot:?}");
+    }
+
+    #[test]
+    fn test_content_version_changes_on_own_writes() {
+        let db = Database::open_in_memory().unwrap();
+        let before = db.content_version().unwrap();
+        assert_eq!(db.content_version().unwrap(), before);
+        db.insert_task(&task("tk-a", "New")).unwrap();
+        assert_ne!(db.content_version().unwrap(), before);
+    }
+
+    #[test]
+    fn test_in_memory_enforces_foreign_keys() {
+        let db = Database::open_in_memory().unwrap();
+        let fk: i64 = db
+            .conn
+            .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(fk, 1);
     }
 }
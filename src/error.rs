@@ -0,0 +1,91 @@
+use std::fmt;
+
+/// What kind of failure an [`Error`] reports. The CLI turns it into an exit
+/// status and the web layer into an HTTP status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Anything else: database, I/O, or unexpected failures (exit 1).
+    Generic,
+    /// A task, comment, template, or view that does not exist (exit 2).
+    NotFound,
+    /// A bad argument or value (exit 3).
+    Validation,
+    /// The change clashes with existing state, e.g. a duplicate or circular
+    /// dependency, or closing a task with open subtasks without `--force`
+    /// (exit 4).
+    Conflict,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Generic => 1,
+            ErrorKind::NotFound => 2,
+            ErrorKind::Validation => 3,
+            ErrorKind::Conflict => 4,
+        }
+    }
+}
+
+/// An error message tagged with its [`ErrorKind`] where it is raised.
+///
+/// A plain `String` converts to [`ErrorKind::Generic`], so `map_err(|e|
+/// format!(...))?` keeps working for query and I/O failures; anything a
+/// caller should branch on is built with one of the constructors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl Error {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Error {
+            kind: ErrorKind::NotFound,
+            message: message.into(),
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Error {
+            kind: ErrorKind::Validation,
+            message: message.into(),
+        }
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Error {
+            kind: ErrorKind::Conflict,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error {
+            kind: ErrorKind::Generic,
+            message,
+        }
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl From<Error> for String {
+    fn from(e: Error) -> Self {
+        e.message
+    }
+}
@@ -1,5 +1,7 @@
 /// Database layer: open, migrate, CRUD, cycle detection.
 pub mod db;
+/// Error type tagged with a kind: not found, validation, conflict, or generic.
+pub mod error;
 /// Data types: Task, Comment, Dependency, Status, CloseReason.
 pub mod models;
 /// Axum-based web server and router.
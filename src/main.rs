@@ -1,65 +1,238 @@
+mod backend;
 mod commands;
 mod db;
+mod error;
 mod models;
 pub mod web;
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::io::IsTerminal;
 use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(
     name = "tk",
     version,
-    about = "Lightweight task manager for AI coding agents"
+    about = "Lightweight task manager for AI coding agents",
+    after_help = "Exit codes:\n  0  success\n  1  other error (database, I/O)\n  2  not found (task, comment, template, view)\n  3  invalid arguments or values\n  4  conflict (duplicate or circular dependency, open subtasks without --force)"
 )]
 struct Cli {
-    /// Path to the database file (default: .tacks/tacks.db in current dir)
+    /// Path to the database file (default: .tacks/tacks.db in current dir); ":memory:" for an ephemeral database
     #[arg(long, env = "TACKS_DB")]
     db: Option<PathBuf>,
 
+    /// Talk to a running `tk serve` (e.g. http://127.0.0.1:3000) instead of the database file.
+    /// Supported by list, ready, show, create, and close
+    #[arg(long, env = "TACKS_SERVER", value_name = "URL")]
+    server: Option<String>,
+
     /// Output as JSON instead of table
     #[arg(long, global = true)]
     json: bool,
 
+    /// Wrap JSON output as {"ok":true,"data":...,"meta":{...}} and errors as {"ok":false,"error":{...}} (implies --json)
+    #[arg(long, global = true)]
+    envelope: bool,
+
+    /// Print list, ready, blocked, and children as JSON Lines: one compact task object per line (implies --json)
+    #[arg(long, global = true, conflicts_with_all = ["envelope", "porcelain"])]
+    ndjson: bool,
+
+    /// Output stable key=value records for scripting (list, show); takes precedence over --json
+    #[arg(long, global = true)]
+    porcelain: bool,
+
+    /// Suppress confirmation messages such as "Created task …"; data output and errors are unaffected
+    #[arg(long, short = 'q', global = true)]
+    quiet: bool,
+
+    /// Output as a table, overriding TACKS_FORMAT and the `format` config key
+    #[arg(long, global = true, conflicts_with_all = ["json", "porcelain", "ndjson"])]
+    table: bool,
+
+    /// Add priority_label, status_label, and color hints to JSON output (list, show)
+    #[arg(long, global = true)]
+    labels: bool,
+
+    /// When to colorize output: auto (only when stdout is a terminal), always, or never
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Apply the choice globally so every command inherits it without checking
+    /// at each call site. `Auto` defers to `colored`'s own env handling
+    /// (NO_COLOR, CLICOLOR_FORCE) when stdout is a terminal.
+    fn apply(self) {
+        match self {
+            ColorChoice::Always => colored::control::set_override(true),
+            ColorChoice::Never => colored::control::set_override(false),
+            ColorChoice::Auto => {
+                if !std::io::stdout().is_terminal() {
+                    colored::control::set_override(false);
+                }
+            }
+        }
+    }
+}
+
+/// Default output format when no `--json`, `--porcelain`, or `--table` flag
+/// is given: from `TACKS_FORMAT`, else the `format` config key, else table.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Table,
+    Json,
+    Porcelain,
+}
+
+impl OutputFormat {
+    fn parse(value: &str, source: &str) -> Result<Self, error::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "porcelain" => Ok(OutputFormat::Porcelain),
+            _ => Err(error::Error::validation(format!(
+                "invalid {source}: {value}. valid formats: table, json, porcelain"
+            ))),
+        }
+    }
+
+    /// Resolve the default. The config key is only read from an existing
+    /// database, so that resolving never creates one, and an unreadable
+    /// database is left for the command itself to report.
+    fn resolve(db_path: &std::path::Path) -> Result<Self, error::Error> {
+        if let Some(value) = std::env::var("TACKS_FORMAT").ok().filter(|v| !v.is_empty()) {
+            return Self::parse(&value, "TACKS_FORMAT");
+        }
+        let configured = if db_path.is_file() {
+            db::Database::open(db_path)
+                .and_then(|db| db.get_config("format"))
+                .ok()
+                .flatten()
+        } else {
+            None
+        };
+        match configured {
+            Some(value) => Self::parse(&value, "format config value"),
+            None => Ok(OutputFormat::Table),
+        }
+    }
+}
+
+/// Whether the `priority_names` config key is on. Like the `format` key, it
+/// is only read from an existing database, and read errors are left for the
+/// command itself to report.
+fn priority_names_configured(db_path: &std::path::Path) -> bool {
+    db_path.is_file()
+        && db::Database::open(db_path)
+            .and_then(|db| db.get_config_bool("priority_names", false))
+            .unwrap_or(false)
+}
+
+/// Output shape for task-list commands (`list`, `ready`).
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ListFormat {
+    /// Aligned table (or JSON with --json)
+    Table,
+    /// One task ID per line, nothing else
+    Ids,
+}
+
+/// Output shape for `tk dep graph`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum GraphFormat {
+    /// Each task with its blockers (or JSON with --json)
+    Text,
+    /// Graphviz DOT, for piping into `dot -Tsvg`
+    Dot,
+}
+
+/// File format for `tk export --format`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExportFormat {
+    /// RFC 4180 CSV with a header row; tags are joined with `;`
+    Csv,
+    /// JSON array of tasks
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize tacks in the current directory
     Init {
-        /// Task ID prefix (default: "tk")
-        #[arg(long, default_value = "tk")]
-        prefix: String,
+        /// Task ID prefix (default: "tk", or the imported export's prefix)
+        #[arg(long)]
+        prefix: Option<String>,
+        /// Create the database from a `tk export --sqlite` file; with --prefix, imported IDs are renamed
+        #[arg(long, value_name = "FILE")]
+        import: Option<PathBuf>,
+        /// Move tasks to blocked when a dependency is added (sets the auto_block config key)
+        #[arg(long)]
+        auto_block: bool,
+        /// Hex digits in new task IDs, 2-32 (sets the id_length config key; default 4)
+        #[arg(long, value_name = "N", value_parser = models::parse_id_length)]
+        id_length: Option<usize>,
+        /// Print priorities as names (high) instead of P0-P3 (sets the priority_names config key)
+        #[arg(long)]
+        priority_names: bool,
+        /// Priority for `tk create` without -p (sets the default_priority config key; default 2)
+        #[arg(long, value_name = "N", value_parser = models::parse_priority)]
+        default_priority: Option<u8>,
     },
     /// Create a new task
     Create {
-        /// Task title
-        title: String,
-        /// Priority (0=critical, 1=high, 2=medium, 3=low)
-        #[arg(short, long, default_value_t = 2)]
-        priority: u8,
+        /// Task title (with --from-template, replaces the template's root title)
+        #[arg(required_unless_present = "from_template")]
+        title: Option<String>,
+        /// Priority (0=critical, 1=high, 2=medium, 3=low; names accepted; default: the default_priority config key, else 2)
+        #[arg(short, long, value_parser = models::parse_priority)]
+        priority: Option<u8>,
         /// Task description
         #[arg(short, long)]
         description: Option<String>,
+        /// Read the description from a file ("-" for stdin)
+        #[arg(long, value_name = "PATH")]
+        description_file: Option<PathBuf>,
         /// Tags (comma-separated)
         #[arg(short, long)]
         tags: Option<String>,
         /// Parent task ID (creates subtask)
         #[arg(long)]
         parent: Option<String>,
+        /// Due date (YYYY-MM-DD for the end of that day in UTC, or RFC3339)
+        #[arg(long, value_name = "DATE", value_parser = models::parse_due_date)]
+        due: Option<chrono::DateTime<chrono::Utc>>,
+        /// Create a saved template's task tree (see `tk template save`)
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = ["priority", "description", "description_file", "tags", "due"]
+        )]
+        from_template: Option<String>,
     },
     /// List tasks (default: open tasks)
     List {
         /// Show all tasks including closed
         #[arg(short, long)]
         all: bool,
+        /// Include archived tasks
+        #[arg(long)]
+        archived: bool,
         /// Filter by status (open, in_progress, done, blocked)
-        #[arg(short, long)]
+        #[arg(short, long, value_parser = status_arg)]
         status: Option<String>,
-        /// Filter by priority
-        #[arg(short, long)]
+        /// Filter by priority (0-3 or critical, high, medium, low)
+        #[arg(short, long, value_parser = models::parse_priority)]
         priority: Option<u8>,
         /// Filter by tag
         #[arg(short, long)]
@@ -67,25 +240,96 @@ enum Commands {
         /// Filter by parent task ID
         #[arg(long)]
         parent: Option<String>,
+        /// Filter by assignee ("" for unassigned tasks)
+        #[arg(long)]
+        assignee: Option<String>,
+        /// Only tasks blocked by this task ID (what it blocks)
+        #[arg(long, value_name = "ID")]
+        blocked_by: Option<String>,
+        /// Only tasks blocking this task ID (what blocks it)
+        #[arg(long, value_name = "ID")]
+        blocking: Option<String>,
+        /// Only show tasks closed with this reason (done, duplicate, absorbed, stale, superseded); implies --all
+        #[arg(short, long)]
+        reason: Option<String>,
+        /// Only show tasks created on or after this date (RFC3339 or YYYY-MM-DD, UTC)
+        #[arg(long)]
+        created_after: Option<String>,
+        /// Only show tasks created strictly before this date (RFC3339, or YYYY-MM-DD for the start of that day, UTC; the day itself is excluded)
+        #[arg(long)]
+        created_before: Option<String>,
+        /// Only show tasks modified on or after this date (RFC3339 or YYYY-MM-DD, UTC)
+        #[arg(long)]
+        updated_after: Option<String>,
+        /// Only show tasks that sort after this task ID (keyset pagination cursor)
+        #[arg(long)]
+        after: Option<String>,
+        /// Limit output to N tasks
+        #[arg(short, long)]
+        limit: Option<u32>,
+        /// Output format; "ids" prints one task ID per line for shell loops
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+        /// Only include these comma-separated keys in each JSON task (implies --json)
+        #[arg(long, value_name = "FIELDS")]
+        fields: Option<String>,
+    },
+    /// List open and in-progress tasks assigned to you
+    Mine {
+        /// Assignee name (default: $TACKS_USER)
+        #[arg(long, env = "TACKS_USER")]
+        assignee: Option<String>,
+    },
+    /// Claim the highest-priority ready task and print it
+    Next {
+        /// Assignee to claim it for (default: $TACKS_USER, else "agent")
+        #[arg(long, env = "TACKS_USER")]
+        assignee: Option<String>,
     },
     /// Show tasks that are ready to work on (no open blockers)
     Ready {
         /// Limit output to N tasks
         #[arg(short, long)]
         limit: Option<u32>,
+        /// Output format; "ids" prints one task ID per line for shell loops
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+        /// Also list open tasks that are not ready, with the reason (e.g. which blockers)
+        #[arg(long)]
+        explain: bool,
     },
     /// Show task counts by status, priority, and tag
     Stats {
         /// Output a compact single-line summary
         #[arg(long)]
         oneline: bool,
+        /// Count each open epic's subtasks by status instead
+        #[arg(long, conflicts_with = "oneline")]
+        epic: bool,
     },
     /// Output an AI-optimized context summary for session bootstrapping
-    Prime,
-    /// Show detailed info for a task
+    Prime {
+        /// Also list tasks whose IDs appear in commit messages of `git log <REF>..HEAD`
+        #[arg(long, value_name = "REF")]
+        since: Option<String>,
+    },
+    /// Re-print the prime summary whenever the database changes (Ctrl+C to stop)
+    Watch {
+        /// Seconds between checks for changes
+        #[arg(long, value_name = "SECONDS", default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+    /// Show detailed info for one or more tasks
     Show {
-        /// Task ID
-        id: String,
+        /// Task ID(s)
+        #[arg(required = true)]
+        ids: Vec<String>,
+        /// Only include these comma-separated keys in the JSON output (implies --json)
+        #[arg(long, value_name = "FIELDS")]
+        fields: Option<String>,
+        /// Also show the parent, siblings, blockers, and dependents
+        #[arg(long)]
+        related: bool,
     },
     /// Update a task
     Update {
@@ -94,15 +338,18 @@ enum Commands {
         /// New title
         #[arg(long)]
         title: Option<String>,
-        /// New priority
-        #[arg(short, long)]
+        /// New priority (0-3 or critical, high, medium, low)
+        #[arg(short, long, value_parser = models::parse_priority)]
         priority: Option<u8>,
         /// New status (open, in_progress, done, blocked)
-        #[arg(short, long)]
+        #[arg(short, long, value_parser = status_arg)]
         status: Option<String>,
         /// New description
         #[arg(short, long)]
         description: Option<String>,
+        /// Read the new description from a file ("-" for stdin)
+        #[arg(long, value_name = "PATH")]
+        description_file: Option<PathBuf>,
         /// Claim task (set assignee + in_progress)
         #[arg(long)]
         claim: bool,
@@ -115,23 +362,130 @@ enum Commands {
         /// Tags to remove (comma-separated)
         #[arg(long)]
         remove_tags: Option<String>,
+        /// Add the tag if absent, remove it if present (repeatable; each toggles independently)
+        #[arg(long = "toggle-tag", value_name = "TAG")]
+        toggle_tags: Vec<String>,
         /// Working notes (overwrites previous value)
         #[arg(long)]
         notes: Option<String>,
+        /// Read the working notes from a file ("-" for stdin)
+        #[arg(long, value_name = "PATH")]
+        notes_file: Option<PathBuf>,
+        /// New due date (YYYY-MM-DD for the end of that day in UTC, or RFC3339)
+        #[arg(long, value_name = "DATE", value_parser = models::parse_due_date)]
+        due: Option<chrono::DateTime<chrono::Utc>>,
+        /// Remove the due date
+        #[arg(long, conflicts_with = "due")]
+        no_due: bool,
+        /// Estimated effort in minutes
+        #[arg(long, value_name = "MINUTES", value_parser = models::parse_minutes, allow_negative_numbers = true)]
+        estimate: Option<u32>,
+        /// Effort spent so far in minutes
+        #[arg(long, value_name = "MINUTES", value_parser = models::parse_minutes, allow_negative_numbers = true)]
+        spent: Option<u32>,
+        /// Refuse the update if the task's updated_at is no longer this (RFC3339, from `tk show --json`)
+        #[arg(long, value_name = "RFC3339", value_parser = models::parse_timestamp)]
+        if_unchanged_since: Option<chrono::DateTime<chrono::Utc>>,
+    },
+    /// Set status, priority, or tags on every task matching a filter
+    BulkUpdate {
+        /// Include closed tasks; with no other filter, update every task
+        #[arg(short, long)]
+        all: bool,
+        /// Filter by status (open, in_progress, done, blocked)
+        #[arg(short, long, value_parser = status_arg)]
+        status: Option<String>,
+        /// Filter by priority (0-3 or critical, high, medium, low)
+        #[arg(short, long, value_parser = models::parse_priority)]
+        priority: Option<u8>,
+        /// Filter by tag
+        #[arg(short, long)]
+        tag: Option<String>,
+        /// Filter by parent task ID
+        #[arg(long)]
+        parent: Option<String>,
+        /// New status for every matching task
+        #[arg(long, value_name = "STATUS", value_parser = status_arg)]
+        set_status: Option<String>,
+        /// New priority for every matching task
+        #[arg(long, value_name = "PRIORITY", value_parser = models::parse_priority)]
+        set_priority: Option<u8>,
+        /// Tags to add to every matching task (comma-separated)
+        #[arg(long, value_name = "TAGS")]
+        add_tags: Option<String>,
     },
     /// Close a task
     Close {
         /// Task ID
         id: String,
-        /// Closing comment
+        /// Closing comment (repeat for several; each is stored separately, in order)
         #[arg(short, long)]
-        comment: Option<String>,
+        comment: Vec<String>,
         /// Close reason (done, duplicate, absorbed, stale, superseded)
         #[arg(short, long, default_value = "done")]
         reason: String,
         /// Force close even if open dependents exist
         #[arg(long)]
         force: bool,
+        /// Also close the parent epic (reason done) if this was its last open subtask
+        #[arg(long)]
+        close_parent: bool,
+    },
+    /// Reopen a done task and clear its close reason
+    Reopen {
+        /// Task ID
+        id: String,
+        /// Status to reopen into
+        #[arg(short, long, default_value = "open", value_parser = ["open", "in_progress"])]
+        status: String,
+    },
+    /// Archive a task (hidden from list, board, ready, and blocked; tk show still finds it)
+    Archive {
+        /// Task ID
+        #[arg(
+            required_unless_present = "closed_before",
+            conflicts_with = "closed_before"
+        )]
+        id: Option<String>,
+        /// Archive every done task closed before this date (RFC3339 or YYYY-MM-DD, UTC)
+        #[arg(long, value_name = "DATE")]
+        closed_before: Option<String>,
+    },
+    /// Bring an archived task back into lists
+    Unarchive {
+        /// Task ID
+        id: String,
+    },
+    /// Search task titles and descriptions (done tasks included), best match first
+    Search {
+        /// Words to find; each matches as a word prefix and all must match
+        query: String,
+    },
+    /// List open and in-progress tasks past their due date, most overdue first
+    Overdue,
+    /// List tasks changed in the last N hours, including closed ones
+    Recent {
+        /// Look-back window in hours
+        #[arg(long, default_value_t = 24)]
+        hours: u32,
+    },
+    /// Show a task's recorded status, priority, assignee, and close changes
+    History {
+        /// Task ID
+        id: String,
+    },
+    /// Show the longest chain of open blockers leading into a task
+    CriticalPath {
+        /// Task ID
+        id: String,
+    },
+    /// Move a task under another parent (its ID stays the same)
+    Move {
+        /// Task ID
+        id: String,
+        /// New parent task ID, or "" to make it a top-level task
+        #[arg(long)]
+        parent: String,
     },
     /// List child tasks of a parent
     Children {
@@ -145,31 +499,280 @@ enum Commands {
         #[command(subcommand)]
         action: DepAction,
     },
-    /// Add a comment to a task
+    /// List, rename or remove tags across all tasks
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+    /// Save a task tree as a reusable template, or list templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+    /// Save a list filter under a name, run it, or list saved views
+    View {
+        #[command(subcommand)]
+        action: ViewAction,
+    },
+    /// Show or change config keys (default_priority, ready_order, ...)
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Add a comment to a task, or edit or delete an existing comment
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Comment {
+        #[command(subcommand)]
+        action: Option<CommentAction>,
         /// Task ID
-        id: String,
+        #[arg(required = true)]
+        id: Option<String>,
         /// Comment text
-        body: String,
+        body: Option<String>,
+        /// Read the comment text from a file ("-" for stdin), preserving newlines
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Reply to this comment ID (must be a comment on the same task)
+        #[arg(long, value_name = "COMMENT_ID")]
+        reply_to: Option<i64>,
+    },
+    /// List comments: recent comments across all tasks, or one task's comments
+    Comments {
+        /// Only this task's comments (oldest first)
+        id: Option<String>,
+        /// Show the most recent comments across all tasks (the default without an ID)
+        #[arg(long, conflicts_with = "id")]
+        recent: bool,
+        /// Maximum number of comments to show
+        #[arg(short, long, default_value_t = 20)]
+        limit: u32,
+    },
+    /// Show blocked tasks (tasks with open blockers), each with what it is waiting on
+    Blocked {
+        /// With --json, emit `{task, blockers}` entries instead of bare tasks
+        #[arg(long)]
+        explain: bool,
+    },
+    /// Order open tasks into waves that can be worked in sequence, following dependencies
+    Plan,
+    /// Show the kanban board (Open / In Progress / Blocked / Done) in the terminal
+    Board {
+        /// Only show children of these epic IDs (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        epic: Vec<String>,
+        /// Only show these priorities (comma-separated or repeated)
+        #[arg(short, long, value_delimiter = ',', value_parser = models::parse_priority)]
+        priority: Vec<u8>,
+    },
+    /// Permanently delete a task with its comments and dependency edges
+    Delete {
+        /// Task ID
+        id: String,
+        /// Delete even if other tasks depend on it or it has subtasks
+        #[arg(long)]
+        force: bool,
+    },
+    /// Delete old done tasks with their comments and dependency edges (dry run unless --yes)
+    Prune {
+        /// Only prune tasks closed more than this many days ago (later edits do not count)
+        #[arg(long, default_value_t = 90)]
+        older_than: u32,
+        /// Only prune tasks closed with this reason
+        #[arg(short, long)]
+        reason: Option<String>,
+        /// Only report what would be pruned (the default)
+        #[arg(long, conflicts_with = "yes")]
+        dry_run: bool,
+        /// Actually delete the matching tasks
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Change the task ID prefix, renaming existing task IDs and all references to them
+    Reprefix {
+        /// New prefix (a letter, then letters, digits, '_' or '-')
+        prefix: String,
+    },
+    /// Export a standalone copy of the database
+    Export {
+        /// Write a SQLite database file to this path (must not exist)
+        #[arg(
+            long,
+            value_name = "PATH",
+            required_unless_present = "format",
+            conflicts_with = "format"
+        )]
+        sqlite: Option<PathBuf>,
+        /// Write the tasks as CSV or a JSON array instead (to stdout unless --output)
+        #[arg(long, value_enum)]
+        format: Option<ExportFormat>,
+        /// Write --format output to this file instead of stdout
+        #[arg(short, long, value_name = "PATH", requires = "format")]
+        output: Option<PathBuf>,
+        /// Leave out done and archived tasks (and their comments and dependency edges)
+        #[arg(long)]
+        open_only: bool,
+    },
+    /// Create tasks from a JSON array (as `tk list --json` prints) or a Markdown checklist (`- [ ] title`)
+    Import {
+        /// File to read, or - for stdin. IDs in it are replaced; parent_id and blocked_by are remapped
+        file: PathBuf,
+    },
+    /// Check the database for dangling dependencies and parents, empty epics, cycles, and stale flags
+    Doctor {
+        /// Delete dangling dependency rows, clear missing parents, and recompute blocked flags
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Dry-run an export under the current code: migrate it in memory and report tasks that fail validation
+    Replay {
+        /// A file written by `tk export --sqlite` (never modified)
+        file: PathBuf,
+        /// Exit with an error if any problem is found
+        #[arg(long)]
+        check: bool,
     },
-    /// Show blocked tasks (tasks with open blockers)
-    Blocked,
     /// Start the web UI server
     Serve {
         /// Port to listen on
         #[arg(short, long, default_value_t = 3000)]
         port: u16,
+        /// Limit POST/PATCH/DELETE requests to /api/* to this many per second (429 when exceeded)
+        #[arg(long, value_name = "N_PER_SEC", value_parser = clap::value_parser!(u32).range(1..))]
+        rate: Option<u32>,
+    },
+}
+
+impl Commands {
+    /// Whether the command can run against `TACKS_SERVER` instead of the database file.
+    fn supports_server(&self) -> bool {
+        matches!(
+            self,
+            Commands::Create {
+                from_template: None,
+                ..
+            } | Commands::List { .. }
+                | Commands::Ready { .. }
+                | Commands::Show { .. }
+                | Commands::Close { .. }
+        )
+    }
+}
+
+#[derive(Subcommand)]
+enum CommentAction {
+    /// Replace a comment's text (marks it as edited)
+    Edit {
+        /// Comment ID
+        comment_id: i64,
+        /// New comment text
+        body: String,
+    },
+    /// Delete a comment (replies to it are kept)
+    Delete {
+        /// Comment ID
+        comment_id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum TagAction {
+    /// List every tag with how many tasks carry it
+    List,
+    /// Rename a tag on every task (other tags are kept)
+    Rename {
+        /// Current tag name
+        old: String,
+        /// New tag name
+        new: String,
+    },
+    /// Remove a tag from every task
+    Remove {
+        /// Tag to remove
+        tag: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplateAction {
+    /// Save a task, its priority, tags, description, and subtasks as a template
+    Save {
+        /// Template name
+        name: String,
+        /// Task to snapshot
+        #[arg(long)]
+        from: String,
+    },
+    /// List saved templates
+    List,
+}
+
+#[derive(Subcommand)]
+enum ViewAction {
+    /// Save these list filters as a view, replacing any view of that name
+    Save {
+        /// View name
+        name: String,
+        /// Include closed tasks
+        #[arg(short, long)]
+        all: bool,
+        /// Filter by status (open, in_progress, done, blocked)
+        #[arg(short, long, value_parser = status_arg)]
+        status: Option<String>,
+        /// Filter by priority (0-3 or critical, high, medium, low)
+        #[arg(short, long, value_parser = models::parse_priority)]
+        priority: Option<u8>,
+        /// Filter by tag
+        #[arg(short, long)]
+        tag: Option<String>,
+        /// Filter by parent task ID
+        #[arg(long)]
+        parent: Option<String>,
+        /// Filter by assignee ("" for unassigned tasks)
+        #[arg(long)]
+        assignee: Option<String>,
+        /// Only tasks whose title or description match these words
+        #[arg(long)]
+        search: Option<String>,
+    },
+    /// List the tasks a saved view matches now
+    Run {
+        /// View name
+        name: String,
+    },
+    /// List saved views and their filters
+    List,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// List every config key and its value
+    List,
+    /// Print one config key's value
+    Get {
+        /// Config key
+        key: String,
+    },
+    /// Set a config key (the value is checked against what the key accepts)
+    Set {
+        /// Config key
+        key: String,
+        /// New value
+        value: String,
     },
 }
 
 #[derive(Subcommand)]
 enum DepAction {
-    /// Add a dependency (child is blocked by parent)
+    /// Add dependencies (child is blocked by each parent)
     Add {
         /// Task that is blocked
         child: String,
-        /// Task that blocks
-        parent: String,
+        /// Tasks that block it; edges that already exist are skipped
+        #[arg(required = true, num_args = 1..)]
+        parents: Vec<String>,
+        /// Set an open child's status to blocked (default: the auto_block config key)
+        #[arg(long)]
+        auto_block: bool,
     },
     /// Remove a dependency
     Remove {
@@ -178,10 +781,56 @@ enum DepAction {
         /// Task that was blocking
         parent: String,
     },
+    /// List a task's blockers and dependents
+    List {
+        /// Task ID
+        id: String,
+    },
+    /// Show every task with the tasks blocking it (non-done tasks unless --all)
+    Graph {
+        /// Include done tasks
+        #[arg(short, long)]
+        all: bool,
+        /// Only this task and the tasks it transitively blocks
+        #[arg(long, value_name = "ID")]
+        root: Option<String>,
+        /// Output format; "dot" emits a Graphviz digraph (blocker -> blocked)
+        #[arg(long, value_enum, default_value_t = GraphFormat::Text)]
+        format: GraphFormat,
+    },
+}
+
+/// Clap parser for `--status`: rejects unknown statuses before any DB work
+/// and normalizes aliases such as `closed` or `in-progress`.
+fn status_arg(value: &str) -> Result<String, String> {
+    let status = value.parse::<models::Status>().map_err(|e| {
+        let valid: Vec<&str> = models::Status::ALL.iter().map(|s| s.as_str()).collect();
+        format!("{e}. valid statuses: {}", valid.join(", "))
+    })?;
+    Ok(status.as_str().to_string())
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::try_parse().unwrap_or_else(|e| {
+        // Help and version go to stdout and exit 0; usage errors exit with
+        // the validation code rather than clap's default of 2, which here
+        // means "not found".
+        if !e.use_stderr() {
+            e.exit();
+        }
+        let _ = e.print();
+        std::process::exit(error::ErrorKind::Validation.exit_code());
+    });
+    cli.color.apply();
+    commands::set_quiet(cli.quiet);
+    if cli.envelope {
+        cli.json = true;
+        commands::set_envelope(true);
+    }
+    if cli.ndjson {
+        cli.json = true;
+        commands::set_ndjson(true);
+    }
 
     let db_path = cli.db.unwrap_or_else(|| {
         let mut p = std::env::current_dir().expect("cannot determine current directory");
@@ -190,53 +839,181 @@ fn main() {
         p
     });
 
+    if !cli.json && !cli.porcelain && !cli.table {
+        match OutputFormat::resolve(&db_path) {
+            Ok(OutputFormat::Json) => cli.json = true,
+            Ok(OutputFormat::Porcelain) => cli.porcelain = true,
+            Ok(OutputFormat::Table) => {}
+            Err(e) => exit_with_error(&e),
+        }
+    }
+
+    if priority_names_configured(&db_path) {
+        commands::set_priority_names(true);
+    }
+
+    let server = cli.server.clone();
+    if server.is_some() && !cli.command.supports_server() {
+        exit_with_error(&error::Error::validation(
+            "this command needs direct database access and cannot go through a server; unset TACKS_SERVER (or drop --server) to run it",
+        ));
+    }
+    let server = server.as_deref();
+
     let result = match cli.command {
-        Commands::Init { prefix } => commands::init::run(&db_path, &prefix),
+        Commands::Init {
+            prefix,
+            import,
+            auto_block,
+            id_length,
+            priority_names,
+            default_priority,
+        } => commands::init::run(
+            &db_path,
+            prefix.as_deref(),
+            import.as_deref(),
+            auto_block,
+            id_length,
+            priority_names,
+            default_priority,
+        ),
         Commands::Create {
             title,
-            priority,
-            description,
-            tags,
             parent,
-        } => commands::create::run(
+            from_template: Some(name),
+            ..
+        } => commands::template::instantiate(
             &db_path,
-            &title,
-            priority,
-            description.as_deref(),
-            tags.as_deref(),
+            &name,
+            title.as_deref(),
             parent.as_deref(),
             cli.json,
         ),
+        Commands::Create {
+            title,
+            priority,
+            description,
+            description_file,
+            tags,
+            parent,
+            due,
+            from_template: None,
+        } => backend::open(&db_path, server).and_then(|b| {
+            commands::create::run(
+                b.as_ref(),
+                title.as_deref().unwrap_or_default(),
+                priority,
+                description.as_deref(),
+                description_file.as_deref(),
+                tags.as_deref(),
+                parent.as_deref(),
+                due,
+                cli.json,
+            )
+        }),
         Commands::List {
             all,
+            archived,
             status,
             priority,
             tag,
             parent,
-        } => commands::list::run(
-            &db_path,
-            all,
-            status.as_deref(),
-            priority,
-            tag.as_deref(),
-            parent.as_deref(),
-            cli.json,
-        ),
-        Commands::Ready { limit } => commands::ready::run(&db_path, limit, cli.json),
-        Commands::Stats { oneline } => commands::stats::run(&db_path, oneline, cli.json),
-        Commands::Prime => commands::prime::run(&db_path, cli.json),
-        Commands::Show { id } => commands::show::run(&db_path, &id, cli.json),
+            assignee,
+            blocked_by,
+            blocking,
+            reason,
+            created_after,
+            created_before,
+            updated_after,
+            after,
+            limit,
+            format,
+            fields,
+        } => backend::open(&db_path, server).and_then(|b| {
+            commands::list::run(
+                b.as_ref(),
+                &db::TaskFilter {
+                    include_done: all || reason.is_some(),
+                    include_archived: archived,
+                    status: status.as_deref(),
+                    priority,
+                    tag: tag.as_deref(),
+                    parent: parent.as_deref(),
+                    assignee: assignee.as_deref(),
+                    blocked_by: blocked_by.as_deref(),
+                    blocking: blocking.as_deref(),
+                    search: None,
+                    close_reason: reason.as_deref(),
+                    after: after.as_deref(),
+                    limit,
+                    ..Default::default()
+                },
+                created_after.as_deref(),
+                created_before.as_deref(),
+                updated_after.as_deref(),
+                cli.json || fields.is_some(),
+                cli.porcelain,
+                cli.labels,
+                format == ListFormat::Ids,
+                fields.as_deref(),
+            )
+        }),
+        Commands::Mine { assignee } => commands::mine::run(&db_path, assignee.as_deref(), cli.json),
+        Commands::Next { assignee } => commands::next::run(&db_path, assignee.as_deref(), cli.json),
+        Commands::Ready {
+            limit,
+            format,
+            explain,
+        } => backend::open(&db_path, server).and_then(|b| {
+            commands::ready::run(
+                b.as_ref(),
+                limit,
+                cli.json,
+                format == ListFormat::Ids,
+                explain,
+            )
+        }),
+        Commands::Stats { oneline, epic } => {
+            commands::stats::run(&db_path, oneline, epic, cli.json)
+        }
+        Commands::Prime { since } => commands::prime::run(&db_path, since.as_deref(), cli.json),
+        Commands::Watch { interval } => {
+            commands::watch::run(&db_path, std::time::Duration::from_secs(interval), cli.json)
+        }
+        Commands::Show {
+            ids,
+            fields,
+            related,
+        } => backend::open(&db_path, server).and_then(|b| {
+            commands::show::run(
+                b.as_ref(),
+                &ids,
+                cli.json || fields.is_some(),
+                cli.porcelain,
+                cli.labels,
+                fields.as_deref(),
+                related,
+            )
+        }),
         Commands::Update {
             id,
             title,
             priority,
             status,
             description,
+            description_file,
             claim,
             assignee,
             add_tags,
             remove_tags,
+            toggle_tags,
             notes,
+            notes_file,
+            due,
+            no_due,
+            estimate,
+            spent,
+            if_unchanged_since,
         } => commands::update::run(
             &db_path,
             &id,
@@ -244,11 +1021,42 @@ fn main() {
             priority,
             status.as_deref(),
             description.as_deref(),
+            description_file.as_deref(),
             claim,
             assignee.as_deref(),
             add_tags.as_deref(),
             remove_tags.as_deref(),
+            &toggle_tags,
             notes.as_deref(),
+            notes_file.as_deref(),
+            if no_due { Some(None) } else { due.map(Some) },
+            estimate,
+            spent,
+            if_unchanged_since,
+            cli.json,
+        ),
+        Commands::BulkUpdate {
+            all,
+            status,
+            priority,
+            tag,
+            parent,
+            set_status,
+            set_priority,
+            add_tags,
+        } => commands::bulk_update::run(
+            &db_path,
+            &db::TaskFilter {
+                status: status.as_deref(),
+                priority,
+                tag: tag.as_deref(),
+                parent: parent.as_deref(),
+                ..Default::default()
+            },
+            all,
+            set_status.as_deref(),
+            set_priority,
+            add_tags.as_deref(),
             cli.json,
         ),
         Commands::Close {
@@ -256,40 +1064,179 @@ fn main() {
             comment,
             reason,
             force,
-        } => commands::close::run(
-            &db_path,
-            &id,
-            comment.as_deref(),
-            Some(&reason),
-            force,
-            cli.json,
-        ),
+            close_parent,
+        } => backend::open(&db_path, server).and_then(|b| {
+            commands::close::run(
+                b.as_ref(),
+                &id,
+                &comment,
+                Some(&reason),
+                force,
+                close_parent,
+                cli.json,
+            )
+        }),
+        Commands::Reopen { id, status } => commands::reopen::run(&db_path, &id, &status, cli.json),
+        Commands::Archive { id, closed_before } => {
+            commands::archive::run(&db_path, id.as_deref(), closed_before.as_deref(), cli.json)
+        }
+        Commands::Unarchive { id } => commands::archive::unarchive(&db_path, &id, cli.json),
+        Commands::Search { query } => commands::search::run(&db_path, &query, cli.json),
+        Commands::Overdue => commands::overdue::run(&db_path, cli.json),
+        Commands::Recent { hours } => commands::recent::run(&db_path, hours, cli.json),
+        Commands::History { id } => commands::history::run(&db_path, &id, cli.json),
+        Commands::CriticalPath { id } => commands::critical_path::run(&db_path, &id, cli.json),
+        Commands::Move { id, parent } => commands::r#move::run(&db_path, &id, &parent, cli.json),
         Commands::Children { id } => commands::children::run(&db_path, &id, cli.json),
         Commands::Epic => commands::epic::run(&db_path, cli.json),
         Commands::Dep { action } => match action {
-            DepAction::Add { child, parent } => commands::dep::add(&db_path, &child, &parent),
+            DepAction::Add {
+                child,
+                parents,
+                auto_block,
+            } => commands::dep::add(&db_path, &child, &parents, auto_block, cli.json),
             DepAction::Remove { child, parent } => commands::dep::remove(&db_path, &child, &parent),
+            DepAction::List { id } => commands::dep::list(&db_path, &id, cli.json),
+            DepAction::Graph { all, root, format } => commands::dep::graph(
+                &db_path,
+                all,
+                root.as_deref(),
+                format == GraphFormat::Dot,
+                cli.json,
+            ),
+        },
+        Commands::Tag { action } => match action {
+            TagAction::List => commands::tag::list(&db_path, cli.json),
+            TagAction::Rename { old, new } => commands::tag::rename(&db_path, &old, &new, cli.json),
+            TagAction::Remove { tag } => commands::tag::remove(&db_path, &tag, cli.json),
         },
-        Commands::Comment { id, body } => commands::comment::run(&db_path, &id, &body, cli.json),
-        Commands::Blocked => commands::blocked::run(&db_path, cli.json),
-        Commands::Serve { port } => {
+        Commands::Template { action } => match action {
+            TemplateAction::Save { name, from } => {
+                commands::template::save(&db_path, &name, &from, cli.json)
+            }
+            TemplateAction::List => commands::template::list(&db_path, cli.json),
+        },
+        Commands::View { action } => match action {
+            ViewAction::Save {
+                name,
+                all,
+                status,
+                priority,
+                tag,
+                parent,
+                assignee,
+                search,
+            } => commands::view::save(
+                &db_path,
+                &name,
+                &models::SavedView {
+                    status,
+                    priority,
+                    tag,
+                    parent,
+                    assignee,
+                    search,
+                    all,
+                },
+                cli.json,
+            ),
+            ViewAction::Run { name } => commands::view::run(&db_path, &name, cli.json),
+            ViewAction::List => commands::view::list(&db_path, cli.json),
+        },
+        Commands::Config { action } => match action {
+            ConfigAction::List => commands::config::list(&db_path, cli.json),
+            ConfigAction::Get { key } => commands::config::get(&db_path, &key, cli.json),
+            ConfigAction::Set { key, value } => {
+                commands::config::set(&db_path, &key, &value, cli.json)
+            }
+        },
+        Commands::Comment {
+            action: Some(CommentAction::Edit { comment_id, body }),
+            ..
+        } => commands::comment::edit(&db_path, comment_id, &body, cli.json),
+        Commands::Comment {
+            action: Some(CommentAction::Delete { comment_id }),
+            ..
+        } => commands::comment::delete(&db_path, comment_id, cli.json),
+        Commands::Comment {
+            action: None,
+            id,
+            body,
+            file,
+            reply_to,
+        } => commands::comment::run(
+            &db_path,
+            id.as_deref().unwrap_or_default(),
+            body.as_deref(),
+            file.as_deref(),
+            reply_to,
+            cli.json,
+        ),
+        Commands::Comments {
+            id,
+            recent: _,
+            limit,
+        } => commands::comments::run(&db_path, id.as_deref(), limit, cli.json),
+        Commands::Blocked { explain } => commands::blocked::run(&db_path, cli.json, explain),
+        Commands::Plan => commands::plan::run(&db_path, cli.json),
+        Commands::Board { epic, priority } => {
+            commands::board::run(&db_path, &epic, &priority, cli.json)
+        }
+        Commands::Delete { id, force } => commands::delete::run(&db_path, &id, force, cli.json),
+        Commands::Prune {
+            older_than,
+            reason,
+            dry_run: _,
+            yes,
+        } => commands::prune::run(&db_path, older_than, reason.as_deref(), yes, cli.json),
+        Commands::Reprefix { prefix } => commands::reprefix::run(&db_path, &prefix, cli.json),
+        Commands::Export {
+            sqlite,
+            format,
+            output,
+            open_only,
+        } => match (sqlite, format) {
+            (Some(sqlite), _) => commands::export::run(&db_path, &sqlite, open_only, cli.json),
+            (None, Some(format)) => commands::export::tasks(
+                &db_path,
+                format == ExportFormat::Csv,
+                output.as_deref(),
+                open_only,
+                cli.json,
+            ),
+            (None, None) => Err(error::Error::validation(
+                "export needs --sqlite or --format",
+            )),
+        },
+        Commands::Import { file } => commands::import::run(&db_path, &file, cli.json),
+        Commands::Doctor { fix } => commands::doctor::run(&db_path, fix, cli.json),
+        Commands::Replay { file, check } => commands::replay::run(&file, check, cli.json),
+        Commands::Serve { port, rate } => {
             let rt = tokio::runtime::Runtime::new()
-                .map_err(|e| format!("failed to create tokio runtime: {e}"))
-                .unwrap_or_else(|e| {
-                    eprintln!("error: {e}");
-                    std::process::exit(1);
-                });
-            let result = rt.block_on(crate::web::serve(&db_path, port));
+                .map_err(|e| error::Error::from(format!("failed to create tokio runtime: {e}")))
+                .unwrap_or_else(|e| exit_with_error(&e));
+            let result = rt.block_on(crate::web::serve(&db_path, port, rate));
             if let Err(e) = result {
-                eprintln!("error: {e}");
-                std::process::exit(1);
+                exit_with_error(&e);
             }
             return;
         }
     };
 
     if let Err(e) = result {
-        eprintln!("error: {e}");
-        std::process::exit(1);
+        exit_with_error(&e);
+    }
+}
+
+/// Report a failed command on stderr (and as an error envelope on stdout
+/// under `--envelope`), then exit with the status for its [`ErrorKind`]:
+/// 1 generic, 2 not found, 3 validation, 4 conflict.
+///
+/// [`ErrorKind`]: error::ErrorKind
+fn exit_with_error(e: &error::Error) -> ! {
+    if commands::envelope_enabled() {
+        commands::print_json_error(&e.message);
     }
+    eprintln!("error: {e}");
+    std::process::exit(e.kind.exit_code());
 }
@@ -18,6 +18,142 @@ pub fn validate_close_reason(reason: &str) -> Result<(), String> {
     }
 }
 
+/// Validate a task ID prefix: a letter followed by letters, digits, `_` or `-`.
+/// `.` is rejected because it separates child-ID segments.
+pub fn validate_prefix(prefix: &str) -> Result<(), String> {
+    let mut chars = prefix.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid prefix: {prefix:?}. prefixes must start with a letter and contain only letters, digits, '_' or '-'"
+        ))
+    }
+}
+
+/// Reject tags containing a comma, which would split into several tags in
+/// the comma-joined `tags` column.
+pub fn validate_tags(tags: &[String]) -> Result<(), String> {
+    match tags.iter().find(|t| t.contains(',')) {
+        Some(tag) => Err(format!("invalid tag '{tag}': tags cannot contain commas")),
+        None => Ok(()),
+    }
+}
+
+/// Parse a task ID hash length (the hex part after the prefix), 2 to 32.
+pub fn parse_id_length(value: &str) -> Result<usize, String> {
+    match value.trim().parse::<usize>() {
+        Ok(n) if (2..=32).contains(&n) => Ok(n),
+        _ => Err(format!("invalid id length: {value}. use 2-32")),
+    }
+}
+
+/// Parse a `--estimate`/`--spent` value: a whole, non-negative number of minutes.
+pub fn parse_minutes(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+    if trimmed.starts_with('-') {
+        return Err(format!("invalid minutes: {value}. must not be negative"));
+    }
+    trimmed
+        .parse::<u32>()
+        .map_err(|_| format!("invalid minutes: {value}. use a whole number of minutes"))
+}
+
+/// Parse a date filter bound: RFC3339, or `YYYY-MM-DD` taken as UTC. A bare
+/// date means the start of that day, or the start of the next day when
+/// `end_of_day` is set (for exclusive upper bounds covering the whole day).
+pub fn parse_date_bound(value: &str, end_of_day: bool) -> Result<DateTime<Utc>, String> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        format!("invalid date: {value}. expected RFC3339 (2024-01-31T12:00:00Z) or YYYY-MM-DD")
+    })?;
+    let date = if end_of_day {
+        date.succ_opt()
+            .ok_or_else(|| format!("invalid date: {value}"))?
+    } else {
+        date
+    };
+    Ok(date.and_time(chrono::NaiveTime::MIN).and_utc())
+}
+
+/// Parse an exact RFC3339 timestamp, such as a task's `updated_at`.
+pub fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| format!("invalid timestamp: {value}. expected RFC3339 (2024-01-31T12:00:00Z)"))
+}
+
+/// Parse a `--due` value: RFC3339, or `YYYY-MM-DD` meaning the end of that
+/// day in UTC (stored as the start of the next day).
+pub fn parse_due_date(value: &str) -> Result<DateTime<Utc>, String> {
+    parse_date_bound(value, true)
+}
+
+/// Keys of a task's JSON object, including the `--labels` display hints.
+pub const TASK_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "status",
+    "priority",
+    "assignee",
+    "parent_id",
+    "tags",
+    "created_at",
+    "updated_at",
+    "close_reason",
+    "notes",
+    "due_at",
+    "position",
+    "archived_at",
+    "estimate_minutes",
+    "spent_minutes",
+    "priority_label",
+    "status_label",
+    "color",
+];
+
+/// Parse a comma-separated field list (`--fields`, `?fields=`), rejecting
+/// keys that are not in `allowed`.
+pub fn parse_fields(spec: &str, allowed: &[&str]) -> Result<Vec<String>, String> {
+    let fields: Vec<String> = spec
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if fields.is_empty() {
+        return Err("no fields given".to_string());
+    }
+    for field in &fields {
+        if !allowed.contains(&field.as_str()) {
+            return Err(format!(
+                "unknown field: {field}. valid fields: {}",
+                allowed.join(", ")
+            ));
+        }
+    }
+    Ok(fields)
+}
+
+/// Keep only `fields` in a JSON object, or in each object of an array.
+/// Requested keys the object lacks (e.g. label hints without `--labels`)
+/// are simply absent.
+pub fn select_fields(value: &mut serde_json::Value, fields: &[String]) {
+    match value {
+        serde_json::Value::Array(items) => {
+            for item in items {
+                select_fields(item, fields);
+            }
+        }
+        serde_json::Value::Object(obj) => obj.retain(|key, _| fields.contains(key)),
+        _ => {}
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Status {
@@ -28,6 +164,15 @@ pub enum Status {
 }
 
 impl Status {
+    /// Every status, in workflow order. JSON stats always include a key for
+    /// each of these (zero when no task has that status).
+    pub const ALL: [Status; 4] = [
+        Status::Open,
+        Status::InProgress,
+        Status::Blocked,
+        Status::Done,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Status::Open => "open",
@@ -38,6 +183,48 @@ impl Status {
     }
 }
 
+/// The priority scale (P0 = critical … P3 = low). JSON stats always include a
+/// key for each of these (zero when no task has that priority).
+pub const PRIORITIES: [u8; 4] = [0, 1, 2, 3];
+
+/// Human-readable priority label (sans ANSI), matching the CLI's P0–P3 scale.
+pub fn priority_label(p: u8) -> &'static str {
+    match p {
+        0 => "critical",
+        1 => "high",
+        2 => "medium",
+        3 => "low",
+        _ => "unknown",
+    }
+}
+
+/// Parse a priority given as a number (`0`–`3`), a `P0`–`P3` label, or a
+/// name (`critical`, `high`, `medium`, `low`), case-insensitively.
+pub fn parse_priority(value: &str) -> Result<u8, String> {
+    let lower = value.trim().to_lowercase();
+    let digits = lower.strip_prefix('p').unwrap_or(&lower);
+    let parsed = match digits.parse::<u8>() {
+        Ok(p) => Some(p),
+        Err(_) => PRIORITIES.into_iter().find(|&p| priority_label(p) == lower),
+    };
+    match parsed {
+        Some(p) if PRIORITIES.contains(&p) => Ok(p),
+        _ => Err(format!(
+            "invalid priority: {value}. use 0-3 or critical, high, medium, low"
+        )),
+    }
+}
+
+/// Color hint for a priority, mirroring the terminal colors used by `tk list`.
+pub fn priority_color(p: u8) -> &'static str {
+    match p {
+        0 => "red",
+        1 => "yellow",
+        2 => "white",
+        _ => "gray",
+    }
+}
+
 impl FromStr for Status {
     type Err = String;
 
@@ -72,6 +259,186 @@ pub struct Task {
     pub updated_at: DateTime<Utc>,
     pub close_reason: Option<String>,
     pub notes: Option<String>,
+    /// Deadline; a task still open or in progress past it is overdue.
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Manual order among tasks of the same priority; lower sorts first.
+    #[serde(default)]
+    pub position: f64,
+    /// When the task was archived; archived tasks are hidden from lists.
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Expected effort in minutes.
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
+    /// Effort logged so far in minutes.
+    #[serde(default)]
+    pub spent_minutes: Option<u32>,
+}
+
+/// Estimate and spent minutes summed over a set of tasks (an epic's
+/// subtasks); tasks without a value count as 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Effort {
+    pub estimate_minutes: u64,
+    pub spent_minutes: u64,
+}
+
+impl Effort {
+    pub fn of(tasks: &[Task]) -> Self {
+        tasks.iter().fold(Self::default(), |acc, t| Effort {
+            estimate_minutes: acc.estimate_minutes + u64::from(t.estimate_minutes.unwrap_or(0)),
+            spent_minutes: acc.spent_minutes + u64::from(t.spent_minutes.unwrap_or(0)),
+        })
+    }
+
+    /// Spent as a share of the estimate, rounded down; `None` without an
+    /// estimate. Over 100 once the estimate is blown.
+    pub fn burn_pct(&self) -> Option<u64> {
+        (self.estimate_minutes > 0).then(|| self.spent_minutes * 100 / self.estimate_minutes)
+    }
+
+    /// Spent minutes capped at the estimate, for a progress bar that stops
+    /// full once the estimate is blown.
+    pub fn spent_within_estimate(&self) -> u64 {
+        self.spent_minutes.min(self.estimate_minutes)
+    }
+}
+
+/// A reusable task shape saved by `tk template save`: a task's title,
+/// priority, tags, and description, and the same for each of its subtasks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Template {
+    pub title: String,
+    pub priority: u8,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub children: Vec<Template>,
+}
+
+impl Template {
+    /// Number of tasks the template creates, itself included.
+    pub fn task_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(Template::task_count)
+            .sum::<usize>()
+    }
+}
+
+/// Validate a template name: letters, digits, `_`, `-`, or `.`.
+pub fn validate_template_name(name: &str) -> Result<(), String> {
+    validate_saved_name("template", name)
+}
+
+/// Validate a saved view name, with the same rules as template names.
+pub fn validate_view_name(name: &str) -> Result<(), String> {
+    validate_saved_name("view", name)
+}
+
+/// Names stored under a config key prefix must stay a single, plain segment.
+fn validate_saved_name(kind: &str, name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid {kind} name: {name:?}. use letters, digits, '_', '-' or '.'"
+        ))
+    }
+}
+
+/// A named `tk list` filter saved by `tk view save`. Unset fields do not
+/// filter; `all` includes done tasks, as `tk list -a` does.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedView {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Exact assignee; an empty string matches unassigned tasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub all: bool,
+}
+
+impl SavedView {
+    /// Query string (with the leading `?`) that applies this view on the
+    /// web task list page and `GET /api/tasks`.
+    pub fn query_string(&self) -> String {
+        let priority = self.priority.map(|p| p.to_string());
+        let pairs: Vec<String> = [
+            ("status", self.status.as_deref()),
+            ("priority", priority.as_deref()),
+            ("tag", self.tag.as_deref()),
+            ("parent", self.parent.as_deref()),
+            ("assignee", self.assignee.as_deref()),
+            ("search", self.search.as_deref()),
+            ("all", self.all.then_some("true")),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some(format!("{key}={}", percent_encode(value?))))
+        .collect();
+        if pairs.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", pairs.join("&"))
+        }
+    }
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters.
+pub fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
+
+impl Task {
+    /// Whether the task is still open or in progress past its due date.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.status, Status::Open | Status::InProgress)
+            && self.due_at.is_some_and(|due| due < now)
+    }
+
+    /// Serialize the task with additive display hints: `priority_label`,
+    /// `status_label`, and `color`. Used when a client opts in via
+    /// `--labels` or `?labels=true`; the plain task JSON is unchanged.
+    pub fn to_labeled_json(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert(
+                "priority_label".to_string(),
+                priority_label(self.priority).into(),
+            );
+            obj.insert(
+                "status_label".to_string(),
+                self.status.as_str().replace('_', " ").into(),
+            );
+            obj.insert("color".to_string(), priority_color(self.priority).into());
+        }
+        value
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +447,43 @@ pub struct Comment {
     pub task_id: String,
     pub body: String,
     pub created_at: DateTime<Utc>,
+    /// ID of the comment on the same task this one replies to.
+    pub reply_to: Option<i64>,
+    /// When the body was last edited; `None` for a comment never edited.
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// One recorded change to a task field (`status`, `priority`, `assignee`,
+/// or `close_reason`), read back by `tk history`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    pub id: i64,
+    pub task_id: String,
+    pub field: String,
+    /// Value before the change; `None` when it was unset.
+    pub old_value: Option<String>,
+    /// Value after the change; `None` when it was cleared.
+    pub new_value: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A comment together with the title of its task, for activity feeds
+/// that span tasks.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentComment {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub task_title: String,
+}
+
+/// A search match with an HTML snippet of the text around it, matched words
+/// wrapped in `<mark>`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub task: Task,
+    pub snippet: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
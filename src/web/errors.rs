@@ -1,22 +1,55 @@
-use axum::http::StatusCode;
+use axum::Json;
+use axum::http::{StatusCode, header};
 use axum::response::{IntoResponse, Response};
+use serde_json::json;
+
+use crate::error::{Error, ErrorKind};
 
 /// Application error type for web handlers.
+///
+/// Rendered as `{"error": {"kind": "...", "message": "..."}}`, where `kind`
+/// is `not_found`, `validation`, `conflict`, `rate_limited`, or `internal`.
+#[derive(Debug)]
 pub enum AppError {
     NotFound(String),
     Validation(String),
     Conflict(String),
+    /// Too many writes; the client may retry after this many seconds.
+    RateLimited(u64),
     Internal(String),
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, message) = match self {
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            AppError::Validation(msg) => (StatusCode::UNPROCESSABLE_ENTITY, msg),
-            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg),
-            AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+        let (status, kind, message) = match self {
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "not_found", msg),
+            AppError::Validation(msg) => (StatusCode::UNPROCESSABLE_ENTITY, "validation", msg),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, "conflict", msg),
+            AppError::RateLimited(retry_after) => {
+                let body = json!({
+                    "error": { "kind": "rate_limited", "message": "rate limit exceeded" }
+                });
+                return (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(header::RETRY_AFTER, retry_after.to_string())],
+                    Json(body),
+                )
+                    .into_response();
+            }
+            AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, "internal", msg),
         };
-        (status, message).into_response()
+        let body = json!({ "error": { "kind": kind, "message": message } });
+        (status, Json(body)).into_response()
+    }
+}
+
+impl From<Error> for AppError {
+    fn from(e: Error) -> Self {
+        match e.kind {
+            ErrorKind::NotFound => AppError::NotFound(e.message),
+            ErrorKind::Validation => AppError::Validation(e.message),
+            ErrorKind::Conflict => AppError::Conflict(e.message),
+            ErrorKind::Generic => AppError::Internal(e.message),
+        }
     }
 }
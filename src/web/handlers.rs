@@ -1,14 +1,21 @@
 use askama::Template;
 use axum::Form;
 use axum::Json;
-use axum::extract::{Path, Query, State};
-use axum::http::{HeaderMap, StatusCode};
+use axum::extract::{Path, Query, RawQuery, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
 use axum::response::{Html, IntoResponse, Redirect, Response};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::atomic::Ordering;
 
-use crate::models::{Comment, Task, validate_close_reason};
+use crate::db::{ChildSort, TaskFilter};
+use crate::error::Error;
+use crate::models::{
+    Comment, Effort, PRIORITIES, RecentComment, SavedView, SearchHit, Status, TASK_FIELDS, Task,
+    VALID_CLOSE_REASONS, parse_date_bound, parse_due_date, parse_fields, percent_encode,
+    select_fields, validate_close_reason, validate_tags,
+};
 use crate::web::AppState;
 use crate::web::errors::AppError;
 
@@ -29,6 +36,80 @@ fn render_template<T: Template>(template: T) -> Response {
     }
 }
 
+/// Weak ETag for a list-like HTML page: a hash of the page name, the
+/// database's content version, and the raw query string (its active filters).
+async fn page_etag(state: &AppState, page: &str, query: Option<&str>) -> Result<String, AppError> {
+    let version = state
+        .with_db(|db| db.content_version())
+        .await?
+        .map_err(AppError::from)?;
+    let mut hasher = DefaultHasher::new();
+    (page, version, query.unwrap_or("")).hash(&mut hasher);
+    Ok(format!("W/\"{:016x}\"", hasher.finish()))
+}
+
+/// ETag for one task as served by `GET /api/tasks/:id`. Built from
+/// `updated_at`, which every edit bumps, plus the columns that change without
+/// bumping it (archiving, position renumbering, a deleted parent). The query
+/// string is left out so the tag also works as a PATCH `If-Match` whatever
+/// `?labels`/`?fields` view it was read through.
+fn task_etag(task: &Task) -> String {
+    let mut hasher = DefaultHasher::new();
+    (
+        &task.id,
+        task.updated_at,
+        task.archived_at,
+        task.position.to_bits(),
+        &task.parent_id,
+    )
+        .hash(&mut hasher);
+    format!("W/\"{:016x}\"", hasher.finish())
+}
+
+/// Whether the request's `If-None-Match` lists `etag` (weak comparison) or `*`.
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(|list| etag_listed(list, etag))
+}
+
+/// Whether a comma-separated `If-None-Match`/`If-Match` value lists `etag`
+/// (weak comparison) or is `*`.
+fn etag_listed(list: &str, etag: &str) -> bool {
+    let opaque = etag.trim_start_matches("W/");
+    list.split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == opaque)
+}
+
+/// Tag a successful response with `etag`; errors are left uncached.
+fn with_etag(mut response: Response, etag: &str) -> Response {
+    if response.status() == StatusCode::OK {
+        let value = HeaderValue::from_str(etag).expect("ETag is a quoted hex digest");
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    response
+}
+
+/// Compute the page's ETag and short-circuit with 304 when the client's copy
+/// is current. `Err` carries the response to return as-is (304 or an error).
+async fn check_page_etag(
+    state: &AppState,
+    headers: &HeaderMap,
+    page: &str,
+    query: Option<&str>,
+) -> Result<String, Response> {
+    let etag = page_etag(state, page, query)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if etag_matches(headers, &etag) {
+        return Err((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+    Ok(etag)
+}
+
 /// Index page handler — renders the home template.
 pub async fn index() -> Response {
     render_template(IndexTemplate)
@@ -46,6 +127,8 @@ pub struct CreateTaskBody {
     pub priority: Option<u8>,
     pub tags: Option<Vec<String>>,
     pub parent_id: Option<String>,
+    /// RFC3339, or `YYYY-MM-DD` for the end of that day (UTC).
+    pub due_at: Option<String>,
 }
 
 /// Request body for PATCH /api/tasks/:id.
@@ -58,6 +141,15 @@ pub struct UpdateTaskBody {
     pub assignee: Option<String>,
     pub tags: Option<Vec<String>>,
     pub notes: Option<String>,
+    /// Order within the task's priority; send the midpoint of the two
+    /// neighbours to drop a task between them.
+    pub position: Option<f64>,
+    /// Whole minutes; negative values are rejected with 422.
+    pub estimate_minutes: Option<i64>,
+    pub spent_minutes: Option<i64>,
+    /// The `updated_at` the client last read; the update is refused with 409
+    /// if the task has changed since.
+    pub expected_updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Request body for POST /api/tasks/:id/close.
@@ -65,6 +157,34 @@ pub struct UpdateTaskBody {
 pub struct CloseTaskBody {
     pub reason: Option<String>,
     pub comment: Option<String>,
+    /// Several closing comments, stored in order after `comment`.
+    pub comments: Option<Vec<String>>,
+    /// Also close the parent if this was its last open child.
+    #[serde(default)]
+    pub close_parent: bool,
+}
+
+/// Request body for POST /api/tasks/:id/status.
+#[derive(Debug, Deserialize)]
+pub struct MoveTaskBody {
+    pub status: String,
+    /// New `position`, set together with the status, as for
+    /// PATCH /api/tasks/:id.
+    pub rank: Option<f64>,
+}
+
+/// Request body for POST /api/board/move.
+#[derive(Debug, Deserialize)]
+pub struct BoardMoveBody {
+    pub id: String,
+    pub status: String,
+    /// Order among tasks of the same priority, as for PATCH /api/tasks/:id.
+    pub position: Option<f64>,
+    /// Board filters (as on GET /board) for the column returned to HTMX.
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub epic: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub priority: Option<String>,
 }
 
 /// Request body for POST /api/tasks/:id/deps.
@@ -77,6 +197,14 @@ pub struct AddDepBody {
 #[derive(Debug, Deserialize)]
 pub struct AddCommentBody {
     pub body: String,
+    /// ID of an earlier comment on the same task to reply to.
+    pub reply_to: Option<i64>,
+}
+
+/// Request body for PATCH /api/comments/:id.
+#[derive(Debug, Deserialize)]
+pub struct UpdateCommentBody {
+    pub body: String,
 }
 
 // ---------------------------------------------------------------------------
@@ -110,10 +238,55 @@ pub struct ListTasksQuery {
     #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
     pub tag: Option<String>,
     pub all: Option<bool>,
+    /// Include archived tasks (left out by default).
+    pub archived: Option<bool>,
     #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
     pub parent: Option<String>,
+    /// Exact assignee; `?assignee=` (empty) matches unassigned tasks.
+    pub assignee: Option<String>,
+    /// Only tasks blocked by this task ID (its dependents).
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub blocked_by: Option<String>,
+    /// Only tasks blocking this task ID (its blockers).
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub blocking: Option<String>,
     #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
     pub search: Option<String>,
+    /// Comma-separated task IDs; when set, returns exactly those tasks (missing IDs omitted).
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub ids: Option<String>,
+    /// Add `priority_label`, `status_label`, and `color` hints to each task.
+    pub labels: Option<bool>,
+    /// Keyset cursor: return only tasks that sort after this task ID.
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub after: Option<String>,
+    /// Maximum number of tasks to return.
+    pub limit: Option<u32>,
+    /// Only tasks closed with this reason (implies done tasks are included).
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub reason: Option<String>,
+    /// Only tasks created on or after this date (RFC3339 or YYYY-MM-DD, UTC).
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub created_after: Option<String>,
+    /// Only tasks created strictly before this date (a bare date means the
+    /// start of that day, UTC, so the day itself is excluded).
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub created_before: Option<String>,
+    /// Only tasks modified on or after this date (RFC3339 or YYYY-MM-DD, UTC).
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub updated_after: Option<String>,
+    /// Comma-separated keys to keep in each task object.
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub fields: Option<String>,
+}
+
+/// Query parameters for GET /api/tasks/:id.
+#[derive(Debug, Deserialize)]
+pub struct ShowTaskQuery {
+    pub labels: Option<bool>,
+    /// Comma-separated keys to keep in the task object.
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub fields: Option<String>,
 }
 
 /// Query parameters for GET /api/tasks/ready.
@@ -132,6 +305,10 @@ pub struct StatsResponse {
     pub by_status: Map<String, Value>,
     pub by_priority: Map<String, Value>,
     pub by_tag: Map<String, Value>,
+    /// Task counts per assignee; unassigned tasks count under `unassigned`.
+    pub by_assignee: Map<String, Value>,
+    /// Done task counts per close reason, every valid reason zero-filled.
+    pub by_reason: Map<String, Value>,
 }
 
 // ---------------------------------------------------------------------------
@@ -148,57 +325,68 @@ pub async fn api_create_task(
         .title
         .ok_or_else(|| AppError::Validation("title is required".to_string()))?;
 
-    let priority = body.priority.unwrap_or(2);
+    let priority = body.priority;
     let description = body.description.clone();
     let tags = body.tags.clone().unwrap_or_default();
+    validate_tags(&tags).map_err(AppError::Validation)?;
     let parent_id = body.parent_id.clone();
+    let due_at = body
+        .due_at
+        .as_deref()
+        .map(parse_due_date)
+        .transpose()
+        .map_err(AppError::Validation)?;
+
+    let result = state
+        .with_db(move |db| -> Result<Task, Error> {
+            db.with_transaction(|db| {
+                let priority = match priority {
+                    Some(p) => p,
+                    None => db.default_priority()?,
+                };
+                // Generate ID
+                let id = if let Some(ref pid) = parent_id {
+                    // Verify parent exists
+                    db.get_task(pid)?
+                        .ok_or_else(|| Error::not_found(format!("parent task not found: {pid}")))?;
+                    db.generate_child_id(pid)?
+                } else {
+                    db.generate_id()?
+                };
 
-    let db = state.db.clone();
-    let result = tokio::task::spawn_blocking(move || -> Result<Task, String> {
-        let db = db.lock().unwrap();
-
-        // Generate ID
-        let id = if let Some(ref pid) = parent_id {
-            // Verify parent exists
-            db.get_task(pid)?
-                .ok_or_else(|| format!("parent task not found: {pid}"))?;
-            db.generate_child_id(pid)?
-        } else {
-            db.generate_id()?
-        };
-
-        let now = chrono::Utc::now();
-        let task = Task {
-            id: id.clone(),
-            title: title.clone(),
-            description: description.clone(),
-            status: crate::models::Status::Open,
-            priority,
-            assignee: None,
-            parent_id: parent_id.clone(),
-            tags: tags.clone(),
-            created_at: now,
-            updated_at: now,
-            close_reason: None,
-            notes: None,
-        };
-
-        db.insert_task(&task)?;
-
-        // Auto-tag parent as epic when a child is created
-        if let Some(ref pid) = parent_id {
-            let mut parent_tags = db.get_task_tags(pid)?;
-            if !parent_tags.contains(&"epic".to_string()) {
-                parent_tags.push("epic".to_string());
-                db.update_tags(pid, &parent_tags)?;
-            }
-        }
+                let now = chrono::Utc::now();
+                let task = Task {
+                    id: id.clone(),
+                    title: title.clone(),
+                    description: description.clone(),
+                    status: crate::models::Status::Open,
+                    priority,
+                    assignee: None,
+                    parent_id: parent_id.clone(),
+                    tags: tags.clone(),
+                    created_at: now,
+                    updated_at: now,
+                    close_reason: None,
+                    notes: None,
+                    due_at,
+                    position: 0.0,
+                    archived_at: None,
+                    estimate_minutes: None,
+                    spent_minutes: None,
+                };
 
-        Ok(task)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+                db.insert_task(&task)?;
+
+                // Auto-tag parent as epic when a child is created (unless disabled in config)
+                if let Some(ref pid) = parent_id {
+                    db.auto_tag_epic(pid)?;
+                }
+                db.get_task(&task.id)?
+                    .ok_or_else(|| format!("task not found after insert: {}", task.id).into())
+            })
+        })
+        .await?
+        .map_err(AppError::from)?;
 
     Ok((StatusCode::CREATED, Json(result)))
 }
@@ -215,17 +403,6 @@ fn parse_tags(tag_param: Option<&str>) -> Vec<String> {
     }
 }
 
-/// Filter a task list to only tasks that have at least one of the given tags (OR semantics).
-fn filter_by_tags(tasks: Vec<Task>, tags: &[String]) -> Vec<Task> {
-    if tags.is_empty() {
-        return tasks;
-    }
-    tasks
-        .into_iter()
-        .filter(|t| tags.iter().any(|tag| t.tags.contains(tag)))
-        .collect()
-}
-
 /// Parse comma-separated priority values into a `Vec<u8>`.
 fn parse_priority_values(s: &Option<String>) -> Vec<u8> {
     match s.as_deref() {
@@ -256,68 +433,143 @@ pub async fn api_list_tasks(
     State(state): State<AppState>,
     Query(query): Query<ListTasksQuery>,
 ) -> Result<impl IntoResponse, AppError> {
+    let labels = query.labels.unwrap_or(false);
+    let fields = parse_fields_query(query.fields.as_deref())?;
+    if let Some(ids) = query.ids.as_deref() {
+        let ids: Vec<String> = ids
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        let tasks = state
+            .with_read_db(move |db| db.get_tasks_by_ids(&ids))
+            .await?
+            .map_err(AppError::from)?;
+        return Ok(Json(tasks_to_json(&tasks, labels, fields.as_deref())));
+    }
+
     let show_all = query.all.unwrap_or(false);
+    let include_archived = query.archived.unwrap_or(false);
     let status_values = parse_status_values(&query.status);
     let priority_values = parse_priority_values(&query.priority);
     let tag_param = query.tag.clone();
     let parent_filter = query.parent.clone();
+    let assignee_filter = query.assignee.clone();
     let search_filter = query.search.clone();
 
-    // Parse comma-separated tags for multi-tag OR filtering
+    // Comma-separated tags are OR-ed, matched in SQL through `task_tags`.
     let tags = parse_tags(tag_param.as_deref());
-    // For DB query: use a single tag when exactly one is selected (uses indexed LIKE);
-    // when multiple tags, skip DB tag filter and post-filter in Rust.
-    let db_tag_filter = if tags.len() == 1 {
-        tags.first().cloned()
-    } else {
+    let after = query.after.clone();
+    let limit = query.limit;
+    let reason = query.reason.clone();
+    let blocked_by = query.blocked_by.clone();
+    let blocking = query.blocking.clone();
+    if let Some(r) = reason.as_deref() {
+        validate_close_reason(r).map_err(AppError::Validation)?;
+    }
+    let created_after = query
+        .created_after
+        .as_deref()
+        .map(|v| parse_date_bound(v, false))
+        .transpose()
+        .map_err(AppError::Validation)?;
+    let created_before = query
+        .created_before
+        .as_deref()
+        .map(|v| parse_date_bound(v, false))
+        .transpose()
+        .map_err(AppError::Validation)?;
+    let updated_after = query
+        .updated_after
+        .as_deref()
+        .map(|v| parse_date_bound(v, false))
+        .transpose()
+        .map_err(AppError::Validation)?;
+    // Multi-value filters are applied in Rust after the query, so the limit
+    // can only be pushed down to SQL when there is nothing to post-filter.
+    let db_limit = if status_values.len() > 1 || priority_values.len() > 1 {
         None
+    } else {
+        limit
     };
-    let multi_tags = if tags.len() > 1 { tags } else { vec![] };
-
-    let db = state.db.clone();
-    let tasks = tokio::task::spawn_blocking(move || -> Result<Vec<Task>, String> {
-        let db = db.lock().unwrap();
-        // For single status/priority, pass directly to DB for efficiency.
-        // For multi-value, load without that filter then post-filter in Rust.
-        let (db_status, db_priority) = match (status_values.len(), priority_values.len()) {
-            (0 | 1, 0 | 1) => (
-                status_values.first().map(|s| s.as_str()),
-                priority_values.first().copied(),
-            ),
-            _ => (None, None),
-        };
-        let mut tasks = db.list_tasks(
-            show_all || !status_values.is_empty(),
-            db_status,
-            db_priority,
-            db_tag_filter.as_deref(),
-            parent_filter.as_deref(),
-            search_filter.as_deref(),
-        )?;
-        // Post-filter for multi-value OR semantics
-        if status_values.len() > 1 {
-            let status_strs: Vec<&str> = status_values.iter().map(|s| s.as_str()).collect();
-            tasks.retain(|t| {
-                let s = match t.status {
-                    crate::models::Status::Open => "open",
-                    crate::models::Status::InProgress => "in_progress",
-                    crate::models::Status::Done => "done",
-                    crate::models::Status::Blocked => "blocked",
-                };
-                status_strs.contains(&s)
-            });
-        }
-        if priority_values.len() > 1 {
-            tasks.retain(|t| priority_values.contains(&t.priority));
-        }
-        let tasks = filter_by_tags(tasks, &multi_tags);
-        Ok(tasks)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
 
-    Ok(Json(tasks))
+    let tasks = state
+        .with_read_db(move |db| -> Result<Vec<Task>, Error> {
+            // For single status/priority, pass directly to DB for efficiency.
+            // For multi-value, load without that filter then post-filter in Rust.
+            let (db_status, db_priority) = match (status_values.len(), priority_values.len()) {
+                (0 | 1, 0 | 1) => (
+                    status_values.first().map(|s| s.as_str()),
+                    priority_values.first().copied(),
+                ),
+                _ => (None, None),
+            };
+            let mut tasks = db.list_tasks(&TaskFilter {
+                include_done: show_all || !status_values.is_empty() || reason.is_some(),
+                include_archived,
+                status: db_status,
+                priority: db_priority,
+                tag: None,
+                any_tag: &tags,
+                parent: parent_filter.as_deref(),
+                assignee: assignee_filter.as_deref(),
+                blocked_by: blocked_by.as_deref(),
+                blocking: blocking.as_deref(),
+                search: search_filter.as_deref(),
+                close_reason: reason.as_deref(),
+                created_after,
+                created_before,
+                updated_after,
+                after: after.as_deref(),
+                limit: db_limit,
+            })?;
+            // Post-filter for multi-value OR semantics
+            if status_values.len() > 1 {
+                let status_strs: Vec<&str> = status_values.iter().map(|s| s.as_str()).collect();
+                tasks.retain(|t| {
+                    let s = match t.status {
+                        crate::models::Status::Open => "open",
+                        crate::models::Status::InProgress => "in_progress",
+                        crate::models::Status::Done => "done",
+                        crate::models::Status::Blocked => "blocked",
+                    };
+                    status_strs.contains(&s)
+                });
+            }
+            if priority_values.len() > 1 {
+                tasks.retain(|t| priority_values.contains(&t.priority));
+            }
+            if let Some(n) = limit {
+                tasks.truncate(n as usize);
+            }
+            Ok(tasks)
+        })
+        .await?
+        .map_err(AppError::from)?;
+
+    Ok(Json(tasks_to_json(&tasks, labels, fields.as_deref())))
+}
+
+/// Serialize tasks for the API, adding display labels when requested and
+/// keeping only `fields` when given.
+fn tasks_to_json(tasks: &[Task], labels: bool, fields: Option<&[String]>) -> Value {
+    let mut value = if labels {
+        Value::Array(tasks.iter().map(Task::to_labeled_json).collect())
+    } else {
+        serde_json::to_value(tasks).unwrap_or_default()
+    };
+    if let Some(fields) = fields {
+        select_fields(&mut value, fields);
+    }
+    value
+}
+
+/// Parse a `?fields=` list against the task keys (422 on unknown keys).
+fn parse_fields_query(fields: Option<&str>) -> Result<Option<Vec<String>>, AppError> {
+    fields
+        .map(|f| parse_fields(f, TASK_FIELDS))
+        .transpose()
+        .map_err(AppError::Validation)
 }
 
 /// GET /api/tasks/ready — Tasks with no open blockers (200).
@@ -326,14 +578,10 @@ pub async fn api_ready_tasks(
     Query(query): Query<ReadyTasksQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let limit = query.limit;
-    let db = state.db.clone();
-    let tasks = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.get_ready_tasks(limit)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+    let tasks = state
+        .with_read_db(move |db| db.get_ready_tasks(limit))
+        .await?
+        .map_err(AppError::from)?;
 
     Ok(Json(tasks))
 }
@@ -342,84 +590,142 @@ pub async fn api_ready_tasks(
 pub async fn api_blocked_tasks(
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let tasks = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.get_blocked_tasks()
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+    let tasks = state
+        .with_read_db(move |db| db.get_blocked_tasks())
+        .await?
+        .map_err(AppError::from)?;
 
     Ok(Json(tasks))
 }
 
-/// GET /api/tasks/:id — Show a task by ID (200 or 404).
+/// GET /api/tasks/:id — Show a task by ID (200, 304, or 404). The response
+/// carries an ETag; sending it back as `If-None-Match` gets a bodiless 304
+/// until the task changes.
 pub async fn api_show_task(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Path(id): Path<String>,
-) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let task = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.get_task(&id)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+    Query(query): Query<ShowTaskQuery>,
+) -> Result<Response, AppError> {
+    let fields = parse_fields_query(query.fields.as_deref())?;
+    let task = state
+        .with_read_db(move |db| db.get_task(&id))
+        .await?
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::NotFound("task not found".to_string()))?;
+
+    let etag = task_etag(&task);
+    if etag_matches(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
 
-    match task {
-        Some(t) => Ok(Json(t)),
-        None => Err(AppError::NotFound("task not found".to_string())),
+    let mut value = if query.labels.unwrap_or(false) {
+        task.to_labeled_json()
+    } else {
+        serde_json::to_value(task).unwrap_or_default()
+    };
+    if let Some(ref fields) = fields {
+        select_fields(&mut value, fields);
     }
+    Ok(with_etag(Json(value).into_response(), &etag))
 }
 
-/// PATCH /api/tasks/:id — Update task fields (200 or 404).
+/// PATCH /api/tasks/:id — Update task fields (200, 404, or 409).
+///
+/// Optimistic concurrency: with an `If-Match` header (the ETag from
+/// `GET /api/tasks/:id`) or an `expected_updated_at` field, the update is
+/// refused with 409 when the task has changed since the client read it.
 pub async fn api_update_task(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Path(id): Path<String>,
     Json(body): Json<UpdateTaskBody>,
 ) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let result = tokio::task::spawn_blocking(move || -> Result<Task, String> {
-        let db = db.lock().unwrap();
-
-        // Verify task exists
-        db.get_task(&id)?
-            .ok_or_else(|| format!("task not found: {id}"))?;
-
-        // Update tags separately if provided
-        if let Some(ref tags) = body.tags {
-            db.update_tags(&id, tags)?;
-        }
-
-        // Update remaining fields
-        db.update_task(
-            &id,
-            body.title.as_deref(),
-            body.priority,
-            body.status.as_deref(),
-            body.description.as_deref(),
-            body.assignee.as_deref(),
-            None,
-            body.notes.as_deref(),
-        )?;
+    let estimate_minutes = minutes_field(body.estimate_minutes, "estimate_minutes")?;
+    let spent_minutes = minutes_field(body.spent_minutes, "spent_minutes")?;
+    if let Some(ref tags) = body.tags {
+        validate_tags(tags).map_err(AppError::Validation)?;
+    }
+    let if_match = headers
+        .get(header::IF_MATCH)
+        .map(|v| {
+            v.to_str()
+                .map(str::to_string)
+                .map_err(|_| AppError::Validation("invalid If-Match header".to_string()))
+        })
+        .transpose()?;
+    let result = state
+        .with_db(move |db| -> Result<Task, Error> {
+            // Verify task exists
+            db.get_task(&id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+
+            // Tags and fields are written together, so an invalid field (e.g. a
+            // bad status) leaves the tags untouched too. The staleness checks
+            // run in the same transaction, under the writer lock, so nothing
+            // can slip in between check and write.
+            db.with_transaction(|db| {
+                if let Some(ref list) = if_match {
+                    let current = db
+                        .get_task(&id)?
+                        .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+                    if !etag_listed(list, &task_etag(&current)) {
+                        return Err(Error::conflict(format!(
+                            "task {id} changed since the If-Match ETag was read"
+                        )));
+                    }
+                }
+                if let Some(expected) = body.expected_updated_at {
+                    db.ensure_unchanged_since(&id, expected)?;
+                }
+                if let Some(ref tags) = body.tags {
+                    db.update_tags(&id, tags)?;
+                }
+                db.update_effort(&id, estimate_minutes, spent_minutes)?;
+                db.update_task(
+                    &id,
+                    body.title.as_deref(),
+                    body.priority,
+                    body.status.as_deref(),
+                    body.description.as_deref(),
+                    body.assignee.as_deref(),
+                    None,
+                    body.notes.as_deref(),
+                )?;
+                // After the priority change, which moves the task to the end
+                // of its new priority.
+                if let Some(position) = body.position {
+                    db.set_position(&id, position)?;
+                }
+                Ok(())
+            })?;
+
+            // Return the updated task
+            db.get_task(&id)?
+                .ok_or_else(|| format!("task not found after update: {id}").into())
+        })
+        .await??;
 
-        // Return the updated task
-        db.get_task(&id)?
-            .ok_or_else(|| format!("task not found after update: {id}"))
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?;
+    Ok(Json(result))
+}
 
-    match result {
-        Ok(task) => Ok(Json(task)),
-        Err(e) if e.contains("not found") => Err(AppError::NotFound(e)),
-        Err(e) => Err(AppError::Internal(e)),
-    }
+/// Check a minutes field from a request body: a whole number from 0 up.
+fn minutes_field(value: Option<i64>, field: &str) -> Result<Option<u32>, AppError> {
+    value
+        .map(|m| {
+            u32::try_from(m).map_err(|_| {
+                AppError::Validation(format!(
+                    "{field} must be a non-negative number of minutes, got {m}"
+                ))
+            })
+        })
+        .transpose()
 }
 
 /// POST /api/tasks/:id/close — Close a task (200, 404, or 422).
+///
+/// With `"close_parent": true`, the parent is closed too when this was its
+/// last open child, as the `auto_close_epic` config key does for every close.
 pub async fn api_close_task(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -432,82 +738,236 @@ pub async fn api_close_task(
     }
 
     let reason_owned = body.reason.clone();
-    let comment_owned = body.comment.clone();
+    let close_parent = body.close_parent;
+    // The single `comment` (if any) comes first, then each of `comments`.
+    let comments: Vec<String> = body
+        .comment
+        .into_iter()
+        .chain(body.comments.unwrap_or_default())
+        .collect();
+
+    let result = state
+        .with_db(move |db| -> Result<Task, Error> {
+            // Verify task exists
+            db.get_task(&id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+
+            // Close the task and record any closing comments
+            db.with_transaction(|db| {
+                db.close_task(&id, reason_owned.as_deref(), &comments)?;
+                if close_parent {
+                    db.close_parent_if_done(&id)?;
+                }
+                Ok(())
+            })?;
+
+            // Return the updated task
+            db.get_task(&id)?
+                .ok_or_else(|| format!("task not found after close: {id}").into())
+        })
+        .await??;
 
-    let db = state.db.clone();
-    let result = tokio::task::spawn_blocking(move || -> Result<Task, String> {
-        let db = db.lock().unwrap();
+    Ok(Json(result))
+}
 
-        // Verify task exists
-        db.get_task(&id)?
-            .ok_or_else(|| format!("task not found: {id}"))?;
+/// POST /api/tasks/:id/status — Move a task to another board column
+/// (200, 404, 409, or 422).
+///
+/// Purpose-built for drag-and-drop: the status changes, and with `rank` the
+/// position too, in one transaction. Moving to `done` goes through
+/// `close_task` (so `auto_close_epic` applies) and, like `tk close`, is
+/// refused with 409 while the task has open subtasks. Moving out of `done`
+/// reopens the task and clears its close reason.
+pub async fn api_move_task(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<MoveTaskBody>,
+) -> Result<impl IntoResponse, AppError> {
+    let status = body
+        .status
+        .parse::<Status>()
+        .map_err(AppError::Validation)?;
+    if body.rank.is_some_and(|r| !r.is_finite()) {
+        return Err(AppError::Validation(
+            "rank must be a finite number".to_string(),
+        ));
+    }
 
-        // Close the task
-        db.close_task(&id, reason_owned.as_deref())?;
+    let task = state
+        .with_db(move |db| -> Result<Task, Error> {
+            db.with_transaction(|db| {
+                move_task_status(db, &id, status)?;
+                if let Some(rank) = body.rank {
+                    db.set_position(&id, rank)?;
+                }
+                Ok(())
+            })?;
+            db.get_task(&id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {id}")))
+        })
+        .await??;
 
-        // Add comment if provided
-        if let Some(ref comment) = comment_owned {
-            db.add_comment(&id, comment)?;
-        }
+    Ok(Json(task))
+}
 
-        // Return the updated task
-        db.get_task(&id)?
-            .ok_or_else(|| format!("task not found after close: {id}"))
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?;
+/// Put a task in the board column for `status`. Moving to `done` goes
+/// through `close_task` and is refused while the task has open subtasks;
+/// any other status is a plain update. Moving to the current status is a
+/// no-op.
+fn move_task_status(db: &crate::db::Database, id: &str, status: Status) -> Result<(), Error> {
+    let task = db
+        .get_task(id)?
+        .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+    if task.status == status {
+        return Ok(());
+    }
+    if status != Status::Done {
+        return db.update_task(
+            id,
+            None,
+            None,
+            Some(status.as_str()),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+    let open_children = db
+        .get_children(id)?
+        .iter()
+        .filter(|c| c.status != Status::Done)
+        .count();
+    if open_children > 0 {
+        return Err(Error::conflict(format!(
+            "task {id} has {open_children} open subtask(s)"
+        )));
+    }
+    db.close_task(id, None, &[])
+}
 
-    match result {
-        Ok(task) => Ok(Json(task)),
-        Err(e) if e.contains("not found") => Err(AppError::NotFound(e)),
-        Err(e) => Err(AppError::Internal(e)),
+/// Response body for POST /api/board/move.
+#[derive(Debug, Serialize)]
+pub struct BoardMoveResponse {
+    pub task: Task,
+    /// Non-fatal notes about the move, e.g. starting a task that still has
+    /// open blockers.
+    pub warnings: Vec<String>,
+}
+
+/// POST /api/board/move — Move a board card to a column and, optionally, a
+/// position within it (200, 404, 409, or 422).
+///
+/// Status and position are written in one transaction. Moving a task with
+/// open blockers to `in_progress` is allowed but reported in `warnings`.
+/// HTMX requests get the re-rendered target column instead of JSON, with
+/// any warnings in an `HX-Trigger: {"board-warning": ...}` header.
+pub async fn api_board_move(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<BoardMoveBody>,
+) -> Result<Response, AppError> {
+    let status = body
+        .status
+        .parse::<Status>()
+        .map_err(AppError::Validation)?;
+    if body.position.is_some_and(|p| !p.is_finite()) {
+        return Err(AppError::Validation(
+            "position must be a finite number".to_string(),
+        ));
+    }
+    let htmx = headers.contains_key("hx-request");
+
+    let (task, warnings, column) = state
+        .with_db(
+            move |db| -> Result<(Task, Vec<String>, Option<BoardColumn>), Error> {
+                let id = body.id.as_str();
+                db.with_transaction(|db| {
+                    move_task_status(db, id, status)?;
+                    if let Some(position) = body.position {
+                        db.set_position(id, position)?;
+                    }
+                    Ok(())
+                })?;
+                let task = db
+                    .get_task(id)?
+                    .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+
+                let mut warnings = Vec::new();
+                if status == Status::InProgress {
+                    let blocker_ids: Vec<String> = db
+                        .get_blockers(id)?
+                        .into_iter()
+                        .map(|d| d.parent_id)
+                        .collect();
+                    let open: Vec<String> = db
+                        .get_tasks_by_ids(&blocker_ids)?
+                        .into_iter()
+                        .filter(|b| b.status != Status::Done)
+                        .map(|b| b.id)
+                        .collect();
+                    if !open.is_empty() {
+                        warnings.push(format!(
+                            "task {id} is in progress but still blocked by {}",
+                            open.join(", ")
+                        ));
+                    }
+                }
+
+                let column = if htmx {
+                    let epic_values = parse_status_values(&body.epic);
+                    let priority_values = parse_priority_values(&body.priority);
+                    board_columns(db, &epic_values, &priority_values)?
+                        .into_iter()
+                        .find(|c| c.status == status.as_str())
+                } else {
+                    None
+                };
+                Ok((task, warnings, column))
+            },
+        )
+        .await??;
+
+    let Some(column) = column else {
+        return Ok(Json(BoardMoveResponse { task, warnings }).into_response());
+    };
+    let mut response = render_template(BoardColumnTemplate { column });
+    if let Some(warning) = warnings.first() {
+        let trigger = serde_json::json!({ "board-warning": warning }).to_string();
+        if let Ok(value) = HeaderValue::from_str(&trigger) {
+            response
+                .headers_mut()
+                .insert(axum_htmx::headers::HX_TRIGGER, value);
+        }
     }
+    Ok(response)
 }
 
-/// POST /api/tasks/:id/deps — Add a dependency (201 or 409).
+/// POST /api/tasks/:id/deps — Add a dependency (201, 404, or 409).
 pub async fn api_add_dep(
     State(state): State<AppState>,
     Path(id): Path<String>,
     Json(body): Json<AddDepBody>,
 ) -> Result<impl IntoResponse, AppError> {
     let parent_id = body.parent_id.clone();
-    let db = state.db.clone();
 
-    let result = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.add_dependency(&id, &parent_id)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?;
+    state
+        .with_db(move |db| db.add_dependency(&id, &parent_id))
+        .await??;
 
-    match result {
-        Ok(()) => Ok(StatusCode::CREATED),
-        Err(e) if e.contains("circular") || e.contains("already exists") => {
-            Err(AppError::Conflict(e))
-        }
-        Err(e) => Err(AppError::Internal(e)),
-    }
+    Ok(StatusCode::CREATED)
 }
 
-/// DELETE /api/tasks/:child_id/deps/:parent_id — Remove a dependency (204).
+/// DELETE /api/tasks/:child_id/deps/:parent_id — Remove a dependency (204 or 404).
 pub async fn api_remove_dep(
     State(state): State<AppState>,
     Path((child_id, parent_id)): Path<(String, String)>,
 ) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-
-    let result = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.remove_dependency(&child_id, &parent_id)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?;
+    state
+        .with_db(move |db| db.remove_dependency(&child_id, &parent_id))
+        .await??;
 
-    match result {
-        Ok(()) => Ok(StatusCode::NO_CONTENT),
-        Err(e) if e.contains("not found") => Err(AppError::NotFound(e)),
-        Err(e) => Err(AppError::Internal(e)),
-    }
+    Ok(StatusCode::NO_CONTENT)
 }
 
 /// POST /api/tasks/:id/comments — Add a comment (201).
@@ -517,90 +977,300 @@ pub async fn api_add_comment(
     Json(body): Json<AddCommentBody>,
 ) -> Result<impl IntoResponse, AppError> {
     let comment_body = body.body.clone();
-    let db = state.db.clone();
+    let reply_to = body.reply_to;
 
-    let comment = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.add_comment(&id, &comment_body)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+    let comment = state
+        .with_db(move |db| match reply_to {
+            Some(parent) => db.add_reply(&id, &comment_body, parent),
+            None => db.add_comment(&id, &comment_body),
+        })
+        .await??;
 
     Ok((StatusCode::CREATED, Json(comment)))
 }
 
+/// PATCH /api/comments/:id — Replace a comment's body and set its
+/// `updated_at` (200, 404 if absent, 422 if the body is blank).
+pub async fn api_update_comment(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Json(body): Json<UpdateCommentBody>,
+) -> Result<impl IntoResponse, AppError> {
+    let comment = state
+        .with_db(move |db| db.update_comment(id, &body.body))
+        .await??;
+
+    Ok(Json(comment))
+}
+
+/// DELETE /api/comments/:id — Delete a comment; replies to it become
+/// top-level (204, 404 if absent).
+pub async fn api_delete_comment(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    state.with_db(move |db| db.delete_comment(id)).await??;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// GET /api/tasks/:id/comments — List comments on a task (200).
 pub async fn api_list_comments(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let comments: Vec<Comment> = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.get_comments(&id)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+    let comments: Vec<Comment> = state
+        .with_read_db(move |db| db.get_comments(&id))
+        .await?
+        .map_err(AppError::from)?;
+
+    Ok(Json(comments))
+}
+
+/// GET /api/tasks/:id/history — Recorded status, priority, assignee, and
+/// close_reason changes, oldest first (200, 404 for an unknown task).
+pub async fn api_task_history(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let activity = state
+        .with_read_db(move |db| {
+            db.get_task(&id)?
+                .ok_or_else(|| Error::not_found(format!("task not found: {id}")))?;
+            db.get_activity(&id)
+        })
+        .await??;
+
+    Ok(Json(activity))
+}
+
+/// Query parameters for GET /api/comments/recent.
+#[derive(Debug, Deserialize)]
+pub struct RecentCommentsQuery {
+    pub limit: Option<u32>,
+}
+
+/// GET /api/comments/recent — Most recent comments across all tasks, newest
+/// first, each with `task_title` (200). `?limit=` defaults to 20.
+pub async fn api_recent_comments(
+    State(state): State<AppState>,
+    Query(query): Query<RecentCommentsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let limit = query.limit.unwrap_or(20);
+    let comments: Vec<RecentComment> = state
+        .with_read_db(move |db| db.recent_comments(limit))
+        .await?
+        .map_err(AppError::from)?;
 
     Ok(Json(comments))
 }
 
-/// GET /api/tasks/:id/children — List subtasks (200).
+/// Query parameters for GET /api/search.
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+    pub limit: Option<usize>,
+}
+
+/// Response body for GET /api/search.
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    /// Number of matching tasks before `limit` was applied.
+    pub total: usize,
+    pub tasks: Vec<SearchHit>,
+}
+
+/// GET /api/search?q= — Tasks matching `q` (as `tk search`), best first, each
+/// with a `snippet` (200). `?limit=` defaults to 20. A blank `q` matches
+/// nothing.
+pub async fn api_search(
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let q = query.q.trim().to_string();
+    if q.is_empty() {
+        return Ok(Json(SearchResponse {
+            total: 0,
+            tasks: Vec::new(),
+        }));
+    }
+    let limit = query.limit.unwrap_or(20);
+    let words = q.clone();
+    let tasks = state
+        .with_read_db(move |db| db.search_tasks(&words))
+        .await?
+        .map_err(AppError::from)?;
+
+    let total = tasks.len();
+    let tasks = tasks
+        .into_iter()
+        .take(limit)
+        .map(|task| {
+            let snippet = search_snippet(&task, &q);
+            SearchHit { task, snippet }
+        })
+        .collect();
+    Ok(Json(SearchResponse { total, tasks }))
+}
+
+/// Characters of context kept on each side of the first match in a snippet.
+const SNIPPET_CONTEXT: usize = 40;
+
+/// HTML snippet of the title, or else the description, around the first word
+/// of `query` it contains. Every query word in the snippet is wrapped in
+/// `<mark>`, the rest is escaped, and `…` marks trimmed text.
+fn search_snippet(task: &Task, query: &str) -> String {
+    let words: Vec<Vec<char>> = query
+        .split_whitespace()
+        .map(|w| w.chars().collect())
+        .collect();
+    let (text, first) = std::iter::once(task.title.as_str())
+        .chain(task.description.as_deref())
+        .map(|s| s.chars().collect::<Vec<char>>())
+        .find_map(|text| {
+            let first = (0..text.len()).find(|&i| match_len(&text, i, &words) > 0)?;
+            Some((text, first))
+        })
+        .unwrap_or_else(|| (task.title.chars().collect(), 0));
+
+    let start = first.saturating_sub(SNIPPET_CONTEXT);
+    let end = (first + SNIPPET_CONTEXT).min(text.len());
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    let mut i = start;
+    while i < end {
+        let len = match_len(&text, i, &words);
+        if len == 0 {
+            push_escaped(&mut out, text[i]);
+            i += 1;
+            continue;
+        }
+        out.push_str("<mark>");
+        for &c in &text[i..i + len] {
+            push_escaped(&mut out, c);
+        }
+        out.push_str("</mark>");
+        i += len;
+    }
+    if i < text.len() {
+        out.push('…');
+    }
+    out
+}
+
+/// Length of the longest of `words` found at `text[at..]`, ignoring case; 0
+/// when none is.
+fn match_len(text: &[char], at: usize, words: &[Vec<char>]) -> usize {
+    words
+        .iter()
+        .filter(|w| {
+            text.len() - at >= w.len()
+                && text[at..at + w.len()]
+                    .iter()
+                    .zip(w.iter())
+                    .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+        })
+        .map(|w| w.len())
+        .max()
+        .unwrap_or(0)
+}
+
+fn push_escaped(out: &mut String, c: char) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        _ => out.push(c),
+    }
+}
+
+/// Query parameters for GET /api/tasks/:id/children.
+#[derive(Debug, Deserialize)]
+pub struct ChildrenQuery {
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub status: Option<String>,
+    pub priority: Option<u8>,
+    /// `index` (default: `.N` suffix order), `priority` (then manual position,
+    /// then creation time), or `created`.
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub sort: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// GET /api/tasks/:id/children — List subtasks (200), optionally filtered by
+/// `?status=` and `?priority=`, ordered by `?sort=`, and capped by `?limit=`.
 pub async fn api_children(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    Query(query): Query<ChildrenQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let tasks: Vec<Task> = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.get_children(&id)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+    let sort = query
+        .sort
+        .as_deref()
+        .map(str::parse::<ChildSort>)
+        .transpose()
+        .map_err(AppError::Validation)?
+        .unwrap_or_default();
+    let status = query
+        .status
+        .as_deref()
+        .map(str::parse::<Status>)
+        .transpose()
+        .map_err(AppError::Validation)?;
+    let tasks: Vec<Task> = state
+        .with_read_db(move |db| {
+            db.get_children_filtered(
+                &id,
+                status.map(|s| s.as_str()),
+                query.priority,
+                sort,
+                query.limit,
+            )
+        })
+        .await?
+        .map_err(AppError::from)?;
 
     Ok(Json(tasks))
 }
 
-/// GET /api/tasks/:id/blockers — List blockers for a task as full Task objects (200).
+/// GET /api/tasks/:id/blockers — Tasks that `:id` is blocked by, as full Task
+/// objects (200). `:id` is the dependency's `child_id`; each returned task is a
+/// `parent_id`. Blockers are listed whatever their status.
 pub async fn api_blockers(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let tasks: Vec<Task> = tokio::task::spawn_blocking(move || -> Result<Vec<Task>, String> {
-        let db = db.lock().unwrap();
-        let deps = db.get_blockers(&id)?;
-        let mut tasks = Vec::with_capacity(deps.len());
-        for dep in deps {
-            if let Some(t) = db.get_task(&dep.parent_id)? {
-                tasks.push(t);
-            }
-        }
-        Ok(tasks)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+    let tasks: Vec<Task> = state
+        .with_read_db(move |db| -> Result<Vec<Task>, Error> {
+            let ids: Vec<String> = db
+                .get_blockers(&id)?
+                .into_iter()
+                .map(|dep| dep.parent_id)
+                .collect();
+            db.get_tasks_by_ids(&ids)
+        })
+        .await?
+        .map_err(AppError::from)?;
 
     Ok(Json(tasks))
 }
 
-/// GET /api/tasks/:id/dependents — List tasks that depend on this task (200).
+/// GET /api/tasks/:id/dependents — Tasks that are blocked by `:id` (200).
+/// `:id` is the dependency's `parent_id`; each returned task is a `child_id`.
+/// The inverse of `/blockers`.
 pub async fn api_dependents(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let tasks: Vec<Task> = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.get_dependents(&id)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+    let tasks: Vec<Task> = state
+        .with_read_db(move |db| db.get_dependents(&id))
+        .await?
+        .map_err(AppError::from)?;
 
     Ok(Json(tasks))
 }
@@ -611,15 +1281,21 @@ pub struct EpicProgress {
     pub task: Task,
     pub children_total: usize,
     pub children_done: usize,
+    /// Subtask estimates summed, in minutes.
+    pub estimate_minutes: u64,
+    /// Subtask spent time summed, in minutes.
+    pub spent_minutes: u64,
 }
 
 /// GET /api/epics — List epics with child completion progress (200).
 pub async fn api_epics(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let result: Vec<EpicProgress> =
-        tokio::task::spawn_blocking(move || -> Result<Vec<EpicProgress>, String> {
-            let db = db.lock().unwrap();
-            let epics = db.list_tasks(true, None, None, Some("epic"), None, None)?;
+    let result: Vec<EpicProgress> = state
+        .with_read_db(move |db| -> Result<Vec<EpicProgress>, Error> {
+            let epics = db.list_tasks(&TaskFilter {
+                include_done: true,
+                tag: Some("epic"),
+                ..Default::default()
+            })?;
             let mut out = Vec::with_capacity(epics.len());
             for epic in epics {
                 let children = db.get_children(&epic.id)?;
@@ -628,74 +1304,144 @@ pub async fn api_epics(State(state): State<AppState>) -> Result<impl IntoRespons
                     .iter()
                     .filter(|c| matches!(c.status, crate::models::Status::Done))
                     .count();
+                let effort = Effort::of(&children);
                 out.push(EpicProgress {
                     task: epic,
                     children_total,
                     children_done,
+                    estimate_minutes: effort.estimate_minutes,
+                    spent_minutes: effort.spent_minutes,
                 });
             }
             Ok(out)
         })
-        .await
-        .map_err(|e| AppError::Internal(e.to_string()))?
-        .map_err(AppError::Internal)?;
+        .await?
+        .map_err(AppError::from)?;
 
     Ok(Json(result))
 }
 
+/// Build the stats payload shared by /api/stats and /api/prime.
+///
+/// Empty-state contract: `by_status`, `by_priority`, and `by_reason` always
+/// contain every canonical key (zero-filled); `by_tag` only lists tags in use
+/// and may be `{}`.
+fn build_stats(db: &crate::db::Database) -> Result<StatsResponse, Error> {
+    let snapshot = db.stats_snapshot()?;
+    let mut by_status: Map<String, Value> = Status::ALL
+        .iter()
+        .map(|s| (s.as_str().to_string(), Value::Number(0.into())))
+        .collect();
+    for (k, v) in snapshot.by_status {
+        by_status.insert(k, Value::Number(v.into()));
+    }
+
+    let mut by_priority: Map<String, Value> = PRIORITIES
+        .iter()
+        .map(|p| (p.to_string(), Value::Number(0.into())))
+        .collect();
+    for (k, v) in snapshot.by_priority {
+        by_priority.insert(k.to_string(), Value::Number(v.into()));
+    }
+
+    let by_tag: Map<String, Value> = snapshot
+        .by_tag
+        .into_iter()
+        .map(|(k, v)| (k, Value::Number(v.into())))
+        .collect();
+
+    let by_assignee: Map<String, Value> = snapshot
+        .by_assignee
+        .into_iter()
+        .map(|(k, v)| (k, Value::Number(v.into())))
+        .collect();
+
+    let mut by_reason: Map<String, Value> = VALID_CLOSE_REASONS
+        .iter()
+        .map(|r| (r.to_string(), Value::Number(0.into())))
+        .collect();
+    for (k, v) in snapshot.by_reason {
+        by_reason.insert(k, Value::Number(v.into()));
+    }
+
+    Ok(StatsResponse {
+        by_status,
+        by_priority,
+        by_tag,
+        by_assignee,
+        by_reason,
+    })
+}
+
 /// Response body for GET /api/prime — AI context output.
 #[derive(Debug, Serialize)]
 pub struct PrimeResponse {
     pub stats: StatsResponse,
     pub in_progress: Vec<Task>,
     pub ready: Vec<Task>,
+    pub recent_comments: Vec<RecentComment>,
 }
 
 /// GET /api/prime — AI context: stats + in-progress tasks + ready queue (200).
 pub async fn api_prime(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let result = tokio::task::spawn_blocking(move || -> Result<PrimeResponse, String> {
-        let db = db.lock().unwrap();
-
-        let by_status_vec = db.task_count_by_status()?;
-        let by_priority_vec = db.task_count_by_priority()?;
-        let by_tag_vec = db.task_count_by_tag()?;
-
-        let by_status: Map<String, Value> = by_status_vec
-            .into_iter()
-            .map(|(k, v)| (k, Value::Number(v.into())))
-            .collect();
-
-        let by_priority: Map<String, Value> = by_priority_vec
-            .into_iter()
-            .map(|(k, v)| (k.to_string(), Value::Number(v.into())))
-            .collect();
-
-        let by_tag: Map<String, Value> = by_tag_vec
-            .into_iter()
-            .map(|(k, v)| (k, Value::Number(v.into())))
-            .collect();
+    let result = state
+        .with_read_db(move |db| -> Result<PrimeResponse, Error> {
+            let stats = build_stats(db)?;
+
+            let in_progress = db.list_tasks(&TaskFilter {
+                status: Some("in_progress"),
+                ..Default::default()
+            })?;
+            let ready = db.get_ready_tasks(Some(5))?;
+            let recent_comments = db.recent_comments(5)?;
+
+            Ok(PrimeResponse {
+                stats,
+                in_progress,
+                ready,
+                recent_comments,
+            })
+        })
+        .await?
+        .map_err(AppError::from)?;
 
-        let stats = StatsResponse {
-            by_status,
-            by_priority,
-            by_tag,
-        };
+    Ok(Json(result))
+}
 
-        let in_progress = db.list_tasks(false, Some("in_progress"), None, None, None, None)?;
-        let ready = db.get_ready_tasks(Some(5))?;
+/// Response body for GET /api/meta — server and database metadata.
+#[derive(Debug, Serialize)]
+pub struct MetaResponse {
+    pub version: &'static str,
+    pub schema_version: i32,
+    /// Database file name only; the full path is deliberately omitted.
+    pub db_file: Option<String>,
+    pub task_count: i64,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub uptime_secs: f64,
+}
 
-        Ok(PrimeResponse {
-            stats,
-            in_progress,
-            ready,
+/// GET /api/meta — Crate version, schema version, task count, and uptime (200).
+///
+/// Intended for dashboards and debugging; unlike a health check it does not
+/// gate traffic, it only reports what is running.
+pub async fn api_meta(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let (schema_version, task_count) = state
+        .with_read_db(move |db| -> Result<(i32, i64), Error> {
+            let schema_version = db.schema_version()?;
+            let task_count: i64 = db.task_count_by_status()?.iter().map(|(_, c)| c).sum();
+            Ok((schema_version, task_count))
         })
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
-
-    Ok(Json(result))
+        .await?
+        .map_err(AppError::from)?;
+
+    Ok(Json(MetaResponse {
+        version: env!("CARGO_PKG_VERSION"),
+        schema_version,
+        db_file: state.db_file.clone(),
+        task_count,
+        started_at: state.started_at,
+        uptime_secs: state.started.elapsed().as_secs_f64(),
+    }))
 }
 
 /// GET /api/poll — Lightweight change-detection endpoint for HTMX polling.
@@ -705,12 +1451,7 @@ pub async fn api_prime(State(state): State<AppState>) -> Result<impl IntoRespons
 /// - `304 Not Modified` when nothing has changed (HTMX treats this as no-swap)
 /// - `200 OK` with `HX-Trigger: data-changed` header when data has changed
 pub async fn api_poll(State(state): State<AppState>) -> Response {
-    let db = state.db.clone();
-    let version = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.data_version()
-    })
-    .await;
+    let version = state.with_db(move |db| db.data_version()).await;
 
     let current = match version {
         Ok(Ok(v)) => v,
@@ -763,18 +1504,18 @@ impl TaskRow {
     }
 }
 
-/// Fetch a map of task_id -> Task for a set of IDs (used to batch-load parent epics).
+/// Fetch a map of task_id -> Task for a set of IDs in a single query (used to
+/// batch-load parent epics).
 fn fetch_parent_map(
     db: &crate::db::Database,
     ids: impl Iterator<Item = String>,
-) -> Result<std::collections::HashMap<String, Task>, String> {
-    let mut map = std::collections::HashMap::new();
-    for id in ids {
-        if let Some(t) = db.get_task(&id)? {
-            map.insert(id, t);
-        }
-    }
-    Ok(map)
+) -> Result<std::collections::HashMap<String, Task>, Error> {
+    let ids: Vec<String> = ids.collect();
+    Ok(db
+        .get_tasks_by_ids(&ids)?
+        .into_iter()
+        .map(|t| (t.id.clone(), t))
+        .collect())
 }
 
 /// Template for the task list page at GET /tasks.
@@ -791,8 +1532,10 @@ struct TaskListTemplate {
     /// All available tags for the dropdown.
     all_tags: Vec<String>,
     search_filter: Option<String>,
-    /// True when any filter (status, priority, tag, search) is active.
+    /// True when any filter (status, priority, tag, parent, assignee, search) is active.
     has_filters: bool,
+    /// Saved views, rendered as quick links above the filters.
+    saved_views: Vec<ViewResponse>,
     /// Pre-built query string for HTMX polling (preserves current filters).
     poll_query: String,
 }
@@ -825,10 +1568,10 @@ struct TaskDetailFragmentTemplate {
 #[derive(Template)]
 #[template(path = "board.html")]
 struct BoardTemplate {
-    open_tasks: Vec<TaskRow>,
-    in_progress_tasks: Vec<TaskRow>,
-    blocked_tasks: Vec<TaskRow>,
-    done_tasks: Vec<TaskRow>,
+    /// Open, In Progress, Blocked, and Done, in that order.
+    columns: Vec<BoardColumn>,
+    /// True when every column is empty.
+    board_empty: bool,
     /// All epics available in the dropdown filter.
     epics: Vec<Task>,
     /// Currently selected epic ID filter (empty string = none).
@@ -839,6 +1582,21 @@ struct BoardTemplate {
     poll_query: String,
 }
 
+/// One kanban column: the status it stands for, its heading, and its cards.
+struct BoardColumn {
+    status: &'static str,
+    heading: &'static str,
+    rows: Vec<TaskRow>,
+}
+
+/// Template for a single board column, returned by POST /api/board/move to
+/// HTMX clients so the target column can be swapped in place.
+#[derive(Template)]
+#[template(path = "board_column.html")]
+struct BoardColumnTemplate {
+    column: BoardColumn,
+}
+
 /// Query parameters for GET /board.
 #[derive(Debug, Deserialize)]
 pub struct BoardQuery {
@@ -872,6 +1630,8 @@ struct EpicDetailTemplate {
     children: Vec<Task>,
     children_done: usize,
     children_total: usize,
+    /// Subtask estimate and spent minutes for the burn indicator.
+    effort: Effort,
     /// Pre-computed per-status counts for board view column headers.
     board_open_count: usize,
     board_in_progress_count: usize,
@@ -890,26 +1650,30 @@ pub struct EpicDetailQuery {
 /// Template for the create task form at GET /tasks/new.
 #[derive(Template)]
 #[template(path = "task_new.html")]
-struct TaskNewTemplate;
+struct TaskNewTemplate {
+    /// Preselected priority: the `default_priority` config key.
+    default_priority: u8,
+}
 
 /// Build a query string from current filter params for HTMX polling.
-fn build_poll_query(
-    status: &Option<String>,
-    priority: &Option<String>,
-    tag: &Option<String>,
-    search: &Option<String>,
-) -> String {
+fn build_poll_query(params: &ListTasksQuery) -> String {
     let mut parts = Vec::new();
-    if let Some(s) = status {
+    if let Some(s) = &params.status {
         parts.push(format!("status={s}"));
     }
-    if let Some(p) = priority {
+    if let Some(p) = &params.priority {
         parts.push(format!("priority={p}"));
     }
-    if let Some(t) = tag {
+    if let Some(t) = &params.tag {
         parts.push(format!("tag={t}"));
     }
-    if let Some(q) = search {
+    if let Some(p) = &params.parent {
+        parts.push(format!("parent={}", percent_encode(p)));
+    }
+    if let Some(a) = &params.assignee {
+        parts.push(format!("assignee={}", percent_encode(a)));
+    }
+    if let Some(q) = &params.search {
         parts.push(format!("search={q}"));
     }
     if parts.is_empty() {
@@ -922,35 +1686,35 @@ fn build_poll_query(
 /// GET /tasks — Task list page with optional filter query params.
 pub async fn task_list(
     State(state): State<AppState>,
+    headers: HeaderMap,
+    RawQuery(raw_query): RawQuery,
     Query(params): Query<ListTasksQuery>,
 ) -> Response {
+    let etag = match check_page_etag(&state, &headers, "tasks", raw_query.as_deref()).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
     let has_filter = params.status.is_some()
         || params.priority.is_some()
         || params.tag.is_some()
+        || params.parent.is_some()
+        || params.assignee.is_some()
         || params.search.as_deref().is_some_and(|s| !s.is_empty());
     let status_values = parse_status_values(&params.status);
     let priority_values = parse_priority_values(&params.priority);
     let show_all = has_filter || params.all.unwrap_or(false);
     let tag_param = params.tag.clone();
+    let parent_filter = params.parent.clone();
+    let assignee_filter = params.assignee.clone();
     let search_filter = params.search.clone();
 
-    // Parse comma-separated tags for multi-tag OR filtering
+    // Comma-separated tags are OR-ed, matched in SQL through `task_tags`.
     let selected_tags = parse_tags(tag_param.as_deref());
-    let db_tag_filter = if selected_tags.len() == 1 {
-        selected_tags.first().cloned()
-    } else {
-        None
-    };
-    let multi_tags = if selected_tags.len() > 1 {
-        selected_tags.clone()
-    } else {
-        vec![]
-    };
+    let db_tags = selected_tags.clone();
 
-    let db = state.db.clone();
-    let (task_rows, all_tags) =
-        tokio::task::spawn_blocking(move || -> Result<(Vec<TaskRow>, Vec<String>), String> {
-            let db = db.lock().unwrap();
+    type PageData = (Vec<TaskRow>, Vec<String>, Vec<ViewResponse>);
+    let (task_rows, all_tags, saved_views) = state
+        .with_read_db(move |db| -> Result<PageData, Error> {
             // For single status/priority, pass directly to DB for efficiency.
             // For multi-value, load without that filter then post-filter in Rust.
             let (db_status, db_priority) = match (status_values.len(), priority_values.len()) {
@@ -960,14 +1724,16 @@ pub async fn task_list(
                 ),
                 _ => (None, None),
             };
-            let mut tasks = db.list_tasks(
-                show_all,
-                db_status,
-                db_priority,
-                db_tag_filter.as_deref(),
-                None,
-                search_filter.as_deref(),
-            )?;
+            let mut tasks = db.list_tasks(&TaskFilter {
+                include_done: show_all,
+                status: db_status,
+                priority: db_priority,
+                any_tag: &db_tags,
+                parent: parent_filter.as_deref(),
+                assignee: assignee_filter.as_deref(),
+                search: search_filter.as_deref(),
+                ..Default::default()
+            })?;
             // Post-filter for multi-value OR semantics
             if status_values.len() > 1 {
                 let status_strs: Vec<&str> = status_values.iter().map(|s| s.as_str()).collect();
@@ -984,11 +1750,10 @@ pub async fn task_list(
             if priority_values.len() > 1 {
                 tasks.retain(|t| priority_values.contains(&t.priority));
             }
-            let tasks = filter_by_tags(tasks, &multi_tags);
             // Batch-load parent epics (avoids N+1: one lookup per unique parent_id)
             let parent_ids: std::collections::HashSet<String> =
                 tasks.iter().filter_map(|t| t.parent_id.clone()).collect();
-            let parents = fetch_parent_map(&db, parent_ids.into_iter())?;
+            let parents = fetch_parent_map(db, parent_ids.into_iter())?;
             let rows: Vec<TaskRow> = tasks
                 .into_iter()
                 .map(|t| TaskRow::from_task(t, &parents))
@@ -999,20 +1764,20 @@ pub async fn task_list(
                 .into_iter()
                 .map(|(tag, _count)| tag)
                 .collect();
-            Ok((rows, all_tags))
+            let saved_views = db
+                .list_views()?
+                .into_iter()
+                .map(|(name, view)| ViewResponse::new(name, view))
+                .collect();
+            Ok((rows, all_tags, saved_views))
         })
         .await
         .unwrap()
-        .unwrap_or_else(|_| (vec![], vec![]));
+        .unwrap_or_else(|_| (vec![], vec![], vec![]));
 
-    let poll_query = build_poll_query(
-        &params.status,
-        &params.priority,
-        &params.tag,
-        &params.search,
-    );
+    let poll_query = build_poll_query(&params);
 
-    render_template(TaskListTemplate {
+    let response = render_template(TaskListTemplate {
         tasks: task_rows,
         status_filter: params.status,
         priority_filter: params.priority,
@@ -1021,13 +1786,27 @@ pub async fn task_list(
         all_tags,
         search_filter: params.search,
         has_filters: has_filter,
+        saved_views,
         poll_query,
-    })
+    });
+    with_etag(response, &etag)
 }
 
 /// GET /tasks/new — Create task form.
-pub async fn task_new() -> Response {
-    render_template(TaskNewTemplate)
+pub async fn task_new(State(state): State<AppState>) -> Response {
+    let result = state
+        .with_read_db(|db| db.default_priority())
+        .await
+        .unwrap();
+
+    match result {
+        Ok(default_priority) => render_template(TaskNewTemplate { default_priority }),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("database error: {e}"),
+        )
+            .into_response(),
+    }
 }
 
 /// Form body for POST /tasks (HTML form submission from task_new.html).
@@ -1051,36 +1830,43 @@ pub async fn task_create_form(
     if title.is_empty() {
         return Err(AppError::Validation("title is required".to_string()));
     }
-    let priority = body.priority.unwrap_or(2);
+    let priority = body.priority;
     let description = body
         .description
         .filter(|d| !d.trim().is_empty())
         .map(|d| d.trim().to_string());
 
-    let db = state.db.clone();
-    tokio::task::spawn_blocking(move || -> Result<(), String> {
-        let db = db.lock().unwrap();
-        let id = db.generate_id()?;
-        let now = chrono::Utc::now();
-        let task = Task {
-            id,
-            title,
-            description,
-            status: crate::models::Status::Open,
-            priority,
-            assignee: None,
-            parent_id: None,
-            tags: vec![],
-            created_at: now,
-            updated_at: now,
-            close_reason: None,
-            notes: None,
-        };
-        db.insert_task(&task)
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+    state
+        .with_db(move |db| -> Result<(), Error> {
+            let priority = match priority {
+                Some(p) => p,
+                None => db.default_priority()?,
+            };
+            let id = db.generate_id()?;
+            let now = chrono::Utc::now();
+            let task = Task {
+                id,
+                title,
+                description,
+                status: crate::models::Status::Open,
+                priority,
+                assignee: None,
+                parent_id: None,
+                tags: vec![],
+                created_at: now,
+                updated_at: now,
+                close_reason: None,
+                notes: None,
+                due_at: None,
+                position: 0.0,
+                archived_at: None,
+                estimate_minutes: None,
+                spent_minutes: None,
+            };
+            db.insert_task(&task)
+        })
+        .await?
+        .map_err(AppError::from)?;
 
     Ok(Redirect::to("/tasks"))
 }
@@ -1095,6 +1881,36 @@ struct TaskDetailData {
     comments: Vec<Comment>,
 }
 
+/// Order comments so each reply follows the comment it answers (depth-first,
+/// oldest first at every level). Replies whose parent is missing stay at the
+/// top level.
+fn thread_comments(comments: Vec<Comment>) -> Vec<Comment> {
+    fn visit(parent: Option<i64>, pool: &mut Vec<Option<Comment>>, out: &mut Vec<Comment>) {
+        for i in 0..pool.len() {
+            if pool[i].as_ref().is_some_and(|c| c.reply_to == parent) {
+                let comment = pool[i].take().unwrap();
+                let id = comment.id;
+                out.push(comment);
+                visit(Some(id), pool, out);
+            }
+        }
+    }
+
+    let ids: Vec<i64> = comments.iter().map(|c| c.id).collect();
+    let mut pool: Vec<Option<Comment>> = comments
+        .into_iter()
+        .map(|mut c| {
+            if c.reply_to.is_some_and(|p| !ids.contains(&p)) {
+                c.reply_to = None;
+            }
+            Some(c)
+        })
+        .collect();
+    let mut out = Vec::with_capacity(pool.len());
+    visit(None, &mut pool, &mut out);
+    out
+}
+
 /// GET /tasks/:id — Task detail page (200 or 404).
 ///
 /// When called from HTMX (`HX-Request: true`), renders a modal fragment.
@@ -1105,39 +1921,37 @@ pub async fn task_detail(
     Path(id): Path<String>,
 ) -> Response {
     let is_htmx = headers.contains_key("HX-Request");
-    let db = state.db.clone();
-    let result = tokio::task::spawn_blocking(move || -> Result<Option<TaskDetailData>, String> {
-        let db = db.lock().unwrap();
-        let task = match db.get_task(&id)? {
-            Some(t) => t,
-            None => return Ok(None),
-        };
-        // Fetch parent epic if this task is a subtask
-        let parent = if let Some(ref pid) = task.parent_id {
-            db.get_task(pid)?
-        } else {
-            None
-        };
-        // Resolve blocker dependency records to full Task objects
-        let blocker_deps = db.get_blockers(&id)?;
-        let mut blockers = Vec::with_capacity(blocker_deps.len());
-        for dep in blocker_deps {
-            if let Some(t) = db.get_task(&dep.parent_id)? {
-                blockers.push(t);
-            }
-        }
-        let dependents = db.get_dependents(&id)?;
-        let comments = db.get_comments(&id)?;
-        Ok(Some(TaskDetailData {
-            task,
-            parent,
-            blockers,
-            dependents,
-            comments,
-        }))
-    })
-    .await
-    .unwrap();
+    let result = state
+        .with_read_db(move |db| -> Result<Option<TaskDetailData>, Error> {
+            let task = match db.get_task(&id)? {
+                Some(t) => t,
+                None => return Ok(None),
+            };
+            // Fetch parent epic if this task is a subtask
+            let parent = if let Some(ref pid) = task.parent_id {
+                db.get_task(pid)?
+            } else {
+                None
+            };
+            // Resolve blocker dependency records to full Task objects in one query
+            let blocker_ids: Vec<String> = db
+                .get_blockers(&id)?
+                .into_iter()
+                .map(|dep| dep.parent_id)
+                .collect();
+            let blockers = db.get_tasks_by_ids(&blocker_ids)?;
+            let dependents = db.get_dependents(&id)?;
+            let comments = thread_comments(db.get_comments(&id)?);
+            Ok(Some(TaskDetailData {
+                task,
+                parent,
+                blockers,
+                dependents,
+                comments,
+            }))
+        })
+        .await
+        .unwrap();
 
     match result {
         Ok(Some(data)) => {
@@ -1184,117 +1998,91 @@ fn build_board_poll_query(epic: &Option<String>, priority: &Option<String>) -> S
     }
 }
 
+/// The board's four columns for the given epic and priority filters, with
+/// each card's parent epic resolved. Column membership (including
+/// dep-blocked open tasks) is shared with `tk board`.
+fn board_columns(
+    db: &crate::db::Database,
+    epic_values: &[String],
+    priority_values: &[u8],
+) -> Result<Vec<BoardColumn>, Error> {
+    let board = db.board(epic_values, priority_values)?;
+
+    // Batch-load all unique parent epics across all columns
+    let parent_ids: std::collections::HashSet<String> = board
+        .open
+        .iter()
+        .chain(board.in_progress.iter())
+        .chain(board.blocked.iter())
+        .chain(board.done.iter())
+        .filter_map(|t| t.parent_id.clone())
+        .collect();
+    let parents = fetch_parent_map(db, parent_ids.into_iter())?;
+
+    let column = |status: Status, heading: &'static str, tasks: Vec<Task>| BoardColumn {
+        status: status.as_str(),
+        heading,
+        rows: tasks
+            .into_iter()
+            .map(|t| TaskRow::from_task(t, &parents))
+            .collect(),
+    };
+    Ok(vec![
+        column(Status::Open, "Open", board.open),
+        column(Status::InProgress, "In Progress", board.in_progress),
+        column(Status::Blocked, "Blocked", board.blocked),
+        column(Status::Done, "Done", board.done),
+    ])
+}
+
 /// GET /board — Kanban board view grouped by status, with optional epic and priority filters.
-pub async fn board(State(state): State<AppState>, Query(query): Query<BoardQuery>) -> Response {
+pub async fn board(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    RawQuery(raw_query): RawQuery,
+    Query(query): Query<BoardQuery>,
+) -> Response {
+    let etag = match check_page_etag(&state, &headers, "board", raw_query.as_deref()).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
     let epic_filter = query.epic.clone();
     let priority_filter = query.priority.clone();
 
-    let db = state.db.clone();
-    let result = tokio::task::spawn_blocking(move || -> Result<BoardTemplate, String> {
-        let db = db.lock().unwrap();
-
-        // Fetch all epics for the dropdown.
-        let epics = db.list_tasks(true, None, None, Some("epic"), None, None)?;
-
-        // Parse multi-select values.
-        let epic_values = parse_status_values(&epic_filter); // epic IDs are strings
-        let priority_values = parse_priority_values(&priority_filter);
-
-        // Helper: fetch tasks for a given status, applying epic and priority filters.
-        // For single values, pass directly to DB for efficiency; for multi-values, post-filter.
-        let fetch = |status: &str, show_done: bool| -> Result<Vec<Task>, String> {
-            let (db_parent, db_priority) = match (epic_values.len(), priority_values.len()) {
-                (1, 1) => (
-                    epic_values.first().map(|s| s.as_str()),
-                    priority_values.first().copied(),
-                ),
-                (1, _) => (epic_values.first().map(|s| s.as_str()), None),
-                (_, 1) => (None, priority_values.first().copied()),
-                _ => (None, None),
-            };
-            db.list_tasks(show_done, Some(status), db_priority, None, db_parent, None)
-        };
-
-        // Fetch the set of task IDs that have at least one open blocker (via dep graph).
-        // These tasks belong in the Blocked column regardless of their `status` field,
-        // because `dep add` does not automatically change a task's status to "blocked".
-        let dep_blocked_ids: std::collections::HashSet<String> =
-            db.get_blocked_tasks()?.into_iter().map(|t| t.id).collect();
-
-        // Fetch open tasks, then split: those with open blockers go to the blocked column.
-        let open_raw = fetch("open", false)?;
-        let (dep_blocked_open, open_raw_filtered): (Vec<Task>, Vec<Task>) = open_raw
-            .into_iter()
-            .partition(|t| dep_blocked_ids.contains(&t.id));
-
-        let in_progress_raw = fetch("in_progress", false)?;
-        // Combine status=blocked tasks with open tasks that have active dep blockers.
-        let mut blocked_raw = fetch("blocked", false)?;
-        blocked_raw.extend(dep_blocked_open);
-        let done_raw = fetch("done", true)?;
-
-        // Post-filter for multi-value epic or priority selections.
-        let post_filter = |mut tasks: Vec<Task>| -> Vec<Task> {
-            if epic_values.len() > 1 {
-                tasks.retain(|t| {
-                    t.parent_id
-                        .as_deref()
-                        .is_some_and(|pid| epic_values.contains(&pid.to_string()))
-                });
-            }
-            if priority_values.len() > 1 {
-                tasks.retain(|t| priority_values.contains(&t.priority));
-            }
-            tasks
-        };
-
-        let open_raw_filtered = post_filter(open_raw_filtered);
-        let in_progress_raw = post_filter(in_progress_raw);
-        let blocked_raw = post_filter(blocked_raw);
-        let done_raw = post_filter(done_raw);
-
-        // Batch-load all unique parent epics across all columns
-        let all_tasks_iter = open_raw_filtered
-            .iter()
-            .chain(in_progress_raw.iter())
-            .chain(blocked_raw.iter())
-            .chain(done_raw.iter());
-        let parent_ids: std::collections::HashSet<String> =
-            all_tasks_iter.filter_map(|t| t.parent_id.clone()).collect();
-        let parents = fetch_parent_map(&db, parent_ids.into_iter())?;
-
-        let to_rows = |tasks: Vec<Task>| -> Vec<TaskRow> {
-            tasks
-                .into_iter()
-                .map(|t| TaskRow::from_task(t, &parents))
-                .collect()
-        };
-
-        let open_tasks = to_rows(open_raw_filtered);
-        let in_progress_tasks = to_rows(in_progress_raw);
-        let blocked_tasks = to_rows(blocked_raw);
-        let done_tasks = to_rows(done_raw);
-
-        let selected_epic = epic_filter.clone().unwrap_or_default();
-        let selected_priority = priority_filter.clone().unwrap_or_default();
-        let poll_query = build_board_poll_query(&epic_filter, &priority_filter);
-
-        Ok(BoardTemplate {
-            open_tasks,
-            in_progress_tasks,
-            blocked_tasks,
-            done_tasks,
-            epics,
-            selected_epic,
-            selected_priority,
-            poll_query,
+    let result = state
+        .with_read_db(move |db| -> Result<BoardTemplate, Error> {
+            // Fetch all epics for the dropdown.
+            let epics = db.list_tasks(&TaskFilter {
+                include_done: true,
+                tag: Some("epic"),
+                ..Default::default()
+            })?;
+
+            // Parse multi-select values.
+            let epic_values = parse_status_values(&epic_filter); // epic IDs are strings
+            let priority_values = parse_priority_values(&priority_filter);
+
+            let columns = board_columns(db, &epic_values, &priority_values)?;
+            let board_empty = columns.iter().all(|c| c.rows.is_empty());
+
+            let selected_epic = epic_filter.clone().unwrap_or_default();
+            let selected_priority = priority_filter.clone().unwrap_or_default();
+            let poll_query = build_board_poll_query(&epic_filter, &priority_filter);
+
+            Ok(BoardTemplate {
+                columns,
+                board_empty,
+                epics,
+                selected_epic,
+                selected_priority,
+                poll_query,
+            })
         })
-    })
-    .await
-    .unwrap();
+        .await
+        .unwrap();
 
     match result {
-        Ok(tmpl) => render_template(tmpl),
+        Ok(tmpl) => with_etag(render_template(tmpl), &etag),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             format!("database error: {e}"),
@@ -1304,32 +2092,39 @@ pub async fn board(State(state): State<AppState>, Query(query): Query<BoardQuery
 }
 
 /// GET /epics — Epics overview with subtask progress.
-pub async fn epics(State(state): State<AppState>) -> Response {
-    let db = state.db.clone();
-    let result = tokio::task::spawn_blocking(move || -> Result<Vec<EpicRow>, String> {
-        let db = db.lock().unwrap();
-        let epic_tasks = db.list_tasks(true, None, None, Some("epic"), None, None)?;
-        let mut rows = Vec::with_capacity(epic_tasks.len());
-        for task in epic_tasks {
-            let children = db.get_children(&task.id)?;
-            let children_total = children.len();
-            let children_done = children
-                .iter()
-                .filter(|c| matches!(c.status, crate::models::Status::Done))
-                .count();
-            rows.push(EpicRow {
-                task,
-                children_total,
-                children_done,
-            });
-        }
-        Ok(rows)
-    })
-    .await
-    .unwrap();
+pub async fn epics(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let etag = match check_page_etag(&state, &headers, "epics", None).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+    let result = state
+        .with_read_db(move |db| -> Result<Vec<EpicRow>, Error> {
+            let epic_tasks = db.list_tasks(&TaskFilter {
+                include_done: true,
+                tag: Some("epic"),
+                ..Default::default()
+            })?;
+            let mut rows = Vec::with_capacity(epic_tasks.len());
+            for task in epic_tasks {
+                let children = db.get_children(&task.id)?;
+                let children_total = children.len();
+                let children_done = children
+                    .iter()
+                    .filter(|c| matches!(c.status, crate::models::Status::Done))
+                    .count();
+                rows.push(EpicRow {
+                    task,
+                    children_total,
+                    children_done,
+                });
+            }
+            Ok(rows)
+        })
+        .await
+        .unwrap();
 
     match result {
-        Ok(epics) => render_template(EpicsTemplate { epics }),
+        Ok(epics) => with_etag(render_template(EpicsTemplate { epics }), &etag),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             format!("database error: {e}"),
@@ -1351,22 +2146,13 @@ pub async fn epic_detail(
     };
     let view_clone = view.clone();
 
-    let db = state.db.clone();
-    let result =
-        tokio::task::spawn_blocking(move || -> Result<Option<EpicDetailTemplate>, String> {
-            let db = db.lock().unwrap();
+    let result = state
+        .with_read_db(move |db| -> Result<Option<EpicDetailTemplate>, Error> {
             let task = match db.get_task(&id)? {
                 Some(t) => t,
                 None => return Ok(None),
             };
-            let mut children = db.get_children(&id)?;
-            // Sort children by the numeric suffix of their hierarchical ID (e.g. `tk-xxxx.N`)
-            // so that ordering is 1, 2, 3 … 10, 11 rather than lexicographic 1, 10, 11 … 2.
-            children.sort_by_key(|c| {
-                c.id.rfind('.')
-                    .and_then(|pos| c.id[pos + 1..].parse::<u64>().ok())
-                    .unwrap_or(0)
-            });
+            let children = db.get_children(&id)?;
             let children_total = children.len();
             let children_done = children
                 .iter()
@@ -1384,11 +2170,13 @@ pub async fn epic_detail(
                 .iter()
                 .filter(|c| matches!(c.status, crate::models::Status::Blocked))
                 .count();
+            let effort = Effort::of(&children);
             Ok(Some(EpicDetailTemplate {
                 task,
                 children,
                 children_done,
                 children_total,
+                effort,
                 board_open_count,
                 board_in_progress_count,
                 board_blocked_count,
@@ -1412,55 +2200,52 @@ pub async fn epic_detail(
 
 /// GET /api/tags — Unique tag names sorted by usage count descending (200).
 pub async fn api_tags(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let tags: Vec<String> = tokio::task::spawn_blocking(move || {
-        let db = db.lock().unwrap();
-        db.task_count_by_tag()
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?
-    .into_iter()
-    .map(|(tag, _count)| tag)
-    .collect();
+    let tags: Vec<String> = state
+        .with_read_db(move |db| db.task_count_by_tag())
+        .await?
+        .map_err(AppError::from)?
+        .into_iter()
+        .map(|(tag, _count)| tag)
+        .collect();
 
     Ok(Json(tags))
 }
 
-/// GET /api/stats — Task statistics (200).
-pub async fn api_stats(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
-    let db = state.db.clone();
-    let result = tokio::task::spawn_blocking(move || -> Result<StatsResponse, String> {
-        let db = db.lock().unwrap();
-
-        let by_status_vec = db.task_count_by_status()?;
-        let by_priority_vec = db.task_count_by_priority()?;
-        let by_tag_vec = db.task_count_by_tag()?;
+/// One entry of GET /api/views: a saved view and the task list page URL
+/// that applies it.
+#[derive(Debug, Serialize)]
+pub struct ViewResponse {
+    pub name: String,
+    pub view: SavedView,
+    pub url: String,
+}
 
-        let by_status: Map<String, Value> = by_status_vec
-            .into_iter()
-            .map(|(k, v)| (k, Value::Number(v.into())))
-            .collect();
+impl ViewResponse {
+    fn new(name: String, view: SavedView) -> Self {
+        let url = format!("/tasks{}", view.query_string());
+        Self { name, view, url }
+    }
+}
 
-        let by_priority: Map<String, Value> = by_priority_vec
-            .into_iter()
-            .map(|(k, v)| (k.to_string(), Value::Number(v.into())))
-            .collect();
+/// GET /api/views — Views saved with `tk view save`, by name (200).
+pub async fn api_views(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let views: Vec<ViewResponse> = state
+        .with_read_db(move |db| db.list_views())
+        .await?
+        .map_err(AppError::from)?
+        .into_iter()
+        .map(|(name, view)| ViewResponse::new(name, view))
+        .collect();
 
-        let by_tag: Map<String, Value> = by_tag_vec
-            .into_iter()
-            .map(|(k, v)| (k, Value::Number(v.into())))
-            .collect();
+    Ok(Json(views))
+}
 
-        Ok(StatsResponse {
-            by_status,
-            by_priority,
-            by_tag,
-        })
-    })
-    .await
-    .map_err(|e| AppError::Internal(e.to_string()))?
-    .map_err(AppError::Internal)?;
+/// GET /api/stats — Task statistics (200).
+pub async fn api_stats(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let result = state
+        .with_read_db(move |db| -> Result<StatsResponse, Error> { build_stats(db) })
+        .await?
+        .map_err(AppError::from)?;
 
     Ok(Json(result))
 }
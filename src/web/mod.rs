@@ -1,24 +1,110 @@
 use crate::db::Database;
+use crate::error::Error;
 use axum::{
     Router,
     extract::Path as AxumPath,
     http::{StatusCode, header},
+    middleware,
     response::{IntoResponse, Response},
-    routing::{delete, get, post},
+    routing::{delete, get, patch, post},
 };
+use chrono::{DateTime, Utc};
+use errors::AppError;
 use rust_embed::Embed;
 use std::sync::{Arc, Mutex, atomic::AtomicI64};
+use std::time::Instant;
 
 /// Shared application state for the web server.
 #[derive(Clone)]
 pub struct AppState {
+    /// The one connection that writes; requests that may write take turns on it.
     pub db: Arc<Mutex<Database>>,
+    /// Read-only connections for requests that only read; `None` for an
+    /// in-memory database, whose reads then share `db`.
+    pub readers: Option<Arc<ReaderPool>>,
     /// Last known SQLite `PRAGMA data_version` value, used for polling-based live updates.
     pub last_data_version: Arc<AtomicI64>,
+    /// Monotonic instant the server started, used to report uptime.
+    pub started: Instant,
+    /// Wall-clock time the server started.
+    pub started_at: DateTime<Utc>,
+    /// Basename of the database file (the full path is never exposed).
+    pub db_file: Option<String>,
+    /// Limit on mutating `/api/*` requests, set by `tk serve --rate`.
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl AppState {
+    /// Build the shared state around an opened database located at `db_path`.
+    pub fn new(db: Database, db_path: &std::path::Path) -> Self {
+        AppState {
+            db: Arc::new(Mutex::new(db)),
+            readers: ReaderPool::new(db_path).map(Arc::new),
+            last_data_version: Arc::new(AtomicI64::new(0)),
+            started: Instant::now(),
+            started_at: Utc::now(),
+            db_file: db_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            rate_limiter: None,
+        }
+    }
+
+    /// Limit mutating `/api/*` requests to `per_sec` per second, across all clients.
+    pub fn with_rate_limit(mut self, per_sec: u32) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(per_sec)));
+        self
+    }
+
+    /// Run `f` against the database on the blocking thread pool.
+    ///
+    /// The lock is taken inside the blocking task, so handlers never hold it
+    /// across an `.await`. A panic in an earlier call poisons the mutex; the
+    /// connection itself is still usable, so the poison is ignored rather
+    /// than failing every later request.
+    pub async fn with_db<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Database) -> T + Send + 'static,
+    {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || {
+            let db = db.lock().unwrap_or_else(|e| e.into_inner());
+            f(&db)
+        })
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    /// Run `f` on a pooled read-only connection on the blocking thread pool,
+    /// so concurrent reads do not wait for each other or for `db`. Falls back
+    /// to [`with_db`](Self::with_db) when there is no pool.
+    ///
+    /// Only for work that never writes: a write through a reader fails.
+    /// Anything comparing `content_version` or `data_version` across requests
+    /// must stay on `with_db`, since those values are per connection.
+    pub async fn with_read_db<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Database) -> T + Send + 'static,
+    {
+        let Some(readers) = self.readers.clone() else {
+            return self.with_db(f).await;
+        };
+        tokio::task::spawn_blocking(move || readers.run(f))
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .map_err(AppError::Internal)
+    }
 }
 
 pub mod errors;
 mod handlers;
+mod pool;
+mod rate_limit;
+
+use pool::ReaderPool;
+use rate_limit::RateLimiter;
 
 /// Embedded static assets (htmx, pico CSS, etc.) compiled into the binary.
 #[derive(Embed)]
@@ -65,13 +151,23 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/tasks/ready", get(handlers::api_ready_tasks))
         .route("/api/tasks/blocked", get(handlers::api_blocked_tasks))
         .route("/api/tags", get(handlers::api_tags))
+        .route("/api/views", get(handlers::api_views))
+        .route("/api/search", get(handlers::api_search))
+        .route("/api/comments/recent", get(handlers::api_recent_comments))
+        .route(
+            "/api/comments/{id}",
+            patch(handlers::api_update_comment).delete(handlers::api_delete_comment),
+        )
         .route("/api/epics", get(handlers::api_epics))
         .route("/api/prime", get(handlers::api_prime))
+        .route("/api/meta", get(handlers::api_meta))
         .route(
             "/api/tasks/{id}",
             get(handlers::api_show_task).patch(handlers::api_update_task),
         )
         .route("/api/tasks/{id}/close", post(handlers::api_close_task))
+        .route("/api/tasks/{id}/status", post(handlers::api_move_task))
+        .route("/api/board/move", post(handlers::api_board_move))
         .route("/api/tasks/{id}/deps", post(handlers::api_add_dep))
         .route(
             "/api/tasks/{child_id}/deps/{parent_id}",
@@ -81,21 +177,27 @@ pub fn create_router(state: AppState) -> Router {
             "/api/tasks/{id}/comments",
             get(handlers::api_list_comments).post(handlers::api_add_comment),
         )
+        .route("/api/tasks/{id}/history", get(handlers::api_task_history))
         .route("/api/tasks/{id}/children", get(handlers::api_children))
         .route("/api/tasks/{id}/blockers", get(handlers::api_blockers))
         .route("/api/tasks/{id}/dependents", get(handlers::api_dependents))
         .route("/api/stats", get(handlers::api_stats))
         .route("/api/poll", get(handlers::api_poll))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::limit_mutations,
+        ))
         .with_state(state)
 }
 
 /// Start the web server on the given port, shutting down gracefully on Ctrl+C.
-pub async fn serve(db_path: &std::path::Path, port: u16) -> Result<(), String> {
+/// With `rate`, mutating `/api/*` requests are limited to that many per second.
+pub async fn serve(db_path: &std::path::Path, port: u16, rate: Option<u32>) -> Result<(), Error> {
     let db = Database::open(db_path)?;
-    let state = AppState {
-        db: Arc::new(Mutex::new(db)),
-        last_data_version: Arc::new(AtomicI64::new(0)),
-    };
+    let mut state = AppState::new(db, db_path);
+    if let Some(per_sec) = rate {
+        state = state.with_rate_limit(per_sec);
+    }
     let app = create_router(state);
     let addr = format!("127.0.0.1:{port}");
     println!("Listening on http://{addr}");
@@ -109,5 +211,5 @@ pub async fn serve(db_path: &std::path::Path, port: u16) -> Result<(), String> {
                 .expect("failed to listen for ctrl_c");
         })
         .await
-        .map_err(|e| format!("server error: {e}"))
+        .map_err(|e| format!("server error: {e}").into())
 }
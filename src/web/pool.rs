@@ -0,0 +1,47 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::db::{Database, IN_MEMORY_PATH};
+
+/// Most idle reader connections kept for reuse; any beyond this are closed
+/// when returned.
+const MAX_IDLE_READERS: usize = 8;
+
+/// Read-only connections to the server's database file, opened on demand and
+/// reused. Each request checks one out for the duration of its blocking task,
+/// so reads run side by side while writes still go through the single
+/// [`AppState::db`](super::AppState::db) connection one at a time.
+pub struct ReaderPool {
+    path: PathBuf,
+    idle: Mutex<Vec<Database>>,
+}
+
+impl ReaderPool {
+    /// A pool for the database at `path`, or `None` for an in-memory
+    /// database, which another connection could not see.
+    pub fn new(path: &Path) -> Option<Self> {
+        if path == Path::new(IN_MEMORY_PATH) {
+            return None;
+        }
+        Some(ReaderPool {
+            path: path.to_path_buf(),
+            idle: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Run `f` on an idle reader, opening a new one when none is free. Meant
+    /// to be called from a blocking task.
+    pub fn run<T>(&self, f: impl FnOnce(&Database) -> T) -> Result<T, String> {
+        let idle = self.idle.lock().unwrap_or_else(|e| e.into_inner()).pop();
+        let db = match idle {
+            Some(db) => db,
+            None => Database::open_read_only(&self.path)?,
+        };
+        let value = f(&db);
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        if idle.len() < MAX_IDLE_READERS {
+            idle.push(db);
+        }
+        Ok(value)
+    }
+}
@@ -0,0 +1,116 @@
+use axum::extract::{Request, State};
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::web::AppState;
+use crate::web::errors::AppError;
+
+/// Global token bucket shared by every client. Holds up to `rate` tokens and
+/// refills at `rate` tokens per second, so short bursts of up to one second's
+/// worth of requests are allowed.
+pub struct RateLimiter {
+    rate: f64,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `per_sec` requests per second (must be at least 1).
+    pub fn new(per_sec: u32) -> Self {
+        let rate = f64::from(per_sec.max(1));
+        RateLimiter {
+            rate,
+            bucket: Mutex::new(Bucket {
+                tokens: rate,
+                last: Instant::now(),
+            }),
+        }
+    }
+
+    /// Take one token, or return how long until one is available.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// `try_acquire` as of `now`, so the refill can be tested without sleeping.
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        let elapsed = now.duration_since(bucket.last).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.rate);
+        bucket.last = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        }
+    }
+}
+
+/// Middleware: rate-limit POST/PATCH/PUT/DELETE requests under `/api/` when
+/// the server was started with a limit. Everything else passes straight through.
+pub async fn limit_mutations(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let limited = matches!(
+        *req.method(),
+        Method::POST | Method::PATCH | Method::PUT | Method::DELETE
+    ) && req.uri().path().starts_with("/api/");
+    if let Some(limiter) = state.rate_limiter.as_ref().filter(|_| limited)
+        && let Err(wait) = limiter.try_acquire()
+    {
+        let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+        return AppError::RateLimited(retry_after).into_response();
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_up_to_rate_then_wait() {
+        let limiter = RateLimiter::new(2);
+        let t0 = Instant::now();
+        assert!(limiter.try_acquire_at(t0).is_ok());
+        assert!(limiter.try_acquire_at(t0).is_ok());
+        assert_eq!(limiter.try_acquire_at(t0), Err(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_refills_at_rate() {
+        let limiter = RateLimiter::new(2);
+        let t0 = Instant::now();
+        limiter.try_acquire_at(t0).unwrap();
+        limiter.try_acquire_at(t0).unwrap();
+        assert_eq!(
+            limiter.try_acquire_at(t0 + Duration::from_millis(250)),
+            Err(Duration::from_millis(250))
+        );
+        assert!(
+            limiter
+                .try_acquire_at(t0 + Duration::from_millis(500))
+                .is_ok()
+        );
+        assert!(
+            limiter
+                .try_acquire_at(t0 + Duration::from_millis(500))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_idle_time_does_not_bank_beyond_rate() {
+        let limiter = RateLimiter::new(2);
+        let later = Instant::now() + Duration::from_secs(60);
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_err());
+    }
+}
@@ -1080,6 +1080,35 @@
     return el ? el.closest('.board-column') : null;
   }
 
+  // The first card in column whose vertical midpoint is below y, skipping
+  // the dragged card; null means the drop lands at the end of the column.
+  function cardAfterPoint(column, y, dragged) {
+    var cards = column.querySelectorAll('.board-card[data-task-id]');
+    for (var i = 0; i < cards.length; i++) {
+      if (cards[i] === dragged) continue;
+      var rect = cards[i].getBoundingClientRect();
+      if (y < rect.top + rect.height / 2) return cards[i];
+    }
+    return null;
+  }
+
+  // Position for a card dropped just above next: the midpoint of its new
+  // neighbours' data-position, or one step past the only neighbour. Null when
+  // the column has no other cards.
+  function dropPosition(column, next, dragged) {
+    var cards = Array.prototype.filter.call(
+      column.querySelectorAll('.board-card[data-task-id]'),
+      function (c) { return c !== dragged; }
+    );
+    var i = next ? cards.indexOf(next) : cards.length;
+    var before = i > 0 ? parseFloat(cards[i - 1].getAttribute('data-position')) : null;
+    var after = next ? parseFloat(next.getAttribute('data-position')) : null;
+    if (before !== null && after !== null) return (before + after) / 2;
+    if (after !== null) return after - 1;
+    if (before !== null) return before + 1;
+    return null;
+  }
+
   // dragstart: capture source info and add .dragging class
   document.addEventListener('dragstart', function (e) {
     var card = e.target.closest('.board-card[data-task-id]');
@@ -1137,7 +1166,7 @@
     dragState = null;
   });
 
-  // drop: move the card and PATCH the API
+  // drop: move the card and persist it with POST /api/board/move
   document.addEventListener('drop', function (e) {
     if (!dragState) return;
     e.preventDefault();
@@ -1164,27 +1193,53 @@
 
     card.classList.remove('dragging');
 
-    // No-op: dropped on the same column
-    if (targetStatus === sourceColumn.getAttribute('data-status')) {
+    var next = cardAfterPoint(targetColumn, e.clientY, card);
+    var sourceNext = card.nextElementSibling;
+    // No-op: dropped back where it was
+    if (targetColumn === sourceColumn && next === sourceNext) {
       return;
     }
+    var position = dropPosition(targetColumn, next, card);
+
+    // Optimistic UI: move card into place immediately
+    targetColumn.insertBefore(card, next);
+
+    // The returned column is filtered like the board itself
+    var filters = new URLSearchParams(window.location.search);
+    var body = {
+      id: taskId,
+      status: targetStatus,
+      epic: filters.get('epic') || '',
+      priority: filters.get('priority') || '',
+    };
+    if (position !== null) body.position = position;
 
-    // Optimistic UI: move card to target column immediately
-    targetColumn.appendChild(card);
-
-    // PATCH the API to persist the status change
-    fetch('/api/tasks/' + taskId, {
-      method: 'PATCH',
-      headers: { 'Content-Type': 'application/json' },
-      body: JSON.stringify({ status: targetStatus }),
+    fetch('/api/board/move', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json', 'HX-Request': 'true' },
+      body: JSON.stringify(body),
     })
       .then(function (r) {
         if (!r.ok) throw new Error('HTTP ' + r.status);
-        // Success — card is already in the right column
+        var trigger = r.headers.get('HX-Trigger');
+        if (trigger) {
+          try {
+            var warning = JSON.parse(trigger)['board-warning'];
+            if (warning) showToast(warning, 'info');
+          } catch (err) {
+            // Not a JSON trigger; nothing to show
+          }
+        }
+        return r.text();
+      })
+      .then(function (html) {
+        // Swap in the server-rendered column so order and count are exact
+        var current = document.getElementById('board-column-' + targetStatus);
+        if (current && html) current.outerHTML = html;
       })
       .catch(function () {
-        // Failure — revert card to its original column, flash error, and show toast
-        sourceColumn.appendChild(card);
+        // Failure — revert card to its original place, flash error, and show toast
+        sourceColumn.insertBefore(card, sourceNext);
         card.classList.add('drag-error');
         setTimeout(function () {
           card.classList.remove('drag-error');
@@ -32,10 +32,20 @@ pub struct TacksWorld {
     pub last_response_content_type: Option<String>,
     /// Body text of the most recent response.
     pub last_response_body: Option<String>,
+    /// Retry-After header of the most recent rate-limited response.
+    pub last_response_retry_after: Option<String>,
+    /// ETag header of the most recent GET response, if any.
+    pub last_response_etag: Option<String>,
     /// The ID of the most recently created task via inline-edit steps.
     pub last_task_id: Option<String>,
     /// Stored created_at timestamp for datetime-immutability assertions.
     pub stored_created_at: Option<String>,
+    /// Outside connection holding the database write lock, if any.
+    pub write_lock: Option<rusqlite::Connection>,
+    /// An API request left running in the background; resolves to its status.
+    pub pending_request: Option<tokio::task::JoinHandle<u16>>,
+    /// Task IDs of the most recent `tk list` page, for cursor pagination.
+    pub last_page_ids: Vec<String>,
 }
 
 impl Default for TacksWorld {
@@ -53,8 +63,13 @@ impl Default for TacksWorld {
             last_response_status: None,
             last_response_content_type: None,
             last_response_body: None,
+            last_response_retry_after: None,
+            last_response_etag: None,
             last_task_id: None,
             stored_created_at: None,
+            write_lock: None,
+            pending_request: None,
+            last_page_ids: Vec::new(),
         }
     }
 }
@@ -10,6 +10,11 @@ use crate::TacksWorld;
 
 /// Run `tk` with the given args against the world's database.
 fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    run_tk_with_env(world, &[], args);
+}
+
+/// Like `run_tk`, with extra environment variables set for the child.
+fn run_tk_with_env(world: &mut TacksWorld, env: &[(&str, &str)], args: &[&str]) {
     let db_path = world
         .db_path
         .as_ref()
@@ -18,6 +23,7 @@ fn run_tk(world: &mut TacksWorld, args: &[&str]) {
     let output = assert_cmd::Command::cargo_bin("tk")
         .expect("tk binary not found")
         .env("TACKS_DB", db_path)
+        .envs(env.iter().copied())
         .args(args)
         .output()
         .expect("failed to run tk");
@@ -31,6 +37,16 @@ fn run_tk(world: &mut TacksWorld, args: &[&str]) {
 // When steps — update / claim
 // ---------------------------------------------------------------------------
 
+#[when(expr = "I list my tasks as {string}")]
+async fn i_list_my_tasks(world: &mut TacksWorld, assignee: String) {
+    run_tk_with_env(world, &[("TACKS_USER", &assignee)], &["--json", "mine"]);
+}
+
+#[when(expr = "I list tasks assigned to {string}")]
+async fn i_list_tasks_assigned_to(world: &mut TacksWorld, assignee: String) {
+    run_tk(world, &["--json", "list", "--assignee", &assignee]);
+}
+
 #[when(expr = "I claim the task {string}")]
 async fn i_claim_the_task(world: &mut TacksWorld, alias: String) {
     let id = world
@@ -41,6 +57,27 @@ async fn i_claim_the_task(world: &mut TacksWorld, alias: String) {
     run_tk(world, &["--json", "update", &id, "--claim"]);
 }
 
+#[when(expr = "I take the next task as {string}")]
+async fn i_take_the_next_task_as(world: &mut TacksWorld, assignee: String) {
+    run_tk_with_env(world, &[("TACKS_USER", &assignee)], &["--json", "next"]);
+}
+
+#[when("I take the next task")]
+async fn i_take_the_next_task(world: &mut TacksWorld) {
+    run_tk(world, &["next"]);
+}
+
+#[given(expr = "the task {string} is assigned to {string}")]
+async fn the_task_is_assigned_to(world: &mut TacksWorld, alias: String, assignee: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["update", &id, "--assignee", &assignee]);
+    assert_eq!(world.last_exit_code, 0, "{}", world.last_stderr);
+}
+
 // ---------------------------------------------------------------------------
 // When steps — comment
 // ---------------------------------------------------------------------------
@@ -55,6 +92,100 @@ async fn i_add_a_comment_to_task(world: &mut TacksWorld, body: String, alias: St
     run_tk(world, &["comment", &id, &body]);
 }
 
+/// ID of the comment with `body` on the task aliased `alias`.
+fn comment_id(world: &mut TacksWorld, alias: &str, body: &str) -> i64 {
+    let id = world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--json", "comments", &id]);
+    let comments: Value = serde_json::from_str(&world.last_stdout)
+        .unwrap_or_else(|e| panic!("comments output is not JSON ({e}): {}", world.last_stdout));
+    comments
+        .as_array()
+        .and_then(|items| items.iter().find(|c| c["body"] == body))
+        .and_then(|c| c["id"].as_i64())
+        .unwrap_or_else(|| panic!("no comment '{body}' on task '{alias}': {comments}"))
+}
+
+#[when(expr = "I reply {string} on the task {string} to the comment {string} of the task {string}")]
+async fn i_reply_to_comment(
+    world: &mut TacksWorld,
+    body: String,
+    alias: String,
+    parent_body: String,
+    parent_alias: String,
+) {
+    let parent = comment_id(world, &parent_alias, &parent_body).to_string();
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["comment", &id, &body, "--reply-to", &parent]);
+}
+
+#[when(expr = "I edit the comment {string} of the task {string} to {string}")]
+async fn i_edit_comment(world: &mut TacksWorld, body: String, alias: String, new_body: String) {
+    let id = comment_id(world, &alias, &body).to_string();
+    run_tk(world, &["comment", "edit", &id, &new_body]);
+}
+
+#[when(expr = "I delete the comment {string} of the task {string}")]
+async fn i_delete_comment(world: &mut TacksWorld, body: String, alias: String) {
+    let id = comment_id(world, &alias, &body).to_string();
+    run_tk(world, &["comment", "delete", &id]);
+}
+
+#[when(expr = "I try to delete the comment {string}")]
+async fn i_try_to_delete_comment(world: &mut TacksWorld, id: String) {
+    run_tk(world, &["comment", "delete", &id]);
+}
+
+/// Write `first\nsecond\n` to a file in the scenario's temp dir and attach it
+/// with `tk comment --file`.
+#[when(expr = "I add a comment from a file with lines {string} and {string} to the task {string}")]
+async fn i_add_a_comment_from_file(
+    world: &mut TacksWorld,
+    first: String,
+    second: String,
+    alias: String,
+) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let path = world
+        .db_dir
+        .as_ref()
+        .expect("db_dir not set")
+        .path()
+        .join("comment.txt");
+    std::fs::write(&path, format!("{first}\n{second}\n")).expect("failed to write comment file");
+    let path = path.to_string_lossy().into_owned();
+    run_tk(world, &["comment", &id, "--file", &path]);
+}
+
+#[when(expr = "I try to add comment {string} and a file to the task {string}")]
+async fn i_try_to_add_comment_and_file(world: &mut TacksWorld, body: String, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let path = world
+        .db_dir
+        .as_ref()
+        .expect("db_dir not set")
+        .path()
+        .join("comment.txt");
+    std::fs::write(&path, "from file\n").expect("failed to write comment file");
+    let path = path.to_string_lossy().into_owned();
+    run_tk(world, &["comment", &id, &body, "--file", &path]);
+}
+
 #[when(expr = "I show the task {string}")]
 async fn i_show_the_named_task(world: &mut TacksWorld, alias: String) {
     let id = world
@@ -90,6 +221,20 @@ async fn when_i_close_the_named_task(world: &mut TacksWorld, alias: String) {
     run_tk(world, &["close", &id]);
 }
 
+#[when("I list recent comments in JSON")]
+async fn i_list_recent_comments_json(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "comments", "--recent"]);
+}
+
+#[when(expr = "I list recent comments in JSON with limit {int}")]
+async fn i_list_recent_comments_json_limit(world: &mut TacksWorld, limit: u32) {
+    let limit = limit.to_string();
+    run_tk(
+        world,
+        &["--json", "comments", "--recent", "--limit", &limit],
+    );
+}
+
 // ---------------------------------------------------------------------------
 // When steps — stats
 // ---------------------------------------------------------------------------
@@ -113,12 +258,116 @@ async fn i_run_tk_ready_json(world: &mut TacksWorld) {
     run_tk(world, &["--json", "ready"]);
 }
 
+#[when("I run tk ready with ids output")]
+async fn i_run_tk_ready_ids(world: &mut TacksWorld) {
+    run_tk(world, &["ready", "--format", "ids"]);
+}
+
+#[when("I run tk list with ids output")]
+async fn i_run_tk_list_ids(world: &mut TacksWorld) {
+    run_tk(world, &["list", "--format", "ids"]);
+}
+
+#[when("I run tk list with envelope output")]
+async fn i_run_tk_list_envelope(world: &mut TacksWorld) {
+    run_tk(world, &["list", "--json", "--envelope"]);
+}
+
+#[when("I run tk list with ndjson output")]
+async fn i_run_tk_list_ndjson(world: &mut TacksWorld) {
+    run_tk(world, &["list", "--ndjson"]);
+}
+
+#[when("I run tk list with both json and ndjson output")]
+async fn i_run_tk_list_json_and_ndjson(world: &mut TacksWorld) {
+    run_tk(world, &["list", "--json", "--ndjson"]);
+}
+
+#[when("I run tk list with ndjson and envelope output")]
+async fn i_run_tk_list_ndjson_envelope(world: &mut TacksWorld) {
+    run_tk(world, &["list", "--ndjson", "--envelope"]);
+}
+
+#[when(expr = "I run tk show {string} with envelope output")]
+async fn i_run_tk_show_envelope(world: &mut TacksWorld, id: String) {
+    run_tk(world, &["show", &id, "--json", "--envelope"]);
+}
+
+#[when(expr = "I run tk list with TACKS_FORMAT set to {string}")]
+async fn i_run_tk_list_with_format_env(world: &mut TacksWorld, format: String) {
+    run_tk_with_env(world, &[("TACKS_FORMAT", &format)], &["list"]);
+}
+
+#[when(expr = "I run tk list with TACKS_FORMAT set to {string} and the flag {string}")]
+async fn i_run_tk_list_with_format_env_and_flag(
+    world: &mut TacksWorld,
+    format: String,
+    flag: String,
+) {
+    run_tk_with_env(world, &[("TACKS_FORMAT", &format)], &["list", &flag]);
+}
+
+#[when("I run tk list")]
+async fn i_run_tk_list(world: &mut TacksWorld) {
+    run_tk(world, &["list"]);
+}
+
+#[when(expr = "I run tk list with fields {string}")]
+async fn i_run_tk_list_with_fields(world: &mut TacksWorld, fields: String) {
+    run_tk(world, &["list", "--fields", &fields]);
+}
+
+#[when(expr = "I show the task {string} with fields {string}")]
+async fn i_show_the_task_with_fields(world: &mut TacksWorld, alias: String, fields: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["show", &id, "--fields", &fields]);
+}
+
+#[when("I run tk ready with explain output")]
+async fn i_run_tk_ready_explain(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "ready", "--explain"]);
+}
+
 #[when(expr = "I run tk ready with limit {int}")]
 async fn i_run_tk_ready_with_limit(world: &mut TacksWorld, limit: i64) {
     let limit_str = limit.to_string();
     run_tk(world, &["--json", "ready", "--limit", &limit_str]);
 }
 
+// ---------------------------------------------------------------------------
+// When steps — --quiet and exit codes
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I quietly create a task titled {string}")]
+async fn i_quietly_create_a_task(world: &mut TacksWorld, title: String) {
+    run_tk(world, &["--quiet", "create", &title]);
+}
+
+#[when(expr = "I quietly create a task titled {string} with json output")]
+async fn i_quietly_create_a_task_json(world: &mut TacksWorld, title: String) {
+    run_tk(world, &["--quiet", "--json", "create", &title]);
+}
+
+#[when(expr = "I quietly close the task {string}")]
+async fn i_quietly_close_the_task(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["-q", "close", &id]);
+}
+
+#[when(expr = "I run tk with the arguments {string}")]
+async fn i_run_tk_with_the_arguments(world: &mut TacksWorld, args: String) {
+    let args: Vec<&str> = args.split_whitespace().collect();
+    run_tk(world, &args);
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — update / claim assertions
 // ---------------------------------------------------------------------------
@@ -186,6 +435,82 @@ async fn the_task_details_show_comment(world: &mut TacksWorld, expected_body: St
     );
 }
 
+#[then(expr = "the comment {string} on the task {string} replies to {string}")]
+async fn the_comment_replies_to(
+    world: &mut TacksWorld,
+    body: String,
+    alias: String,
+    parent: String,
+) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "expected the reply to succeed: {}",
+        world.last_stderr
+    );
+    let parent_id = comment_id(world, &alias, &parent);
+    let comments: Value = serde_json::from_str(&world.last_stdout).expect("comments JSON");
+    let reply = comments
+        .as_array()
+        .and_then(|items| items.iter().find(|c| c["body"] == body.as_str()))
+        .unwrap_or_else(|| panic!("no comment '{body}' on task '{alias}': {comments}"));
+    assert_eq!(
+        reply["reply_to"].as_i64(),
+        Some(parent_id),
+        "comment: {reply}"
+    );
+}
+
+#[then(expr = "the comment {string} on the task {string} is marked as edited")]
+async fn the_comment_is_edited(world: &mut TacksWorld, body: String, alias: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "expected the edit to succeed: {}",
+        world.last_stderr
+    );
+    comment_id(world, &alias, &body);
+    let comments: Value = serde_json::from_str(&world.last_stdout).expect("comments JSON");
+    let comment = comments
+        .as_array()
+        .and_then(|items| items.iter().find(|c| c["body"] == body.as_str()))
+        .unwrap_or_else(|| panic!("no comment '{body}' on task '{alias}': {comments}"));
+    assert!(
+        comment["updated_at"].is_string(),
+        "expected updated_at to be set: {comment}"
+    );
+}
+
+#[then(expr = "the task {string} has only the top-level comment {string}")]
+async fn the_task_has_only_top_level_comment(world: &mut TacksWorld, alias: String, body: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "expected the delete to succeed: {}",
+        world.last_stderr
+    );
+    comment_id(world, &alias, &body);
+    let comments: Value = serde_json::from_str(&world.last_stdout).expect("comments JSON");
+    let items = comments.as_array().expect("comments array");
+    assert_eq!(items.len(), 1, "comments: {comments}");
+    assert!(items[0]["reply_to"].is_null(), "comment: {}", items[0]);
+}
+
+#[then(expr = "the task details show one comment with lines {string} and {string}")]
+async fn the_task_details_show_multiline_comment(
+    world: &mut TacksWorld,
+    first: String,
+    second: String,
+) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    let comments = json["comments"]
+        .as_array()
+        .expect("show JSON has no 'comments' array");
+    assert_eq!(comments.len(), 1, "expected exactly one comment");
+    assert_eq!(
+        comments[0]["body"].as_str().unwrap_or(""),
+        format!("{first}\n{second}")
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — stats assertions
 // ---------------------------------------------------------------------------
@@ -203,6 +528,36 @@ async fn the_stats_json_shows_count(world: &mut TacksWorld, status: String, expe
     );
 }
 
+#[then(expr = "the stats JSON shows assignee {string} count of {int}")]
+async fn the_stats_json_shows_assignee_count(
+    world: &mut TacksWorld,
+    assignee: String,
+    expected: i64,
+) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("stats output is not valid JSON");
+
+    let count = json["by_assignee"][&assignee].as_i64().unwrap_or(0);
+    assert_eq!(
+        count, expected,
+        "expected by_assignee[\"{assignee}\"] == {expected} but got {count}: {json}"
+    );
+}
+
+#[then(expr = "the stats JSON shows close reason {string} count of {int}")]
+async fn the_stats_json_shows_reason_count(world: &mut TacksWorld, reason: String, expected: i64) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("stats output is not valid JSON");
+
+    let count = json["by_reason"][&reason]
+        .as_i64()
+        .unwrap_or_else(|| panic!("by_reason has no key '{reason}': {json}"));
+    assert_eq!(
+        count, expected,
+        "expected by_reason[\"{reason}\"] == {expected} but got {count}: {json}"
+    );
+}
+
 #[then(expr = "the stats JSON has a {string} field")]
 async fn the_stats_json_has_field(world: &mut TacksWorld, field: String) {
     let json: Value =
@@ -258,3 +613,238 @@ async fn the_ready_list_is_empty(world: &mut TacksWorld) {
         serde_json::to_string_pretty(tasks).unwrap_or_default()
     );
 }
+
+/// Assert stdout is exactly the IDs of the comma-separated aliases, one per
+/// line in the given order, with nothing else (no header, no color).
+#[then(expr = "the output is exactly the IDs of {string}")]
+async fn the_output_is_exactly_the_ids_of(world: &mut TacksWorld, aliases: String) {
+    assert_eq!(world.last_exit_code, 0, "tk failed: {}", world.last_stderr);
+    let expected: String = aliases
+        .split(',')
+        .map(|a| {
+            let id = world
+                .task_ids
+                .get(a.trim())
+                .unwrap_or_else(|| panic!("no task with alias '{a}'"));
+            format!("{id}\n")
+        })
+        .collect();
+    assert_eq!(world.last_stdout, expected);
+}
+
+/// Assert the recent-comments feed bodies, in order, match the
+/// comma-separated list.
+#[then(expr = "the recent comment bodies are {string}")]
+async fn the_recent_comment_bodies_are(world: &mut TacksWorld, expected: String) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("comments output is not valid JSON");
+    let bodies: Vec<&str> = json
+        .as_array()
+        .expect("comments JSON is not an array")
+        .iter()
+        .map(|c| c["body"].as_str().unwrap_or(""))
+        .collect();
+    let expected: Vec<&str> = expected.split(',').map(str::trim).collect();
+    assert_eq!(bodies, expected);
+}
+
+#[then(expr = "the recent comment {string} belongs to the task {string} titled {string}")]
+async fn the_recent_comment_belongs_to(
+    world: &mut TacksWorld,
+    body: String,
+    alias: String,
+    title: String,
+) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("comments output is not valid JSON");
+    let comment = json
+        .as_array()
+        .expect("comments JSON is not an array")
+        .iter()
+        .find(|c| c["body"].as_str() == Some(body.as_str()))
+        .unwrap_or_else(|| panic!("no comment {body:?} in {}", world.last_stdout));
+    assert_eq!(comment["task_id"].as_str(), Some(id.as_str()));
+    assert_eq!(comment["task_title"].as_str(), Some(title.as_str()));
+}
+
+// ---------------------------------------------------------------------------
+// Then steps — ready --explain
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the explained ready list contains {string}")]
+async fn the_explained_ready_list_contains(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("ready --explain output is not JSON");
+    let ready = json["ready"].as_array().expect("no 'ready' array");
+    assert!(
+        ready.iter().any(|t| t["id"].as_str() == Some(id.as_str())),
+        "expected {id} in ready: {json}"
+    );
+}
+
+#[then(expr = "{string} is not ready because it is blocked by {string}")]
+async fn is_not_ready_because_blocked_by(world: &mut TacksWorld, alias: String, blocker: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let blocker_id = world
+        .task_ids
+        .get(&blocker)
+        .unwrap_or_else(|| panic!("no task with alias '{blocker}'"))
+        .clone();
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("ready --explain output is not JSON");
+    let entry = json["not_ready"]
+        .as_array()
+        .expect("no 'not_ready' array")
+        .iter()
+        .find(|e| e["task"]["id"].as_str() == Some(id.as_str()))
+        .unwrap_or_else(|| panic!("expected {id} under not_ready: {json}"))
+        .clone();
+    let reasons = entry["reasons"].as_array().expect("no 'reasons' array");
+    assert!(
+        reasons
+            .iter()
+            .any(|r| r.as_str().unwrap_or("").contains(&blocker_id)),
+        "expected a reason naming {blocker_id}: {entry}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Then steps — --envelope
+// ---------------------------------------------------------------------------
+
+fn envelope(world: &TacksWorld) -> Value {
+    serde_json::from_str(&world.last_stdout)
+        .unwrap_or_else(|e| panic!("stdout is not a JSON envelope ({e}): {}", world.last_stdout))
+}
+
+#[then(expr = "the envelope is ok with {int} items")]
+async fn the_envelope_is_ok_with_items(world: &mut TacksWorld, count: u64) {
+    let json = envelope(world);
+    assert_eq!(json["ok"], Value::Bool(true), "envelope: {json}");
+    assert_eq!(
+        json["meta"]["count"].as_u64(),
+        Some(count),
+        "envelope: {json}"
+    );
+    assert_eq!(
+        json["data"].as_array().map(Vec::len),
+        Some(count as usize),
+        "envelope: {json}"
+    );
+    assert!(json["meta"]["version"].is_string(), "envelope: {json}");
+}
+
+#[then(expr = "the envelope is an error mentioning {string}")]
+async fn the_envelope_is_an_error(world: &mut TacksWorld, expected: String) {
+    assert_ne!(world.last_exit_code, 0, "expected the command to fail");
+    let json = envelope(world);
+    assert_eq!(json["ok"], Value::Bool(false), "envelope: {json}");
+    let message = json["error"]["message"].as_str().unwrap_or("");
+    assert!(
+        message.contains(&expected),
+        "expected error message to contain '{expected}', got: {json}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Then steps — --ndjson
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the output is {int} JSON lines with titles {string}")]
+async fn the_output_is_json_lines(world: &mut TacksWorld, count: usize, titles: String) {
+    assert_eq!(world.last_exit_code, 0, "tk failed: {}", world.last_stderr);
+    let lines: Vec<&str> = world.last_stdout.lines().collect();
+    assert_eq!(lines.len(), count, "stdout: {}", world.last_stdout);
+    let mut got: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            let task: Value = serde_json::from_str(line)
+                .unwrap_or_else(|e| panic!("line is not JSON ({e}): {line}"));
+            task["title"].as_str().unwrap_or_default().to_string()
+        })
+        .collect();
+    let mut expected: Vec<String> = titles.split(',').map(|t| t.trim().to_string()).collect();
+    got.sort();
+    expected.sort();
+    assert_eq!(got, expected);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps — --fields
+// ---------------------------------------------------------------------------
+
+#[then(expr = "every JSON task has exactly the keys {string}")]
+async fn every_json_task_has_exactly_the_keys(world: &mut TacksWorld, keys: String) {
+    let json: Value = serde_json::from_str(&world.last_stdout)
+        .unwrap_or_else(|e| panic!("stdout is not JSON ({e}): {}", world.last_stdout));
+    let tasks = match json {
+        Value::Array(items) => items,
+        other => vec![other],
+    };
+    assert!(!tasks.is_empty(), "expected at least one task");
+    let mut expected: Vec<&str> = keys.split(',').collect();
+    expected.sort_unstable();
+    for task in &tasks {
+        let obj = task
+            .as_object()
+            .unwrap_or_else(|| panic!("expected a JSON object, got: {task}"));
+        let mut actual: Vec<&str> = obj.keys().map(String::as_str).collect();
+        actual.sort_unstable();
+        assert_eq!(actual, expected, "task: {task}");
+    }
+}
+
+#[then("the output is a JSON array")]
+async fn the_output_is_a_json_array(world: &mut TacksWorld) {
+    assert_eq!(world.last_exit_code, 0, "tk failed: {}", world.last_stderr);
+    let json: Value = serde_json::from_str(&world.last_stdout)
+        .unwrap_or_else(|e| panic!("output is not JSON ({e}): {}", world.last_stdout));
+    assert!(json.is_array(), "expected a JSON array: {json}");
+}
+
+#[then("the output is not JSON")]
+async fn the_output_is_not_json(world: &mut TacksWorld) {
+    assert_eq!(world.last_exit_code, 0, "tk failed: {}", world.last_stderr);
+    assert!(
+        serde_json::from_str::<Value>(&world.last_stdout).is_err(),
+        "expected non-JSON output: {}",
+        world.last_stdout
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Then steps — --quiet and exit codes
+// ---------------------------------------------------------------------------
+
+#[then("the command succeeds with no output")]
+async fn the_command_succeeds_with_no_output(world: &mut TacksWorld) {
+    assert_eq!(world.last_exit_code, 0, "tk failed: {}", world.last_stderr);
+    assert!(
+        world.last_stdout.is_empty(),
+        "expected no output, got: {}",
+        world.last_stdout
+    );
+}
+
+#[then(expr = "the exit code is {int}")]
+async fn the_exit_code_is(world: &mut TacksWorld, code: i32) {
+    assert_eq!(
+        world.last_exit_code, code,
+        "stdout: {}\nstderr: {}",
+        world.last_stdout, world.last_stderr
+    );
+}
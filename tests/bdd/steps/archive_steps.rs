@@ -0,0 +1,105 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn task_id(world: &TacksWorld, alias: &str) -> String {
+    world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone()
+}
+
+fn assert_success(world: &TacksWorld, what: &str) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "{what} failed: {}",
+        world.last_stderr
+    );
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I archive the task {string}")]
+async fn i_archive_the_task(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    run_tk(world, &["archive", &id]);
+    assert_success(world, "tk archive");
+}
+
+#[when(expr = "I unarchive the task {string}")]
+async fn i_unarchive_the_task(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    run_tk(world, &["unarchive", &id]);
+    assert_success(world, "tk unarchive");
+}
+
+#[when(expr = "I archive tasks closed more than {int} days ago")]
+async fn i_archive_tasks_closed_before(world: &mut TacksWorld, days: i64) {
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+    run_tk(world, &["--json", "archive", "--closed-before", &cutoff]);
+    assert_success(world, "tk archive --closed-before");
+}
+
+#[when("I list all tasks including archived")]
+async fn i_list_all_tasks_including_archived(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "list", "--all", "--archived"]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the archive reports the tasks {string}")]
+async fn the_archive_reports(world: &mut TacksWorld, aliases: String) {
+    let json: Value = serde_json::from_str(&world.last_stdout)
+        .unwrap_or_else(|e| panic!("archive output is not JSON ({e}): {}", world.last_stdout));
+    let got: Vec<&str> = json
+        .as_array()
+        .expect("archive JSON is not an array")
+        .iter()
+        .filter_map(|t| t["id"].as_str())
+        .collect();
+    let expected: Vec<String> = aliases.split(',').map(|a| task_id(world, a)).collect();
+    assert_eq!(got, expected);
+}
+
+#[then("the shown task is archived")]
+async fn the_shown_task_is_archived(world: &mut TacksWorld) {
+    assert_success(world, "tk show");
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("show output is not valid JSON");
+    assert!(
+        json["archived_at"].is_string(),
+        "expected archived_at to be set: {}",
+        world.last_stdout
+    );
+}
@@ -37,6 +37,31 @@ async fn i_run_tk_blocked_json(world: &mut TacksWorld) {
     run_tk(world, &["--json", "blocked"]);
 }
 
+#[when("I run tk blocked with explanations in JSON")]
+async fn i_run_tk_blocked_explain_json(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "blocked", "--explain"]);
+}
+
+#[when("I run tk blocked as a table")]
+async fn i_run_tk_blocked_table(world: &mut TacksWorld) {
+    run_tk(world, &["blocked"]);
+}
+
+#[when(expr = "I reopen the task {string}")]
+async fn i_reopen_the_task(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["reopen", &id]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk reopen failed: {}",
+        world.last_stderr
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — blocked output assertions
 // ---------------------------------------------------------------------------
@@ -70,3 +95,55 @@ async fn the_blocked_output_does_not_contain(world: &mut TacksWorld, expected_ti
         expected_title
     );
 }
+
+#[then(expr = "the blocked task {string} is waiting on {string}")]
+async fn the_blocked_task_is_waiting_on(world: &mut TacksWorld, title: String, blockers: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk blocked failed: {}",
+        world.last_stderr
+    );
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("blocked output is not valid JSON");
+    let entry = json
+        .as_array()
+        .expect("blocked JSON is not an array")
+        .iter()
+        .find(|e| e["task"]["title"].as_str() == Some(title.as_str()))
+        .unwrap_or_else(|| panic!("no blocked entry for '{title}': {}", world.last_stdout));
+    let got: Vec<&str> = entry["blockers"]
+        .as_array()
+        .expect("blockers is not an array")
+        .iter()
+        .filter_map(|b| b["title"].as_str())
+        .collect();
+    let expected: Vec<&str> = blockers.split(',').collect();
+    assert_eq!(got, expected);
+}
+
+#[then(expr = "the blocked table lists {string} waiting on {string}")]
+async fn the_blocked_table_lists_waiting_on(
+    world: &mut TacksWorld,
+    alias: String,
+    blocker: String,
+) {
+    let id = world.task_ids[&alias].clone();
+    let blocker_id = world.task_ids[&blocker].clone();
+    let lines: Vec<&str> = world.last_stdout.lines().collect();
+    let row = lines
+        .iter()
+        .position(|l| l.starts_with(&id))
+        .unwrap_or_else(|| panic!("no row for {id} in:\n{}", world.last_stdout));
+    let waiting = lines[row + 1..]
+        .iter()
+        .take_while(|l| l.starts_with("    "))
+        .any(|l| {
+            l.trim_start()
+                .starts_with(&format!("waiting on {blocker_id} "))
+        });
+    assert!(
+        waiting,
+        "expected {id} to be waiting on {blocker_id} in:\n{}",
+        world.last_stdout
+    );
+}
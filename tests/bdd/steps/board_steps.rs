@@ -0,0 +1,118 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+/// Titles in the given column of the `tk --json board` output.
+fn board_column_titles(world: &TacksWorld, column: &str) -> Vec<String> {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("board output is not valid JSON");
+    json[column]
+        .as_array()
+        .unwrap_or_else(|| panic!("board JSON has no '{column}' array: {}", world.last_stdout))
+        .iter()
+        .map(|t| t["title"].as_str().unwrap_or("").to_string())
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when("I run tk board")]
+async fn i_run_tk_board(world: &mut TacksWorld) {
+    run_tk(world, &["board"]);
+}
+
+#[when("I run tk board with JSON")]
+async fn i_run_tk_board_json(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "board"]);
+}
+
+#[when(expr = "I run tk board with JSON filtered by priority {string}")]
+async fn i_run_tk_board_json_priority(world: &mut TacksWorld, priorities: String) {
+    run_tk(world, &["--json", "board", "--priority", &priorities]);
+}
+
+#[when(expr = "I run tk board with JSON for the epic {string}")]
+async fn i_run_tk_board_json_epic(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--json", "board", "--epic", &id]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the board column {string} contains {string}")]
+async fn the_board_column_contains(world: &mut TacksWorld, column: String, title: String) {
+    let titles = board_column_titles(world, &column);
+    assert!(
+        titles.contains(&title),
+        "expected '{title}' in board column '{column}' but got: {titles:?}"
+    );
+}
+
+#[then(expr = "the board column {string} does not contain {string}")]
+async fn the_board_column_does_not_contain(world: &mut TacksWorld, column: String, title: String) {
+    let titles = board_column_titles(world, &column);
+    assert!(
+        !titles.contains(&title),
+        "expected '{title}' NOT in board column '{column}' but got: {titles:?}"
+    );
+}
+
+#[then(expr = "the board output lists {string} under the {string} heading")]
+async fn the_board_output_lists_under(world: &mut TacksWorld, title: String, heading: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk board failed: {}",
+        world.last_stderr
+    );
+    // Find the section that starts with the heading and ends at the next blank line.
+    let section: Vec<&str> = world
+        .last_stdout
+        .lines()
+        .skip_while(|l| !l.starts_with(&format!("{heading} (")))
+        .take_while(|l| !l.is_empty())
+        .collect();
+    assert!(
+        !section.is_empty(),
+        "no '{heading}' heading in board output:\n{}",
+        world.last_stdout
+    );
+    assert!(
+        section.iter().any(|l| l.contains(&title)),
+        "expected '{title}' under '{heading}' but section was:\n{}",
+        section.join("\n")
+    );
+}
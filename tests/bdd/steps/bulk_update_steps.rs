@@ -0,0 +1,109 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn task_id(world: &TacksWorld, alias: &str) -> String {
+    world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I bulk-update tasks tagged {string} to status {string}")]
+async fn i_bulk_update_tagged_status(world: &mut TacksWorld, tag: String, status: String) {
+    run_tk(
+        world,
+        &["bulk-update", "--tag", &tag, "--set-status", &status],
+    );
+}
+
+#[when(expr = "I bulk-update tasks with priority {int} adding tags {string} in JSON")]
+async fn i_bulk_update_priority_add_tags(world: &mut TacksWorld, priority: u8, tags: String) {
+    let priority = priority.to_string();
+    run_tk(
+        world,
+        &[
+            "--json",
+            "bulk-update",
+            "--priority",
+            &priority,
+            "--add-tags",
+            &tags,
+        ],
+    );
+}
+
+#[when(expr = "I bulk-update tasks with no filter to status {string}")]
+async fn i_bulk_update_without_filter(world: &mut TacksWorld, status: String) {
+    run_tk(world, &["bulk-update", "--set-status", &status]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the bulk update reports {string}")]
+async fn the_bulk_update_reports(world: &mut TacksWorld, expected: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "bulk-update failed: {}",
+        world.last_stderr
+    );
+    assert_eq!(world.last_stdout.trim(), expected);
+}
+
+#[then(expr = "the bulk update returned the tasks {string}")]
+async fn the_bulk_update_returned(world: &mut TacksWorld, aliases: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "bulk-update failed: {}",
+        world.last_stderr
+    );
+    let tasks: Value = serde_json::from_str(&world.last_stdout).unwrap_or_else(|e| {
+        panic!(
+            "bulk-update output is not JSON ({e}): {}",
+            world.last_stdout
+        )
+    });
+    let mut got: Vec<String> = tasks
+        .as_array()
+        .expect("bulk-update JSON is not an array")
+        .iter()
+        .map(|t| t["id"].as_str().unwrap_or_default().to_string())
+        .collect();
+    got.sort();
+    let mut expected: Vec<String> = aliases.split(',').map(|a| task_id(world, a)).collect();
+    expected.sort();
+    assert_eq!(got, expected);
+}
@@ -1,5 +1,5 @@
 #![allow(deprecated)]
-use cucumber::{then, when};
+use cucumber::{given, then, when};
 use serde_json::Value;
 
 use crate::TacksWorld;
@@ -28,6 +28,55 @@ fn run_tk(world: &mut TacksWorld, args: &[&str]) {
     world.last_exit_code = output.status.code().unwrap_or(-1);
 }
 
+// ---------------------------------------------------------------------------
+// Given steps — subtasks with aliases
+// ---------------------------------------------------------------------------
+
+#[given(expr = "I have a subtask called {string} of {string} with title {string}")]
+async fn i_have_a_subtask_called(
+    world: &mut TacksWorld,
+    alias: String,
+    parent_alias: String,
+    title: String,
+) {
+    let parent_id = world
+        .task_ids
+        .get(&parent_alias)
+        .unwrap_or_else(|| panic!("no task with alias '{parent_alias}'"))
+        .clone();
+    run_tk(world, &["--json", "create", &title, "--parent", &parent_id]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk create failed: {}",
+        world.last_stderr
+    );
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("create output is not valid JSON");
+    let id = json["id"]
+        .as_str()
+        .expect("create JSON has no 'id' field")
+        .to_string();
+    world.task_ids.insert(alias, id);
+}
+
+#[given(expr = "I have {int} subtasks of {string}")]
+async fn i_have_n_subtasks_of(world: &mut TacksWorld, count: usize, parent_alias: String) {
+    let parent_id = world
+        .task_ids
+        .get(&parent_alias)
+        .unwrap_or_else(|| panic!("no task with alias '{parent_alias}'"))
+        .clone();
+    for n in 1..=count {
+        let title = format!("Step {n}");
+        run_tk(world, &["create", &title, "--parent", &parent_id]);
+        assert_eq!(
+            world.last_exit_code, 0,
+            "tk create failed: {}",
+            world.last_stderr
+        );
+    }
+}
+
 // ---------------------------------------------------------------------------
 // When steps — children command
 // ---------------------------------------------------------------------------
@@ -53,6 +102,46 @@ async fn i_run_tk_children_json(world: &mut TacksWorld, alias: String) {
     run_tk(world, &["--json", "children", &id_str]);
 }
 
+#[when(expr = "I show the task {string} with related tasks")]
+async fn i_show_the_task_with_related(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--json", "show", &id, "--related"]);
+}
+
+#[when(expr = "I move the task {string} under {string}")]
+async fn i_move_the_task_under(world: &mut TacksWorld, alias: String, parent_alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let parent_id = world
+        .task_ids
+        .get(&parent_alias)
+        .unwrap_or_else(|| panic!("no task with alias '{parent_alias}'"))
+        .clone();
+    run_tk(world, &["--json", "move", &id, "--parent", &parent_id]);
+}
+
+#[when(expr = "I move the task {string} to the top level")]
+async fn i_move_the_task_to_the_top_level(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--json", "move", &id, "--parent", ""]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk move failed: {}",
+        world.last_stderr
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — output assertions
 // ---------------------------------------------------------------------------
@@ -67,6 +156,16 @@ async fn the_output_contains(world: &mut TacksWorld, expected: String) {
     );
 }
 
+#[then(expr = "the output does not contain {string}")]
+async fn the_output_does_not_contain(world: &mut TacksWorld, unexpected: String) {
+    assert!(
+        !world.last_stdout.contains(&unexpected),
+        "expected stdout not to contain '{}' but got: {}",
+        unexpected,
+        world.last_stdout
+    );
+}
+
 #[then("the JSON output is an empty array")]
 async fn the_json_output_is_empty_array(world: &mut TacksWorld) {
     let json: Value =
@@ -79,3 +178,77 @@ async fn the_json_output_is_empty_array(world: &mut TacksWorld) {
         world.last_stdout
     );
 }
+
+#[then(expr = "the related parent is {string}")]
+async fn the_related_parent_is(world: &mut TacksWorld, alias: String) {
+    let expected = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"));
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    assert_eq!(
+        json["related"]["parent"]["id"].as_str(),
+        Some(expected.as_str()),
+        "unexpected related parent: {}",
+        world.last_stdout
+    );
+}
+
+#[then("the related parent is empty")]
+async fn the_related_parent_is_empty(world: &mut TacksWorld) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    assert!(
+        json["related"]["parent"].is_null(),
+        "expected no related parent: {}",
+        world.last_stdout
+    );
+}
+
+/// `aliases` is a comma-separated list of task aliases, or empty for none.
+#[then(expr = "the related {string} are {string}")]
+async fn the_related_group_is(world: &mut TacksWorld, group: String, aliases: String) {
+    let expected: Vec<String> = aliases
+        .split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(|a| {
+            world
+                .task_ids
+                .get(a)
+                .unwrap_or_else(|| panic!("no task with alias '{a}'"))
+                .clone()
+        })
+        .collect();
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    let actual: Vec<String> = json["related"][&group]
+        .as_array()
+        .unwrap_or_else(|| panic!("no related '{group}' array in: {}", world.last_stdout))
+        .iter()
+        .filter_map(|t| t["id"].as_str().map(str::to_string))
+        .collect();
+    assert_eq!(actual, expected, "unexpected related {group}");
+}
+
+/// Child IDs run `<parent>.1` to `<parent>.<last>`, compared as numbers so
+/// that `.10` comes after `.9`.
+#[then(expr = "the children of {string} are numbered 1 to {int} in order")]
+async fn the_children_are_numbered_in_order(world: &mut TacksWorld, alias: String, last: usize) {
+    let parent_id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    let actual: Vec<&str> = json
+        .as_array()
+        .expect("children output is not a JSON array")
+        .iter()
+        .map(|t| t["id"].as_str().unwrap_or(""))
+        .collect();
+    let expected: Vec<String> = (1..=last).map(|n| format!("{parent_id}.{n}")).collect();
+    assert_eq!(actual, expected, "children are out of order");
+}
@@ -53,8 +53,19 @@ async fn i_force_close_task(world: &mut TacksWorld, alias: String) {
 
 #[when(expr = "I force close subtask {string}")]
 async fn i_force_close_subtask(world: &mut TacksWorld, title: String) {
-    // Look up the subtask by title — scan the full list since subtasks are stored
-    // under "last_subtask" alias and we need to find by title.
+    let task_id = subtask_id_by_title(world, &title);
+    run_tk(world, &["close", &task_id, "--force"]);
+}
+
+#[when(expr = "I close subtask {string} with --close-parent")]
+async fn i_close_subtask_with_close_parent(world: &mut TacksWorld, title: String) {
+    let task_id = subtask_id_by_title(world, &title);
+    run_tk(world, &["close", &task_id, "--close-parent"]);
+}
+
+/// Look up a subtask by title — scan the full list since subtasks are stored
+/// under the "last_subtask" alias and we need to find them by title.
+fn subtask_id_by_title(world: &TacksWorld, title: &str) -> String {
     let db_path = world.db_path.as_ref().expect("db_path not set").clone();
 
     let output = assert_cmd::Command::cargo_bin("tk")
@@ -68,7 +79,7 @@ async fn i_force_close_subtask(world: &mut TacksWorld, title: String) {
     let tasks: serde_json::Value =
         serde_json::from_str(&stdout).expect("tk list --json output is not valid JSON");
 
-    let task_id = tasks
+    tasks
         .as_array()
         .expect("tk list --json is not an array")
         .iter()
@@ -76,7 +87,5 @@ async fn i_force_close_subtask(world: &mut TacksWorld, title: String) {
         .unwrap_or_else(|| panic!("task with title '{}' not found in list", title))["id"]
         .as_str()
         .expect("task has no 'id' field")
-        .to_string();
-
-    run_tk(world, &["close", &task_id, "--force"]);
+        .to_string()
 }
@@ -0,0 +1,69 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+///
+/// CLICOLOR_FORCE and NO_COLOR are cleared so the `--color` flag alone
+/// decides, regardless of the environment the tests run in.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .env_remove("CLICOLOR_FORCE")
+        .env_remove("NO_COLOR")
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I list tasks with color {string}")]
+async fn i_list_tasks_with_color(world: &mut TacksWorld, choice: String) {
+    run_tk(world, &["list", &format!("--color={choice}")]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk list --color={choice} failed: {}",
+        world.last_stderr
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then("the command output has no ANSI escape sequences")]
+async fn the_output_has_no_ansi(world: &mut TacksWorld) {
+    assert!(
+        !world.last_stdout.contains('\x1b'),
+        "expected no ANSI escapes but got: {:?}",
+        world.last_stdout
+    );
+}
+
+#[then("the command output has ANSI escape sequences")]
+async fn the_output_has_ansi(world: &mut TacksWorld) {
+    assert!(
+        world.last_stdout.contains("\x1b["),
+        "expected ANSI escapes but got: {:?}",
+        world.last_stdout
+    );
+}
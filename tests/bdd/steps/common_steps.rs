@@ -1,5 +1,5 @@
 #![allow(deprecated)]
-use cucumber::given;
+use cucumber::{given, then};
 
 use crate::TacksWorld;
 
@@ -26,3 +26,29 @@ async fn a_tacks_database_is_initialized(world: &mut TacksWorld) {
     // Keep the TempDir alive for the lifetime of the scenario.
     world.db_dir = Some(dir);
 }
+
+/// Write a config key directly into the scenario's database.
+#[given(expr = "the config key {string} is set to {string}")]
+async fn the_config_key_is_set_to(world: &mut TacksWorld, key: String, value: String) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+    let db = tacks::db::Database::open(db_path).expect("failed to open database");
+    db.set_config(&key, &value).expect("failed to set config");
+}
+
+/// Invariant check: recomputing the denormalized `is_blocked` flags from the
+/// dependency graph must not change any row.
+#[then("the stored blocked flags match a fresh computation")]
+async fn the_stored_blocked_flags_match(world: &mut TacksWorld) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+    let db = tacks::db::Database::open(db_path).expect("failed to open database");
+    let stale = db
+        .recompute_blocked_flags()
+        .expect("failed to recompute blocked flags");
+    assert_eq!(stale, 0, "{stale} task(s) had a stale is_blocked flag");
+}
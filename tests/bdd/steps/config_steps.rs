@@ -0,0 +1,95 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the scenario's database, recording
+/// the outcome.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn assert_success(world: &TacksWorld, what: &str) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "{what} failed\nstdout: {}\nstderr: {}",
+        world.last_stdout, world.last_stderr
+    );
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+/// Run `tk init --default-priority <priority>` against a fresh temp directory.
+#[when(expr = "I run tk init with default priority {string}")]
+async fn i_run_tk_init_with_default_priority(world: &mut TacksWorld, priority: String) {
+    let dir = tempfile::TempDir::new().expect("create temp dir");
+    world.db_path = Some(dir.path().join(".tacks").join("tacks.db"));
+    world.db_dir = Some(dir);
+    run_tk(world, &["init", "--default-priority", &priority]);
+    assert_success(world, "tk init");
+}
+
+#[when(expr = "I set the config key {string} to {string}")]
+async fn i_set_the_config_key(world: &mut TacksWorld, key: String, value: String) {
+    run_tk(world, &["config", "set", &key, &value]);
+    assert_success(world, "tk config set");
+}
+
+#[when(expr = "I try to set the config key {string} to {string}")]
+async fn i_try_to_set_the_config_key(world: &mut TacksWorld, key: String, value: String) {
+    run_tk(world, &["config", "set", &key, &value]);
+}
+
+#[when(expr = "I get the config key {string}")]
+async fn i_get_the_config_key(world: &mut TacksWorld, key: String) {
+    run_tk(world, &["config", "get", &key]);
+}
+
+#[when("I list config in JSON")]
+async fn i_list_config_in_json(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "config", "list"]);
+    assert_success(world, "tk config list");
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the config value printed is {string}")]
+async fn the_config_value_printed_is(world: &mut TacksWorld, expected: String) {
+    assert_success(world, "tk config get");
+    assert_eq!(world.last_stdout.trim(), expected);
+}
+
+#[then(expr = "the config list shows {string} set to {string}")]
+async fn the_config_list_shows(world: &mut TacksWorld, key: String, expected: String) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("config list output is not valid JSON");
+    assert_eq!(
+        json[&key].as_str(),
+        Some(expected.as_str()),
+        "unexpected value for '{key}' in: {}",
+        world.last_stdout
+    );
+}
@@ -0,0 +1,97 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn task_id(world: &TacksWorld, alias: &str) -> String {
+    world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I delete the task {string}")]
+async fn i_delete_the_task(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    run_tk(world, &["--json", "delete", &id]);
+}
+
+#[when(expr = "I force delete the task {string}")]
+async fn i_force_delete_the_task(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    run_tk(world, &["--json", "delete", &id, "--force"]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the deleted task has title {string}")]
+async fn the_deleted_task_has_title(world: &mut TacksWorld, expected: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk delete failed: {}",
+        world.last_stderr
+    );
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("delete output is not valid JSON");
+    assert_eq!(json["title"].as_str(), Some(expected.as_str()));
+}
+
+#[then(expr = "the error output names the task {string}")]
+async fn the_error_output_names_the_task(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    assert!(
+        world.last_stderr.contains(&id),
+        "expected stderr to mention {id}, got: {}",
+        world.last_stderr
+    );
+}
+
+#[then(expr = "the task {string} has no parent")]
+async fn the_task_has_no_parent(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    run_tk(world, &["--json", "show", &id]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk show failed: {}",
+        world.last_stderr
+    );
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("show output is not valid JSON");
+    assert!(
+        json["parent_id"].is_null(),
+        "expected no parent, got: {}",
+        json["parent_id"]
+    );
+}
@@ -84,6 +84,16 @@ async fn i_have_a_task_called_with_title_and_priority(
     create_task_with_alias(world, &alias, &[&title, "-p", &priority_str]);
 }
 
+#[given(expr = "I have a task called {string} with title {string} and description {string}")]
+async fn i_have_a_task_called_with_title_and_description(
+    world: &mut TacksWorld,
+    alias: String,
+    title: String,
+    description: String,
+) {
+    create_task_with_alias(world, &alias, &[&title, "-d", &description]);
+}
+
 #[given(expr = "I have a task called {string} with title {string} and tag {string}")]
 async fn i_have_a_task_called_with_title_and_tag(
     world: &mut TacksWorld,
@@ -98,6 +108,34 @@ async fn i_have_a_task_called_with_title_and_tag(
 // When steps — dependencies
 // ---------------------------------------------------------------------------
 
+#[when(expr = "I add an auto-blocking dependency so {string} is blocked by {string}")]
+async fn i_add_an_auto_blocking_dependency(
+    world: &mut TacksWorld,
+    child_alias: String,
+    parent_alias: String,
+) {
+    let child_id = world
+        .task_ids
+        .get(&child_alias)
+        .unwrap_or_else(|| panic!("no task with alias '{child_alias}'"))
+        .clone();
+    let parent_id = world
+        .task_ids
+        .get(&parent_alias)
+        .unwrap_or_else(|| panic!("no task with alias '{parent_alias}'"))
+        .clone();
+    run_tk(
+        world,
+        &["dep", "add", &child_id, &parent_id, "--auto-block"],
+    );
+}
+
+#[when(expr = "I set the status of {string} to {string}")]
+async fn i_set_the_status_of(world: &mut TacksWorld, alias: String, status: String) {
+    let id = alias_ids(world, &alias).remove(0);
+    run_tk(world, &["update", &id, "-s", &status]);
+}
+
 #[when(expr = "I add a dependency so {string} is blocked by {string}")]
 async fn i_add_a_dependency_so_blocked_by(
     world: &mut TacksWorld,
@@ -117,6 +155,31 @@ async fn i_add_a_dependency_so_blocked_by(
     run_tk(world, &["dep", "add", &child_id, &parent_id]);
 }
 
+/// Resolve a comma-separated list of aliases (blank means none) to task IDs.
+fn alias_ids(world: &TacksWorld, aliases: &str) -> Vec<String> {
+    aliases
+        .split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(|a| {
+            world
+                .task_ids
+                .get(a)
+                .unwrap_or_else(|| panic!("no task with alias '{a}'"))
+                .clone()
+        })
+        .collect()
+}
+
+#[when(expr = "I add dependencies so {string} is blocked by {string}")]
+async fn i_add_dependencies(world: &mut TacksWorld, child_alias: String, parent_aliases: String) {
+    let child_id = alias_ids(world, &child_alias).remove(0);
+    let parent_ids = alias_ids(world, &parent_aliases);
+    let mut args = vec!["--json", "dep", "add", child_id.as_str()];
+    args.extend(parent_ids.iter().map(String::as_str));
+    run_tk(world, &args);
+}
+
 #[when(expr = "I remove the dependency so {string} is no longer blocked by {string}")]
 async fn i_remove_the_dependency(
     world: &mut TacksWorld,
@@ -140,16 +203,17 @@ async fn i_remove_the_dependency(
 async fn i_try_to_add_dependency_invalid(
     world: &mut TacksWorld,
     child_alias: String,
-    parent_id_literal: String,
+    parent: String,
 ) {
-    // child_alias is a real task alias; parent_id_literal may be a raw invalid ID
+    // child_alias is a real task alias; parent is an alias when one exists,
+    // otherwise a raw (possibly invalid) ID such as "tk-0000"
     let child_id = world
         .task_ids
         .get(&child_alias)
         .unwrap_or_else(|| panic!("no task with alias '{child_alias}'"))
         .clone();
-    // parent_id_literal may be "tk-0000" — used literally, not looked up as an alias
-    run_tk(world, &["dep", "add", &child_id, &parent_id_literal]);
+    let parent_id = world.task_ids.get(&parent).cloned().unwrap_or(parent);
+    run_tk(world, &["dep", "add", &child_id, &parent_id]);
 }
 
 #[when(expr = "I try to remove a dependency so {string} is no longer blocked by {string}")]
@@ -192,6 +256,56 @@ async fn i_show_task_in_json(world: &mut TacksWorld, alias: String) {
     run_tk(world, &["--json", "show", &id]);
 }
 
+#[when(expr = "I list the dependencies of {string} in JSON")]
+async fn i_list_dependencies_in_json(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--json", "dep", "list", &id]);
+}
+
+#[when("I print the dependency graph in JSON")]
+async fn i_print_the_dependency_graph_in_json(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "dep", "graph"]);
+}
+
+#[when(expr = "I print the dependency graph as DOT from {string}")]
+async fn i_print_the_dependency_graph_as_dot(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["dep", "graph", "--format", "dot", "--root", &id]);
+}
+
+#[when(expr = "I show the critical path of {string} in JSON")]
+async fn i_show_the_critical_path(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--json", "critical-path", &id]);
+}
+
+#[when("I print the plan in JSON")]
+async fn i_print_the_plan_in_json(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "plan"]);
+}
+
+#[when(expr = "I list the dependencies of {string}")]
+async fn i_list_dependencies(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["dep", "list", &id]);
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — show dependents/blockers
 // ---------------------------------------------------------------------------
@@ -230,6 +344,35 @@ async fn the_task_details_include_blocker(world: &mut TacksWorld, expected_title
     );
 }
 
+#[then(expr = "the task details include an edge where {string} is blocked by {string}")]
+async fn the_task_details_include_edge(
+    world: &mut TacksWorld,
+    child_alias: String,
+    parent_alias: String,
+) {
+    let child_id = world
+        .task_ids
+        .get(&child_alias)
+        .unwrap_or_else(|| panic!("no task with alias '{child_alias}'"));
+    let parent_id = world
+        .task_ids
+        .get(&parent_alias)
+        .unwrap_or_else(|| panic!("no task with alias '{parent_alias}'"));
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    let edges = json["dependency_edges"]
+        .as_array()
+        .expect("no 'dependency_edges' array in show output");
+    let found = edges.iter().any(|e| {
+        e["child_id"].as_str() == Some(child_id.as_str())
+            && e["parent_id"].as_str() == Some(parent_id.as_str())
+    });
+    assert!(
+        found,
+        "expected edge {child_id} -> {parent_id} not found in: {edges:?}"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — ready list
 // ---------------------------------------------------------------------------
@@ -285,6 +428,159 @@ async fn the_ready_list_does_not_contain(world: &mut TacksWorld, expected_title:
     );
 }
 
+#[then(expr = "the graph JSON has {int} nodes and {int} edges")]
+async fn the_graph_json_has(world: &mut TacksWorld, nodes: usize, edges: usize) {
+    assert_eq!(world.last_exit_code, 0, "tk failed: {}", world.last_stderr);
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("graph output is not valid JSON");
+    let count = |key: &str| {
+        json[key]
+            .as_array()
+            .unwrap_or_else(|| panic!("graph JSON has no '{key}' array: {json}"))
+            .len()
+    };
+    assert_eq!(count("nodes"), nodes, "unexpected node count");
+    assert_eq!(count("edges"), edges, "unexpected edge count");
+}
+
+#[then(expr = "the DOT output has an edge from {string} to {string}")]
+async fn the_dot_output_has_edge(world: &mut TacksWorld, from: String, to: String) {
+    assert_eq!(world.last_exit_code, 0, "tk failed: {}", world.last_stderr);
+    assert!(
+        world.last_stdout.starts_with("digraph"),
+        "not DOT output: {}",
+        world.last_stdout
+    );
+    let edge = format!(
+        "\"{}\" -> \"{}\";",
+        world.task_ids[&from], world.task_ids[&to]
+    );
+    assert!(
+        world.last_stdout.contains(&edge),
+        "expected edge {edge} in: {}",
+        world.last_stdout
+    );
+}
+
+#[then(expr = "the DOT output does not mention {string}")]
+async fn the_dot_output_does_not_mention(world: &mut TacksWorld, alias: String) {
+    let id = format!("\"{}\"", world.task_ids[&alias]);
+    assert!(
+        !world.last_stdout.contains(&id),
+        "expected {id} to be outside the graph: {}",
+        world.last_stdout
+    );
+}
+
+/// Assert the critical path is exactly the comma-separated aliases, in order.
+#[then(expr = "the critical path is {string}")]
+async fn the_critical_path_is(world: &mut TacksWorld, aliases: String) {
+    assert_eq!(world.last_exit_code, 0, "tk failed: {}", world.last_stderr);
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("critical path is not valid JSON");
+    let actual: Vec<&str> = json
+        .as_array()
+        .expect("critical path JSON is not an array")
+        .iter()
+        .filter_map(|t| t["id"].as_str())
+        .collect();
+    let expected: Vec<&str> = aliases
+        .split(',')
+        .map(|a| world.task_ids[a].as_str())
+        .collect();
+    assert_eq!(actual, expected);
+}
+
+/// Assert plan wave `wave` (1-based) holds exactly the comma-separated aliases.
+#[then(expr = "plan wave {int} is {string}")]
+async fn plan_wave_is(world: &mut TacksWorld, wave: usize, aliases: String) {
+    assert_eq!(world.last_exit_code, 0, "tk failed: {}", world.last_stderr);
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("plan output is not valid JSON");
+    let mut actual: Vec<String> = json["waves"][wave - 1]
+        .as_array()
+        .unwrap_or_else(|| panic!("plan JSON has no wave {wave}: {json}"))
+        .iter()
+        .filter_map(|t| t["id"].as_str().map(str::to_string))
+        .collect();
+    let mut expected: Vec<String> = aliases
+        .split(',')
+        .map(|a| {
+            world
+                .task_ids
+                .get(a.trim())
+                .unwrap_or_else(|| panic!("no task with alias '{a}'"))
+                .clone()
+        })
+        .collect();
+    actual.sort();
+    expected.sort();
+    assert_eq!(actual, expected, "unexpected tasks in wave {wave}");
+}
+
+/// The `unblocked: ...` line `tk close` prints, split into IDs.
+fn reported_unblocked(world: &TacksWorld) -> Vec<String> {
+    world
+        .last_stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("unblocked: "))
+        .map(|ids| ids.split(", ").map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+#[then(expr = "the close output reports {string} as unblocked")]
+async fn the_close_output_reports_unblocked(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    assert!(
+        reported_unblocked(world).contains(&id),
+        "expected {id} to be reported unblocked, got: {}",
+        world.last_stdout
+    );
+}
+
+#[then(expr = "the close output does not report {string} as unblocked")]
+async fn the_close_output_does_not_report_unblocked(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    assert!(
+        !reported_unblocked(world).contains(&id),
+        "expected {id} not to be reported unblocked, got: {}",
+        world.last_stdout
+    );
+}
+
+#[then(expr = "the dependency batch added {string} and skipped {string}")]
+async fn the_dependency_batch_added_and_skipped(
+    world: &mut TacksWorld,
+    added: String,
+    skipped: String,
+) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk dep add failed: {}",
+        world.last_stderr
+    );
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("dep add output is not valid JSON");
+    let ids = |key: &str| -> Vec<String> {
+        json[key]
+            .as_array()
+            .unwrap_or_else(|| panic!("missing '{key}' array: {json}"))
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect()
+    };
+    assert_eq!(ids("added"), alias_ids(world, &added), "added");
+    assert_eq!(ids("skipped"), alias_ids(world, &skipped), "skipped");
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — command success/failure
 // ---------------------------------------------------------------------------
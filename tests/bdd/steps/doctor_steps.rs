@@ -0,0 +1,134 @@
+#![allow(deprecated)]
+use cucumber::{given, then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+/// Run raw SQL against the world's database with foreign keys off, the way a
+/// manual edit could break it.
+fn corrupt(world: &TacksWorld, sql: &str) {
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let conn = rusqlite::Connection::open(db_path).expect("failed to open database");
+    conn.execute_batch(&format!("PRAGMA foreign_keys = OFF; {sql}"))
+        .expect("failed to corrupt database");
+}
+
+fn task_id(world: &TacksWorld, alias: &str) -> String {
+    world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone()
+}
+
+fn findings(world: &TacksWorld) -> Vec<Value> {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("doctor output is not valid JSON");
+    json["findings"]
+        .as_array()
+        .expect("doctor output has no 'findings' array")
+        .clone()
+}
+
+// ---------------------------------------------------------------------------
+// Given steps
+// ---------------------------------------------------------------------------
+
+#[given(expr = "task {string} is left blocked by a deleted task")]
+async fn task_is_left_blocked_by_a_deleted_task(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    corrupt(
+        world,
+        &format!("INSERT INTO dependencies (child_id, parent_id) VALUES ('{id}', 'tk-gone');"),
+    );
+}
+
+#[given(expr = "task {string} is left with a deleted parent")]
+async fn task_is_left_with_a_deleted_parent(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    corrupt(
+        world,
+        &format!("UPDATE tasks SET parent_id = 'tk-gone' WHERE id = '{id}';"),
+    );
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when("I run tk doctor in JSON")]
+async fn i_run_tk_doctor(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "doctor"]);
+}
+
+#[when("I run tk doctor --fix in JSON")]
+async fn i_run_tk_doctor_fix(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "doctor", "--fix"]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the doctor reports {string} for {string}")]
+async fn the_doctor_reports(world: &mut TacksWorld, check: String, alias: String) {
+    let id = task_id(world, &alias);
+    let found = findings(world)
+        .iter()
+        .any(|f| f["check"].as_str() == Some(check.as_str()) && f["task_id"].as_str() == Some(&id));
+    assert!(
+        found,
+        "expected a {check} finding for {id} in: {}",
+        world.last_stdout
+    );
+}
+
+#[then("the doctor reports no findings")]
+async fn the_doctor_reports_no_findings(world: &mut TacksWorld) {
+    assert!(
+        findings(world).is_empty(),
+        "expected no findings but got: {}",
+        world.last_stdout
+    );
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk doctor failed: {}",
+        world.last_stderr
+    );
+}
+
+#[then(expr = "the doctor fixed {int} row(s)")]
+async fn the_doctor_fixed_rows(world: &mut TacksWorld, expected: i64) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("doctor output is not valid JSON");
+    assert_eq!(
+        json["fixed"].as_i64(),
+        Some(expected),
+        "{}",
+        world.last_stdout
+    );
+}
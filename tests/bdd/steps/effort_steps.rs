@@ -0,0 +1,123 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+use crate::steps::web_steps::http_get;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn task_id(world: &TacksWorld, alias: &str) -> String {
+    world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone()
+}
+
+/// Find the epic titled `title` in a JSON array of epics. `tk epic` puts the
+/// title at the top level; `/api/epics` nests it under `task`.
+fn find_epic<'a>(epics: &'a Value, title: &str) -> &'a Value {
+    epics
+        .as_array()
+        .expect("epic output is not a JSON array")
+        .iter()
+        .find(|e| e["title"].as_str() == Some(title) || e["task"]["title"].as_str() == Some(title))
+        .unwrap_or_else(|| panic!("epic '{title}' not found in: {epics}"))
+}
+
+fn assert_rollup(epic: &Value, estimate: i64, spent: i64) {
+    assert_eq!(
+        epic["estimate_minutes"].as_i64(),
+        Some(estimate),
+        "wrong estimate rollup in: {epic}"
+    );
+    assert_eq!(
+        epic["spent_minutes"].as_i64(),
+        Some(spent),
+        "wrong spent rollup in: {epic}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I log effort on {string} with estimate {int} and spent {int}")]
+async fn i_log_effort(world: &mut TacksWorld, alias: String, estimate: i64, spent: i64) {
+    let id = task_id(world, &alias);
+    run_tk(
+        world,
+        &[
+            "update",
+            &id,
+            "--estimate",
+            &estimate.to_string(),
+            "--spent",
+            &spent.to_string(),
+        ],
+    );
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk update failed: {}",
+        world.last_stderr
+    );
+}
+
+#[when(expr = "I try to set the estimate of {string} to {string}")]
+async fn i_try_to_set_the_estimate(world: &mut TacksWorld, alias: String, value: String) {
+    let id = task_id(world, &alias);
+    run_tk(world, &["update", &id, "--estimate", &value]);
+}
+
+#[when(expr = "I GET the epic page for {string}")]
+async fn i_get_the_epic_page(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    http_get(world, &format!("/epics/{id}")).await;
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the epic output shows {string} with {int} estimated and {int} spent minutes")]
+async fn the_epic_output_shows_effort(
+    world: &mut TacksWorld,
+    title: String,
+    estimate: i64,
+    spent: i64,
+) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    assert_rollup(find_epic(&json, &title), estimate, spent);
+}
+
+#[then(expr = "the API epic {string} rolls up {int} estimated and {int} spent minutes")]
+async fn the_api_epic_rolls_up(world: &mut TacksWorld, title: String, estimate: i64, spent: i64) {
+    let body = world
+        .last_response_body
+        .as_deref()
+        .expect("no HTTP response body recorded");
+    let json: Value = serde_json::from_str(body).expect("response body is not valid JSON");
+    assert_rollup(find_epic(&json, &title), estimate, spent);
+}
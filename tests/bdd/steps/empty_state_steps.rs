@@ -0,0 +1,92 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+/// Parse the last stdout and return the named top-level field.
+fn output_field(world: &TacksWorld, field: &str) -> Value {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    json.get(field)
+        .unwrap_or_else(|| panic!("JSON output has no '{field}' field: {json}"))
+        .clone()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+/// Run a tk subcommand (whitespace-separated args) with `--json`.
+#[when(expr = "I run tk {string} in JSON mode")]
+async fn i_run_tk_in_json_mode(world: &mut TacksWorld, command: String) {
+    let mut args = vec!["--json"];
+    args.extend(command.split_whitespace());
+    run_tk(world, &args);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk {command} failed: {}",
+        world.last_stderr
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the JSON output field {string} has {string} equal to {int}")]
+async fn the_json_output_field_has_key(
+    world: &mut TacksWorld,
+    field: String,
+    key: String,
+    expected: i64,
+) {
+    let value = output_field(world, &field);
+    assert_eq!(
+        value[&key].as_i64(),
+        Some(expected),
+        "expected {field}.{key} == {expected} but got: {value}"
+    );
+}
+
+#[then(expr = "the JSON output field {string} is an empty object")]
+async fn the_json_output_field_is_empty_object(world: &mut TacksWorld, field: String) {
+    let value = output_field(world, &field);
+    assert!(
+        value.as_object().is_some_and(|o| o.is_empty()),
+        "expected {field} to be {{}} but got: {value}"
+    );
+}
+
+#[then(expr = "the JSON output field {string} is an empty array")]
+async fn the_json_output_field_is_empty_array(world: &mut TacksWorld, field: String) {
+    let value = output_field(world, &field);
+    assert!(
+        value.as_array().is_some_and(|a| a.is_empty()),
+        "expected {field} to be [] but got: {value}"
+    );
+}
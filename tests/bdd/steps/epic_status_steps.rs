@@ -37,6 +37,16 @@ async fn i_run_tk_epic_json(world: &mut TacksWorld) {
     run_tk(world, &["--json", "epic"]);
 }
 
+#[when("I run tk stats by epic with JSON")]
+async fn i_run_tk_stats_by_epic_json(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "stats", "--epic"]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk stats --epic failed: {}",
+        world.last_stderr
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — epic progress assertions
 // ---------------------------------------------------------------------------
@@ -73,3 +83,25 @@ async fn the_epic_output_shows_progress(
         total, actual_total
     );
 }
+
+#[then(expr = "the epic stats show {string} with {int} {string} subtask(s)")]
+async fn the_epic_stats_show_status_count(
+    world: &mut TacksWorld,
+    title: String,
+    expected: i64,
+    status: String,
+) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    let epic = json
+        .as_array()
+        .expect("epic stats output is not an array")
+        .iter()
+        .find(|e| e["title"].as_str() == Some(title.as_str()))
+        .unwrap_or_else(|| panic!("epic '{title}' not in: {}", world.last_stdout));
+    assert_eq!(
+        epic["by_status"][&status].as_i64(),
+        Some(expected),
+        "wrong {status} count for '{title}': {epic}"
+    );
+}
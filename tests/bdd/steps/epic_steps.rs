@@ -84,6 +84,23 @@ async fn the_task_details_include_tag(world: &mut TacksWorld, expected_tag: Stri
     );
 }
 
+#[then(expr = "the task details do not include tag {string}")]
+async fn the_task_details_do_not_include_tag(world: &mut TacksWorld, unexpected_tag: String) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    let tags = json["tags"]
+        .as_array()
+        .expect("no 'tags' array in show output");
+    let found = tags
+        .iter()
+        .any(|t| t.as_str().unwrap_or("") == unexpected_tag);
+    assert!(
+        !found,
+        "expected tag '{}' to be absent but found it in: {:?}",
+        unexpected_tag, tags
+    );
+}
+
 #[then(expr = "the task details show exactly one {string} tag")]
 async fn the_task_details_show_exactly_one_tag(world: &mut TacksWorld, tag: String) {
     let json: Value =
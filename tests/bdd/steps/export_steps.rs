@@ -0,0 +1,291 @@
+#![allow(deprecated)]
+use std::path::{Path, PathBuf};
+
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against `db`, storing stdout, stderr, and
+/// exit code on the world.
+fn run_tk_on(world: &mut TacksWorld, db: &PathBuf, args: &[&str]) {
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn world_db(world: &TacksWorld) -> PathBuf {
+    world
+        .db_path
+        .clone()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?")
+}
+
+/// Path for an export file next to the world's database.
+fn export_path(world: &TacksWorld, name: &str) -> PathBuf {
+    world_db(world)
+        .parent()
+        .expect("db_path has no parent directory")
+        .join(name)
+}
+
+fn task_id(world: &TacksWorld, alias: &str) -> String {
+    world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone()
+}
+
+/// Parse RFC 4180 CSV into records of fields, keeping quoted commas, quotes,
+/// and line breaks inside their field.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) => in_quotes = true,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    records
+}
+
+/// The data rows of a CSV export as header-name -> value maps.
+fn csv_rows(text: &str) -> Vec<std::collections::HashMap<String, String>> {
+    let mut records = parse_csv(text).into_iter();
+    let header = records.next().expect("CSV export has no header row");
+    records
+        .map(|r| header.iter().cloned().zip(r).collect())
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I export the database to {string}")]
+async fn i_export_the_database(world: &mut TacksWorld, name: String) {
+    let db = world_db(world);
+    let out = export_path(world, &name);
+    run_tk_on(world, &db, &["export", "--sqlite", out.to_str().unwrap()]);
+}
+
+#[when(expr = "I export open tasks to {string}")]
+async fn i_export_open_tasks(world: &mut TacksWorld, name: String) {
+    let db = world_db(world);
+    let out = export_path(world, &name);
+    run_tk_on(
+        world,
+        &db,
+        &["export", "--sqlite", out.to_str().unwrap(), "--open-only"],
+    );
+}
+
+#[when(expr = "I export the tasks as CSV to {string}")]
+async fn i_export_the_tasks_as_csv_to(world: &mut TacksWorld, name: String) {
+    let db = world_db(world);
+    let out = export_path(world, &name);
+    run_tk_on(
+        world,
+        &db,
+        &[
+            "export",
+            "--format",
+            "csv",
+            "--output",
+            out.to_str().unwrap(),
+        ],
+    );
+}
+
+#[when("I export open tasks as CSV")]
+async fn i_export_open_tasks_as_csv(world: &mut TacksWorld) {
+    let db = world_db(world);
+    run_tk_on(world, &db, &["export", "--format", "csv", "--open-only"]);
+}
+
+/// Run `tk init --import <export>` (plus `extra` args) for a fresh project
+/// beside the current one, and point the world at the new database.
+fn init_from(world: &mut TacksWorld, import: &Path, extra: &[&str]) {
+    let restored = export_path(world, "restored")
+        .join(".tacks")
+        .join("tacks.db");
+    let mut args = vec!["init", "--import", import.to_str().unwrap()];
+    args.extend_from_slice(extra);
+    run_tk_on(world, &restored, &args);
+    world.db_path = Some(restored);
+}
+
+#[when(expr = "I init a new project from the export {string}")]
+async fn i_init_from_export(world: &mut TacksWorld, name: String) {
+    let import = export_path(world, &name);
+    init_from(world, &import, &[]);
+}
+
+/// Like the plain import, but with `--prefix`; task aliases are re-pointed
+/// at the renamed IDs so later steps keep working.
+#[when(expr = "I init a new project from the export {string} with prefix {string}")]
+async fn i_init_from_export_with_prefix(world: &mut TacksWorld, name: String, prefix: String) {
+    let import = export_path(world, &name);
+    init_from(world, &import, &["--prefix", &prefix]);
+    for id in world.task_ids.values_mut() {
+        let (_, rest) = id.split_once('-').expect("task ID has no prefix");
+        *id = format!("{prefix}-{rest}");
+    }
+}
+
+#[when("I init a new project from a file that is not an export")]
+async fn i_init_from_non_export(world: &mut TacksWorld) {
+    let import = export_path(world, "backup.jsonl");
+    std::fs::write(&import, "{\"id\":\"tk-1\"}\n").expect("failed to write import file");
+    init_from(world, &import, &[]);
+}
+
+/// Write a priority the CLI would reject straight into an export file, as an
+/// older build without range checks might have stored it.
+#[when(expr = "the export {string} stores priority {int} for the task {string}")]
+async fn the_export_stores_priority(
+    world: &mut TacksWorld,
+    name: String,
+    priority: i64,
+    alias: String,
+) {
+    let out = export_path(world, &name);
+    let id = task_id(world, &alias);
+    let conn = rusqlite::Connection::open(&out).expect("failed to open export");
+    conn.execute(
+        "UPDATE tasks SET priority = ?1 WHERE id = ?2",
+        rusqlite::params![priority, id],
+    )
+    .expect("failed to update export");
+}
+
+#[when(expr = "I replay the export {string} with --check")]
+async fn i_replay_the_export(world: &mut TacksWorld, name: String) {
+    let db = world_db(world);
+    let out = export_path(world, &name);
+    run_tk_on(world, &db, &["replay", out.to_str().unwrap(), "--check"]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then("the export succeeds")]
+async fn the_export_succeeds(world: &mut TacksWorld) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "expected export to succeed: {}",
+        world.last_stderr
+    );
+}
+
+#[then("the export fails")]
+async fn the_export_fails(world: &mut TacksWorld) {
+    assert_ne!(world.last_exit_code, 0, "expected export to fail");
+}
+
+#[then(expr = "the export {string} contains the task {string}")]
+async fn the_export_contains(world: &mut TacksWorld, name: String, alias: String) {
+    let out = export_path(world, &name);
+    let id = task_id(world, &alias);
+    run_tk_on(world, &out, &["show", &id]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "expected {id} in the export: {}",
+        world.last_stderr
+    );
+}
+
+#[then(expr = "the export {string} does not contain the task {string}")]
+async fn the_export_does_not_contain(world: &mut TacksWorld, name: String, alias: String) {
+    let out = export_path(world, &name);
+    let id = task_id(world, &alias);
+    run_tk_on(world, &out, &["show", &id]);
+    assert_ne!(
+        world.last_exit_code, 0,
+        "expected {id} to be left out of the export"
+    );
+}
+
+#[then(expr = "the task {string} in the export {string} has no parent")]
+async fn the_exported_task_has_no_parent(world: &mut TacksWorld, alias: String, name: String) {
+    let out = export_path(world, &name);
+    let id = task_id(world, &alias);
+    run_tk_on(world, &out, &["--json", "show", &id]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "expected {id} in the export: {}",
+        world.last_stderr
+    );
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("show output is not valid JSON");
+    assert!(
+        json["parent_id"].is_null(),
+        "expected {id} to have no parent in the export: {}",
+        world.last_stdout
+    );
+}
+
+#[then(expr = "the CSV export {string} has a row titled {string} with tags {string}")]
+async fn the_csv_export_has_a_row(
+    world: &mut TacksWorld,
+    name: String,
+    title: String,
+    tags: String,
+) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "CSV export failed: {}",
+        world.last_stderr
+    );
+    let text = std::fs::read_to_string(export_path(world, &name)).expect("CSV export not written");
+    let rows = csv_rows(&text);
+    let row = rows
+        .iter()
+        .find(|r| r["title"] == title)
+        .unwrap_or_else(|| panic!("no row titled {title:?} in CSV export:\n{text}"));
+    assert_eq!(row["tags"], tags);
+    assert_eq!(row.len(), 10, "expected 10 columns: {row:?}");
+}
+
+#[then(expr = "the CSV output has the rows {string}")]
+async fn the_csv_output_has_the_rows(world: &mut TacksWorld, titles: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "CSV export failed: {}",
+        world.last_stderr
+    );
+    let actual: Vec<String> = csv_rows(&world.last_stdout)
+        .into_iter()
+        .map(|r| r["title"].clone())
+        .collect();
+    let expected: Vec<&str> = titles.split(',').map(str::trim).collect();
+    assert_eq!(actual, expected);
+}
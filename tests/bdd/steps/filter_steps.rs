@@ -1,5 +1,5 @@
 #![allow(deprecated)]
-use cucumber::{then, when};
+use cucumber::{given, then, when};
 use serde_json::Value;
 
 use crate::TacksWorld;
@@ -27,10 +27,88 @@ fn run_tk(world: &mut TacksWorld, args: &[&str]) {
     world.last_exit_code = output.status.code().unwrap_or(-1);
 }
 
+// ---------------------------------------------------------------------------
+// Given steps
+// ---------------------------------------------------------------------------
+
+/// Rewrite the task's `created_at` to `days` ago. There is no CLI for this,
+/// so the row is edited directly.
+#[given(expr = "the task {string} was created {int} days ago")]
+async fn the_task_was_created_days_ago(world: &mut TacksWorld, alias: String, days: i64) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let conn = rusqlite::Connection::open(db_path).expect("failed to open database");
+    let when = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+    conn.execute(
+        "UPDATE tasks SET created_at = ?1 WHERE id = ?2",
+        rusqlite::params![when, id],
+    )
+    .expect("failed to backdate task");
+}
+
+#[given(expr = "the task {string} was last updated {int} days ago")]
+async fn the_task_was_last_updated_days_ago(world: &mut TacksWorld, alias: String, days: i64) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let conn = rusqlite::Connection::open(db_path).expect("failed to open database");
+    let when = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+    conn.execute(
+        "UPDATE tasks SET updated_at = ?1 WHERE id = ?2",
+        rusqlite::params![when, id],
+    )
+    .expect("failed to backdate task");
+}
+
+/// Set the task's due date through `tk update --due`; negative `days` put it
+/// in the future.
+#[given(expr = "the task {string} was due {int} days ago")]
+async fn the_task_was_due_days_ago(world: &mut TacksWorld, alias: String, days: i64) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let due = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+    run_tk(world, &["update", &id, "--due", &due]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk update --due failed: {}",
+        world.last_stderr
+    );
+}
+
 // ---------------------------------------------------------------------------
 // When steps — filtered list variants
 // ---------------------------------------------------------------------------
 
+#[when(expr = "I list the IDs of tasks blocked by {string}")]
+async fn i_list_ids_blocked_by(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["list", "--format", "ids", "--blocked-by", &id]);
+}
+
+#[when(expr = "I list the IDs of tasks blocking {string}")]
+async fn i_list_ids_blocking(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["list", "--format", "ids", "--blocking", &id]);
+}
+
 #[when(expr = "I list tasks filtered by status {string}")]
 async fn i_list_tasks_filtered_by_status(world: &mut TacksWorld, status: String) {
     run_tk(world, &["--json", "list", "--status", &status]);
@@ -47,6 +125,45 @@ async fn i_list_tasks_filtered_by_tag(world: &mut TacksWorld, tag: String) {
     run_tk(world, &["--json", "list", "--tag", &tag]);
 }
 
+#[when(expr = "I list tasks filtered by close reason {string}")]
+async fn i_list_tasks_filtered_by_close_reason(world: &mut TacksWorld, reason: String) {
+    run_tk(world, &["--json", "list", "--reason", &reason]);
+}
+
+#[when("I list tasks created after today")]
+async fn i_list_tasks_created_after_today(world: &mut TacksWorld) {
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    run_tk(world, &["--json", "list", "--created-after", &today]);
+}
+
+#[when("I list tasks created before today")]
+async fn i_list_tasks_created_before_today(world: &mut TacksWorld) {
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    run_tk(world, &["--json", "list", "--created-before", &today]);
+}
+
+#[when(expr = "I list tasks created after {string}")]
+async fn i_list_tasks_created_after(world: &mut TacksWorld, bound: String) {
+    run_tk(world, &["--json", "list", "--created-after", &bound]);
+}
+
+#[when("I list tasks updated after today")]
+async fn i_list_tasks_updated_after_today(world: &mut TacksWorld) {
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    run_tk(world, &["--json", "list", "--updated-after", &today]);
+}
+
+#[when(expr = "I list tasks updated after {string}")]
+async fn i_list_tasks_updated_after(world: &mut TacksWorld, bound: String) {
+    run_tk(world, &["--json", "list", "--updated-after", &bound]);
+}
+
+#[when(expr = "I list tasks changed in the last {int} hours")]
+async fn i_list_tasks_changed_in_the_last_hours(world: &mut TacksWorld, hours: i64) {
+    let hours = hours.to_string();
+    run_tk(world, &["--json", "recent", "--hours", &hours]);
+}
+
 #[when("I list all tasks including closed")]
 async fn i_list_all_tasks_including_closed(world: &mut TacksWorld) {
     run_tk(world, &["--json", "list", "--all"]);
@@ -57,6 +174,21 @@ async fn i_list_tasks_with_default_settings(world: &mut TacksWorld) {
     run_tk(world, &["--json", "list"]);
 }
 
+#[when("I list overdue tasks")]
+async fn i_list_overdue_tasks(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "overdue"]);
+}
+
+#[when(expr = "I clear the due date of {string}")]
+async fn i_clear_the_due_date(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["update", &id, "--no-due"]);
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — filtered list assertions
 // ---------------------------------------------------------------------------
@@ -0,0 +1,106 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn history(world: &TacksWorld) -> Vec<Value> {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk history failed: {}",
+        world.last_stderr
+    );
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("history output is not valid JSON");
+    json.as_array()
+        .expect("history JSON is not an array")
+        .clone()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I view the history of {string}")]
+async fn i_view_the_history(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--json", "history", &id]);
+}
+
+#[when(expr = "I set the priority of {string} to {int}")]
+async fn i_set_the_priority(world: &mut TacksWorld, alias: String, priority: u8) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let priority = priority.to_string();
+    run_tk(world, &["update", &id, "-p", &priority]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk update failed: {}",
+        world.last_stderr
+    );
+}
+
+#[when(expr = "I view the history of task ID {string}")]
+async fn i_view_the_history_of_id(world: &mut TacksWorld, id: String) {
+    run_tk(world, &["--json", "history", &id]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+/// `from`/`to` of `""` stand for an unset value (JSON null).
+#[then(expr = "history entry {int} changes {string} from {string} to {string}")]
+async fn history_entry_changes(
+    world: &mut TacksWorld,
+    n: usize,
+    field: String,
+    from: String,
+    to: String,
+) {
+    let entries = history(world);
+    let entry = entries
+        .get(n - 1)
+        .unwrap_or_else(|| panic!("no history entry {n}: {entries:?}"));
+    let value = |key: &str| entry[key].as_str().unwrap_or("").to_string();
+    assert_eq!(value("field"), field, "entry {n}: {entry}");
+    assert_eq!(value("old_value"), from, "entry {n}: {entry}");
+    assert_eq!(value("new_value"), to, "entry {n}: {entry}");
+}
+
+#[then("the history is empty")]
+async fn the_history_is_empty(world: &mut TacksWorld) {
+    let entries = history(world);
+    assert!(entries.is_empty(), "expected no history: {entries:?}");
+}
@@ -0,0 +1,155 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+/// Write `contents` to a file in the world's temp dir, then `tk import` it.
+fn import_file(world: &mut TacksWorld, name: &str, contents: &str) {
+    let path = world
+        .db_dir
+        .as_ref()
+        .expect("db_dir not set")
+        .path()
+        .join(name);
+    std::fs::write(&path, contents).expect("failed to write import file");
+    let path = path.to_string_lossy().into_owned();
+    run_tk(world, &["import", &path]);
+}
+
+/// Every task in the database, done ones included, from `tk list -a --json`.
+fn all_tasks(world: &mut TacksWorld) -> Vec<Value> {
+    run_tk(world, &["--json", "list", "-a"]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk list failed: {}",
+        world.last_stderr
+    );
+    let json: Value = serde_json::from_str(&world.last_stdout).expect("tk list output is not JSON");
+    json.as_array()
+        .expect("tk list output is not an array")
+        .clone()
+}
+
+fn task_titled<'a>(tasks: &'a [Value], title: &str) -> &'a Value {
+    tasks
+        .iter()
+        .find(|t| t["title"] == title)
+        .unwrap_or_else(|| panic!("no task titled {title:?}"))
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+/// `lines` is split on `|` into the lines of a Markdown file.
+#[when(expr = "I import a checklist with lines {string}")]
+async fn i_import_a_checklist(world: &mut TacksWorld, lines: String) {
+    let contents = lines.split('|').collect::<Vec<_>>().join("\n");
+    import_file(world, "plan.md", &contents);
+}
+
+#[when(expr = "I import the JSON {string}")]
+async fn i_import_the_json(world: &mut TacksWorld, contents: String) {
+    import_file(world, "tasks.json", &contents);
+}
+
+#[when("I import the output of tk list")]
+async fn i_import_the_output_of_tk_list(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "list", "-a"]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk list failed: {}",
+        world.last_stderr
+    );
+    let contents = world.last_stdout.clone();
+    import_file(world, "export.json", &contents);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the import reports {int} task(s)")]
+async fn the_import_reports(world: &mut TacksWorld, count: usize) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk import failed: {}",
+        world.last_stderr
+    );
+    let expected = format!("Imported {count} task(s)");
+    assert!(
+        world.last_stdout.contains(&expected),
+        "expected {expected:?} in: {}",
+        world.last_stdout
+    );
+}
+
+#[then(expr = "there are {int} tasks titled {string}")]
+async fn there_are_tasks_titled(world: &mut TacksWorld, count: usize, title: String) {
+    let tasks = all_tasks(world);
+    let found = tasks
+        .iter()
+        .filter(|t| t["title"] == title.as_str())
+        .count();
+    assert_eq!(found, count, "tasks: {tasks:?}");
+}
+
+#[then(expr = "the imported task {string} has status {string}")]
+async fn the_imported_task_has_status(world: &mut TacksWorld, title: String, status: String) {
+    let tasks = all_tasks(world);
+    assert_eq!(task_titled(&tasks, &title)["status"], status.as_str());
+}
+
+#[then(expr = "the imported task {string} is a subtask of {string}")]
+async fn the_imported_task_is_a_subtask_of(world: &mut TacksWorld, child: String, parent: String) {
+    let tasks = all_tasks(world);
+    let parent_id = task_titled(&tasks, &parent)["id"].clone();
+    assert_eq!(task_titled(&tasks, &child)["parent_id"], parent_id);
+}
+
+#[then(expr = "the imported task {string} is blocked by {string}")]
+async fn the_imported_task_is_blocked_by(world: &mut TacksWorld, child: String, blocker: String) {
+    let tasks = all_tasks(world);
+    let child_id = task_titled(&tasks, &child)["id"]
+        .as_str()
+        .expect("task has no id")
+        .to_string();
+    let blocker_id = task_titled(&tasks, &blocker)["id"].clone();
+    run_tk(world, &["--json", "show", &child_id]);
+    let json: Value = serde_json::from_str(&world.last_stdout).expect("tk show output is not JSON");
+    let blockers = json["blockers"].as_array().expect("no blockers array");
+    assert!(
+        blockers.iter().any(|b| b["id"] == blocker_id),
+        "{child:?} is not blocked by {blocker:?}: {json}"
+    );
+}
+
+#[then("nothing was imported")]
+async fn nothing_was_imported(world: &mut TacksWorld) {
+    assert_ne!(world.last_exit_code, 0, "expected tk import to fail");
+    let tasks = all_tasks(world);
+    assert!(tasks.is_empty(), "expected no tasks, got: {tasks:?}");
+}
@@ -0,0 +1,188 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+/// Run `tk init --prefix <prefix>` against a fresh temp directory. Unlike
+/// "a tacks database is initialized", the outcome is recorded rather than
+/// asserted so failing prefixes can be checked.
+#[when(expr = "I run tk init with prefix {string}")]
+async fn i_run_tk_init_with_prefix(world: &mut TacksWorld, prefix: String) {
+    let dir = tempfile::TempDir::new().expect("create temp dir");
+    let db_path = dir.path().join(".tacks").join("tacks.db");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", &db_path)
+        .args(["init", "--prefix", &prefix])
+        .output()
+        .expect("failed to run tk init");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+    world.db_path = Some(db_path);
+    world.db_dir = Some(dir);
+}
+
+/// Run `tk init --prefix <prefix> --id-length <n>` against a fresh temp
+/// directory, recording the outcome.
+#[when(expr = "I run tk init with prefix {string} and ID length {string}")]
+async fn i_run_tk_init_with_id_length(world: &mut TacksWorld, prefix: String, length: String) {
+    let dir = tempfile::TempDir::new().expect("create temp dir");
+    let db_path = dir.path().join(".tacks").join("tacks.db");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", &db_path)
+        .args(["init", "--prefix", &prefix, "--id-length", &length])
+        .output()
+        .expect("failed to run tk init");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+    world.db_path = Some(db_path);
+    world.db_dir = Some(dir);
+}
+
+/// Re-run `tk init --prefix <prefix>` against the scenario's existing database.
+#[when(expr = "I run tk init again with prefix {string}")]
+async fn i_run_tk_init_again_with_prefix(world: &mut TacksWorld, prefix: String) {
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(["init", "--prefix", &prefix])
+        .output()
+        .expect("failed to run tk init");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+/// Run `tk reprefix <prefix>`. On success every task alias is re-pointed at
+/// its renamed ID so later steps keep working.
+#[when(expr = "I run tk reprefix {string}")]
+async fn i_run_tk_reprefix(world: &mut TacksWorld, prefix: String) {
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(["reprefix", &prefix])
+        .output()
+        .expect("failed to run tk reprefix");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+    assert!(
+        output.status.success(),
+        "tk reprefix failed: {}",
+        world.last_stderr
+    );
+
+    for id in world.task_ids.values_mut() {
+        let (_, rest) = id.split_once('-').expect("task ID has no prefix");
+        *id = format!("{prefix}-{rest}");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then("no database file was created")]
+async fn no_database_file_was_created(world: &mut TacksWorld) {
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    assert!(
+        !db_path.exists(),
+        "expected no database at {} after a rejected init",
+        db_path.display()
+    );
+}
+
+#[then(expr = "new task IDs start with {string}")]
+async fn new_task_ids_start_with(world: &mut TacksWorld, expected: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk init failed: {}",
+        world.last_stderr
+    );
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(["--json", "create", "Prefix probe"])
+        .output()
+        .expect("failed to run tk create");
+    assert!(
+        output.status.success(),
+        "tk create failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("create output is not valid JSON");
+    let id = json["id"].as_str().expect("create JSON has no 'id' field");
+    assert!(
+        id.starts_with(&expected),
+        "expected task ID to start with {expected:?} but got {id:?}"
+    );
+}
+
+#[then(expr = "new task IDs have {int} hex digits after {string}")]
+async fn new_task_ids_have_hex_digits(world: &mut TacksWorld, digits: usize, prefix: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk init failed: {}",
+        world.last_stderr
+    );
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(["--json", "create", "Length probe"])
+        .output()
+        .expect("failed to run tk create");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("create output is not valid JSON");
+    let id = json["id"].as_str().expect("create JSON has no 'id' field");
+    let hash = id
+        .strip_prefix(&prefix)
+        .unwrap_or_else(|| panic!("expected {id:?} to start with {prefix:?}"));
+    assert_eq!(hash.len(), digits, "unexpected hash in {id:?}");
+    assert!(
+        hash.chars().all(|c| c.is_ascii_hexdigit()),
+        "non-hex hash in {id:?}"
+    );
+}
+
+#[then(expr = "the task {string} has an ID starting with {string}")]
+async fn the_task_has_an_id_starting_with(world: &mut TacksWorld, alias: String, expected: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    assert!(
+        id.starts_with(&expected),
+        "expected {id:?} to start with {expected:?}"
+    );
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(["show", &id])
+        .output()
+        .expect("failed to run tk show");
+    assert!(
+        output.status.success(),
+        "renamed task {id} not found: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
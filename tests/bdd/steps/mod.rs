@@ -1,15 +1,37 @@
 pub mod agent_steps;
+pub mod archive_steps;
 pub mod blocked_steps;
+pub mod board_steps;
+pub mod bulk_update_steps;
 pub mod children_steps;
 pub mod close_guard_steps;
+pub mod color_steps;
 pub mod common_steps;
+pub mod config_steps;
+pub mod delete_steps;
 pub mod dep_steps;
+pub mod doctor_steps;
+pub mod effort_steps;
+pub mod empty_state_steps;
 pub mod epic_status_steps;
 pub mod epic_steps;
+pub mod export_steps;
 pub mod filter_steps;
+pub mod history_steps;
+pub mod import_steps;
+pub mod init_steps;
 pub mod inline_edit_steps;
 pub mod notes_steps;
+pub mod pagination_steps;
 pub mod parent_filter_steps;
+pub mod porcelain_steps;
+pub mod prune_steps;
+pub mod remote_steps;
+pub mod tag_steps;
 pub mod task_steps;
+pub mod template_steps;
+pub mod text_file_steps;
+pub mod view_steps;
+pub mod watch_steps;
 pub mod web_api_steps;
 pub mod web_steps;
@@ -0,0 +1,111 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+use crate::steps::web_steps::http_get;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+/// Run `tk --json list` with `args` and remember the page's task IDs, so
+/// later steps can page on from it even after other commands have run.
+fn list_page(world: &mut TacksWorld, args: &[&str]) {
+    let mut full = vec!["--json", "list"];
+    full.extend_from_slice(args);
+    run_tk(world, &full);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk list failed: {}",
+        world.last_stderr
+    );
+    world.last_page_ids = page_ids(world);
+}
+
+/// IDs of the tasks in the last `tk --json list` output, in order.
+fn page_ids(world: &TacksWorld) -> Vec<String> {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("list output is not valid JSON");
+    json.as_array()
+        .expect("list JSON is not an array")
+        .iter()
+        .map(|t| t["id"].as_str().unwrap_or("").to_string())
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I list the first page of {int} tasks")]
+async fn i_list_the_first_page(world: &mut TacksWorld, size: u32) {
+    list_page(world, &["--limit", &size.to_string()]);
+}
+
+/// Use the last task of the previous page as the cursor.
+#[when(expr = "I list the next page of {int} tasks")]
+async fn i_list_the_next_page(world: &mut TacksWorld, size: u32) {
+    let cursor = world
+        .last_page_ids
+        .last()
+        .cloned()
+        .expect("previous page was empty — nothing to page after");
+    list_page(world, &["--after", &cursor, "--limit", &size.to_string()]);
+}
+
+#[when(expr = "I list tasks after cursor {string}")]
+async fn i_list_tasks_after_cursor(world: &mut TacksWorld, cursor: String) {
+    run_tk(world, &["--json", "list", "--after", &cursor]);
+}
+
+#[when(expr = "I GET the API task list after {string} with limit {int}")]
+async fn i_get_the_api_task_list_after(world: &mut TacksWorld, alias: String, limit: u32) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    http_get(world, &format!("/api/tasks?after={id}&limit={limit}")).await;
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+/// Compare the page against a comma-separated list of aliases, in order.
+#[then(expr = "the page contains exactly {string}")]
+async fn the_page_contains_exactly(world: &mut TacksWorld, aliases: String) {
+    let expected: Vec<String> = aliases
+        .split(',')
+        .filter(|a| !a.is_empty())
+        .map(|a| {
+            world
+                .task_ids
+                .get(a)
+                .unwrap_or_else(|| panic!("no task with alias '{a}'"))
+                .clone()
+        })
+        .collect();
+    assert_eq!(world.last_page_ids, expected, "unexpected page contents");
+}
@@ -0,0 +1,117 @@
+#![allow(deprecated)]
+use std::collections::HashMap;
+
+use cucumber::{then, when};
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+/// Trivial porcelain parser: records are separated by blank lines and each
+/// line is split on the first `=`.
+fn parse_porcelain(output: &str) -> Vec<HashMap<String, String>> {
+    output
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| {
+            block
+                .lines()
+                .map(|line| {
+                    let (key, value) = line
+                        .split_once('=')
+                        .unwrap_or_else(|| panic!("porcelain line has no '=': {line:?}"));
+                    (key.to_string(), value.to_string())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when("I list tasks in porcelain mode")]
+async fn i_list_tasks_in_porcelain_mode(world: &mut TacksWorld) {
+    run_tk(world, &["--porcelain", "list"]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk list --porcelain failed: {}",
+        world.last_stderr
+    );
+}
+
+#[when(expr = "I show task {string} in porcelain mode")]
+async fn i_show_task_in_porcelain_mode(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--porcelain", "show", &id]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk show --porcelain failed: {}",
+        world.last_stderr
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the porcelain output parses into {int} records")]
+async fn the_porcelain_output_parses_into(world: &mut TacksWorld, expected: i64) {
+    let records = parse_porcelain(&world.last_stdout);
+    assert_eq!(
+        records.len() as i64,
+        expected,
+        "expected {expected} porcelain record(s) but got: {}",
+        world.last_stdout
+    );
+}
+
+#[then(expr = "a porcelain record has {string} equal to {string}")]
+async fn a_porcelain_record_has(world: &mut TacksWorld, key: String, expected: String) {
+    let records = parse_porcelain(&world.last_stdout);
+    let found = records
+        .iter()
+        .any(|r| r.get(&key).map(String::as_str) == Some(expected.as_str()));
+    assert!(
+        found,
+        "expected a porcelain record with {key}={expected} but got: {}",
+        world.last_stdout
+    );
+}
+
+#[then("repeating the porcelain list produces identical output")]
+async fn repeating_the_porcelain_list_is_identical(world: &mut TacksWorld) {
+    let first = world.last_stdout.clone();
+    run_tk(world, &["--porcelain", "list"]);
+    assert_eq!(
+        first, world.last_stdout,
+        "porcelain output changed between runs"
+    );
+}
@@ -0,0 +1,128 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn task_id(world: &TacksWorld, alias: &str) -> String {
+    world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone()
+}
+
+/// IDs listed in the `tasks` array of the last `tk --json prune` report.
+fn pruned_ids(world: &TacksWorld) -> Vec<String> {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("prune output is not valid JSON");
+    json["tasks"]
+        .as_array()
+        .expect("prune JSON has no 'tasks' array")
+        .iter()
+        .map(|v| v.as_str().unwrap_or("").to_string())
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+/// Rewrite the task's `updated_at` and activity log so it looks like it was
+/// closed `days` ago. There is no CLI for this, so the rows are edited
+/// directly.
+#[when(expr = "I backdate the task {string} by {int} days")]
+async fn i_backdate_the_task(world: &mut TacksWorld, alias: String, days: i64) {
+    let id = task_id(world, &alias);
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let conn = rusqlite::Connection::open(db_path).expect("failed to open database");
+    let when = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+    conn.execute(
+        "UPDATE tasks SET updated_at = ?1 WHERE id = ?2",
+        rusqlite::params![when, id],
+    )
+    .expect("failed to backdate task");
+    conn.execute(
+        "UPDATE activity SET created_at = ?1 WHERE task_id = ?2",
+        rusqlite::params![when, id],
+    )
+    .expect("failed to backdate task activity");
+}
+
+#[when(expr = "I prune done tasks older than {int} days")]
+async fn i_prune_done_tasks(world: &mut TacksWorld, days: u32) {
+    let days = days.to_string();
+    run_tk(world, &["--json", "prune", "--older-than", &days, "--yes"]);
+}
+
+#[when(expr = "I dry-run prune done tasks older than {int} days")]
+async fn i_dry_run_prune_done_tasks(world: &mut TacksWorld, days: u32) {
+    let days = days.to_string();
+    run_tk(world, &["--json", "prune", "--older-than", &days]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the prune report lists {string}")]
+async fn the_prune_report_lists(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    let ids = pruned_ids(world);
+    assert!(ids.contains(&id), "expected {id} in prune report: {ids:?}");
+}
+
+#[then(expr = "the prune report does not list {string}")]
+async fn the_prune_report_does_not_list(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    let ids = pruned_ids(world);
+    assert!(
+        !ids.contains(&id),
+        "expected {id} NOT in prune report: {ids:?}"
+    );
+}
+
+#[then(expr = "the task {string} no longer exists")]
+async fn the_task_no_longer_exists(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    run_tk(world, &["show", &id]);
+    assert_ne!(
+        world.last_exit_code, 0,
+        "expected task {id} to be gone but tk show succeeded"
+    );
+}
+
+#[then(expr = "the task {string} still exists")]
+async fn the_task_still_exists(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    run_tk(world, &["show", &id]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "expected task {id} to exist: {}",
+        world.last_stderr
+    );
+}
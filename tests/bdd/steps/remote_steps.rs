@@ -0,0 +1,98 @@
+#![allow(deprecated)]
+use cucumber::when;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with `TACKS_SERVER` pointing at the in-process test server and
+/// `TACKS_DB` pointing at a file that does not exist, so any direct database
+/// access would be visible as a missing or empty database.
+/// Stores stdout, stderr, and exit code on the world.
+async fn run_tk_remote(world: &mut TacksWorld, args: &[&str]) {
+    let port = world
+        .server_port
+        .expect("server not started — add 'Given the web server is running'");
+    let unused_db = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?")
+        .with_file_name("not-used.db");
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+
+    // The server runs on this runtime, so run the blocking CLI call elsewhere.
+    let output = tokio::task::spawn_blocking(move || {
+        assert_cmd::Command::cargo_bin("tk")
+            .expect("tk binary not found")
+            .env("TACKS_DB", &unused_db)
+            .env("TACKS_SERVER", format!("http://127.0.0.1:{port}"))
+            .args(&args)
+            .output()
+            .expect("failed to run tk")
+    })
+    .await
+    .expect("tk task panicked");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn task_id(world: &TacksWorld, alias: &str) -> String {
+    world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone()
+}
+
+fn assert_success(world: &TacksWorld) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "expected tk to succeed through the server: {}",
+        world.last_stderr
+    );
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when("I list tasks through the server")]
+async fn i_list_tasks_through_the_server(world: &mut TacksWorld) {
+    run_tk_remote(world, &["list"]).await;
+    assert_success(world);
+}
+
+#[when("I list ready tasks through the server")]
+async fn i_list_ready_tasks_through_the_server(world: &mut TacksWorld) {
+    run_tk_remote(world, &["ready"]).await;
+    assert_success(world);
+}
+
+#[when(expr = "I show the API task {string} through the server")]
+async fn i_show_the_task_through_the_server(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    run_tk_remote(world, &["show", &id]).await;
+    assert_success(world);
+}
+
+#[when(expr = "I create a task {string} through the server")]
+async fn i_create_a_task_through_the_server(world: &mut TacksWorld, title: String) {
+    run_tk_remote(world, &["create", &title]).await;
+    assert_success(world);
+}
+
+#[when(expr = "I close the API task {string} through the server")]
+async fn i_close_the_task_through_the_server(world: &mut TacksWorld, alias: String) {
+    let id = task_id(world, &alias);
+    run_tk_remote(world, &["close", &id]).await;
+    assert_success(world);
+}
+
+#[when("I run tk stats through the server")]
+async fn i_run_stats_through_the_server(world: &mut TacksWorld) {
+    run_tk_remote(world, &["stats"]).await;
+}
@@ -0,0 +1,92 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn assert_success(world: &TacksWorld, what: &str) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "{what} failed: {}",
+        world.last_stderr
+    );
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when("I list tags in JSON")]
+async fn i_list_tags_in_json(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "tag", "list"]);
+    assert_success(world, "tk tag list");
+}
+
+#[when(expr = "I rename the tag {string} to {string}")]
+async fn i_rename_the_tag(world: &mut TacksWorld, old: String, new: String) {
+    run_tk(world, &["tag", "rename", &old, &new]);
+    assert_success(world, "tk tag rename");
+}
+
+#[when(expr = "I remove the tag {string} everywhere")]
+async fn i_remove_the_tag(world: &mut TacksWorld, tag: String) {
+    run_tk(world, &["tag", "remove", &tag]);
+    assert_success(world, "tk tag remove");
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the tag list shows {string} on {int} task(s)")]
+async fn the_tag_list_shows(world: &mut TacksWorld, tag: String, count: i64) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("tag list output is not valid JSON");
+    let entry = json
+        .as_array()
+        .expect("tag list is not an array")
+        .iter()
+        .find(|e| e["tag"].as_str() == Some(tag.as_str()))
+        .unwrap_or_else(|| panic!("tag '{tag}' not in: {}", world.last_stdout));
+    assert_eq!(
+        entry["count"].as_i64(),
+        Some(count),
+        "wrong count for '{tag}'"
+    );
+}
+
+#[then(expr = "the tag list does not show {string}")]
+async fn the_tag_list_does_not_show(world: &mut TacksWorld, tag: String) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("tag list output is not valid JSON");
+    let found = json
+        .as_array()
+        .expect("tag list is not an array")
+        .iter()
+        .any(|e| e["tag"].as_str() == Some(tag.as_str()));
+    assert!(!found, "tag '{tag}' still listed in: {}", world.last_stdout);
+}
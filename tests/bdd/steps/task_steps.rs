@@ -84,6 +84,34 @@ async fn i_create_a_task_with_title_priority_tags(
     create_task_with_alias(world, "last", &[&title, "-p", &priority_str, "-t", &tags]);
 }
 
+#[when(expr = "I create a task with title {string} and priority {string}")]
+async fn i_create_a_task_with_named_priority(
+    world: &mut TacksWorld,
+    title: String,
+    priority: String,
+) {
+    create_task_with_alias(world, "last", &[&title, "-p", &priority]);
+}
+
+#[when(expr = "I try to create a task with title {string} and priority {string}")]
+async fn i_try_to_create_a_task_with_priority(
+    world: &mut TacksWorld,
+    title: String,
+    priority: String,
+) {
+    run_tk(world, &["create", &title, "-p", &priority]);
+}
+
+#[when(expr = "I set the task status to {string}")]
+async fn i_set_the_task_status(world: &mut TacksWorld, status: String) {
+    let id = world
+        .task_ids
+        .get("last")
+        .expect("no 'last' task id — create a task first")
+        .clone();
+    run_tk(world, &["update", &id, "-s", &status]);
+}
+
 #[when("I show the task")]
 async fn i_show_the_task(world: &mut TacksWorld) {
     let id = world
@@ -94,6 +122,27 @@ async fn i_show_the_task(world: &mut TacksWorld) {
     run_tk(world, &["--json", "show", &id]);
 }
 
+#[when(expr = "I toggle the tags {string} on the task")]
+async fn i_toggle_tags_on_the_task(world: &mut TacksWorld, tags: String) {
+    let id = world
+        .task_ids
+        .get("last")
+        .expect("no 'last' task id — create a task first")
+        .clone();
+    let mut args = vec!["update".to_string(), id];
+    for tag in tags.split(',') {
+        args.push("--toggle-tag".to_string());
+        args.push(tag.trim().to_string());
+    }
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    run_tk(world, &args);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk update --toggle-tag failed: {}",
+        world.last_stderr
+    );
+}
+
 #[when("I close the task")]
 async fn i_close_the_task(world: &mut TacksWorld) {
     let id = world
@@ -104,6 +153,34 @@ async fn i_close_the_task(world: &mut TacksWorld) {
     run_tk(world, &["--json", "close", &id]);
 }
 
+/// Show several tasks in one call. Unknown aliases are passed through as IDs.
+#[when(expr = "I show the tasks {string} in JSON")]
+async fn i_show_the_tasks_in_json(world: &mut TacksWorld, aliases: String) {
+    let ids: Vec<String> = aliases
+        .split(',')
+        .map(|a| {
+            world
+                .task_ids
+                .get(a)
+                .cloned()
+                .unwrap_or_else(|| a.to_string())
+        })
+        .collect();
+    let mut args = vec!["--json", "show"];
+    args.extend(ids.iter().map(String::as_str));
+    run_tk(world, &args);
+}
+
+#[when("I list tasks in JSON with labels")]
+async fn i_list_tasks_in_json_with_labels(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "--labels", "list"]);
+}
+
+#[when("I list tasks in JSON")]
+async fn i_list_tasks_in_json(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "list"]);
+}
+
 // ---------------------------------------------------------------------------
 // Then steps
 // ---------------------------------------------------------------------------
@@ -212,6 +289,50 @@ async fn i_close_task_with_reason(world: &mut TacksWorld, alias: String, reason:
     run_tk(world, &["close", &id, "--reason", &reason]);
 }
 
+#[when(expr = "I retitle the task {string} to {string}")]
+async fn i_retitle_the_task(world: &mut TacksWorld, alias: String, title: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["update", &id, "--title", &title]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk update failed: {}",
+        world.last_stderr
+    );
+}
+
+/// `tk update --title ... --if-unchanged-since <created_at>`: succeeds only
+/// while the task has not been touched since it was created.
+#[when(expr = "I try to retitle task {string} to {string} if unchanged since creation")]
+async fn i_try_to_retitle_if_unchanged(world: &mut TacksWorld, alias: String, title: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--json", "show", &id]);
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("show output is not valid JSON");
+    let created_at = json["created_at"]
+        .as_str()
+        .expect("task has no created_at")
+        .to_string();
+    run_tk(
+        world,
+        &[
+            "update",
+            &id,
+            "--title",
+            &title,
+            "--if-unchanged-since",
+            &created_at,
+        ],
+    );
+}
+
 #[when(expr = "I try to close task {string} with reason {string}")]
 async fn i_try_to_close_task_with_reason(world: &mut TacksWorld, alias: String, reason: String) {
     let id = world
@@ -222,6 +343,59 @@ async fn i_try_to_close_task_with_reason(world: &mut TacksWorld, alias: String,
     run_tk(world, &["close", &id, "--reason", &reason]);
 }
 
+#[when(expr = "I reopen the task {string} as {string}")]
+async fn i_reopen_the_task_as(world: &mut TacksWorld, alias: String, status: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["--json", "reopen", &id, "--status", &status]);
+}
+
+#[when(expr = "I try to reopen the task {string}")]
+async fn i_try_to_reopen_the_task(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["reopen", &id]);
+}
+
+#[when(expr = "I search for {string}")]
+async fn i_search_for(world: &mut TacksWorld, query: String) {
+    run_tk(world, &["--json", "search", &query]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk search failed: {}",
+        world.last_stderr
+    );
+}
+
+#[when(expr = "I close task {string} with comments {string} and {string}")]
+async fn i_close_task_with_two_comments(
+    world: &mut TacksWorld,
+    alias: String,
+    first: String,
+    second: String,
+) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(
+        world,
+        &["close", &id, "--comment", &first, "--comment", &second],
+    );
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk close failed: {}",
+        world.last_stderr
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — close reason
 // ---------------------------------------------------------------------------
@@ -237,3 +411,85 @@ async fn the_task_details_show_close_reason(world: &mut TacksWorld, expected: St
         expected, actual
     );
 }
+
+#[then("the task details have no close_reason")]
+async fn the_task_details_have_no_close_reason(world: &mut TacksWorld) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    assert!(
+        json["close_reason"].is_null(),
+        "expected a null close_reason but got {}",
+        json["close_reason"]
+    );
+}
+
+#[then(expr = "the shown JSON array has titles {string}")]
+async fn the_shown_json_array_has_titles(world: &mut TacksWorld, expected: String) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    let titles: Vec<&str> = json
+        .as_array()
+        .expect("show output is not a JSON array")
+        .iter()
+        .map(|t| t["title"].as_str().unwrap_or(""))
+        .collect();
+    let expected: Vec<&str> = expected.split(',').collect();
+    assert_eq!(titles, expected, "unexpected titles in show output");
+}
+
+/// Find the task with the given alias in the last JSON list output.
+fn listed_task(world: &TacksWorld, alias: &str) -> Value {
+    let id = world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"));
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    json.as_array()
+        .expect("list output is not a JSON array")
+        .iter()
+        .find(|t| t["id"].as_str() == Some(id.as_str()))
+        .unwrap_or_else(|| panic!("task '{alias}' not in list output"))
+        .clone()
+}
+
+#[then(expr = "the listed task {string} has {string} set to {string}")]
+async fn the_listed_task_has_field(
+    world: &mut TacksWorld,
+    alias: String,
+    field: String,
+    expected: String,
+) {
+    let task = listed_task(world, &alias);
+    assert_eq!(
+        task[&field].as_str(),
+        Some(expected.as_str()),
+        "unexpected {field} on task '{alias}': {task}"
+    );
+}
+
+#[then(expr = "the listed task {string} has no {string} field")]
+async fn the_listed_task_has_no_field(world: &mut TacksWorld, alias: String, field: String) {
+    let task = listed_task(world, &alias);
+    assert!(
+        task.get(&field).is_none(),
+        "expected no {field} on task '{alias}': {task}"
+    );
+}
+
+#[then(expr = "the task details show comments {string} then {string}")]
+async fn the_task_details_show_comments_in_order(
+    world: &mut TacksWorld,
+    first: String,
+    second: String,
+) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    let bodies: Vec<&str> = json["comments"]
+        .as_array()
+        .expect("show JSON has no 'comments' array")
+        .iter()
+        .map(|c| c["body"].as_str().unwrap_or(""))
+        .collect();
+    assert_eq!(bodies, vec![first.as_str(), second.as_str()]);
+}
@@ -0,0 +1,122 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+/// The tasks printed by `tk --json create --from-template`, root first.
+fn created_tree(world: &TacksWorld) -> Vec<Value> {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("create output is not valid JSON");
+    json.as_array()
+        .expect("create --from-template output is not a JSON array")
+        .clone()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "I save task {string} as the template {string}")]
+async fn i_save_the_template(world: &mut TacksWorld, alias: String, name: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    run_tk(world, &["template", "save", &name, "--from", &id]);
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk template save failed: {}",
+        world.last_stderr
+    );
+}
+
+#[when(expr = "I create a task from the template {string} titled {string}")]
+async fn i_create_from_template(world: &mut TacksWorld, name: String, title: String) {
+    run_tk(
+        world,
+        &["--json", "create", &title, "--from-template", &name],
+    );
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk create --from-template failed: {}",
+        world.last_stderr
+    );
+}
+
+#[when(expr = "I try to create a task from the template {string}")]
+async fn i_try_to_create_from_template(world: &mut TacksWorld, name: String) {
+    run_tk(world, &["create", "--from-template", &name]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the created tree has {int} tasks")]
+async fn the_created_tree_has(world: &mut TacksWorld, count: usize) {
+    assert_eq!(created_tree(world).len(), count, "{}", world.last_stdout);
+}
+
+#[then(expr = "the created root is titled {string} and tagged {string}")]
+async fn the_created_root_is(world: &mut TacksWorld, title: String, tag: String) {
+    let tree = created_tree(world);
+    let root = &tree[0];
+    assert_eq!(root["title"].as_str(), Some(title.as_str()), "{root}");
+    let tags: Vec<&str> = root["tags"]
+        .as_array()
+        .expect("root has no tags array")
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    assert!(tags.contains(&tag.as_str()), "missing tag {tag} on {root}");
+}
+
+#[then(expr = "the created subtasks are {string} under the created root")]
+async fn the_created_subtasks_are(world: &mut TacksWorld, titles: String) {
+    let tree = created_tree(world);
+    let root_id = tree[0]["id"].as_str().expect("root has no id");
+    let expected: Vec<&str> = titles.split(',').map(str::trim).collect();
+    let actual: Vec<&str> = tree[1..]
+        .iter()
+        .map(|t| {
+            assert_eq!(t["parent_id"].as_str(), Some(root_id), "{t}");
+            t["title"].as_str().expect("task has no title")
+        })
+        .collect();
+    assert_eq!(actual, expected);
+}
+
+#[then("the created tasks all have new IDs")]
+async fn the_created_tasks_have_new_ids(world: &mut TacksWorld) {
+    for task in created_tree(world) {
+        let id = task["id"].as_str().expect("task has no id").to_string();
+        assert!(
+            !world.task_ids.values().any(|existing| *existing == id),
+            "template reused existing ID {id}"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers (local to this module)
+// ---------------------------------------------------------------------------
+
+/// Run `tk` with the given args and stdin against the world's database.
+/// Stores stdout, stderr, and exit code on the world.
+fn run_tk(world: &mut TacksWorld, args: &[&str], stdin: &str) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .write_stdin(stdin)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+fn task_id(world: &TacksWorld, alias: &str) -> String {
+    world
+        .task_ids
+        .get(alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone()
+}
+
+/// Write `contents` to a file in the world's temp dir and return its path.
+fn write_file(world: &TacksWorld, name: &str, contents: &str) -> String {
+    let path = world
+        .db_dir
+        .as_ref()
+        .expect("db_dir not set")
+        .path()
+        .join(name);
+    std::fs::write(&path, contents).expect("failed to write text file");
+    path.to_string_lossy().into_owned()
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(
+    expr = "I create a task called {string} with its description piped as lines {string} and {string}"
+)]
+async fn i_create_with_piped_description(
+    world: &mut TacksWorld,
+    alias: String,
+    first: String,
+    second: String,
+) {
+    run_tk(
+        world,
+        &["--json", "create", &alias, "--description-file", "-"],
+        &format!("{first}\n{second}\n"),
+    );
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk create failed: {}",
+        world.last_stderr
+    );
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("create output is not valid JSON");
+    let id = json["id"]
+        .as_str()
+        .expect("created task has no id")
+        .to_string();
+    world.task_ids.insert(alias, id);
+}
+
+#[when(expr = "I update task {string} with notes from a file containing {string}")]
+async fn i_update_notes_from_file(world: &mut TacksWorld, alias: String, notes: String) {
+    let id = task_id(world, &alias);
+    let path = write_file(world, "notes.txt", &format!("{notes}\n"));
+    run_tk(world, &["update", &id, "--notes-file", &path], "");
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk update --notes-file failed: {}",
+        world.last_stderr
+    );
+}
+
+#[when(expr = "I try to update task {string} with description {string} and a description file")]
+async fn i_try_description_and_file(world: &mut TacksWorld, alias: String, description: String) {
+    let id = task_id(world, &alias);
+    let path = write_file(world, "description.txt", "from file\n");
+    run_tk(
+        world,
+        &[
+            "update",
+            &id,
+            "--description",
+            &description,
+            "--description-file",
+            &path,
+        ],
+        "",
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the task details show a description with lines {string} and {string}")]
+async fn the_description_has_lines(world: &mut TacksWorld, first: String, second: String) {
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("last output is not valid JSON");
+    assert_eq!(
+        json["description"].as_str(),
+        Some(format!("{first}\n{second}").as_str()),
+        "unexpected description in: {}",
+        world.last_stdout
+    );
+}
+
+#[then("the update is rejected")]
+async fn the_update_is_rejected(world: &mut TacksWorld) {
+    assert_ne!(
+        world.last_exit_code, 0,
+        "expected tk update to fail, stdout: {}",
+        world.last_stdout
+    );
+}
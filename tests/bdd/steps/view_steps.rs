@@ -0,0 +1,72 @@
+#![allow(deprecated)]
+use cucumber::{then, when};
+use serde_json::Value;
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn run_tk(world: &mut TacksWorld, args: &[&str]) {
+    let db_path = world
+        .db_path
+        .as_ref()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+
+    let output = assert_cmd::Command::cargo_bin("tk")
+        .expect("tk binary not found")
+        .env("TACKS_DB", db_path)
+        .args(args)
+        .output()
+        .expect("failed to run tk");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+/// `tk view save <name> <filters>`; `filters` is split on whitespace.
+#[when(expr = "I save the view {string} with filters {string}")]
+async fn i_save_the_view(world: &mut TacksWorld, name: String, filters: String) {
+    let mut args = vec!["view", "save", name.as_str()];
+    args.extend(filters.split_whitespace());
+    run_tk(world, &args);
+}
+
+#[when(expr = "I run the view {string}")]
+async fn i_run_the_view(world: &mut TacksWorld, name: String) {
+    run_tk(world, &["--json", "view", "run", &name]);
+}
+
+#[when("I list the saved views")]
+async fn i_list_the_saved_views(world: &mut TacksWorld) {
+    run_tk(world, &["--json", "view", "list"]);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the saved views are {string}")]
+async fn the_saved_views_are(world: &mut TacksWorld, names: String) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk view list failed: {}",
+        world.last_stderr
+    );
+    let json: Value =
+        serde_json::from_str(&world.last_stdout).expect("view list output is not valid JSON");
+    let actual: Vec<&str> = json
+        .as_array()
+        .expect("view list JSON is not an array")
+        .iter()
+        .filter_map(|v| v["name"].as_str())
+        .collect();
+    let expected: Vec<&str> = names.split(',').map(str::trim).collect();
+    assert_eq!(actual, expected);
+}
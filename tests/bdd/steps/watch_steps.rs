@@ -0,0 +1,89 @@
+#![allow(deprecated)]
+use std::process::{Command, Stdio};
+
+use cucumber::{then, when};
+use tokio::time::{Duration, sleep};
+
+use crate::TacksWorld;
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+/// Start `tk watch --interval 1`, create a task from a second process once
+/// the first render is out, then stop the watcher with SIGINT (Ctrl+C).
+#[when(expr = "I watch the project while a task titled {string} is created")]
+async fn i_watch_while_creating(world: &mut TacksWorld, title: String) {
+    let db_path = world
+        .db_path
+        .clone()
+        .expect("db_path not set — did you forget 'Given a tacks database is initialized'?");
+    let tk = assert_cmd::cargo::cargo_bin("tk");
+
+    let watcher = Command::new(&tk)
+        .env("TACKS_DB", &db_path)
+        .args(["watch", "--interval", "1"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start tk watch");
+    sleep(Duration::from_millis(1500)).await;
+
+    let created = Command::new(&tk)
+        .env("TACKS_DB", &db_path)
+        .args(["create", &title])
+        .output()
+        .expect("failed to run tk create");
+    assert!(created.status.success(), "tk create failed");
+    sleep(Duration::from_millis(2500)).await;
+
+    let interrupted = Command::new("kill")
+        .args(["-INT", &watcher.id().to_string()])
+        .status()
+        .expect("failed to send SIGINT");
+    assert!(interrupted.success(), "kill -INT failed");
+    let output = watcher
+        .wait_with_output()
+        .expect("failed to wait for tk watch");
+
+    world.last_stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    world.last_stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    world.last_exit_code = output.status.code().unwrap_or(-1);
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the watch printed the summary {int} times")]
+async fn the_watch_printed_the_summary(world: &mut TacksWorld, expected: usize) {
+    let renders = world.last_stdout.matches("# Tacks: Project Status").count();
+    assert_eq!(
+        renders, expected,
+        "unexpected number of renders in:\n{}",
+        world.last_stdout
+    );
+}
+
+#[then(expr = "the last watch summary mentions {string}")]
+async fn the_last_watch_summary_mentions(world: &mut TacksWorld, expected: String) {
+    let last = world
+        .last_stdout
+        .rsplit("# Tacks: Project Status")
+        .next()
+        .unwrap_or_default();
+    assert!(
+        last.contains(&expected),
+        "expected the last render to mention {expected:?}:\n{}",
+        world.last_stdout
+    );
+}
+
+#[then("the watch exited cleanly")]
+async fn the_watch_exited_cleanly(world: &mut TacksWorld) {
+    assert_eq!(
+        world.last_exit_code, 0,
+        "tk watch did not exit cleanly: {}",
+        world.last_stderr
+    );
+}
@@ -3,7 +3,7 @@ use cucumber::{given, then, when};
 use serde_json::{Value, json};
 
 use crate::TacksWorld;
-use crate::steps::web_steps::http_get;
+use crate::steps::web_steps::{http_get, http_get_with_last_etag};
 
 // ---------------------------------------------------------------------------
 // HTTP helper functions
@@ -176,6 +176,158 @@ async fn i_created_a_subtask_via_api(
     .await;
 }
 
+#[given(expr = "I created {int} subtasks via API under {string}")]
+async fn i_created_n_subtasks_via_api(world: &mut TacksWorld, count: usize, parent_alias: String) {
+    let parent_id = world
+        .task_ids
+        .get(&parent_alias)
+        .unwrap_or_else(|| panic!("no task with alias '{parent_alias}'"))
+        .clone();
+    for n in 1..=count {
+        api_create_task(
+            world,
+            &format!("{parent_alias}-child{n}"),
+            json!({"title": format!("Child {n}"), "parent_id": parent_id}),
+        )
+        .await;
+    }
+}
+
+/// POST `count` tasks back to back, recording the status, body, and
+/// Retry-After header of the last response.
+#[when(expr = "I create {int} tasks via API in a burst")]
+async fn i_create_tasks_in_a_burst(world: &mut TacksWorld, count: usize) {
+    let port = world
+        .server_port
+        .expect("server not started — add 'Given the web server is running'");
+    let url = format!("http://127.0.0.1:{port}/api/tasks");
+    for n in 1..=count {
+        let resp = world
+            .http_client
+            .post(&url)
+            .json(&json!({"title": format!("Burst {n}")}))
+            .send()
+            .await
+            .unwrap_or_else(|e| panic!("POST {url} failed: {e}"));
+        world.last_response_status = Some(resp.status().as_u16());
+        world.last_response_retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        world.last_response_content_type = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        world.last_response_body = Some(resp.text().await.unwrap_or_default());
+    }
+}
+
+#[when(expr = "I send {int} concurrent API reads and writes")]
+async fn i_send_concurrent_api_requests(world: &mut TacksWorld, count: usize) {
+    let port = world
+        .server_port
+        .expect("server not started — add 'Given the web server is running'");
+    let base = format!("http://127.0.0.1:{port}/api/tasks");
+    let mut handles = Vec::new();
+    for n in 0..count {
+        let client = world.http_client.clone();
+        let base = base.clone();
+        handles.push(tokio::spawn(async move {
+            let req = if n % 2 == 0 {
+                client
+                    .post(&base)
+                    .json(&json!({"title": format!("Concurrent {n}")}))
+            } else {
+                client.get(&base)
+            };
+            req.send()
+                .await
+                .unwrap_or_else(|e| panic!("request {n} failed: {e}"))
+                .status()
+                .as_u16()
+        }));
+    }
+    let statuses = tokio::time::timeout(tokio::time::Duration::from_secs(10), async {
+        let mut statuses = Vec::new();
+        for handle in handles {
+            statuses.push(handle.await.expect("request task panicked"));
+        }
+        statuses
+    })
+    .await
+    .expect("concurrent requests did not finish within 10s — handlers deadlocked?");
+    for (n, status) in statuses.iter().enumerate() {
+        assert!((200..300).contains(status), "request {n} returned {status}");
+    }
+}
+
+/// Take SQLite's write lock from a connection outside the server, so any
+/// server write waits on it (up to the connection's busy timeout).
+#[given("another connection holds the database write lock")]
+async fn another_connection_holds_the_write_lock(world: &mut TacksWorld) {
+    let db_path = world.db_path.as_ref().expect("db_path not set");
+    let conn = rusqlite::Connection::open(db_path).expect("failed to open database");
+    conn.execute_batch("BEGIN IMMEDIATE")
+        .expect("failed to take the write lock");
+    world.write_lock = Some(conn);
+}
+
+/// Start POST /api/tasks in the background; it holds the server's write
+/// connection while it waits for the lock.
+#[when("a task is being created via API in the background")]
+async fn a_task_is_being_created_in_the_background(world: &mut TacksWorld) {
+    let port = world
+        .server_port
+        .expect("server not started — add 'Given the web server is running'");
+    let client = world.http_client.clone();
+    world.pending_request = Some(tokio::spawn(async move {
+        client
+            .post(format!("http://127.0.0.1:{port}/api/tasks"))
+            .json(&json!({"title": "Waiting writer"}))
+            .send()
+            .await
+            .expect("background POST failed")
+            .status()
+            .as_u16()
+    }));
+    // Let the request reach the database before the next step.
+    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+}
+
+/// GET `path`, failing if it does not answer within `secs` seconds.
+#[when(expr = "I GET {string} within {int} second(s)")]
+async fn i_get_path_within(world: &mut TacksWorld, path: String, secs: u64) {
+    tokio::time::timeout(
+        tokio::time::Duration::from_secs(secs),
+        http_get(world, &path),
+    )
+    .await
+    .unwrap_or_else(|_| panic!("GET {path} took over {secs}s — reads waited on the writer"));
+}
+
+/// Release the outside write lock and check the background request then
+/// completes successfully.
+#[then("once the write lock is released the background request succeeds")]
+async fn once_released_the_background_request_succeeds(world: &mut TacksWorld) {
+    let conn = world.write_lock.take().expect("no write lock held");
+    conn.execute_batch("ROLLBACK")
+        .expect("failed to release the write lock");
+    let status = world
+        .pending_request
+        .take()
+        .expect("no background request")
+        .await
+        .expect("background request panicked");
+    assert_eq!(status, 201, "background POST /api/tasks returned {status}");
+}
+
+#[when(expr = "I wait {int} milliseconds")]
+async fn i_wait_milliseconds(_world: &mut TacksWorld, ms: u64) {
+    tokio::time::sleep(tokio::time::Duration::from_millis(ms)).await;
+}
+
 #[given(expr = "I closed the API task {string}")]
 async fn i_closed_the_api_task(world: &mut TacksWorld, alias: String) {
     let id = world
@@ -262,6 +414,26 @@ async fn i_get_the_api_task(world: &mut TacksWorld, alias: String) {
     http_get(world, &format!("/api/tasks/{id}")).await;
 }
 
+#[when(expr = "I GET the API task {string} with labels")]
+async fn i_get_the_api_task_with_labels(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    http_get(world, &format!("/api/tasks/{id}?labels=true")).await;
+}
+
+#[when(expr = "I GET the API task {string} with the last ETag")]
+async fn i_get_the_api_task_with_last_etag(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    http_get_with_last_etag(world, &format!("/api/tasks/{id}")).await;
+}
+
 #[when(expr = "I PATCH the API task {string} with body {string}")]
 async fn i_patch_the_api_task(world: &mut TacksWorld, alias: String, raw_body: String) {
     let id = world
@@ -274,6 +446,36 @@ async fn i_patch_the_api_task(world: &mut TacksWorld, alias: String, raw_body: S
     http_patch(world, &format!("/api/tasks/{id}"), body).await;
 }
 
+/// PATCH with the previous GET's ETag as `If-Match`.
+#[when(expr = "I PATCH the API task {string} if it still matches the last ETag with body {string}")]
+async fn i_patch_the_api_task_if_match(world: &mut TacksWorld, alias: String, raw_body: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let etag = world
+        .last_response_etag
+        .clone()
+        .expect("no ETag on the previous response");
+    let body: Value = serde_json::from_str(&raw_body)
+        .unwrap_or_else(|e| panic!("step body {raw_body:?} is not valid JSON: {e}"));
+    let port = world
+        .server_port
+        .expect("server not started — add 'Given the web server is running'");
+    let url = format!("http://127.0.0.1:{port}/api/tasks/{id}");
+    let resp = world
+        .http_client
+        .patch(&url)
+        .header(reqwest::header::IF_MATCH, &etag)
+        .json(&body)
+        .send()
+        .await
+        .unwrap_or_else(|e| panic!("PATCH {url} failed: {e}"));
+    world.last_response_status = Some(resp.status().as_u16());
+    world.last_response_body = Some(resp.text().await.unwrap_or_default());
+}
+
 #[when(expr = "I POST the close endpoint for API task {string} with body {string}")]
 async fn i_post_close_endpoint(world: &mut TacksWorld, alias: String, raw_body: String) {
     let id = world
@@ -286,6 +488,31 @@ async fn i_post_close_endpoint(world: &mut TacksWorld, alias: String, raw_body:
     http_post(world, &format!("/api/tasks/{id}/close"), body).await;
 }
 
+#[when(expr = "I POST the status endpoint for API task {string} with body {string}")]
+async fn i_post_status_endpoint(world: &mut TacksWorld, alias: String, raw_body: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let body: Value = serde_json::from_str(&raw_body)
+        .unwrap_or_else(|e| panic!("step body {raw_body:?} is not valid JSON: {e}"));
+    http_post(world, &format!("/api/tasks/{id}/status"), body).await;
+}
+
+#[when(expr = "I POST the board move endpoint for API task {string} with body {string}")]
+async fn i_post_board_move_endpoint(world: &mut TacksWorld, alias: String, raw_body: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    let mut body: Value = serde_json::from_str(&raw_body)
+        .unwrap_or_else(|e| panic!("step body {raw_body:?} is not valid JSON: {e}"));
+    body["id"] = Value::String(id);
+    http_post(world, "/api/board/move", body).await;
+}
+
 #[when(expr = "I POST the deps endpoint for API task {string} with body {string}")]
 async fn i_post_deps_endpoint(world: &mut TacksWorld, alias: String, raw_body: String) {
     let id = world
@@ -297,10 +524,10 @@ async fn i_post_deps_endpoint(world: &mut TacksWorld, alias: String, raw_body: S
     let mut body: Value = serde_json::from_str(&raw_body)
         .unwrap_or_else(|e| panic!("step body {raw_body:?} is not valid JSON: {e}"));
     // If parent_id is an alias, resolve it to the actual task id
-    if let Some(parent_ref) = body.get("parent_id").and_then(|v| v.as_str()) {
-        if let Some(resolved_id) = world.task_ids.get(parent_ref).cloned() {
-            body["parent_id"] = Value::String(resolved_id);
-        }
+    if let Some(parent_ref) = body.get("parent_id").and_then(|v| v.as_str())
+        && let Some(resolved_id) = world.task_ids.get(parent_ref).cloned()
+    {
+        body["parent_id"] = Value::String(resolved_id);
     }
     http_post(world, &format!("/api/tasks/{id}/deps"), body).await;
 }
@@ -346,6 +573,16 @@ async fn i_get_comments_endpoint(world: &mut TacksWorld, alias: String) {
     http_get(world, &format!("/api/tasks/{id}/comments")).await;
 }
 
+#[when(expr = "I GET the history endpoint for API task {string}")]
+async fn i_get_history_endpoint(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    http_get(world, &format!("/api/tasks/{id}/history")).await;
+}
+
 #[when(expr = "I GET the children endpoint for API task {string}")]
 async fn i_get_children_endpoint(world: &mut TacksWorld, alias: String) {
     let id = world
@@ -356,6 +593,70 @@ async fn i_get_children_endpoint(world: &mut TacksWorld, alias: String) {
     http_get(world, &format!("/api/tasks/{id}/children")).await;
 }
 
+#[when(expr = "I GET the children endpoint for API task {string} with query {string}")]
+async fn i_get_children_endpoint_with_query(world: &mut TacksWorld, alias: String, query: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    http_get(world, &format!("/api/tasks/{id}/children?{query}")).await;
+}
+
+/// GET /api/tasks/:id/blockers — tasks that `alias` is blocked by.
+#[when(expr = "I GET the blockers endpoint for API task {string}")]
+async fn i_get_blockers_endpoint(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    http_get(world, &format!("/api/tasks/{id}/blockers")).await;
+}
+
+/// GET /api/tasks/:id/dependents — tasks that are blocked by `alias`.
+#[when(expr = "I GET the dependents endpoint for API task {string}")]
+async fn i_get_dependents_endpoint(world: &mut TacksWorld, alias: String) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    http_get(world, &format!("/api/tasks/{id}/dependents")).await;
+}
+
+/// GET /api/tasks?{param}=<id of alias>, for relational list filters.
+#[when(expr = "I GET the API task list with {string} set to task {string}")]
+async fn i_get_the_api_task_list_with_task_param(
+    world: &mut TacksWorld,
+    param: String,
+    alias: String,
+) {
+    let id = world
+        .task_ids
+        .get(&alias)
+        .unwrap_or_else(|| panic!("no task with alias '{alias}'"))
+        .clone();
+    http_get(world, &format!("/api/tasks?{param}={id}")).await;
+}
+
+/// GET /api/tasks?ids=... for a comma-separated list of aliases. Entries that
+/// are not known aliases are passed through verbatim as (missing) task IDs.
+#[when(expr = "I GET the API tasks by ids {string}")]
+async fn i_get_the_api_tasks_by_ids(world: &mut TacksWorld, aliases: String) {
+    let ids: Vec<String> = aliases
+        .split(',')
+        .map(|a| {
+            world
+                .task_ids
+                .get(a)
+                .cloned()
+                .unwrap_or_else(|| a.to_string())
+        })
+        .collect();
+    http_get(world, &format!("/api/tasks?ids={}", ids.join(","))).await;
+}
+
 // ---------------------------------------------------------------------------
 // Then steps — JSON response assertions
 // ---------------------------------------------------------------------------
@@ -371,6 +672,30 @@ async fn the_response_json_has_field(world: &mut TacksWorld, field: String) {
     );
 }
 
+/// Assert that the response is a task object, or an array of them, whose
+/// keys are exactly the comma-separated `keys`.
+#[then(expr = "the response JSON tasks have exactly the keys {string}")]
+async fn the_response_json_tasks_have_exactly_the_keys(world: &mut TacksWorld, keys: String) {
+    let json = parse_last_response(world);
+    let tasks = match json {
+        Value::Array(items) => items,
+        other => vec![other],
+    };
+    assert!(!tasks.is_empty(), "expected at least one task");
+    let mut expected: Vec<&str> = keys.split(',').collect();
+    expected.sort_unstable();
+    for task in &tasks {
+        let mut actual: Vec<&str> = task
+            .as_object()
+            .unwrap_or_else(|| panic!("expected a JSON object, got: {task}"))
+            .keys()
+            .map(String::as_str)
+            .collect();
+        actual.sort_unstable();
+        assert_eq!(actual, expected, "task: {task}");
+    }
+}
+
 /// Assert that the response body JSON has a string field equal to expected.
 #[then(expr = "the response JSON field {string} equals {string}")]
 async fn the_response_json_field_equals_string(
@@ -474,6 +799,71 @@ async fn the_response_json_array_has_length(world: &mut TacksWorld, expected: i6
     );
 }
 
+#[then("the response has a Retry-After header")]
+async fn the_response_has_retry_after(world: &mut TacksWorld) {
+    let value = world
+        .last_response_retry_after
+        .as_deref()
+        .expect("expected a Retry-After header on the last response");
+    assert!(
+        value.parse::<u64>().is_ok_and(|secs| secs >= 1),
+        "expected Retry-After to be a positive number of seconds, got '{value}'"
+    );
+}
+
+/// Assert the titles of the response JSON array, in order (comma-separated).
+#[then(expr = "the response JSON array titles are {string}")]
+async fn the_response_json_array_titles_are(world: &mut TacksWorld, expected: String) {
+    let json = parse_last_response(world);
+    let arr = json
+        .as_array()
+        .unwrap_or_else(|| panic!("expected response body to be a JSON array but got: {json}"));
+    let titles: Vec<&str> = arr.iter().filter_map(|t| t["title"].as_str()).collect();
+    let expected: Vec<&str> = expected.split(',').map(str::trim).collect();
+    assert_eq!(titles, expected, "unexpected title order");
+}
+
+/// Assert the titles of a GET /api/search response's `tasks`, in order
+/// (comma-separated).
+#[then(expr = "the search results are titled {string}")]
+async fn the_search_results_are_titled(world: &mut TacksWorld, expected: String) {
+    let json = parse_last_response(world);
+    let titles: Vec<&str> = json["tasks"]
+        .as_array()
+        .unwrap_or_else(|| panic!("expected a 'tasks' array but got: {json}"))
+        .iter()
+        .filter_map(|t| t["title"].as_str())
+        .collect();
+    let expected: Vec<&str> = expected.split(',').map(str::trim).collect();
+    assert_eq!(titles, expected, "unexpected search results");
+}
+
+/// Assert that a GET /api/search response has no tasks.
+#[then("the search returned no tasks")]
+async fn the_search_returned_no_tasks(world: &mut TacksWorld) {
+    let json = parse_last_response(world);
+    let tasks = json["tasks"]
+        .as_array()
+        .unwrap_or_else(|| panic!("expected a 'tasks' array but got: {json}"));
+    assert!(
+        tasks.is_empty(),
+        "expected no search results but got: {json}"
+    );
+}
+
+/// Assert the `snippet` of the search result with the given title.
+#[then(expr = "the search result {string} has snippet {string}")]
+async fn the_search_result_has_snippet(world: &mut TacksWorld, title: String, expected: String) {
+    let json = parse_last_response(world);
+    let hit = json["tasks"]
+        .as_array()
+        .unwrap_or_else(|| panic!("expected a 'tasks' array but got: {json}"))
+        .iter()
+        .find(|t| t["title"].as_str() == Some(title.as_str()))
+        .unwrap_or_else(|| panic!("no search result titled '{title}' in: {json}"));
+    assert_eq!(hit["snippet"].as_str(), Some(expected.as_str()));
+}
+
 /// Assert that the response body JSON is an empty array.
 #[then("the response JSON is an empty array")]
 async fn the_response_json_is_empty_array(world: &mut TacksWorld) {
@@ -515,3 +905,59 @@ async fn the_response_json_nested_field_equals_int(
         "expected JSON path '{path}' to equal {expected} but got {actual}"
     );
 }
+
+/// Assert that a nested JSON field (dot-separated path) equals a string.
+/// Example path: "error.kind"
+#[then(expr = "the response JSON nested field {string} equals {string}")]
+async fn the_response_json_nested_field_equals_string(
+    world: &mut TacksWorld,
+    path: String,
+    expected: String,
+) {
+    let json = parse_last_response(world);
+    let mut current = &json;
+    for key in path.split('.') {
+        current = current.get(key).unwrap_or_else(|| {
+            panic!(
+                "expected path '{path}' to exist in JSON but '{key}' not found at this level:\n{}",
+                serde_json::to_string_pretty(current).unwrap_or_default()
+            )
+        });
+    }
+    let actual = current
+        .as_str()
+        .unwrap_or_else(|| panic!("expected '{path}' to be a string but got: {current}"));
+    assert_eq!(
+        actual, expected,
+        "expected JSON path '{path}' to equal '{expected}' but got '{actual}'"
+    );
+}
+
+/// Assert that a string field in the response JSON equals the crate version
+/// the test binary was built from.
+#[then(expr = "the response JSON field {string} equals the crate version")]
+async fn the_response_json_field_equals_crate_version(world: &mut TacksWorld, field: String) {
+    let json = parse_last_response(world);
+    let actual = json[&field].as_str().unwrap_or("");
+    assert_eq!(
+        actual,
+        env!("CARGO_PKG_VERSION"),
+        "expected response JSON field '{field}' to equal the crate version"
+    );
+}
+
+/// Assert that a numeric field in the response JSON is strictly positive.
+#[then(expr = "the response JSON field {string} is a positive number")]
+async fn the_response_json_field_is_positive(world: &mut TacksWorld, field: String) {
+    let json = parse_last_response(world);
+    let actual = json[&field].as_f64().unwrap_or_else(|| {
+        panic!(
+            "expected field '{field}' to be a number in: {}",
+            serde_json::to_string_pretty(&json).unwrap_or_default()
+        )
+    });
+    assert!(
+        actual > 0.0,
+        "expected response JSON field '{field}' to be positive but got {actual}"
+    );
+}
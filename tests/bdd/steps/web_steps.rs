@@ -11,6 +11,12 @@ use crate::TacksWorld;
 /// Binds to a random free port (port 0), stores the port and task handle
 /// in the world for later use and cleanup.
 pub async fn start_test_server(world: &mut TacksWorld) -> u16 {
+    start_test_server_with_rate(world, None).await
+}
+
+/// Like [`start_test_server`], optionally limiting mutating `/api/*` requests
+/// to `rate` per second.
+pub async fn start_test_server_with_rate(world: &mut TacksWorld, rate: Option<u32>) -> u16 {
     let db_path = world
         .db_path
         .as_ref()
@@ -18,10 +24,10 @@ pub async fn start_test_server(world: &mut TacksWorld) -> u16 {
         .clone();
 
     let db = tacks::db::Database::open(&db_path).expect("failed to open database for web server");
-    let state = tacks::web::AppState {
-        db: std::sync::Arc::new(std::sync::Mutex::new(db)),
-        last_data_version: std::sync::Arc::new(std::sync::atomic::AtomicI64::new(0)),
-    };
+    let mut state = tacks::web::AppState::new(db, &db_path);
+    if let Some(per_sec) = rate {
+        state = state.with_rate_limit(per_sec);
+    }
     let app = tacks::web::create_router(state);
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
@@ -79,6 +85,11 @@ pub async fn http_get(world: &mut TacksWorld, path: &str) -> (u16, String) {
         .get(reqwest::header::CONTENT_TYPE)
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string());
+    world.last_response_etag = resp
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
     let body = resp
         .text()
         .await
@@ -99,6 +110,12 @@ async fn the_web_server_is_running(world: &mut TacksWorld) {
     start_test_server(world).await;
 }
 
+/// Start the web server with `tk serve --rate <n>` semantics.
+#[given(expr = "the web server is running with a rate limit of {int} per second")]
+async fn the_web_server_is_running_with_rate_limit(world: &mut TacksWorld, rate: u32) {
+    start_test_server_with_rate(world, Some(rate)).await;
+}
+
 // ---------------------------------------------------------------------------
 // When steps
 // ---------------------------------------------------------------------------
@@ -109,6 +126,35 @@ async fn i_get_path(world: &mut TacksWorld, path: String) {
     http_get(world, &path).await;
 }
 
+/// Repeat a conditional GET, sending the previous response's ETag as
+/// `If-None-Match`.
+#[when(expr = "I GET {string} with the last ETag")]
+async fn i_get_path_with_last_etag(world: &mut TacksWorld, path: String) {
+    http_get_with_last_etag(world, &path).await;
+}
+
+/// GET `path` with the previous response's ETag as `If-None-Match`, recording
+/// the status and body.
+pub async fn http_get_with_last_etag(world: &mut TacksWorld, path: &str) {
+    let etag = world
+        .last_response_etag
+        .clone()
+        .expect("no ETag on the previous response");
+    let port = world
+        .server_port
+        .expect("server not started — add 'Given the web server is running'");
+    let url = format!("http://127.0.0.1:{port}{path}");
+    let resp = world
+        .http_client
+        .get(&url)
+        .header(reqwest::header::IF_NONE_MATCH, &etag)
+        .send()
+        .await
+        .unwrap_or_else(|e| panic!("GET {url} failed: {e}"));
+    world.last_response_status = Some(resp.status().as_u16());
+    world.last_response_body = Some(resp.text().await.unwrap_or_default());
+}
+
 /// Perform a GET request with HX-Request header (simulates HTMX request).
 #[when(expr = "I HTMX GET {string}")]
 async fn i_htmx_get_path(world: &mut TacksWorld, path: String) {
@@ -142,6 +188,15 @@ async fn i_htmx_get_path(world: &mut TacksWorld, path: String) {
 // Then steps
 // ---------------------------------------------------------------------------
 
+/// Assert that the most recent GET response carried an ETag header.
+#[then("the response has an ETag")]
+async fn the_response_has_an_etag(world: &mut TacksWorld) {
+    assert!(
+        world.last_response_etag.is_some(),
+        "expected an ETag header on the last response"
+    );
+}
+
 /// Assert that the most recent HTTP response had the given status code.
 #[then(expr = "the response status is {int}")]
 async fn the_response_status_is(world: &mut TacksWorld, expected: u16) {
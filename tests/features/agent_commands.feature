@@ -12,6 +12,39 @@ Feature: Agent workflow commands
     Then the task "work" has status "in_progress"
     And the task "work" has assignee "agent"
 
+  Scenario: tk next claims the highest-priority ready task
+    Given I have a task called "later" with title "Polish docs" and priority 3
+    And I have a task called "urgent" with title "Fix outage" and priority 0
+    When I take the next task as "alice"
+    Then the task "urgent" has status "in_progress"
+    And the task "urgent" has assignee "alice"
+    And the task "later" has status "open"
+    When I take the next task as "bob"
+    Then the task "later" has assignee "bob"
+
+  Scenario: tk next with nothing ready exits cleanly
+    Given I have a task called "only" with title "Only task"
+    When I take the next task as "alice"
+    And I take the next task
+    Then the output contains "Nothing ready"
+
+  Scenario: tk mine lists the tasks an assignee is working on
+    Given I have a task called "claimed" with title "Claimed task"
+    And I have a task called "free" with title "Unclaimed task"
+    And I have a task called "finished" with title "Finished task"
+    When I claim the task "claimed"
+    And I claim the task "finished"
+    And I close the task "finished"
+    And I list my tasks as "agent"
+    Then the shown JSON array has titles "Claimed task"
+
+  Scenario: tk list --assignee "" lists unassigned tasks
+    Given I have a task called "claimed" with title "Claimed task"
+    And I have a task called "free" with title "Unclaimed task"
+    When I claim the task "claimed"
+    And I list tasks assigned to ""
+    Then the shown JSON array has titles "Unclaimed task"
+
   Scenario: tk comment adds a comment visible in tk show
     Given I have a task called "commented" with title "Task to comment on"
     When I add a comment "Making progress" to the task "commented"
@@ -37,6 +70,18 @@ Feature: Agent workflow commands
     Then the stats JSON has a "by_status" field
     And the stats JSON has a "by_priority" field
     And the stats JSON has a "by_tag" field
+    And the stats JSON has a "by_assignee" field
+
+  Scenario: tk stats counts tasks per assignee with an unassigned bucket
+    Given I have a task called "a" with title "Agent A work"
+    And I have a task called "b" with title "Agent B work"
+    And I have a task called "c" with title "Nobody's work"
+    And the task "a" is assigned to "agent-a"
+    And the task "b" is assigned to "agent-b"
+    When I run tk stats with json output
+    Then the stats JSON shows assignee "agent-a" count of 1
+    And the stats JSON shows assignee "agent-b" count of 1
+    And the stats JSON shows assignee "unassigned" count of 1
 
   Scenario: tk ready --limit 1 returns only one task
     Given I have a task called "a" with title "Ready task A"
@@ -45,6 +90,70 @@ Feature: Agent workflow commands
     When I run tk ready with limit 1
     Then the ready list contains exactly 1 task
 
+  Scenario: tk ready --format ids prints bare IDs in ready order
+    Given I have a task called "later" with title "Later work" and priority 3
+    And I have a task called "urgent" with title "Urgent work" and priority 0
+    And I have a task called "soon" with title "Soon work" and priority 1
+    And I have a task called "stuck" with title "Stuck work" and priority 0
+    When I add a dependency so "stuck" is blocked by "later"
+    And I run tk ready with ids output
+    Then the output is exactly the IDs of "urgent,soon,later"
+
+  Scenario: tk list --format ids prints bare IDs
+    Given I have a task called "only" with title "Only task"
+    When I run tk list with ids output
+    Then the output is exactly the IDs of "only"
+
+  Scenario: tk list --json --envelope wraps results with ok:true
+    Given I have a task called "one" with title "First"
+    And I have a task called "two" with title "Second"
+    When I run tk list with envelope output
+    Then the envelope is ok with 2 items
+
+  Scenario: tk list --ndjson prints one task object per line
+    Given I have a task called "one" with title "First"
+    And I have a task called "two" with title "Second"
+    When I run tk list with ndjson output
+    Then the output is 2 JSON lines with titles "First,Second"
+
+  Scenario: --ndjson wins over --json
+    Given I have a task called "one" with title "First"
+    When I run tk list with both json and ndjson output
+    Then the output is 1 JSON lines with titles "First"
+
+  Scenario: --ndjson cannot be combined with --envelope
+    When I run tk list with ndjson and envelope output
+    Then the command should fail
+
+  Scenario: A failing command under --envelope reports ok:false
+    When I run tk show "tk-0000" with envelope output
+    Then the envelope is an error mentioning "not found"
+
+  Scenario: tk list --fields keeps only the requested keys
+    Given I have a task called "one" with title "First"
+    And I have a task called "two" with title "Second"
+    When I run tk list with fields "id,title"
+    Then every JSON task has exactly the keys "id,title"
+
+  Scenario: tk show --fields can select detail keys
+    Given I have a task called "detail" with title "Detailed"
+    When I show the task "detail" with fields "id,status,comments"
+    Then every JSON task has exactly the keys "id,status,comments"
+
+  Scenario: tk list --fields rejects an unknown key
+    Given I have a task called "one" with title "First"
+    When I run tk list with fields "id,bogus"
+    Then the command should fail
+    And the error output contains "unknown field: bogus"
+
+  Scenario: tk ready --explain names the blocker of a non-ready task
+    Given I have a task called "blocker" with title "Do this first"
+    And I have a task called "waiting" with title "Then this"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I run tk ready with explain output
+    Then the explained ready list contains "blocker"
+    And "waiting" is not ready because it is blocked by "blocker"
+
   Scenario: tk ready with no ready tasks returns empty list
     Given I have a task called "blocker" with title "The blocker"
     And I have a task called "blocked" with title "The blocked"
@@ -53,3 +162,69 @@ Feature: Agent workflow commands
     And I close the task "blocked"
     When I run tk ready with json output
     Then the ready list is empty
+
+  Scenario: tk comment --reply-to threads a reply under its parent
+    Given I have a task called "talk" with title "Discussion"
+    When I add a comment "Which approach?" to the task "talk"
+    And I reply "The simple one" on the task "talk" to the comment "Which approach?" of the task "talk"
+    Then the comment "The simple one" on the task "talk" replies to "Which approach?"
+
+  Scenario: tk comment --reply-to rejects a comment from another task
+    Given I have a task called "here" with title "This task"
+    And I have a task called "there" with title "Other task"
+    When I add a comment "Over there" to the task "there"
+    And I reply "Misplaced" on the task "here" to the comment "Over there" of the task "there"
+    Then the command should fail
+    And the error output contains "belongs to task"
+
+  Scenario: tk comment edit rewrites a comment and marks it edited
+    Given I have a task called "typo" with title "Commented task"
+    When I add a comment "Fixd the bug" to the task "typo"
+    And I edit the comment "Fixd the bug" of the task "typo" to "Fixed the bug"
+    Then the comment "Fixed the bug" on the task "typo" is marked as edited
+
+  Scenario: tk comment delete keeps replies as top-level comments
+    Given I have a task called "thread" with title "Threaded task"
+    When I add a comment "Wrong task, sorry" to the task "thread"
+    And I reply "No worries" on the task "thread" to the comment "Wrong task, sorry" of the task "thread"
+    And I delete the comment "Wrong task, sorry" of the task "thread"
+    Then the task "thread" has only the top-level comment "No worries"
+
+  Scenario: tk comment delete rejects an unknown comment
+    When I try to delete the comment "9999"
+    Then the command should fail
+    And the error output contains "comment not found"
+
+  Scenario: tk comment --file keeps newlines in a single comment
+    Given I have a task called "log" with title "Task with a log"
+    When I add a comment from a file with lines "error: boom" and "at main.rs:42" to the task "log"
+    And I show the task "log"
+    Then the task details show one comment with lines "error: boom" and "at main.rs:42"
+
+  Scenario: tk comment rejects a body and --file together
+    Given I have a task called "both" with title "Ambiguous comment"
+    When I try to add comment "inline" and a file to the task "both"
+    Then the command should fail
+    And the error output contains "not both"
+
+  Scenario: TACKS_FORMAT=json makes a bare tk list emit JSON
+    Given I have a task called "fmt" with title "Formatted task"
+    When I run tk list with TACKS_FORMAT set to "json"
+    Then the output is a JSON array
+
+  Scenario: --table overrides TACKS_FORMAT
+    Given I have a task called "fmt" with title "Formatted task"
+    When I run tk list with TACKS_FORMAT set to "json" and the flag "--table"
+    Then the output is not JSON
+    And the output contains "Formatted task"
+
+  Scenario: The format config key applies when TACKS_FORMAT is unset
+    Given I have a task called "fmt" with title "Formatted task"
+    And the config key "format" is set to "json"
+    When I run tk list
+    Then the output is a JSON array
+
+  Scenario: An unknown TACKS_FORMAT is rejected
+    When I run tk list with TACKS_FORMAT set to "yaml"
+    Then the command should fail
+    And the error output contains "invalid TACKS_FORMAT: yaml"
@@ -0,0 +1,49 @@
+Feature: Archiving tasks
+  As a user with a long project history
+  I want to archive old closed tasks
+  So that lists stay focused without deleting anything
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: An archived task is hidden from lists but still shown by ID
+    Given I have a task called "stale" with title "Stale task"
+    And I have a task called "live" with title "Live task"
+    When I close task "stale" with reason "done"
+    And I archive the task "stale"
+    And I list all tasks including closed
+    Then the shown JSON array has titles "Live task"
+    When I list all tasks including archived
+    Then the shown JSON array has titles "Stale task,Live task"
+    When I show task "stale" in JSON
+    Then the shown task is archived
+
+  Scenario: tk unarchive brings a task back into lists
+    Given I have a task called "back" with title "Coming back"
+    When I archive the task "back"
+    And I unarchive the task "back"
+    And I list all tasks including closed
+    Then the shown JSON array has titles "Coming back"
+
+  Scenario: tk archive --closed-before archives old done tasks only
+    Given I have a task called "old" with title "Ancient history"
+    And I have a task called "recent" with title "Just finished"
+    And I have a task called "open" with title "Still open"
+    When I close task "old" with reason "done"
+    And I close task "recent" with reason "done"
+    And I backdate the task "old" by 120 days
+    And I archive tasks closed more than 90 days ago
+    Then the archive reports the tasks "old"
+    When I list all tasks including closed
+    Then the shown JSON array has titles "Just finished,Still open"
+
+  Scenario: An archived task drops out of tk blocked
+    Given I have a task called "blocker" with title "Migrate schema"
+    And I have a task called "waiting" with title "Use new schema"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I archive the task "waiting"
+    And I run tk blocked with JSON
+    Then the blocked output does not contain "Use new schema"
+    When I unarchive the task "waiting"
+    And I run tk blocked with JSON
+    Then the blocked output contains "Use new schema"
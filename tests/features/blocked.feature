@@ -14,6 +14,18 @@ Feature: Blocked task listing
     Then the blocked output contains "Waiting on blocker"
     And the blocked output does not contain "Must finish first"
 
+  Scenario: tk blocked lists the open blockers of each task
+    Given I have a task called "open-blocker" with title "Still open"
+    And I have a task called "done-blocker" with title "Already done"
+    And I have a task called "waiting" with title "Waiting on both"
+    When I add a dependency so "waiting" is blocked by "open-blocker"
+    And I add a dependency so "waiting" is blocked by "done-blocker"
+    And I close task "done-blocker" with reason "done"
+    And I run tk blocked with explanations in JSON
+    Then the blocked task "Waiting on both" is waiting on "Still open"
+    When I run tk blocked as a table
+    Then the blocked table lists "waiting" waiting on "open-blocker"
+
   Scenario: No blocked tasks shows empty list
     Given I have a task called "free" with title "Free task"
     When I run tk blocked with JSON
@@ -26,3 +38,22 @@ Feature: Blocked task listing
     And I close task "blocker" with reason "done"
     And I run tk blocked with JSON
     Then the JSON output is an empty array
+
+  Scenario: Stored blocked flags stay consistent through dependency and close changes
+    Given I have a task called "a" with title "Root blocker"
+    And I have a task called "b" with title "Middle task"
+    And I have a task called "c" with title "Leaf task"
+    When I add a dependency so "b" is blocked by "a"
+    And I add a dependency so "c" is blocked by "b"
+    Then the stored blocked flags match a fresh computation
+    When I close task "a" with reason "done"
+    Then the stored blocked flags match a fresh computation
+    When I run tk blocked with JSON
+    Then the blocked output contains "Leaf task"
+    And the blocked output does not contain "Middle task"
+    When I remove the dependency so "c" is no longer blocked by "b"
+    And I reopen the task "a"
+    Then the stored blocked flags match a fresh computation
+    When I run tk blocked with JSON
+    Then the blocked output contains "Middle task"
+    And the blocked output does not contain "Leaf task"
@@ -0,0 +1,67 @@
+Feature: Terminal kanban board
+  As an AI coding agent
+  I want to see the kanban board from the command line
+  So that I get the same column view as the web UI without a browser
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: Board JSON has the four columns
+    Given I have a task called "todo" with title "Plain open task"
+    When I run tk board with JSON
+    Then the board column "open" contains "Plain open task"
+    And the JSON output field "in_progress" is an empty array
+    And the JSON output field "blocked" is an empty array
+    And the JSON output field "done" is an empty array
+
+  Scenario: A dependency-blocked open task lands in the Blocked column
+    Given I have a task called "blocker" with title "Must finish first"
+    And I have a task called "waiting" with title "Waiting on blocker"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I run tk board with JSON
+    Then the board column "blocked" contains "Waiting on blocker"
+    And the board column "open" does not contain "Waiting on blocker"
+    And the board column "open" contains "Must finish first"
+
+  Scenario: Human-readable board shows a dependency-blocked task under Blocked
+    Given I have a task called "blocker" with title "Must finish first"
+    And I have a task called "waiting" with title "Waiting on blocker"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I run tk board
+    Then the board output lists "Waiting on blocker" under the "Blocked" heading
+    And the board output lists "Must finish first" under the "Open" heading
+
+  Scenario: Board can be filtered by priority
+    Given I have a task called "hot" with title "Urgent fix" and priority 0
+    And I have a task called "cold" with title "Someday" and priority 3
+    When I run tk board with JSON filtered by priority "0,1"
+    Then the board column "open" contains "Urgent fix"
+    And the board column "open" does not contain "Someday"
+
+  Scenario: An open task whose blocker is done lands in the Open column
+    Given I have a task called "blocker" with title "Must finish first"
+    And I have a task called "waiting" with title "Was waiting"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I close the task "blocker"
+    And I run tk board with JSON
+    Then the board column "open" contains "Was waiting"
+    And the board column "done" contains "Must finish first"
+    And the board column "blocked" does not contain "Was waiting"
+
+  Scenario: A task blocked by an in-progress task stays in the Blocked column
+    Given I have a task called "blocker" with title "Underway"
+    And I have a task called "waiting" with title "Waiting on blocker"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I set the status of "blocker" to "in_progress"
+    And I run tk board with JSON
+    Then the board column "blocked" contains "Waiting on blocker"
+    And the board column "in_progress" contains "Underway"
+
+  Scenario: Board can be limited to the subtasks of an epic
+    Given I have a task called "epic" with title "Epic task"
+    And I have a subtask called "part" of "epic" with title "Epic part"
+    And I have a task called "loose" with title "Loose task"
+    When I run tk board with JSON for the epic "epic"
+    Then the board column "open" contains "Epic part"
+    And the board column "open" does not contain "Loose task"
+    And the board column "open" does not contain "Epic task"
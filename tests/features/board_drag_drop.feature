@@ -2,7 +2,8 @@ Feature: Board drag-and-drop status transitions
   As a user viewing the kanban board
   I want to drag tasks between status columns
   So that I can update task status visually
-  The board drag-and-drop JS calls PATCH /api/tasks/:id with the new status.
+  The board drag-and-drop JS calls POST /api/board/move with the new status
+  and the card's position within the target column.
   These scenarios verify that the underlying API correctly handles each transition.
 
   Background:
@@ -68,3 +69,76 @@ Feature: Board drag-and-drop status transitions
     When I GET the API task "subtask"
     Then the response status is 200
     And the response JSON field "status" equals "in_progress"
+
+  # ---------------------------------------------------------------------------
+  # Purpose-built move endpoint — POST /api/tasks/:id/status
+  # ---------------------------------------------------------------------------
+
+  Scenario: Move endpoint takes a task from Open to In Progress
+    Given I created a task via API with title "Dragged card" as "card"
+    When I POST the status endpoint for API task "card" with body '{"status":"in_progress","rank":0}'
+    Then the response status is 200
+    And the response JSON field "status" equals "in_progress"
+    When I GET the API task "card"
+    Then the response JSON field "status" equals "in_progress"
+
+  Scenario: Move endpoint sets the position from rank with the status
+    Given I created a task via API with title "Ranked card" as "card"
+    When I POST the status endpoint for API task "card" with body '{"status":"in_progress","rank":2.5}'
+    Then the response status is 200
+    And the response JSON field "status" equals "in_progress"
+    And the response body contains "2.5"
+
+  Scenario: Move endpoint closes a task dropped on Done
+    Given I created a task via API with title "Finished card" as "card"
+    When I POST the status endpoint for API task "card" with body '{"status":"done"}'
+    Then the response status is 200
+    And the response JSON field "status" equals "done"
+
+  Scenario: Move endpoint refuses Done for an epic with open subtasks
+    Given I created a task via API with title "Busy epic" as "epic"
+    And I created a subtask via API with title "Open subtask" under "epic" as "subtask"
+    When I POST the status endpoint for API task "epic" with body '{"status":"done"}'
+    Then the response status is 409
+    When I GET the API task "epic"
+    Then the response JSON field "status" equals "open"
+
+  Scenario: Move endpoint rejects an unknown status
+    Given I created a task via API with title "Odd card" as "card"
+    When I POST the status endpoint for API task "card" with body '{"status":"sideways"}'
+    Then the response status is 422
+
+  # ---------------------------------------------------------------------------
+  # Board move endpoint — POST /api/board/move
+  # ---------------------------------------------------------------------------
+
+  Scenario: Board move sets the status and the position within the column
+    Given I created a task via API with title "Placed card" as "card"
+    When I POST the board move endpoint for API task "card" with body '{"status":"in_progress","position":2.5}'
+    Then the response status is 200
+    And the response JSON nested field "task.status" equals "in_progress"
+    And the response body contains "2.5"
+
+  Scenario: Board move warns when a blocked task is started
+    Given I created a task via API with title "Blocker" as "blocker"
+    And I created a task via API with title "Waiting card" as "waiting"
+    And I added API dependency so "waiting" is blocked by "blocker"
+    When I POST the board move endpoint for API task "waiting" with body '{"status":"in_progress"}'
+    Then the response status is 200
+    And the response JSON nested field "task.status" equals "in_progress"
+    And the response body contains "still blocked by"
+
+  Scenario: Board move refuses Done for an epic with open subtasks
+    Given I created a task via API with title "Busy epic" as "epic"
+    And I created a subtask via API with title "Open subtask" under "epic" as "subtask"
+    When I POST the board move endpoint for API task "epic" with body '{"status":"done"}'
+    Then the response status is 409
+
+  Scenario: Board move rejects an unknown status
+    Given I created a task via API with title "Odd card" as "card"
+    When I POST the board move endpoint for API task "card" with body '{"status":"sideways"}'
+    Then the response status is 422
+
+  Scenario: Board move returns 404 for an unknown task
+    When I POST "/api/board/move" with body '{"id":"tk-zzzz","status":"open"}'
+    Then the response status is 404
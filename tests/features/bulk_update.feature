@@ -0,0 +1,33 @@
+Feature: Bulk update by filter
+  As an AI coding agent
+  I want to change many matching tasks in one command
+  So that I don't have to update them one at a time
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: tk bulk-update sets the status of every task with a tag
+    Given I have a task called "a" with title "First wip" and tag "wip"
+    And I have a task called "b" with title "Second wip" and tag "wip"
+    And I have a task called "c" with title "Not wip" and tag "later"
+    When I bulk-update tasks tagged "wip" to status "in_progress"
+    Then the bulk update reports "Updated 2 tasks"
+    And the task "a" has status "in_progress"
+    And the task "b" has status "in_progress"
+    And the task "c" has status "open"
+
+  Scenario: tk bulk-update --json lists the updated tasks
+    Given I have a task called "urgent" with title "Urgent" and priority 0
+    And I have a task called "calm" with title "Calm" and priority 3
+    When I bulk-update tasks with priority 0 adding tags "hot,triage" in JSON
+    Then the bulk update returned the tasks "urgent"
+    When I show task "urgent" in JSON
+    Then the task details include tag "hot"
+    And the task details include tag "triage"
+
+  Scenario: tk bulk-update refuses to run without a filter
+    Given I have a task called "safe" with title "Leave me alone"
+    When I bulk-update tasks with no filter to status "done"
+    Then the command should fail
+    And the error output contains "--all"
+    And the task "safe" has status "open"
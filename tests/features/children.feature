@@ -23,3 +23,54 @@ Feature: List child tasks
     When I run tk children for "tk-0000"
     Then the command should fail
     And the error output contains "not found"
+
+  Scenario: Show --related lists parent, siblings, blockers, and dependents
+    Given I have a task called "epic" with title "Epic task"
+    And I have a subtask called "first" of "epic" with title "First step"
+    And I have a subtask called "second" of "epic" with title "Second step"
+    And I have a subtask called "third" of "epic" with title "Third step"
+    When I add a dependency so "second" is blocked by "first"
+    And I add a dependency so "third" is blocked by "second"
+    And I show the task "second" with related tasks
+    Then the related parent is "epic"
+    And the related "siblings" are "first,third"
+    And the related "blockers" are "first"
+    And the related "dependents" are "third"
+
+  Scenario: Show --related on a top-level task has no parent or siblings
+    Given I have a task called "solo" with title "Solo task"
+    And I have a task called "other" with title "Other task"
+    When I show the task "solo" with related tasks
+    Then the related parent is empty
+    And the related "siblings" are ""
+
+  Scenario: Moving a subtask keeps its ID and re-tags the parents
+    Given I have a task called "old" with title "Old epic"
+    And I have a task called "new" with title "New epic"
+    And I have a subtask called "part" of "old" with title "Moving part"
+    When I move the task "part" under "new"
+    And I show the task "part" with related tasks
+    Then the related parent is "new"
+    When I show task "new" in JSON
+    Then the task details include tag "epic"
+    When I show task "old" in JSON
+    Then the task details do not include tag "epic"
+
+  Scenario: Moving a task under its own subtask is rejected
+    Given I have a task called "epic" with title "Epic task"
+    And I have a subtask called "part" of "epic" with title "Part"
+    When I move the task "epic" under "part"
+    Then the command should fail
+    And the error output contains "cannot move"
+
+  Scenario: Moving a subtask to the top level detaches it
+    Given I have a task called "epic" with title "Epic task"
+    And I have a subtask called "part" of "epic" with title "Part"
+    When I move the task "part" to the top level
+    Then the task "part" has no parent
+
+  Scenario: Children are listed in numeric order past the ninth
+    Given I have a task called "parent" with title "Epic task"
+    And I have 11 subtasks of "parent"
+    When I run tk children for "parent" with JSON
+    Then the children of "parent" are numbered 1 to 11 in order
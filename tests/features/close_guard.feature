@@ -33,3 +33,48 @@ Feature: Close guard for tasks with open subtasks
     And I close task "parent" with reason "done"
     And I show task "parent" in JSON
     Then the task details show status "done"
+
+  Scenario: Closing the last open subtask auto-closes the epic when enabled
+    Given I have a task called "parent" with title "Auto-closing epic"
+    And the config key "auto_close_epic" is set to "true"
+    When I create a subtask of "parent" with title "Only child"
+    And I force close subtask "Only child"
+    And I show task "parent" in JSON
+    Then the task details show status "done"
+    And the task details show close_reason "done"
+    And the task details show a comment with body "Auto-closed: all subtasks are done"
+
+  Scenario: Closing the last open subtask leaves the epic open by default
+    Given I have a task called "parent" with title "Manual epic"
+    When I create a subtask of "parent" with title "Lonely child"
+    And I force close subtask "Lonely child"
+    And I show task "parent" in JSON
+    Then the task details show status "open"
+
+  Scenario: Auto-close only climbs one level for nested epics
+    Given I have a task called "parent" with title "Top epic"
+    And the config key "auto_close_epic" is set to "true"
+    When I create a subtask of "parent" with title "Middle epic"
+    And I create a subtask of "last_subtask" with title "Leaf task"
+    And I force close subtask "Leaf task"
+    And I show task "parent" in JSON
+    Then the task details show status "open"
+
+  Scenario: --close-parent closes the epic with its last open subtask
+    Given I have a task called "parent" with title "Flagged epic"
+    When I create a subtask of "parent" with title "Final child"
+    And I close subtask "Final child" with --close-parent
+    Then the output contains "Closed parent"
+    When I show task "parent" in JSON
+    Then the task details show status "done"
+    And the task details show close_reason "done"
+    And the task details show a comment with body "Auto-closed: all subtasks are done"
+
+  Scenario: --close-parent leaves the epic open while other subtasks are open
+    Given I have a task called "parent" with title "Busy epic"
+    When I create a subtask of "parent" with title "First child"
+    And I create a subtask of "parent" with title "Second child"
+    And I close subtask "First child" with --close-parent
+    Then the output does not contain "Closed parent"
+    When I show task "parent" in JSON
+    Then the task details show status "open"
@@ -24,8 +24,74 @@ Feature: Close reason tracking
     And I show task "old" in JSON
     Then the task details show close_reason "stale"
 
+  Scenario: tk stats counts done tasks by close reason
+    Given I have a task called "dup1" with title "Duplicate one"
+    And I have a task called "dup2" with title "Duplicate two"
+    And I have a task called "real" with title "Real work"
+    When I close task "dup1" with reason "duplicate"
+    And I close task "dup2" with reason "duplicate"
+    And I close task "real" with reason "done"
+    And I run tk stats with json output
+    Then the stats JSON shows close reason "duplicate" count of 2
+    And the stats JSON shows close reason "done" count of 1
+    And the stats JSON shows close reason "stale" count of 0
+
   Scenario: Invalid close reason is rejected
     Given I have a task called "bad" with title "Bad reason"
     When I try to close task "bad" with reason "invalid_reason"
     Then the command should fail
     And the error output contains "invalid close reason"
+
+  Scenario: Closing with repeated comments stores each one in order
+    Given I have a task called "multi" with title "Checklist task"
+    When I close task "multi" with comments "Implemented parser" and "Follow-up: add docs"
+    And I show task "multi" in JSON
+    Then the task details show status "done"
+    And the task details show comments "Implemented parser" then "Follow-up: add docs"
+
+  Scenario: Reopening a closed task clears its close reason
+    Given I have a task called "back" with title "Came back"
+    When I close task "back" with reason "stale"
+    And I reopen the task "back"
+    And I show task "back" in JSON
+    Then the task details show status "open"
+    And the task details have no close_reason
+
+  Scenario: Reopening into in_progress prints the updated task
+    Given I have a task called "redo" with title "Closed by mistake"
+    When I close task "redo" with reason "done"
+    And I reopen the task "redo" as "in_progress"
+    Then the task details show status "in_progress"
+    And the task details have no close_reason
+
+  Scenario: Reopening a task that is not closed fails
+    Given I have a task called "live" with title "Still open"
+    When I try to reopen the task "live"
+    Then the command should fail
+    And the error output contains "is not closed"
+
+  Scenario: Claiming a closed task clears its close reason
+    Given I have a task called "resumed" with title "Resumed work"
+    When I close task "resumed" with reason "duplicate"
+    And I claim the task "resumed"
+    And I show task "resumed" in JSON
+    Then the task details have no close_reason
+
+  Scenario: A reopened task drops out of the close reason counts
+    Given I have a task called "dup" with title "Duplicate"
+    And I have a task called "back" with title "Came back"
+    When I close task "dup" with reason "duplicate"
+    And I close task "back" with reason "stale"
+    And I reopen the task "back"
+    And I run tk stats with json output
+    Then the stats JSON shows close reason "duplicate" count of 1
+    And the stats JSON shows close reason "stale" count of 0
+
+  Scenario: Reopening a blocker takes its dependent off the ready list
+    Given I have a task called "blocker" with title "Migrate schema"
+    And I have a task called "waiting" with title "Use new schema"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I close task "blocker" with reason "done"
+    Then the ready list contains "Use new schema"
+    When I reopen the task "blocker"
+    Then the ready list does not contain "Use new schema"
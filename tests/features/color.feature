@@ -0,0 +1,21 @@
+Feature: Color control
+  As a user redirecting tk output to files or pagers
+  I want ANSI colors only where a terminal will render them
+  So that piped output stays clean text
+
+  Background:
+    Given a tacks database is initialized
+    And I have a task called "a" with title "Colorful task" and priority 1
+
+  Scenario: Redirected output has no escape sequences by default
+    When I list tasks with color "auto"
+    Then the command output has no ANSI escape sequences
+    And the output contains "Colorful task"
+
+  Scenario: --color=always forces escape sequences into redirected output
+    When I list tasks with color "always"
+    Then the command output has ANSI escape sequences
+
+  Scenario: --color=never disables colors
+    When I list tasks with color "never"
+    Then the command output has no ANSI escape sequences
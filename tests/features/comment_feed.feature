@@ -0,0 +1,41 @@
+Feature: Recent comments feed
+  As an AI coding agent
+  I want to see the latest comments across all tasks
+  So that I can catch up on project activity in one place
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: Recent comments span tasks, newest first
+    Given I have a task called "api" with title "Build API"
+    And I have a task called "ui" with title "Build UI"
+    When I add a comment "first on api" to the task "api"
+    And I add a comment "then on ui" to the task "ui"
+    And I add a comment "latest on api" to the task "api"
+    And I list recent comments in JSON
+    Then the recent comment bodies are "latest on api,then on ui,first on api"
+    And the recent comment "then on ui" belongs to the task "ui" titled "Build UI"
+
+  Scenario: Recent comments honor the limit
+    Given I have a task called "api" with title "Build API"
+    When I add a comment "one" to the task "api"
+    And I add a comment "two" to the task "api"
+    And I add a comment "three" to the task "api"
+    And I list recent comments in JSON with limit 2
+    Then the recent comment bodies are "three,two"
+
+  Scenario: No comments yields an empty array
+    When I list recent comments in JSON
+    Then the JSON output is an empty array
+
+  Scenario: GET /api/comments/recent returns comments across tasks
+    Given I have a task called "api" with title "Build API"
+    And I have a task called "ui" with title "Build UI"
+    When I add a comment "api note" to the task "api"
+    And I add a comment "ui note" to the task "ui"
+    Given the web server is running
+    When I GET "/api/comments/recent?limit=10"
+    Then the response status is 200
+    And the response JSON array has length 2
+    And the response body contains "Build UI"
+    And the response body contains "api note"
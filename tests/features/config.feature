@@ -0,0 +1,44 @@
+Feature: Configuration
+  As a team lead
+  I want to choose the default priority and ready-queue order for a database
+  So that tk follows our conventions without every command spelling them out
+
+  Scenario: tk init --default-priority is used when create omits -p
+    When I run tk init with default priority "high"
+    And I create a task with title "Triage inbox"
+    And I show the task
+    Then the task details show priority 1
+
+  Scenario: An explicit -p still wins over the default priority
+    When I run tk init with default priority "1"
+    And I create a task with title "Tidy docs" and priority "low"
+    And I show the task
+    Then the task details show priority 3
+
+  Scenario: The ready queue can be sorted by age first
+    Given a tacks database is initialized
+    And I have a task called "old" with title "Old chore" and priority 3
+    And I have a task called "urgent" with title "Urgent fix" and priority 0
+    When I run tk ready with json output
+    Then the shown JSON array has titles "Urgent fix,Old chore"
+    When I set the config key "ready_order" to "age"
+    And I run tk ready with json output
+    Then the shown JSON array has titles "Old chore,Urgent fix"
+
+  Scenario: tk config get and list show stored values
+    Given a tacks database is initialized
+    When I set the config key "default_priority" to "critical"
+    And I get the config key "default_priority"
+    Then the config value printed is "0"
+    When I list config in JSON
+    Then the config list shows "default_priority" set to "0"
+    And the config list shows "prefix" set to "tk"
+
+  Scenario: tk config set rejects values a key does not accept
+    Given a tacks database is initialized
+    When I try to set the config key "ready_order" to "random"
+    Then the command should fail
+    And the error output contains "invalid ready_order value"
+    When I try to set the config key "prefix" to "new"
+    Then the command should fail
+    And the error output contains "tk reprefix"
@@ -0,0 +1,51 @@
+Feature: Deleting tasks
+  As a user who created a task by mistake
+  I want to permanently delete it
+  So that it no longer clutters the database
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: Delete removes a standalone task
+    Given I have a task called "oops" with title "Created by mistake"
+    When I delete the task "oops"
+    Then the deleted task has title "Created by mistake"
+    And the task "oops" no longer exists
+
+  Scenario: Delete refuses a task that blocks others
+    Given I have a task called "blocker" with title "Schema change"
+    And I have a task called "waiting" with title "Use new schema"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I delete the task "blocker"
+    Then the command should fail
+    And the error output contains "--force"
+    And the error output names the task "waiting"
+    And the task "blocker" still exists
+
+  Scenario: Forced delete drops the dependency and unblocks dependents
+    Given I have a task called "blocker" with title "Schema change"
+    And I have a task called "waiting" with title "Use new schema"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I force delete the task "blocker"
+    Then the deleted task has title "Schema change"
+    And the task "blocker" no longer exists
+    And the ready list contains "Use new schema"
+
+  Scenario: Delete refuses a task with subtasks unless forced
+    Given I have a task called "epic" with title "Big feature"
+    And I have a subtask called "part" of "epic" with title "First part"
+    When I delete the task "epic"
+    Then the command should fail
+    And the error output names the task "part"
+    When I force delete the task "epic"
+    Then the task "epic" no longer exists
+    And the task "part" has no parent
+
+  Scenario: Force-deleting the last blocker reopens an auto-blocked task
+    Given the config key "auto_block" is set to "true"
+    And I have a task called "blocker" with title "Schema change"
+    And I have a task called "waiting" with title "Use new schema"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    Then the task "waiting" has status "blocked"
+    When I force delete the task "blocker"
+    Then the task "waiting" has status "open"
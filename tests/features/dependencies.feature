@@ -52,8 +52,167 @@ Feature: Dependency management
     Then the task details include blocker "Task A"
     And the task details include dependent "Task C"
 
+  Scenario: Show JSON includes the raw dependency edges around a task
+    Given I have a task called "A" with title "Edge blocker"
+    And I have a task called "B" with title "Edge middle"
+    And I have a task called "C" with title "Edge dependent"
+    When I add a dependency so "B" is blocked by "A"
+    And I add a dependency so "C" is blocked by "B"
+    And I show task "B" in JSON
+    Then the task details include an edge where "B" is blocked by "A"
+    And the task details include an edge where "C" is blocked by "B"
+
+  Scenario: dep list shows both directions for a task mid-chain
+    Given I have a task called "A" with title "Chain start"
+    And I have a task called "B" with title "Chain middle"
+    And I have a task called "C" with title "Chain end"
+    When I add a dependency so "B" is blocked by "A"
+    And I add a dependency so "C" is blocked by "B"
+    And I list the dependencies of "B" in JSON
+    Then the task details include blocker "Chain start"
+    And the task details include dependent "Chain end"
+
+  Scenario: dep list prints blockers and dependents as text
+    Given I have a task called "A" with title "Chain start"
+    And I have a task called "B" with title "Chain middle"
+    And I have a task called "C" with title "Chain end"
+    When I add a dependency so "B" is blocked by "A"
+    And I add a dependency so "C" is blocked by "B"
+    And I list the dependencies of "B"
+    Then the output contains "Blockers:"
+    And the output contains "Chain start"
+    And the output contains "Dependents:"
+    And the output contains "Chain end"
+
+  Scenario: dep add accepts several blockers and skips existing edges
+    Given I have a task called "schema" with title "Design schema"
+    And I have a task called "auth" with title "Add auth"
+    And I have a task called "api" with title "Build API"
+    When I add a dependency so "api" is blocked by "schema"
+    And I add dependencies so "api" is blocked by "schema,auth"
+    Then the dependency batch added "auth" and skipped "schema"
+
+  Scenario: A cycle anywhere in a dep add batch rolls back every edge
+    Given I have a task called "A" with title "Chain start"
+    And I have a task called "B" with title "Chain middle"
+    And I have a task called "C" with title "Loose end"
+    When I add a dependency so "B" is blocked by "A"
+    And I add dependencies so "A" is blocked by "C,B"
+    Then the command should fail
+    And the error output contains "circular dependency"
+    And the ready list contains "Chain start"
+
   Scenario: Removing a dependency with invalid task ID fails
     Given I have a task called "real" with title "Valid task"
     When I try to remove a dependency so "real" is no longer blocked by "tk-0000"
     Then the command should fail
     And the error output contains "not found"
+
+  Scenario: A chain deeper than max_dep_depth is rejected
+    Given the config key "max_dep_depth" is set to "2"
+    And I have a task called "a" with title "Depth one"
+    And I have a task called "b" with title "Depth two"
+    And I have a task called "c" with title "Depth three"
+    And I have a task called "d" with title "Depth four"
+    When I add a dependency so "b" is blocked by "a"
+    And I add a dependency so "c" is blocked by "b"
+    And I try to add a dependency so "d" is blocked by "c"
+    Then the command should fail
+    And the error output contains "max_dep_depth"
+
+  Scenario: dep add --auto-block blocks the child
+    Given I have a task called "blocker" with title "Migrate schema"
+    And I have a task called "waiting" with title "Use new schema"
+    When I add an auto-blocking dependency so "waiting" is blocked by "blocker"
+    Then the task "waiting" has status "blocked"
+
+  Scenario: With auto_block on, a task is blocked until its blocker closes
+    Given the config key "auto_block" is set to "true"
+    And I have a task called "blocker" with title "Migrate schema"
+    And I have a task called "waiting" with title "Use new schema"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    Then the task "waiting" has status "blocked"
+    When I close the task "blocker"
+    Then the task "waiting" has status "open"
+
+  Scenario: With auto_block off, a task blocked by hand stays blocked
+    Given I have a task called "blocker" with title "Migrate schema"
+    And I have a task called "waiting" with title "Use new schema"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I set the status of "waiting" to "blocked"
+    And I remove the dependency so "waiting" is no longer blocked by "blocker"
+    Then the task "waiting" has status "blocked"
+
+  Scenario: Closing a blocker reports the dependents it unblocked
+    Given I have a task called "first" with title "Design API"
+    And I have a task called "second" with title "Provision database"
+    And I have a task called "client" with title "Write client"
+    And I have a task called "server" with title "Write server"
+    When I add a dependency so "client" is blocked by "first"
+    And I add a dependency so "server" is blocked by "first"
+    And I add a dependency so "server" is blocked by "second"
+    And I close the task "first"
+    Then the close output reports "client" as unblocked
+    And the close output does not report "server" as unblocked
+    When I close the task "second"
+    Then the close output reports "server" as unblocked
+
+  Scenario: dep graph JSON has one node per task in scope
+    Given I have a task called "design" with title "Design"
+    And I have a task called "build" with title "Build"
+    And I have a task called "ship" with title "Ship"
+    And I have a task called "old" with title "Already finished"
+    When I add a dependency so "build" is blocked by "design"
+    And I add a dependency so "ship" is blocked by "build"
+    And I close task "old" with reason "done"
+    And I print the dependency graph in JSON
+    Then the graph JSON has 3 nodes and 2 edges
+
+  Scenario: dep graph --format dot --root keeps what the root blocks
+    Given I have a task called "design" with title "Design"
+    And I have a task called "build" with title "Build"
+    And I have a task called "ship" with title "Ship"
+    And I have a task called "docs" with title "Docs"
+    When I add a dependency so "build" is blocked by "design"
+    And I add a dependency so "ship" is blocked by "build"
+    And I add a dependency so "build" is blocked by "docs"
+    And I print the dependency graph as DOT from "design"
+    Then the DOT output has an edge from "design" to "build"
+    And the DOT output has an edge from "build" to "ship"
+    And the DOT output does not mention "docs"
+
+  Scenario: critical-path follows the longest chain of open blockers
+    Given I have a task called "design" with title "Design"
+    And I have a task called "build" with title "Build"
+    And I have a task called "docs" with title "Docs"
+    And I have a task called "ship" with title "Ship"
+    When I add a dependency so "build" is blocked by "design"
+    And I add a dependency so "ship" is blocked by "build"
+    And I add a dependency so "ship" is blocked by "docs"
+    And I show the critical path of "ship" in JSON
+    Then the critical path is "design,build,ship"
+
+  Scenario: plan JSON groups open tasks into dependency waves
+    Given I have a task called "design" with title "Design"
+    And I have a task called "build" with title "Build"
+    And I have a task called "docs" with title "Docs"
+    And I have a task called "ship" with title "Ship"
+    When I add a dependency so "build" is blocked by "design"
+    And I add a dependency so "ship" is blocked by "build"
+    And I add a dependency so "ship" is blocked by "docs"
+    And I print the plan in JSON
+    Then plan wave 1 is "design,docs"
+    And plan wave 2 is "build"
+    And plan wave 3 is "ship"
+
+  Scenario: With auto_block on, a task stays blocked until its last blocker is gone
+    Given the config key "auto_block" is set to "true"
+    And I have a task called "first" with title "Migrate schema"
+    And I have a task called "second" with title "Backfill data"
+    And I have a task called "waiting" with title "Use new schema"
+    When I add a dependency so "waiting" is blocked by "first"
+    And I add a dependency so "waiting" is blocked by "second"
+    And I close the task "first"
+    Then the task "waiting" has status "blocked"
+    When I remove the dependency so "waiting" is no longer blocked by "second"
+    Then the task "waiting" has status "open"
@@ -0,0 +1,33 @@
+Feature: Database integrity checks
+  As an AI coding agent
+  I want tk doctor to find and repair broken rows
+  So that manual edits or bugs do not leave the task graph inconsistent
+
+  Background:
+    Given a tacks database is initialized
+    And I have a task called "alpha" with title "Alpha task"
+    And I have a task called "beta" with title "Beta task"
+
+  Scenario: A healthy database has no findings
+    When I run tk doctor in JSON
+    Then the doctor reports no findings
+
+  Scenario: Dangling dependencies and parents are reported as errors
+    Given task "alpha" is left blocked by a deleted task
+    And task "beta" is left with a deleted parent
+    When I run tk doctor in JSON
+    Then the command should fail
+    And the doctor reports "dangling_dependency" for "alpha"
+    And the doctor reports "dangling_parent" for "beta"
+
+  Scenario: tk doctor --fix removes the bad rows
+    Given task "alpha" is left blocked by a deleted task
+    And task "beta" is left with a deleted parent
+    When I run tk doctor --fix in JSON
+    Then the doctor fixed 2 rows
+    And the doctor reports no findings
+
+  Scenario: An epic without subtasks is a warning
+    Given I have a task called "epic" with title "Empty epic" and tag "epic"
+    When I run tk doctor in JSON
+    Then the doctor reports "empty_epic" for "epic"
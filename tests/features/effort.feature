@@ -0,0 +1,41 @@
+Feature: Effort tracking
+  As a team lead
+  I want tasks to carry estimated and spent minutes that roll up to their epic
+  So that I can see how an epic is burning against its estimate
+
+  Scenario: tk epic rolls up subtask estimates and spent time
+    Given a tacks database is initialized
+    And I have a task called "epic" with title "Launch"
+    When I create a subtask of "epic" with title "Write copy"
+    And I log effort on "last_subtask" with estimate 120 and spent 45
+    And I create a subtask of "epic" with title "Design banner"
+    And I log effort on "last_subtask" with estimate 60 and spent 90
+    And I run tk epic with JSON
+    Then the epic output shows "Launch" with 180 estimated and 135 spent minutes
+
+  Scenario: Negative minutes are rejected on the CLI
+    Given a tacks database is initialized
+    And I have a task called "chore" with title "Sweep"
+    When I try to set the estimate of "chore" to "-30"
+    Then the command should fail
+    And the error output contains "must not be negative"
+
+  Scenario: PATCH sets effort and GET /api/epics rolls it up
+    Given a tacks database is initialized
+    And the web server is running
+    And I created a task via API with title "Migration" as "epic"
+    And I created a subtask via API with title "Schema" under "epic" as "schema"
+    When I PATCH the API task "schema" with body '{"estimate_minutes":90,"spent_minutes":30}'
+    Then the response status is 200
+    And the response JSON field "estimate_minutes" equals 90
+    When I GET "/api/epics"
+    Then the API epic "Migration" rolls up 90 estimated and 30 spent minutes
+    When I GET the epic page for "epic"
+    Then the response body contains "30m of 90m (33%)"
+
+  Scenario: PATCH rejects negative minutes
+    Given a tacks database is initialized
+    And the web server is running
+    And I created a task via API with title "Cleanup" as "cleanup"
+    When I PATCH the API task "cleanup" with body '{"spent_minutes":-5}'
+    Then the response status is 422
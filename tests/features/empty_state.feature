@@ -0,0 +1,53 @@
+Feature: Empty-state JSON contract
+  As a client parsing tk JSON output
+  I want every command to return a predictable shape when there is no data
+  So that I never need special cases for an empty database
+
+  # Contract:
+  #   list, ready, blocked, children, epic -> []
+  #   stats  -> by_status / by_priority zero-filled with canonical keys, by_tag {}, by_assignee {}
+  #   prime  -> stats zero-filled with canonical statuses, in_progress [] and ready []
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario Outline: Task-list commands return an empty array
+    When I run tk "<command>" in JSON mode
+    Then the JSON output is an empty array
+
+    Examples:
+      | command |
+      | list    |
+      | ready   |
+      | blocked |
+      | epic    |
+
+  Scenario: children of a leaf task returns an empty array
+    Given I have a task called "leaf" with title "Leaf"
+    When I run tk children for "leaf" with JSON
+    Then the JSON output is an empty array
+
+  Scenario: stats on an empty database zero-fills canonical keys
+    When I run tk "stats" in JSON mode
+    Then the JSON output field "by_status" has "open" equal to 0
+    And the JSON output field "by_status" has "in_progress" equal to 0
+    And the JSON output field "by_status" has "blocked" equal to 0
+    And the JSON output field "by_status" has "done" equal to 0
+    And the JSON output field "by_priority" has "P0" equal to 0
+    And the JSON output field "by_priority" has "P3" equal to 0
+    And the JSON output field "by_tag" is an empty object
+    And the JSON output field "by_assignee" is an empty object
+
+  Scenario: prime on an empty database zero-fills stats and empties queues
+    When I run tk "prime" in JSON mode
+    Then the JSON output field "stats" has "open" equal to 0
+    And the JSON output field "stats" has "done" equal to 0
+    And the JSON output field "in_progress" is an empty array
+    And the JSON output field "ready" is an empty array
+
+  Scenario: GET /api/stats on an empty database zero-fills canonical keys
+    Given the web server is running
+    When I GET "/api/stats"
+    Then the response status is 200
+    And the response JSON nested field "by_status.open" equals 0
+    And the response JSON nested field "by_priority.2" equals 0
@@ -21,6 +21,18 @@ Feature: Epic progress tracking
     And I run tk epic with JSON
     Then the epic output shows "Progressing feature" with 1 of 2 done
 
+  Scenario: tk stats --epic counts each epic's subtasks by status
+    Given I have a task called "epic" with title "Counted feature" and tag "epic"
+    When I create a subtask of "epic" with title "Done step"
+    And I create a subtask of "epic" with title "Open step"
+    And I create a subtask of "epic" with title "Another open step"
+    And I force close subtask "Done step"
+    And I run tk stats by epic with JSON
+    Then the epic output shows "Counted feature" with 1 of 3 done
+    And the epic stats show "Counted feature" with 2 "open" subtasks
+    And the epic stats show "Counted feature" with 1 "done" subtask
+    And the epic stats show "Counted feature" with 0 "blocked" subtasks
+
   Scenario: No epics shows empty output
     Given I have a task called "task" with title "Regular task"
     When I run tk epic with JSON
@@ -22,3 +22,19 @@ Feature: Epic auto-tagging
     Given I have a task called "parent" with title "Top level"
     When I create a subtask of "parent" with title "Sub level"
     Then the subtask has parent ID matching "parent"
+
+  Scenario: Auto-tagging can be disabled via config
+    Given I have a task called "parent" with title "Plain parent" and tag "backend"
+    And the config key "auto_epic_tag" is set to "false"
+    When I create a subtask of "parent" with title "Untagging child"
+    And I show task "parent" in JSON
+    Then the task details do not include tag "epic"
+    And the task details include tag "backend"
+
+  Scenario: Auto-tagging explicitly enabled adds the epic tag once
+    Given I have a task called "parent" with title "Enabled parent"
+    And the config key "auto_epic_tag" is set to "true"
+    When I create a subtask of "parent" with title "First child"
+    And I create a subtask of "parent" with title "Second child"
+    And I show task "parent" in JSON
+    Then the task details show exactly one "epic" tag
@@ -0,0 +1,74 @@
+Feature: Quiet output and exit codes
+  As a script driving tk
+  I want confirmations I can silence and exit codes that say what went wrong
+  So that I can branch on outcomes without parsing messages
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: --quiet suppresses the create confirmation
+    When I quietly create a task titled "Silent task"
+    Then the command succeeds with no output
+    And the ready list contains "Silent task"
+
+  Scenario: --quiet keeps JSON output
+    When I quietly create a task titled "Scripted task" with json output
+    Then the output contains "Scripted task"
+
+  Scenario: -q suppresses the close confirmation
+    Given I have a task called "done" with title "Finish me"
+    When I quietly close the task "done"
+    Then the command succeeds with no output
+
+  Scenario: --quiet still reports errors on stderr
+    When I run tk with the arguments "--quiet show tk-0000"
+    Then the exit code is 2
+    And the error output contains "not found"
+
+  Scenario: A missing task exits with 2
+    When I run tk with the arguments "close tk-0000"
+    Then the exit code is 2
+
+  Scenario: A bad value exits with 3
+    When I run tk with the arguments "config set no_such_key 1"
+    Then the exit code is 3
+
+  Scenario: A flag value clap rejects also exits with 3
+    When I run tk with the arguments "list -s sideways"
+    Then the exit code is 3
+
+  Scenario: A circular dependency exits with 4
+    Given I have a task called "A" with title "First"
+    And I have a task called "B" with title "Second"
+    When I add a dependency so "B" is blocked by "A"
+    And I add dependencies so "A" is blocked by "B"
+    Then the exit code is 4
+
+  Scenario: Showing only missing tasks exits with 2
+    When I run tk with the arguments "show tk-0000 tk-0001"
+    Then the exit code is 2
+
+  Scenario: Getting an unset config key exits with 2
+    When I run tk with the arguments "config get no_such_key"
+    Then the exit code is 2
+
+  Scenario: A missing task ID containing a conflict keyword still exits with 2
+    When I run tk with the arguments "show tk-cycle"
+    Then the exit code is 2
+
+  Scenario: A bad import priority exits with 3 whatever the title says
+    When I import the JSON '[{"title":"Break the cycle","priority":9}]'
+    Then the exit code is 3
+
+  Scenario: Removing a dependency that does not exist exits with 2
+    Given I have a task called "A" with title "First"
+    And I have a task called "B" with title "Second"
+    When I remove the dependency so "B" is no longer blocked by "A"
+    Then the exit code is 2
+
+  Scenario: Deleting a task that still blocks another exits with 4
+    Given I have a task called "A" with title "First"
+    And I have a task called "B" with title "Second"
+    And I add a dependency so "B" is blocked by "A"
+    When I delete the task "A"
+    Then the exit code is 4
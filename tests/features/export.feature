@@ -0,0 +1,101 @@
+Feature: Exporting a portable SQLite snapshot
+  As a developer sharing a project
+  I want to write a standalone copy of the task database
+  So that others can open it directly with tk or sqlite3
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: A full export contains every task
+    Given I have a task called "open" with title "Still going"
+    And I have a task called "finished" with title "All done"
+    When I close task "finished" with reason "done"
+    And I export the database to "snapshot.db"
+    Then the export succeeds
+    And the export "snapshot.db" contains the task "open"
+    And the export "snapshot.db" contains the task "finished"
+
+  Scenario: An open-only export leaves done tasks out
+    Given I have a task called "open" with title "Still going"
+    And I have a task called "finished" with title "All done"
+    When I add a dependency so "open" is blocked by "finished"
+    And I close task "finished" with reason "done"
+    And I export open tasks to "snapshot.db"
+    Then the export succeeds
+    And the export "snapshot.db" contains the task "open"
+    And the export "snapshot.db" does not contain the task "finished"
+
+  Scenario: Export refuses to overwrite an existing file
+    Given I have a task called "open" with title "Still going"
+    When I export the database to "snapshot.db"
+    And I export the database to "snapshot.db"
+    Then the export fails
+
+  Scenario: tk init --import reproduces the exported tasks
+    Given I have a task called "open" with title "Still going"
+    And I have a task called "finished" with title "All done"
+    When I close task "finished" with reason "done"
+    And I export the database to "snapshot.db"
+    And I init a new project from the export "snapshot.db"
+    Then the task "open" has an ID starting with "tk-"
+    And the task "finished" has an ID starting with "tk-"
+    And new task IDs start with "tk-"
+
+  Scenario: tk init --import with a different prefix renames the imported tasks
+    Given I have a task called "blocker" with title "Blocker"
+    And I have a task called "waiting" with title "Waiting"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I export the database to "snapshot.db"
+    And I init a new project from the export "snapshot.db" with prefix "proj"
+    Then the task "waiting" has an ID starting with "proj-"
+    And the task "blocker" has an ID starting with "proj-"
+    And new task IDs start with "proj-"
+
+  Scenario: tk init --import rejects a file that is not an export
+    When I init a new project from a file that is not an export
+    Then the command should fail
+    And the error output contains "not a tacks export"
+    And no database file was created
+
+  Scenario: tk replay --check passes for a clean export
+    Given I have a task called "open" with title "Still going"
+    When I export the database to "snapshot.db"
+    And I replay the export "snapshot.db" with --check
+    Then the export succeeds
+    And the output contains "Replayed 1 task(s)"
+    And the output contains "No problems found"
+
+  Scenario: tk replay --check flags an out-of-range priority
+    Given I have a task called "open" with title "Still going"
+    And I have a task called "odd" with title "Odd priority"
+    When I export the database to "snapshot.db"
+    And the export "snapshot.db" stores priority 9 for the task "odd"
+    And I replay the export "snapshot.db" with --check
+    Then the command should fail
+    And the output contains "priority 9 is outside 0-3"
+    And the error output contains "1 problem(s) found"
+    And the export "snapshot.db" contains the task "odd"
+
+  Scenario: A CSV export round-trips a title containing a comma
+    When I create a task with title 'Fix parser, then "lexer"' and priority 1 and tags "bug,parser"
+    And I export the tasks as CSV to "tasks.csv"
+    Then the CSV export "tasks.csv" has a row titled 'Fix parser, then "lexer"' with tags "bug;parser"
+
+  Scenario: An open-only CSV export goes to stdout without done tasks
+    Given I have a task called "open" with title "Still going"
+    And I have a task called "finished" with title "All done"
+    When I close task "finished" with reason "done"
+    And I export open tasks as CSV
+    Then the CSV output has the rows "Still going"
+
+  Scenario: An open-only export skips archived tasks and detaches orphaned subtasks
+    Given I have a task called "finished" with title "All done"
+    And I have a subtask called "part" of "finished" with title "Left over"
+    And I have a task called "shelved" with title "Shelved work"
+    When I force close task "finished"
+    And I archive the task "shelved"
+    And I export open tasks to "snapshot.db"
+    Then the export succeeds
+    And the export "snapshot.db" does not contain the task "finished"
+    And the export "snapshot.db" does not contain the task "shelved"
+    And the task "part" in the export "snapshot.db" has no parent
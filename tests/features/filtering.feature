@@ -43,3 +43,132 @@ Feature: List filtering
     When I list tasks with default settings
     Then the filtered list contains "Active task"
     And the filtered list does not contain "Finished task"
+
+  Scenario: tk list --reason shows only tasks closed with that reason
+    Given I have a task called "dup" with title "Duplicate report"
+    And I have a task called "old" with title "Stale idea"
+    And I have a task called "live" with title "Still open"
+    When I close task "dup" with reason "duplicate"
+    And I close task "old" with reason "stale"
+    And I list tasks filtered by close reason "duplicate"
+    Then the filtered list contains "Duplicate report"
+    And the filtered list does not contain "Stale idea"
+    And the filtered list does not contain "Still open"
+
+  Scenario: tk list --reason rejects an unknown reason
+    Given I have a task called "any" with title "Any task"
+    When I list tasks filtered by close reason "bogus"
+    Then the command should fail
+    And the error output contains "invalid close reason"
+
+  Scenario: tk list --created-after today includes new tasks and excludes older ones
+    Given I have a task called "new" with title "Added today"
+    And I have a task called "old" with title "Added last month"
+    And the task "old" was created 30 days ago
+    When I list tasks created after today
+    Then the filtered list contains "Added today"
+    And the filtered list does not contain "Added last month"
+
+  Scenario: tk list --created-before today excludes tasks created today
+    Given I have a task called "new" with title "Added today"
+    And I have a task called "old" with title "Added last month"
+    And the task "old" was created 30 days ago
+    When I list tasks created before today
+    Then the filtered list contains "Added last month"
+    And the filtered list does not contain "Added today"
+
+  Scenario: tk list --created-after rejects an invalid date
+    Given I have a task called "any" with title "Any task"
+    When I list tasks created after "last tuesday"
+    Then the command should fail
+    And the error output contains "invalid date"
+
+  Scenario: tk list --updated-after skips tasks untouched since the bound
+    Given I have a task called "busy" with title "Touched today"
+    And I have a task called "idle" with title "Untouched for weeks"
+    And the task "idle" was last updated 30 days ago
+    When I list tasks updated after today
+    Then the filtered list contains "Touched today"
+    And the filtered list does not contain "Untouched for weeks"
+
+  Scenario: tk list --updated-after rejects an invalid timestamp
+    Given I have a task called "any" with title "Any task"
+    When I list tasks updated after "2024-13-45"
+    Then the command should fail
+    And the error output contains "invalid date"
+
+  Scenario: tk recent includes tasks closed within the window
+    Given I have a task called "fixed" with title "Fixed this morning"
+    And I have a task called "idle" with title "Untouched for weeks"
+    And the task "idle" was last updated 30 days ago
+    When I close task "fixed" with reason "done"
+    And I list tasks changed in the last 24 hours
+    Then the filtered list contains "Fixed this morning"
+    And the filtered list does not contain "Untouched for weeks"
+
+  Scenario: tk list --blocked-by shows what a task blocks
+    Given I have a task called "schema" with title "Design schema"
+    And I have a task called "api" with title "Build API"
+    And I have a task called "docs" with title "Write docs"
+    When I add a dependency so "api" is blocked by "schema"
+    And I list the IDs of tasks blocked by "schema"
+    Then the output is exactly the IDs of "api"
+
+  Scenario: tk list --blocking shows what blocks a task
+    Given I have a task called "schema" with title "Design schema"
+    And I have a task called "api" with title "Build API"
+    And I have a task called "docs" with title "Write docs"
+    When I add a dependency so "api" is blocked by "schema"
+    And I list the IDs of tasks blocking "api"
+    Then the output is exactly the IDs of "schema"
+
+  Scenario: Search matches titles and descriptions, title matches first
+    Given I have a task called "desc" with title "Tidy logging" and description "The parser drops spans"
+    And I have a task called "title" with title "Parser rewrite"
+    And I have a task called "other" with title "Unrelated work"
+    When I close task "title" with reason "done"
+    And I search for "pars"
+    Then the shown JSON array has titles "Parser rewrite,Tidy logging"
+
+  Scenario: Search treats query syntax as plain text
+    Given I have a task called "title" with title "Parser rewrite"
+    When I search for 'parser" OR'
+    Then the JSON output is an empty array
+    When I search for "%' *"
+    Then the JSON output is an empty array
+
+  Scenario: Search follows retitles and deletes
+    Given I have a task called "desc" with title "Tidy logging" and description "The parser drops spans"
+    And I have a task called "title" with title "Parser rewrite"
+    And I have a task called "other" with title "Unrelated work"
+    When I retitle the task "other" to "Parser docs"
+    And I delete the task "title"
+    And I search for "parser"
+    Then the shown JSON array has titles "Parser docs,Tidy logging"
+
+  Scenario: tk overdue lists open tasks past due, most overdue first
+    Given I have a task called "late" with title "A day late"
+    And I have a task called "later" with title "Five days late"
+    And I have a task called "future" with title "Due next week"
+    And I have a task called "closed" with title "Late but done"
+    And I have a task called "undated" with title "No due date"
+    And the task "late" was due 1 days ago
+    And the task "later" was due 5 days ago
+    And the task "future" was due -7 days ago
+    And the task "closed" was due 9 days ago
+    When I close the task "closed"
+    And I list overdue tasks
+    Then the shown JSON array has titles "Five days late,A day late"
+    When I clear the due date of "later"
+    And I list overdue tasks
+    Then the shown JSON array has titles "A day late"
+
+  Scenario: tk list -t matches the whole tag, not a pattern
+    Given I have a task called "half" with title "Half done" and tag "50%,ui"
+    And I have a task called "fast" with title "Five times faster" and tag "5x"
+    When I list tasks filtered by tag "5%"
+    Then the filtered list does not contain "Half done"
+    And the filtered list does not contain "Five times faster"
+    When I list tasks filtered by tag "50%"
+    Then the filtered list contains "Half done"
+    And the filtered list does not contain "Five times faster"
@@ -0,0 +1,33 @@
+Feature: Task history
+  As an agent picking up someone else's task
+  I want to see how the task reached its current state
+  So that I know who worked on it and why it was closed
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: History records a claim and a close in order
+    Given I have a task called "work" with title "Tracked work"
+    When I claim the task "work"
+    And I close the task "work"
+    And I view the history of "work"
+    Then history entry 1 changes "status" from "open" to "in_progress"
+    And history entry 2 changes "assignee" from "" to "agent"
+    And history entry 3 changes "status" from "in_progress" to "done"
+    And history entry 4 changes "close_reason" from "" to "done"
+
+  Scenario: A new task has no history
+    Given I have a task called "fresh" with title "Untouched"
+    When I view the history of "fresh"
+    Then the history is empty
+
+  Scenario: History of an unknown task fails
+    When I view the history of task ID "tk-zzzz"
+    Then the command should fail
+    And the error output contains "task not found"
+
+  Scenario: History records a priority change
+    Given I have a task called "work" with title "Tracked work"
+    When I set the priority of "work" to 1
+    And I view the history of "work"
+    Then history entry 1 changes "priority" from "2" to "1"
@@ -0,0 +1,42 @@
+Feature: Import tasks from a file
+  As a developer starting tacks on an existing project
+  I want to load tasks from a JSON list or a Markdown checklist
+  So that I can bootstrap the tracker from a plan I already have
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: Each checklist item becomes a task
+    When I import a checklist with lines "# Launch plan|- [ ] Write docs|- [ ] Cut release|Some notes"
+    Then the import reports 2 tasks
+    And the imported task "Write docs" has status "open"
+    And the imported task "Cut release" has status "open"
+
+  Scenario: Indented checklist items become subtasks and checked items are done
+    When I import a checklist with lines "- [ ] Build API|  - [x] Design schema|  - [ ] Add auth"
+    Then the import reports 3 tasks
+    And the imported task "Design schema" is a subtask of "Build API"
+    And the imported task "Add auth" is a subtask of "Build API"
+    And the imported task "Design schema" has status "done"
+
+  Scenario: JSON tasks keep their parents and dependencies under new IDs
+    When I import the JSON '[{"id":"x-1","title":"Epic"},{"id":"x-1.1","title":"Step","parent_id":"x-1","blocked_by":["x-2"]},{"id":"x-2","title":"Prereq"}]'
+    Then the import reports 3 tasks
+    And the imported task "Step" is a subtask of "Epic"
+    And the imported task "Step" is blocked by "Prereq"
+
+  Scenario: A subtask listed before its parent still lands under it
+    When I import the JSON '[{"id":"x-1.1","title":"Step","parent_id":"x-1"},{"id":"x-1","title":"Epic","blocked_by":["x-2"]},{"id":"x-2","title":"Prereq"}]'
+    Then the import reports 3 tasks
+    And the imported task "Step" is a subtask of "Epic"
+    And the imported task "Epic" is blocked by "Prereq"
+
+  Scenario: The output of tk list can be imported again
+    Given I have a task called "a" with title "Round trip"
+    When I import the output of tk list
+    Then the import reports 1 task
+    And there are 2 tasks titled "Round trip"
+
+  Scenario: A bad reference imports nothing
+    When I import the JSON '[{"id":"x-1","title":"Kept?"},{"id":"x-2","title":"Orphan","blocked_by":["nowhere"]}]'
+    Then nothing was imported
@@ -0,0 +1,56 @@
+Feature: Initialization
+  As an AI coding agent
+  I want tk init to reject prefixes that would break ID parsing
+  So that every generated task ID can be resolved later
+
+  Scenario: A simple prefix is accepted and used for new IDs
+    When I run tk init with prefix "proj"
+    Then new task IDs start with "proj-"
+
+  Scenario: --id-length picks longer task IDs
+    When I run tk init with prefix "proj" and ID length "8"
+    Then new task IDs have 8 hex digits after "proj-"
+
+  Scenario: An out-of-range ID length is rejected
+    When I run tk init with prefix "proj" and ID length "40"
+    Then the command should fail
+    And the error output contains "invalid id length"
+
+  Scenario: A prefix with a space is rejected
+    When I run tk init with prefix "my proj"
+    Then the command should fail
+    And the error output contains "invalid prefix"
+    And no database file was created
+
+  Scenario: A prefix containing a dot is rejected
+    When I run tk init with prefix "my.proj"
+    Then the command should fail
+    And the error output contains "invalid prefix"
+
+  Scenario: A prefix must start with a letter
+    When I run tk init with prefix "1proj"
+    Then the command should fail
+
+  Scenario: Re-running init with the same prefix is idempotent
+    When I run tk init with prefix "proj"
+    And I run tk init again with prefix "proj"
+    Then new task IDs start with "proj-"
+
+  Scenario: Re-running init with a different prefix is refused once tasks exist
+    Given a tacks database is initialized
+    And I have a task called "first" with title "First task"
+    When I run tk init again with prefix "other"
+    Then the command should fail
+    And the error output contains "tk reprefix"
+
+  Scenario: Reprefix renames existing tasks and their references
+    Given a tacks database is initialized
+    And I have a task called "blocker" with title "Blocker"
+    And I have a task called "waiting" with title "Waiting"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I run tk reprefix "proj"
+    And I show task "waiting" in JSON
+    Then the task details include an edge where "waiting" is blocked by "blocker"
+    And the task "waiting" has an ID starting with "proj-"
+    And the task "blocker" has an ID starting with "proj-"
+    And new task IDs start with "proj-"
@@ -0,0 +1,41 @@
+Feature: Keyset pagination
+  As an agent paging through a large backlog
+  I want to fetch tasks page by page after a cursor
+  So that concurrent inserts never make me skip or repeat a task
+
+  Background:
+    Given a tacks database is initialized
+    And I have a task called "t1" with title "Page task one"
+    And I have a task called "t2" with title "Page task two"
+    And I have a task called "t3" with title "Page task three"
+    And I have a task called "t4" with title "Page task four"
+
+  Scenario: Pages follow the list order without gaps
+    When I list the first page of 2 tasks
+    Then the page contains exactly "t1,t2"
+    When I list the next page of 2 tasks
+    Then the page contains exactly "t3,t4"
+    When I list the next page of 2 tasks
+    Then the page contains exactly ""
+
+  Scenario: Inserting a task mid-pagination does not shift later pages
+    When I list the first page of 2 tasks
+    Then the page contains exactly "t1,t2"
+    Given I have a task called "urgent" with title "Inserted ahead" and priority 0
+    And I have a task called "t5" with title "Inserted behind"
+    When I list the next page of 2 tasks
+    Then the page contains exactly "t3,t4"
+    When I list the next page of 2 tasks
+    Then the page contains exactly "t5"
+
+  Scenario: An unknown cursor is rejected
+    When I list tasks after cursor "tk-0000"
+    Then the command should fail
+    And the error output contains "unknown cursor"
+
+  Scenario: GET /api/tasks pages with after and limit
+    Given the web server is running
+    When I GET the API task list after "t2" with limit 1
+    Then the response status is 200
+    And the response JSON array has length 1
+    And the response JSON array contains a task with title "Page task three"
@@ -0,0 +1,29 @@
+Feature: Porcelain output
+  As a shell script
+  I want a stable key=value output format
+  So that I can read task fields without a JSON parser
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: List in porcelain mode emits one record per task
+    Given I have a task called "a" with title "First porcelain task" and priority 1
+    And I have a task called "b" with title "Second, with comma" and tag "backend"
+    When I list tasks in porcelain mode
+    Then the porcelain output parses into 2 records
+    And a porcelain record has "title" equal to "First porcelain task"
+    And a porcelain record has "tags" equal to "backend"
+    And a porcelain record has "priority" equal to "1"
+
+  Scenario: Porcelain output is deterministic
+    Given I have a task called "a" with title "Stable one"
+    And I have a task called "b" with title "Stable two"
+    When I list tasks in porcelain mode
+    Then repeating the porcelain list produces identical output
+
+  Scenario: Show in porcelain mode emits a single record
+    Given I have a task called "solo" with title "Shown task"
+    When I show task "solo" in porcelain mode
+    Then the porcelain output parses into 1 records
+    And a porcelain record has "status" equal to "open"
+    And a porcelain record has "assignee" equal to ""
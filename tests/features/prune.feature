@@ -0,0 +1,54 @@
+Feature: Pruning old done tasks
+  As a maintainer of a long-lived project
+  I want to bulk-delete done tasks closed long ago
+  So that the database stays small
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: Prune removes an old done task but keeps a recent one
+    Given I have a task called "old" with title "Ancient history"
+    And I have a task called "recent" with title "Just finished"
+    When I close task "old" with reason "done"
+    And I close task "recent" with reason "done"
+    And I backdate the task "old" by 120 days
+    And I prune done tasks older than 90 days
+    Then the prune report lists "old"
+    And the prune report does not list "recent"
+    And the task "old" no longer exists
+    And the task "recent" still exists
+
+  Scenario: Editing an old done task does not postpone its prune
+    Given I have a task called "old" with title "Ancient history"
+    When I close task "old" with reason "done"
+    And I backdate the task "old" by 120 days
+    And I update task "old" with notes "Postmortem link"
+    And I prune done tasks older than 90 days
+    Then the prune report lists "old"
+    And the task "old" no longer exists
+
+  Scenario: Prune is a dry run by default
+    Given I have a task called "old" with title "Ancient history"
+    When I close task "old" with reason "done"
+    And I backdate the task "old" by 120 days
+    And I dry-run prune done tasks older than 90 days
+    Then the prune report lists "old"
+    And the task "old" still exists
+
+  Scenario: Prune never touches open tasks
+    Given I have a task called "idle" with title "Still open"
+    When I backdate the task "idle" by 365 days
+    And I prune done tasks older than 90 days
+    Then the prune report does not list "idle"
+    And the task "idle" still exists
+
+  Scenario: Prune removes the old task's dependency edges
+    Given I have a task called "blocker" with title "Old blocker"
+    And I have a task called "waiting" with title "Was waiting"
+    When I add a dependency so "waiting" is blocked by "blocker"
+    And I close task "blocker" with reason "done"
+    And I close task "waiting" with reason "done"
+    And I backdate the task "blocker" by 120 days
+    And I prune done tasks older than 90 days
+    Then the prune report lists "blocker"
+    And the task "waiting" still exists
@@ -0,0 +1,29 @@
+Feature: Rate limiting mutating API requests
+  As the operator of a shared tk serve instance
+  I want to cap how fast clients can write through the API
+  So that a runaway agent cannot flood the task database
+
+  Background:
+    Given a tacks database is initialized
+    # At 1 per second no token can refill during a burst of requests.
+    And the web server is running with a rate limit of 1 per second
+
+  Scenario: Writes beyond the limit get 429 with Retry-After
+    When I create 3 tasks via API in a burst
+    Then the response status is 429
+    And the response has a Retry-After header
+    And the response content type is "application/json"
+    And the response JSON nested field "error.kind" equals "rate_limited"
+    And the response JSON nested field "error.message" equals "rate limit exceeded"
+
+  Scenario: Writes are accepted again once the bucket refills
+    When I create 3 tasks via API in a burst
+    Then the response status is 429
+    When I wait 1100 milliseconds
+    And I create 1 tasks via API in a burst
+    Then the response status is 201
+
+  Scenario: Reads are never rate limited
+    When I create 3 tasks via API in a burst
+    And I GET "/api/tasks"
+    Then the response status is 200
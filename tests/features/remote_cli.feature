@@ -0,0 +1,42 @@
+Feature: CLI talking to a running server
+  As an agent in a container that cannot open the database file
+  I want tk to send its requests to tk serve when TACKS_SERVER is set
+  So that the server stays the only process touching SQLite
+
+  Background:
+    Given a tacks database is initialized
+    And the web server is running
+
+  Scenario: tk list reads tasks from the server
+    Given I created a task via API with title "Served task" as "served"
+    When I list tasks through the server
+    Then the output contains "Served task"
+
+  Scenario: tk ready reads the ready queue from the server
+    Given I created a task via API with title "Ready on server" as "ready"
+    When I list ready tasks through the server
+    Then the output contains "Ready on server"
+
+  Scenario: tk show includes blockers from the server
+    Given I created a task via API with title "Blocked one" as "blocked"
+    And I created a task via API with title "The blocker" as "blocker"
+    And I added API dependency so "blocked" is blocked by "blocker"
+    When I show the API task "blocked" through the server
+    Then the output contains "Blocked one"
+    And the output contains "The blocker"
+
+  Scenario: tk create writes through the server
+    When I create a task "Made remotely" through the server
+    And I GET "/api/tasks"
+    Then the response JSON array contains a task with title "Made remotely"
+
+  Scenario: tk close writes through the server
+    Given I created a task via API with title "Close me" as "closeme"
+    When I close the API task "closeme" through the server
+    And I GET "/api/tasks"
+    Then the response JSON array does not contain a task with title "Close me"
+
+  Scenario: Commands without server support refuse to run
+    When I run tk stats through the server
+    Then the command should fail
+    And the error output contains "TACKS_SERVER"
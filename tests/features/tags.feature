@@ -0,0 +1,33 @@
+Feature: Tag management
+  As an AI coding agent
+  I want to list, rename and remove tags across all tasks
+  So that the tag vocabulary stays tidy without editing tasks one by one
+
+  Background:
+    Given a tacks database is initialized
+    And I have a task called "mixed" with title "Fix layout bug" and tag "bug,ui,urgent"
+    And I have a task called "plain" with title "Polish buttons" and tag "ui"
+
+  Scenario: tk tag list counts tasks per tag
+    When I list tags in JSON
+    Then the tag list shows "ui" on 2 tasks
+    And the tag list shows "bug" on 1 task
+
+  Scenario: Renaming a tag keeps the other tags on a task
+    When I rename the tag "ui" to "frontend"
+    And I show task "mixed" in JSON
+    Then the task details include tag "frontend"
+    And the task details include tag "bug"
+    And the task details include tag "urgent"
+    And the task details do not include tag "ui"
+    When I list tags in JSON
+    Then the tag list shows "frontend" on 2 tasks
+    And the tag list does not show "ui"
+
+  Scenario: Removing a tag strips it from every task
+    When I remove the tag "ui" everywhere
+    And I show task "mixed" in JSON
+    Then the task details include tag "bug"
+    And the task details do not include tag "ui"
+    When I list tags in JSON
+    Then the tag list does not show "ui"
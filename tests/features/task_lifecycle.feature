@@ -33,3 +33,80 @@ Feature: Task lifecycle
     When I show the task
     Then the task details show title "Urgent fix"
     And the task details show priority 1
+
+  Scenario: Toggling tags twice restores the original tag set
+    When I create a task with title "Flag me" and priority 2 and tags "backend"
+    And I toggle the tags "needs-review,backend" on the task
+    And I show the task
+    Then the task details include tag "needs-review"
+    And the task details do not include tag "backend"
+    When I toggle the tags "needs-review,backend" on the task
+    And I show the task
+    Then the task details include tag "backend"
+    And the task details do not include tag "needs-review"
+
+  Scenario: Showing several tasks at once skips missing IDs
+    Given I have a task called "one" with title "First shown"
+    And I have a task called "two" with title "Second shown"
+    When I show the tasks "two,tk-0000,one" in JSON
+    Then the shown JSON array has titles "Second shown,First shown"
+    And the error output contains "task not found: tk-0000"
+
+  Scenario: --labels adds display labels to JSON output
+    Given I have a task called "hot" with title "Hot fix" and priority 1
+    When I list tasks in JSON with labels
+    Then the listed task "hot" has "priority_label" set to "high"
+    And the listed task "hot" has "status_label" set to "open"
+    And the listed task "hot" has "color" set to "yellow"
+
+  Scenario: JSON output has no labels by default
+    Given I have a task called "hot" with title "Plain fix" and priority 1
+    When I list tasks in JSON
+    Then the listed task "hot" has no "priority_label" field
+
+  Scenario: Priorities can be given by name
+    When I create a task with title "Hotfix" and priority "critical"
+    And I show the task
+    Then the task details show priority 0
+
+  Scenario: Priorities print as P0-P3 by default
+    Given I have a task called "hot" with title "Plain fix" and priority 1
+    When I list tasks with color "never"
+    Then the output contains "P1"
+    And the output does not contain "high"
+
+  Scenario: Priorities print as names when priority_names is on
+    Given the config key "priority_names" is set to "true"
+    And I have a task called "hot" with title "Plain fix" and priority 1
+    When I list tasks with color "never"
+    Then the output contains "high"
+    And the output does not contain "P1"
+
+  Scenario: An out-of-range priority is rejected before any work
+    When I try to create a task with title "Too urgent" and priority "9"
+    Then the command should fail
+    And the error output contains "invalid priority: 9"
+    And the task list does not contain "Too urgent"
+
+  Scenario: An unknown status is rejected by update
+    When I create a task with title "Status check"
+    And I set the task status to "nope"
+    Then the command should fail
+    And the error output contains "unknown status: nope"
+    And the error output contains "valid statuses: open, in_progress, blocked, done"
+
+  Scenario: Status aliases are stored under their canonical name
+    When I create a task with title "Aliased status"
+    And I set the task status to "in-progress"
+    And I show the task
+    Then the task details show status "in_progress"
+
+  Scenario: --if-unchanged-since guards an update against concurrent edits
+    Given I have a task called "shared" with title "Shared task"
+    When I try to retitle task "shared" to "Fresh edit" if unchanged since creation
+    Then the task list contains "Fresh edit"
+    When I update task "shared" with notes "someone else was here"
+    And I try to retitle task "shared" to "Stale edit" if unchanged since creation
+    Then the command should fail
+    And the error output contains "changed since"
+    And the task list does not contain "Stale edit"
@@ -0,0 +1,23 @@
+Feature: Task templates
+  As an AI coding agent
+  I want to save a task tree as a template and create copies of it
+  So that recurring multi-task workflows take one command to set up
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: A saved template recreates the task tree with fresh IDs
+    Given I have a task called "review" with title "Code review" and tag "review"
+    When I create a subtask of "review" with title "Read the diff"
+    And I create a subtask of "review" with title "Run the tests"
+    And I save task "review" as the template "code-review"
+    And I create a task from the template "code-review" titled "Review PR 42"
+    Then the created tree has 3 tasks
+    And the created root is titled "Review PR 42" and tagged "review"
+    And the created subtasks are "Read the diff, Run the tests" under the created root
+    And the created tasks all have new IDs
+
+  Scenario: Creating from an unknown template fails
+    When I try to create a task from the template "missing"
+    Then the command should fail
+    And the error output contains "template not found: missing"
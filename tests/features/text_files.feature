@@ -0,0 +1,24 @@
+Feature: Long text from files and stdin
+  As an AI coding agent
+  I want to pass descriptions and notes as files or on stdin
+  So that multi-paragraph text needs no shell escaping
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: tk create --description-file - reads the description from stdin
+    When I create a task called "spec" with its description piped as lines "## Goal" and "Ship it."
+    And I show task "spec" in JSON
+    Then the task details show a description with lines "## Goal" and "Ship it."
+
+  Scenario: tk update --notes-file replaces the notes with the file contents
+    Given I have a task called "noted" with title "Task with notes"
+    When I update task "noted" with notes from a file containing "Tried the cache; too slow"
+    And I show task "noted" in JSON
+    Then the task details show notes "Tried the cache; too slow"
+
+  Scenario: --description and --description-file together are rejected
+    Given I have a task called "both" with title "Ambiguous description"
+    When I try to update task "both" with description "inline" and a description file
+    Then the update is rejected
+    And the error output contains "not both"
@@ -0,0 +1,30 @@
+Feature: Saved views
+  As a developer who runs the same list filters every day
+  I want to save a filter combination under a name
+  So that one short command brings the list back
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: A saved view lists the tasks its filters match
+    Given I have a task called "hot" with title "Fix prod outage" and priority 1
+    And I have a task called "cold" with title "Tidy README" and priority 3
+    When I save the view "urgent" with filters "-p 1"
+    And I run the view "urgent"
+    Then the filtered list contains "Fix prod outage"
+    And the filtered list does not contain "Tidy README"
+
+  Scenario: Saved views are listed by name
+    When I save the view "a-view" with filters "-s open"
+    And I save the view "b-view" with filters "-a -t docs"
+    And I list the saved views
+    Then the saved views are "a-view, b-view"
+
+  Scenario: Running an unknown view fails
+    When I run the view "missing"
+    Then the command should fail
+    And the error output contains "view not found: missing"
+
+  Scenario: A view name must be a single plain word
+    When I save the view "my view" with filters "-s open"
+    Then the command should fail
@@ -0,0 +1,13 @@
+Feature: Watching the project summary
+  As an AI coding agent
+  I want a live prime summary that refreshes on change
+  So that I don't have to poll tk prime myself
+
+  Background:
+    Given a tacks database is initialized
+
+  Scenario: tk watch re-prints the summary only when the database changes
+    When I watch the project while a task titled "Freshly added" is created
+    Then the watch exited cleanly
+    And the watch printed the summary 2 times
+    And the last watch summary mentions "Freshly added"
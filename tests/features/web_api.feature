@@ -23,6 +23,11 @@ Feature: REST API endpoints
     Then the response status is 201
     And the response JSON field "title" equals "Tagged task"
 
+  Scenario: POST /api/tasks rejects a tag containing a comma
+    When I POST "/api/tasks" with body '{"title":"Comma tag","tags":["a,b"]}'
+    Then the response status is 422
+    And the response JSON nested field "error.kind" equals "validation"
+
   Scenario: POST /api/tasks with missing title returns 422
     When I POST "/api/tasks" with body '{"priority":1}'
     Then the response status is 422
@@ -48,6 +53,20 @@ Feature: REST API endpoints
     And the response JSON array contains a task with title "Done task"
     And the response JSON array does not contain a task with title "Open task"
 
+  Scenario: GET /api/tasks?reason=duplicate filters by close reason
+    Given I created a task via API with title "Dup via API" as "dup"
+    And I created a task via API with title "Done via API" as "finished"
+    And I closed the API task "finished"
+    When I POST the close endpoint for API task "dup" with body '{"reason":"duplicate"}'
+    And I GET "/api/tasks?reason=duplicate"
+    Then the response status is 200
+    And the response JSON array contains a task with title "Dup via API"
+    And the response JSON array does not contain a task with title "Done via API"
+
+  Scenario: GET /api/tasks?reason= with an unknown reason returns 422
+    When I GET "/api/tasks?reason=bogus"
+    Then the response status is 422
+
   Scenario: GET /api/tasks?priority=1 filters by priority
     Given I created a task via API with title "P1 task" and priority 1 as "p1"
     And I created a task via API with title "P3 task" and priority 3 as "p3"
@@ -64,6 +83,26 @@ Feature: REST API endpoints
     And the response JSON array contains a task with title "Backend task"
     And the response JSON array does not contain a task with title "Frontend task"
 
+  Scenario: GET /api/tasks?tag=a,b matches any of the tags
+    Given I created a task via API with title "Backend task" and tag "backend" as "be"
+    And I created a task via API with title "Frontend task" and tag "frontend" as "fe"
+    And I created a task via API with title "Docs task" and tag "docs" as "docs"
+    When I GET "/api/tasks?tag=backend,frontend"
+    Then the response status is 200
+    And the response JSON array has length 2
+    And the response JSON array does not contain a task with title "Docs task"
+
+  Scenario: GET /api/tasks?ids= returns only the tasks that exist
+    Given I created a task via API with title "First by id" as "id1"
+    And I created a task via API with title "Second by id" as "id2"
+    And I created a task via API with title "Not requested" as "id3"
+    When I GET the API tasks by ids "id1,tk-0000,id2"
+    Then the response status is 200
+    And the response JSON array has length 2
+    And the response JSON array contains a task with title "First by id"
+    And the response JSON array contains a task with title "Second by id"
+    And the response JSON array does not contain a task with title "Not requested"
+
   # ---------------------------------------------------------------------------
   # Task detail — GET /api/tasks/:id
   # ---------------------------------------------------------------------------
@@ -77,9 +116,57 @@ Feature: REST API endpoints
     And the response JSON has field "status"
     And the response JSON has field "priority"
 
+  Scenario: GET /api/tasks/:id?labels=true adds display labels
+    Given I created a task via API with title "Labeled task" and priority 1 as "labeled"
+    When I GET the API task "labeled" with labels
+    Then the response status is 200
+    And the response JSON field "priority_label" equals "high"
+    And the response JSON field "status_label" equals "open"
+
+  Scenario: GET /api/tasks/:id answers 304 to a repeat request with its ETag
+    Given I created a task via API with title "Cached task" as "cached"
+    When I GET the API task "cached"
+    Then the response status is 200
+    And the response has an ETag
+    When I GET the API task "cached" with the last ETag
+    Then the response status is 304
+
+  Scenario: A task's ETag is invalidated when the task changes
+    Given I created a task via API with title "Changing task" as "changing"
+    When I GET the API task "changing"
+    And I PATCH the API task "changing" with body '{"title":"Changed task"}'
+    And I GET the API task "changing" with the last ETag
+    Then the response status is 200
+    And the response JSON field "title" equals "Changed task"
+
+  Scenario: PATCH with If-Match succeeds only while the ETag is current
+    Given I created a task via API with title "Contested task" as "contested"
+    When I GET the API task "contested"
+    And I PATCH the API task "contested" if it still matches the last ETag with body '{"title":"First edit"}'
+    Then the response status is 200
+    When I PATCH the API task "contested" if it still matches the last ETag with body '{"title":"Second edit"}'
+    Then the response status is 409
+    And the response JSON nested field "error.kind" equals "conflict"
+
+  Scenario: PATCH with If-Match accepts an ETag read with ?labels=true
+    Given I created a task via API with title "Labeled edit" as "labeled-edit"
+    When I GET the API task "labeled-edit" with labels
+    And I PATCH the API task "labeled-edit" if it still matches the last ETag with body '{"title":"Edited"}'
+    Then the response status is 200
+    And the response JSON field "title" equals "Edited"
+
+  Scenario: PATCH with a stale expected_updated_at is refused
+    Given I created a task via API with title "Stale task" as "stale"
+    When I PATCH the API task "stale" with body '{"title":"Too late","expected_updated_at":"2000-01-01T00:00:00Z"}'
+    Then the response status is 409
+    And the response JSON nested field "error.kind" equals "conflict"
+
   Scenario: GET /api/tasks/:id returns 404 for unknown id
     When I GET "/api/tasks/tk-0000"
     Then the response status is 404
+    And the response content type is "application/json"
+    And the response JSON nested field "error.kind" equals "not_found"
+    And the response JSON nested field "error.message" equals "task not found"
 
   # ---------------------------------------------------------------------------
   # Task update — PATCH /api/tasks/:id
@@ -103,6 +190,23 @@ Feature: REST API endpoints
     Then the response status is 200
     And the response JSON field "priority" equals 1
 
+  Scenario: PATCH /api/tasks/:id with a position reorders tasks within a priority
+    Given I created a task via API with title "First" as "first"
+    And I created a task via API with title "Second" as "second"
+    And I created a task via API with title "Third" as "third"
+    When I PATCH the API task "third" with body '{"position":1.5}'
+    Then the response status is 200
+    When I GET "/api/tasks"
+    Then the response JSON array titles are "First, Third, Second"
+
+  Scenario: PATCH /api/tasks/:id with a new priority puts the task last in that priority
+    Given I created a task via API with title "Promoted" as "promoted"
+    And I created a task via API with title "Urgent" and priority 1 as "urgent"
+    When I PATCH the API task "promoted" with body '{"priority":1}'
+    Then the response status is 200
+    When I GET "/api/tasks"
+    Then the response JSON array titles are "Urgent, Promoted"
+
   Scenario: PATCH /api/tasks/:id returns 404 for unknown id
     When I PATCH "/api/tasks/tk-0000" with body '{"title":"Ghost"}'
     Then the response status is 404
@@ -118,16 +222,43 @@ Feature: REST API endpoints
     And the response JSON field "status" equals "done"
     And the response JSON field "close_reason" equals "done"
 
+  Scenario: POST /api/tasks/:id/close with close_parent closes the epic
+    Given I created a task via API with title "API epic" as "api-epic"
+    And I created a subtask via API with title "Last API subtask" under "api-epic" as "api-sub"
+    When I POST the close endpoint for API task "api-sub" with body '{"close_parent":true}'
+    Then the response status is 200
+    When I GET the API task "api-epic"
+    Then the response JSON field "status" equals "done"
+    And the response JSON field "close_reason" equals "done"
+
+  Scenario: POST /api/tasks/:id/close leaves the epic open without close_parent
+    Given I created a task via API with title "Open API epic" as "open-epic"
+    And I created a subtask via API with title "Only API subtask" under "open-epic" as "only-sub"
+    When I POST the close endpoint for API task "only-sub" with body '{}'
+    Then the response status is 200
+    When I GET the API task "open-epic"
+    Then the response JSON field "status" equals "open"
+
   Scenario: POST /api/tasks/:id/close with a comment stores it
     Given I created a task via API with title "Task with comment" as "comment-task"
     When I POST the close endpoint for API task "comment-task" with body '{"reason":"duplicate","comment":"Covered by tk-0001"}'
     Then the response status is 200
     And the response JSON field "status" equals "done"
 
+  Scenario: POST /api/tasks/:id/close with a comments list stores each comment
+    Given I created a task via API with title "Checklist close" as "checklist"
+    When I POST the close endpoint for API task "checklist" with body '{"reason":"done","comments":["Shipped","Follow-up filed"]}'
+    Then the response status is 200
+    When I GET the comments endpoint for API task "checklist"
+    Then the response JSON array has length 2
+    And the response JSON array contains a comment with body "Shipped"
+    And the response JSON array contains a comment with body "Follow-up filed"
+
   Scenario: POST /api/tasks/:id/close with invalid reason returns 422
     Given I created a task via API with title "Bad close task" as "bad-close"
     When I POST the close endpoint for API task "bad-close" with body '{"reason":"bogus"}'
     Then the response status is 422
+    And the response JSON nested field "error.kind" equals "validation"
 
   Scenario: POST /api/tasks/:id/close returns 404 for unknown id
     When I POST "/api/tasks/tk-0000/close" with body '{"reason":"done"}'
@@ -186,12 +317,37 @@ Feature: REST API endpoints
     When I POST the deps endpoint for API task "dep-child" with body '{"parent_id":"dep-parent"}'
     Then the response status is 201
 
+  Scenario: GET /api/tasks filters by blocked_by and blocking
+    Given I created a task via API with title "Upstream" as "up"
+    And I created a task via API with title "Downstream" as "down"
+    And I created a task via API with title "Unrelated" as "other"
+    And I added API dependency so "down" is blocked by "up"
+    When I GET the API task list with "blocked_by" set to task "up"
+    Then the response status is 200
+    And the response JSON array titles are "Downstream"
+    When I GET the API task list with "blocking" set to task "down"
+    Then the response status is 200
+    And the response JSON array titles are "Upstream"
+
   Scenario: POST /api/tasks/:id/deps returns 409 on cycle
     Given I created a task via API with title "Cycle A" as "cyc-a"
     And I created a task via API with title "Cycle B" as "cyc-b"
     And I added API dependency so "cyc-b" is blocked by "cyc-a"
     When I POST the deps endpoint for API task "cyc-a" with body '{"parent_id":"cyc-b"}'
     Then the response status is 409
+    And the response JSON nested field "error.kind" equals "conflict"
+
+  Scenario: POST /api/tasks/:id/deps returns 404 for an unknown blocker
+    Given I created a task via API with title "Lonely child" as "lonely"
+    When I POST the deps endpoint for API task "lonely" with body '{"parent_id":"tk-zzzz"}'
+    Then the response status is 404
+    And the response JSON nested field "error.kind" equals "not_found"
+
+  Scenario: DELETE /api/tasks/:child/deps/:parent returns 404 when there is no such dependency
+    Given I created a task via API with title "Free parent" as "free-parent"
+    And I created a task via API with title "Free child" as "free-child"
+    When I DELETE the API dependency from "free-child" to "free-parent"
+    Then the response status is 404
 
   Scenario: DELETE /api/tasks/:child/deps/:parent removes a dependency
     Given I created a task via API with title "Parent dep" as "rem-parent"
@@ -226,6 +382,13 @@ Feature: REST API endpoints
     Then the response status is 200
     And the response JSON is an empty array
 
+  Scenario: GET /api/tasks/:id/history lists the close as field changes
+    Given I created a task via API with title "Audited task" as "audited"
+    And I closed the API task "audited"
+    When I GET the history endpoint for API task "audited"
+    Then the response status is 200
+    And the response JSON array has length 2
+
   # ---------------------------------------------------------------------------
   # Stats — GET /api/stats
   # ---------------------------------------------------------------------------
@@ -264,3 +427,186 @@ Feature: REST API endpoints
     When I GET the children endpoint for API task "childless"
     Then the response status is 200
     And the response JSON is an empty array
+
+  Scenario: GET /api/tasks/:id/children orders ten children by numeric suffix
+    Given I created a task via API with title "Big epic" as "big"
+    And I created 10 subtasks via API under "big"
+    When I GET the children endpoint for API task "big"
+    Then the response status is 200
+    And the response JSON array titles are "Child 1, Child 2, Child 3, Child 4, Child 5, Child 6, Child 7, Child 8, Child 9, Child 10"
+
+  Scenario: GET /api/tasks/:id/children supports status filter and limit
+    Given I created a task via API with title "Paged epic" as "paged"
+    And I created 10 subtasks via API under "paged"
+    And I closed the API task "paged-child2"
+    When I GET the children endpoint for API task "paged" with query "status=open&limit=3"
+    Then the response status is 200
+    And the response JSON array titles are "Child 1, Child 3, Child 4"
+
+  Scenario: GET /api/tasks/:id/children rejects an unknown sort with 422
+    Given I created a task via API with title "Sorted epic" as "sorted"
+    When I GET the children endpoint for API task "sorted" with query "sort=bogus"
+    Then the response status is 422
+
+  Scenario: PATCH /api/tasks/:id with an invalid field leaves tags unchanged
+    Given I created a task via API with title "Atomic update" and tag "keep" as "atomic"
+    When I PATCH the API task "atomic" with body '{"tags":["changed"],"status":"bogus"}'
+    Then the response status is 422
+    When I GET the API task "atomic"
+    Then the response body contains "keep"
+    And the response body does not contain "changed"
+
+  Scenario: GET /api/tasks?created_after= rejects an invalid date with 422
+    When I GET "/api/tasks?created_after=yesterday"
+    Then the response status is 422
+
+  Scenario: GET /api/tasks?created_after= accepts a date-only bound
+    Given I created a task via API with title "Fresh task" as "fresh"
+    When I GET "/api/tasks?created_after=2000-01-01"
+    Then the response status is 200
+    And the response JSON array contains a task with title "Fresh task"
+
+  Scenario: GET /api/views lists saved views with their task list URL
+    Given the config key "view.urgent-api" is set to '{"priority":1,"tag":"api"}'
+    When I GET "/api/views"
+    Then the response status is 200
+    And the response body contains "urgent-api"
+    And the response body contains "/tasks?priority=1&tag=api"
+
+  Scenario: GET /api/tasks?updated_after= filters on the last modification time
+    Given I created a task via API with title "Fresh task" as "fresh"
+    When I GET "/api/tasks?updated_after=2000-01-01T00:00:00Z"
+    Then the response JSON array contains a task with title "Fresh task"
+    When I GET "/api/tasks?updated_after=2999-01-01"
+    Then the response status is 200
+    And the response JSON array does not contain a task with title "Fresh task"
+    When I GET "/api/tasks?updated_after=soon"
+    Then the response status is 422
+
+  # ---------------------------------------------------------------------------
+  # Dependency direction — GET /api/tasks/:id/blockers and /dependents
+  #
+  # Chain: "design" blocks "build", which blocks "ship". Each endpoint must
+  # return only its own side of "build", never the other.
+  # ---------------------------------------------------------------------------
+
+  Scenario: GET /api/tasks/:id/blockers returns the tasks it is blocked by
+    Given I created a task via API with title "Design" as "design"
+    And I created a task via API with title "Build" as "build"
+    And I created a task via API with title "Ship" as "ship"
+    And I added API dependency so "build" is blocked by "design"
+    And I added API dependency so "ship" is blocked by "build"
+    When I GET the blockers endpoint for API task "build"
+    Then the response status is 200
+    And the response JSON array has length 1
+    And the response JSON array contains a task with title "Design"
+    And the response JSON array does not contain a task with title "Ship"
+
+  Scenario: GET /api/tasks/:id/dependents returns the tasks blocked by it
+    Given I created a task via API with title "Design" as "design"
+    And I created a task via API with title "Build" as "build"
+    And I created a task via API with title "Ship" as "ship"
+    And I added API dependency so "build" is blocked by "design"
+    And I added API dependency so "ship" is blocked by "build"
+    When I GET the dependents endpoint for API task "build"
+    Then the response status is 200
+    And the response JSON array has length 1
+    And the response JSON array contains a task with title "Ship"
+    And the response JSON array does not contain a task with title "Design"
+
+  Scenario: The ends of a dependency chain have empty opposite sides
+    Given I created a task via API with title "Design" as "design"
+    And I created a task via API with title "Ship" as "ship"
+    And I added API dependency so "ship" is blocked by "design"
+    When I GET the blockers endpoint for API task "design"
+    Then the response JSON is an empty array
+    When I GET the dependents endpoint for API task "ship"
+    Then the response JSON is an empty array
+    When I GET the dependents endpoint for API task "design"
+    Then the response JSON array contains a task with title "Ship"
+    When I GET the blockers endpoint for API task "ship"
+    Then the response JSON array contains a task with title "Design"
+
+  # ---------------------------------------------------------------------------
+  # Server metadata — GET /api/meta
+  # ---------------------------------------------------------------------------
+
+  Scenario: GET /api/meta reports crate version and uptime
+    Given I created a task via API with title "Meta task" as "meta"
+    When I GET "/api/meta"
+    Then the response status is 200
+    And the response JSON field "version" equals the crate version
+    And the response JSON field "uptime_secs" is a positive number
+    And the response JSON field "task_count" equals 1
+    And the response JSON field "db_file" equals "tacks.db"
+
+  # ---------------------------------------------------------------------------
+  # Field selection — ?fields=
+  # ---------------------------------------------------------------------------
+
+  Scenario: GET /api/tasks?fields= keeps only the requested keys
+    Given I created a task via API with title "Slim one" as "s1"
+    And I created a task via API with title "Slim two" as "s2"
+    When I GET "/api/tasks?fields=id,status"
+    Then the response status is 200
+    And the response JSON tasks have exactly the keys "id,status"
+
+  Scenario: GET /api/tasks?fields= rejects an unknown key
+    When I GET "/api/tasks?fields=id,nope"
+    Then the response status is 422
+
+  # ---------------------------------------------------------------------------
+  # Search — GET /api/search
+  # ---------------------------------------------------------------------------
+
+  Scenario: GET /api/search returns matches with highlighted snippets
+    Given I created a task via API with title "Parser rewrite" as "rewrite"
+    And I created a task via API with title "Tidy logging" and description "the parser drops spans" as "logging"
+    And I created a task via API with title "Unrelated chore" as "chore"
+    When I GET "/api/search?q=parser"
+    Then the response status is 200
+    And the response JSON field "total" equals 2
+    And the search results are titled "Parser rewrite, Tidy logging"
+    And the search result "Parser rewrite" has snippet "<mark>Parser</mark> rewrite"
+    And the search result "Tidy logging" has snippet "the <mark>parser</mark> drops spans"
+
+  Scenario: GET /api/search?limit= keeps the total of all matches
+    Given I created a task via API with title "Parser rewrite" as "rewrite"
+    And I created a task via API with title "Tidy logging" and description "the parser drops spans" as "logging"
+    When I GET "/api/search?q=parser&limit=1"
+    Then the response JSON field "total" equals 2
+    And the search results are titled "Parser rewrite"
+
+  Scenario: GET /api/search with a blank query matches nothing
+    Given I created a task via API with title "Parser rewrite" as "rewrite"
+    When I GET "/api/search?q=%20"
+    Then the response status is 200
+    And the response JSON field "total" equals 0
+    And the search returned no tasks
+
+  Scenario: GET /api/search treats quotes and wildcards literally and escapes snippets
+    Given I created a task via API with title "Fix <b> tags" as "markup"
+    When I GET "/api/search?q=%25%22%27"
+    Then the response status is 200
+    And the search returned no tasks
+    When I GET "/api/search?q=fix"
+    Then the search result "Fix <b> tags" has snippet "<mark>Fix</mark> &lt;b&gt; tags"
+
+  # ---------------------------------------------------------------------------
+  # Concurrency — handlers share one database connection
+  # ---------------------------------------------------------------------------
+
+  Scenario: Reads do not wait for a write in progress
+    Given I created a task via API with title "Already there" as "there"
+    And another connection holds the database write lock
+    When a task is being created via API in the background
+    And I GET "/api/tasks" within 2 seconds
+    Then the response status is 200
+    And the response JSON array contains a task with title "Already there"
+    And once the write lock is released the background request succeeds
+
+  Scenario: Concurrent reads and writes all complete
+    When I send 40 concurrent API reads and writes
+    And I GET "/api/meta"
+    Then the response status is 200
+    And the response JSON field "task_count" equals 20
@@ -45,6 +45,20 @@ Feature: Web view pages
     Then the response status is 200
     And the response body contains "High priority task"
 
+  Scenario: Task list page links to saved views
+    Given the config key "view.mine" is set to '{"assignee":"alice"}'
+    When I GET "/tasks"
+    Then the response status is 200
+    And the response body contains "saved-views"
+    And the response body contains ">mine</a>"
+
+  Scenario: Task list page filters by assignee
+    Given I created a task via API with title "Unassigned chore" as "free"
+    When I GET "/tasks?assignee=alice"
+    Then the response status is 200
+    And the response body does not contain "Unassigned chore"
+    And the response body contains "No tasks match your filters"
+
   # ---------------------------------------------------------------------------
   # Task detail page — GET /tasks/:id
   # ---------------------------------------------------------------------------
@@ -119,6 +133,36 @@ Feature: Web view pages
     Then the response status is 200
     And the response body contains "Main epic"
 
+  # ---------------------------------------------------------------------------
+  # Conditional requests — ETag / If-None-Match on list-like pages
+  # ---------------------------------------------------------------------------
+
+  Scenario: Board page returns 304 for a repeat request with its ETag
+    Given I created a task via API with title "Cached card" as "cached"
+    When I GET "/board"
+    Then the response status is 200
+    And the response has an ETag
+    When I GET "/board" with the last ETag
+    Then the response status is 304
+
+  Scenario: Task list ETag is invalidated by a change
+    When I GET "/tasks"
+    Then the response has an ETag
+    Given I created a task via API with title "Fresh task" as "fresh"
+    When I GET "/tasks" with the last ETag
+    Then the response status is 200
+    And the response body contains "Fresh task"
+
+  Scenario: Task list ETag depends on the active filters
+    When I GET "/tasks?status=open"
+    And I GET "/tasks?status=done" with the last ETag
+    Then the response status is 200
+
+  Scenario: Epics page returns 304 for a repeat request with its ETag
+    When I GET "/epics"
+    And I GET "/epics" with the last ETag
+    Then the response status is 304
+
   # ---------------------------------------------------------------------------
   # Create task form — GET /tasks/new
   # ---------------------------------------------------------------------------